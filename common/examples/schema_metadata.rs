@@ -0,0 +1,96 @@
+// Writes metadata about the AccessKit schema to standard output as JSON,
+// for consumption by external documentation pipelines and by the
+// generators of language bindings.
+//
+// Run with `cargo run -p accesskit --features metadata --example schema_metadata`.
+// The output is checked in as `common/schema.json`; regenerate it after
+// changing the schema.
+
+use accesskit::metadata::{enums, properties, ValueType};
+use std::fmt::Write;
+
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c if c.is_control() => write!(result, "\\u{:04x}", c as u32).unwrap(),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn camel_case(snake: &str) -> String {
+    let mut result = String::new();
+    let mut capitalize = false;
+    for c in snake.chars() {
+        if c == '_' {
+            capitalize = true;
+        } else if capitalize {
+            result.push(c.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn unindent_doc(doc: &str) -> String {
+    let lines = doc
+        .lines()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>();
+    lines.join("\n")
+}
+
+fn type_name(value_type: ValueType) -> String {
+    match value_type {
+        ValueType::Enum(name) => name.into(),
+        other => format!("{:?}", other),
+    }
+}
+
+fn main() {
+    let properties = properties()
+        .map(|p| {
+            format!(
+                "    {{\"name\": {}, \"getter\": {}, \"bindingName\": {}, \"type\": {}, \"doc\": {}}}",
+                json_string(p.name),
+                json_string(p.getter),
+                json_string(&camel_case(p.getter)),
+                json_string(&type_name(p.value_type)),
+                json_string(&unindent_doc(p.doc)),
+            )
+        })
+        .collect::<Vec<_>>();
+    let enums = enums()
+        .map(|info| {
+            let variants = info
+                .variants
+                .iter()
+                .map(|variant| {
+                    format!(
+                        "      {{\"name\": {}, \"value\": {}}}",
+                        json_string(&variant.name),
+                        variant.value
+                    )
+                })
+                .collect::<Vec<_>>();
+            format!(
+                "    {}: [\n{}\n    ]",
+                json_string(info.name),
+                variants.join(",\n")
+            )
+        })
+        .collect::<Vec<_>>();
+    println!("{{");
+    println!("  \"schemaVersion\": {},", accesskit::SCHEMA_VERSION);
+    println!("  \"properties\": [\n{}\n  ],", properties.join(",\n"));
+    println!("  \"enums\": {{\n{}\n  }}", enums.join(",\n"));
+    println!("}}");
+}
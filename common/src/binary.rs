@@ -0,0 +1,987 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+//! A compact binary encoding of [`TreeUpdate`], for sending updates
+//! to a consumer in another process without depending on serde.
+//!
+//! An encoded update starts with the bytes `AKTU`, followed by
+//! the [`FORMAT_VERSION`] and the [`SCHEMA_VERSION`] of the encoder.
+//! [`decode`] rejects updates with a different format version.
+//!
+//! Integers are written as LEB128 variable-length integers. Node IDs
+//! are written as signed differences from a nearby ID, such as the ID of
+//! the previous node in the update or of the node that refers to them,
+//! so that sequentially allocated IDs usually take one byte. Properties are
+//! written in ascending order of their [`PropertyId`], each preceded by
+//! the difference from the previous one and by the length of its value
+//! in bytes.
+//!
+//! Because every property value is length-prefixed, an update encoded
+//! against a newer schema version can still be decoded: properties that
+//! this copy of the crate doesn't know about, or whose values it can't
+//! decode, such as a newly added enumeration value, are skipped, and
+//! unknown roles are decoded as [`Role::Unknown`]. Actions and flags
+//! that it doesn't know about are kept, but ignored by every accessor.
+//! In an update encoded against the same or an older schema version,
+//! all of these are errors.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+    Affine, AriaCurrent, AutoComplete, ChangeReason, CustomAction, CustomActionId, HasPopup,
+    Invalid, ListStyle, Live, Node, NodeId, Orientation, Properties, PropertyId, PropertyValue,
+    Rect, Role, SortDirection, TextAlign, TextDecoration, TextDirection, TextPosition,
+    TextSelection, Toggled, Tree, TreeUpdate, VerticalOffset, SCHEMA_VERSION,
+};
+
+const MAGIC: &[u8; 4] = b"AKTU";
+
+/// The version of the binary format written by [`encode`]. This is
+/// incremented whenever the layout of an encoded update changes.
+pub const FORMAT_VERSION: u8 = 2;
+
+/// The error returned by [`decode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The data doesn't start with the bytes that identify an encoded update.
+    NotAnUpdate,
+    /// The update was encoded with a different version of the format.
+    UnsupportedFormatVersion(u8),
+    /// The data ends in the middle of the update.
+    UnexpectedEnd,
+    /// The data continues after the end of the update.
+    TrailingBytes,
+    /// An integer, string, enumeration value or property ID isn't valid.
+    InvalidValue,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAnUpdate => write!(f, "the data isn't an encoded tree update"),
+            Self::UnsupportedFormatVersion(version) => write!(
+                f,
+                "the update uses version {} of the binary format, but only version {} is supported",
+                version, FORMAT_VERSION
+            ),
+            Self::UnexpectedEnd => write!(f, "the update is truncated"),
+            Self::TrailingBytes => write!(f, "unexpected data after the end of the update"),
+            Self::InvalidValue => write!(f, "the update contains an invalid value"),
+        }
+    }
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn byte(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn unsigned(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn signed(&mut self, value: i64) {
+        self.unsigned(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn node_id(&mut self, id: NodeId, base: NodeId) {
+        self.signed(id.0.wrapping_sub(base.0) as i64);
+    }
+
+    fn str(&mut self, value: &str) {
+        self.unsigned(value.len() as u64);
+        self.0.extend_from_slice(value.as_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if len > self.0.len() {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn unsigned(&mut self) -> Result<u64, DecodeError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            let bits = (byte & 0x7f) as u64;
+            if shift == 63 && bits > 1 {
+                return Err(DecodeError::InvalidValue);
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError::InvalidValue)
+    }
+
+    fn signed(&mut self) -> Result<i64, DecodeError> {
+        let value = self.unsigned()?;
+        Ok(((value >> 1) as i64) ^ -((value & 1) as i64))
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        self.unsigned()?
+            .try_into()
+            .map_err(|_| DecodeError::InvalidValue)
+    }
+
+    fn usize(&mut self) -> Result<usize, DecodeError> {
+        self.unsigned()?
+            .try_into()
+            .map_err(|_| DecodeError::InvalidValue)
+    }
+
+    /// Reads the length of a sequence whose items each take at least
+    /// `item_size` bytes, failing early if the data is too short.
+    fn len(&mut self, item_size: usize) -> Result<usize, DecodeError> {
+        let len = self.usize()?;
+        if len.saturating_mul(item_size) > self.0.len() {
+            return Err(DecodeError::UnexpectedEnd);
+        }
+        Ok(len)
+    }
+
+    fn node_id(&mut self, base: NodeId) -> Result<NodeId, DecodeError> {
+        Ok(NodeId(base.0.wrapping_add(self.signed()? as u64)))
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let len = self.len(1)?;
+        let bytes = self.bytes(len)?;
+        core::str::from_utf8(bytes)
+            .map(String::from)
+            .map_err(|_| DecodeError::InvalidValue)
+    }
+
+    fn f64(&mut self) -> Result<f64, DecodeError> {
+        Ok(f64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+}
+
+/// A property value, encoded relative to the ID of the node that it
+/// belongs to.
+trait Value: Sized {
+    fn encode(&self, writer: &mut Writer, base: NodeId);
+    fn decode(reader: &mut Reader, base: NodeId) -> Result<Self, DecodeError>;
+}
+
+impl Value for NodeId {
+    fn encode(&self, writer: &mut Writer, base: NodeId) {
+        writer.node_id(*self, base);
+    }
+
+    fn decode(reader: &mut Reader, base: NodeId) -> Result<Self, DecodeError> {
+        reader.node_id(base)
+    }
+}
+
+impl Value for Vec<NodeId> {
+    // Each ID is relative to the previous one, since lists such as
+    // the children of a node are usually allocated sequentially.
+    fn encode(&self, writer: &mut Writer, mut base: NodeId) {
+        writer.unsigned(self.len() as u64);
+        for id in self {
+            writer.node_id(*id, base);
+            base = *id;
+        }
+    }
+
+    fn decode(reader: &mut Reader, mut base: NodeId) -> Result<Self, DecodeError> {
+        let len = reader.len(1)?;
+        let mut ids = Vec::with_capacity(len);
+        for _ in 0..len {
+            base = reader.node_id(base)?;
+            ids.push(base);
+        }
+        Ok(ids)
+    }
+}
+
+impl Value for Box<str> {
+    fn encode(&self, writer: &mut Writer, _: NodeId) {
+        writer.str(self);
+    }
+
+    fn decode(reader: &mut Reader, _: NodeId) -> Result<Self, DecodeError> {
+        reader.string().map(String::into_boxed_str)
+    }
+}
+
+impl Value for f64 {
+    fn encode(&self, writer: &mut Writer, _: NodeId) {
+        writer.f64(*self);
+    }
+
+    fn decode(reader: &mut Reader, _: NodeId) -> Result<Self, DecodeError> {
+        reader.f64()
+    }
+}
+
+impl Value for usize {
+    fn encode(&self, writer: &mut Writer, _: NodeId) {
+        writer.unsigned(*self as u64);
+    }
+
+    fn decode(reader: &mut Reader, _: NodeId) -> Result<Self, DecodeError> {
+        reader.usize()
+    }
+}
+
+impl Value for u32 {
+    fn encode(&self, writer: &mut Writer, _: NodeId) {
+        writer.0.extend_from_slice(&self.to_le_bytes());
+    }
+
+    fn decode(reader: &mut Reader, _: NodeId) -> Result<Self, DecodeError> {
+        Ok(u32::from_le_bytes(reader.bytes(4)?.try_into().unwrap()))
+    }
+}
+
+impl Value for bool {
+    fn encode(&self, writer: &mut Writer, _: NodeId) {
+        writer.byte(*self as u8);
+    }
+
+    fn decode(reader: &mut Reader, _: NodeId) -> Result<Self, DecodeError> {
+        match reader.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(DecodeError::InvalidValue),
+        }
+    }
+}
+
+impl Value for Box<[u8]> {
+    fn encode(&self, writer: &mut Writer, _: NodeId) {
+        writer.unsigned(self.len() as u64);
+        writer.0.extend_from_slice(self);
+    }
+
+    fn decode(reader: &mut Reader, _: NodeId) -> Result<Self, DecodeError> {
+        let len = reader.len(1)?;
+        Ok(reader.bytes(len)?.into())
+    }
+}
+
+impl Value for Box<[f32]> {
+    fn encode(&self, writer: &mut Writer, _: NodeId) {
+        writer.unsigned(self.len() as u64);
+        for value in self.iter() {
+            writer.0.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    fn decode(reader: &mut Reader, _: NodeId) -> Result<Self, DecodeError> {
+        let len = reader.len(4)?;
+        (0..len)
+            .map(|_| Ok(f32::from_le_bytes(reader.bytes(4)?.try_into().unwrap())))
+            .collect()
+    }
+}
+
+impl Value for Box<Affine> {
+    fn encode(&self, writer: &mut Writer, _: NodeId) {
+        for value in self.as_coeffs() {
+            writer.f64(value);
+        }
+    }
+
+    fn decode(reader: &mut Reader, _: NodeId) -> Result<Self, DecodeError> {
+        let mut coeffs = [0.0; 6];
+        for value in &mut coeffs {
+            *value = reader.f64()?;
+        }
+        Ok(Box::new(Affine::new(coeffs)))
+    }
+}
+
+impl Value for Rect {
+    fn encode(&self, writer: &mut Writer, _: NodeId) {
+        for value in [self.x0, self.y0, self.x1, self.y1] {
+            writer.f64(value);
+        }
+    }
+
+    fn decode(reader: &mut Reader, _: NodeId) -> Result<Self, DecodeError> {
+        Ok(Rect::new(
+            reader.f64()?,
+            reader.f64()?,
+            reader.f64()?,
+            reader.f64()?,
+        ))
+    }
+}
+
+impl Value for TextSelection {
+    fn encode(&self, writer: &mut Writer, base: NodeId) {
+        for position in [self.anchor, self.focus] {
+            writer.node_id(position.node, base);
+            writer.unsigned(position.character_index as u64);
+        }
+    }
+
+    fn decode(reader: &mut Reader, base: NodeId) -> Result<Self, DecodeError> {
+        let mut position = || -> Result<TextPosition, DecodeError> {
+            Ok(TextPosition {
+                node: reader.node_id(base)?,
+                character_index: reader.usize()?,
+            })
+        };
+        Ok(TextSelection {
+            anchor: position()?,
+            focus: position()?,
+        })
+    }
+}
+
+impl Value for Box<TextSelection> {
+    fn encode(&self, writer: &mut Writer, base: NodeId) {
+        (**self).encode(writer, base);
+    }
+
+    fn decode(reader: &mut Reader, base: NodeId) -> Result<Self, DecodeError> {
+        TextSelection::decode(reader, base).map(Box::new)
+    }
+}
+
+impl Value for CustomAction {
+    fn encode(&self, writer: &mut Writer, _: NodeId) {
+        match &self.id {
+            CustomActionId::Integer(id) => {
+                writer.byte(0);
+                writer.signed(*id as i64);
+            }
+            CustomActionId::String(id) => {
+                writer.byte(1);
+                writer.str(id);
+            }
+        }
+        writer.str(&self.description);
+    }
+
+    fn decode(reader: &mut Reader, _: NodeId) -> Result<Self, DecodeError> {
+        let id = match reader.byte()? {
+            0 => CustomActionId::Integer(
+                reader
+                    .signed()?
+                    .try_into()
+                    .map_err(|_| DecodeError::InvalidValue)?,
+            ),
+            1 => CustomActionId::String(reader.string()?.into()),
+            _ => return Err(DecodeError::InvalidValue),
+        };
+        Ok(CustomAction {
+            id,
+            description: reader.string()?.into(),
+        })
+    }
+}
+
+macro_rules! vec_values {
+    ($($type:ty),+) => {
+        $(impl Value for Vec<$type> {
+            fn encode(&self, writer: &mut Writer, base: NodeId) {
+                writer.unsigned(self.len() as u64);
+                for item in self {
+                    item.encode(writer, base);
+                }
+            }
+
+            fn decode(reader: &mut Reader, base: NodeId) -> Result<Self, DecodeError> {
+                let len = reader.len(1)?;
+                (0..len).map(|_| <$type>::decode(reader, base)).collect()
+            }
+        })*
+    }
+}
+
+vec_values! { CustomAction, TextSelection }
+
+macro_rules! enum_values {
+    ($($type:ident),+) => {
+        $(impl Value for $type {
+            fn encode(&self, writer: &mut Writer, _: NodeId) {
+                writer.byte(*self as u8);
+            }
+
+            fn decode(reader: &mut Reader, _: NodeId) -> Result<Self, DecodeError> {
+                $type::n(reader.byte()?).ok_or(DecodeError::InvalidValue)
+            }
+        })*
+    }
+}
+
+enum_values! {
+    TextDecoration,
+    Invalid,
+    Toggled,
+    Live,
+    TextDirection,
+    Orientation,
+    SortDirection,
+    AriaCurrent,
+    AutoComplete,
+    HasPopup,
+    ListStyle,
+    TextAlign,
+    VerticalOffset,
+    ChangeReason
+}
+
+macro_rules! encode_property {
+    ($value:expr, $writer:ident, $base:ident, { $($variant:ident),+ }) => {
+        match $value {
+            PropertyValue::None => (),
+            $(PropertyValue::$variant(value) => value.encode($writer, $base),)*
+        }
+    }
+}
+
+macro_rules! decode_property {
+    ($reader:ident, $base:ident, $id:ident, { $($type:ident { $($property:ident),+ }),+ }) => {
+        match $id {
+            $($(PropertyId::$property)|+ => PropertyValue::$type(Value::decode($reader, $base)?),)*
+            PropertyId::Unset => return Err(DecodeError::InvalidValue),
+        }
+    }
+}
+
+/// Options that depend on the header of the update being decoded.
+#[derive(Clone, Copy)]
+struct DecodeOptions {
+    /// Whether the update was encoded against a newer schema version,
+    /// so that values this copy of the crate doesn't know about
+    /// should be skipped rather than rejected.
+    newer_schema: bool,
+}
+
+fn encode_properties(properties: &Properties, writer: &mut Writer, base: NodeId) {
+    let set_properties = || {
+        properties
+            .indices
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(_, index)| **index != PropertyId::Unset as u8)
+            .map(|(id, index)| (id, &properties.values[*index as usize]))
+            .filter(|(_, value)| !value.is_unset())
+    };
+    writer.unsigned(set_properties().count() as u64);
+    let mut previous_id = 0;
+    let mut value_writer = Writer(Vec::new());
+    for (id, value) in set_properties() {
+        writer.unsigned((id - previous_id) as u64);
+        previous_id = id;
+        value_writer.0.clear();
+        encode_value(value, &mut value_writer, base);
+        writer.unsigned(value_writer.0.len() as u64);
+        writer.0.extend_from_slice(&value_writer.0);
+    }
+}
+
+fn encode_value(value: &PropertyValue, writer: &mut Writer, base: NodeId) {
+    encode_property!(value, writer, base, {
+            NodeIdVec,
+            NodeId,
+            String,
+            F64,
+            Usize,
+            Color,
+            TextDecoration,
+            LengthSlice,
+            CoordSlice,
+            Bool,
+            Invalid,
+            Toggled,
+            Live,
+            TextDirection,
+            Orientation,
+            SortDirection,
+            AriaCurrent,
+            AutoComplete,
+            HasPopup,
+            ListStyle,
+            TextAlign,
+            VerticalOffset,
+            ChangeReason,
+            Affine,
+            Rect,
+            TextSelection,
+            CustomActionVec,
+            TextSelectionVec
+    });
+}
+
+fn decode_properties(
+    reader: &mut Reader,
+    base: NodeId,
+    options: DecodeOptions,
+) -> Result<Properties, DecodeError> {
+    let mut properties = Properties::default();
+    // Each property takes at least two bytes: its ID and the length
+    // of its value.
+    let count = reader.len(2)?;
+    let mut id = 0usize;
+    for i in 0..count {
+        let delta = reader.usize()?;
+        if i > 0 && delta == 0 {
+            return Err(DecodeError::InvalidValue);
+        }
+        id = id.checked_add(delta).ok_or(DecodeError::InvalidValue)?;
+        let len = reader.len(1)?;
+        let mut value_reader = Reader(reader.bytes(len)?);
+        let Some(property) = u8::try_from(id).ok().and_then(PropertyId::n) else {
+            if options.newer_schema {
+                continue;
+            }
+            return Err(DecodeError::InvalidValue);
+        };
+        match decode_value(&mut value_reader, base, property) {
+            Ok(value) if value_reader.0.is_empty() => properties.set(property, value),
+            _ if options.newer_schema => (),
+            Ok(_) => return Err(DecodeError::InvalidValue),
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(properties)
+}
+
+fn decode_value(
+    reader: &mut Reader,
+    base: NodeId,
+    property: PropertyId,
+) -> Result<PropertyValue, DecodeError> {
+    Ok(decode_property!(reader, base, property, {
+            NodeIdVec {
+                Children,
+                Controls,
+                Details,
+                DescribedBy,
+                FlowTo,
+                LabelledBy,
+                Owns,
+                RadioGroup
+            },
+            NodeId {
+                ActiveDescendant,
+                ErrorMessage,
+                InPageLinkTarget,
+                MemberOf,
+                NextOnLine,
+                PreviousOnLine,
+                PopupFor
+            },
+            String {
+                Label,
+                Description,
+                Value,
+                AccessKey,
+                AuthorId,
+                ClassName,
+                FontFamily,
+                HtmlTag,
+                InnerHtml,
+                KeyboardShortcut,
+                Language,
+                Placeholder,
+                RoleDescription,
+                StateDescription,
+                Tooltip,
+                Url,
+                RowIndexText,
+                ColumnIndexText,
+                Earcon,
+                HelpText,
+                Pronunciation
+            },
+            F64 {
+                ScrollX,
+                ScrollXMin,
+                ScrollXMax,
+                ScrollY,
+                ScrollYMin,
+                ScrollYMax,
+                NumericValue,
+                MinNumericValue,
+                MaxNumericValue,
+                NumericValueStep,
+                NumericValueJump,
+                FontSize,
+                FontWeight,
+                LetterSpacing,
+                LineHeight,
+                TextIndent,
+                MarginTop,
+                MarginBottom,
+                MarginLeading,
+                MarginTrailing
+            },
+            Usize {
+                RowCount,
+                ColumnCount,
+                RowIndex,
+                ColumnIndex,
+                RowSpan,
+                ColumnSpan,
+                Level,
+                SizeOfSet,
+                PositionInSet
+            },
+            Color {
+                ColorValue,
+                BackgroundColor,
+                ForegroundColor
+            },
+            TextDecoration {
+                Overline,
+                Strikethrough,
+                Underline
+            },
+            LengthSlice {
+                CharacterLengths,
+                WordLengths
+            },
+            CoordSlice {
+                CharacterPositions,
+                CharacterWidths
+            },
+            Bool {
+                Expanded,
+                Selected
+            },
+            Invalid { Invalid },
+            Toggled { Toggled },
+            Live { Live },
+            TextDirection { TextDirection },
+            Orientation { Orientation },
+            SortDirection { SortDirection },
+            AriaCurrent { AriaCurrent },
+            AutoComplete { AutoComplete },
+            HasPopup { HasPopup },
+            ListStyle { ListStyle },
+            TextAlign { TextAlign },
+            VerticalOffset { VerticalOffset },
+            ChangeReason { ChangeReason },
+            Affine { Transform },
+            Rect { Bounds },
+            TextSelection { TextSelection },
+            CustomActionVec { CustomActions },
+            TextSelectionVec { TextSelections }
+    }))
+}
+
+fn encode_node(node: &Node, writer: &mut Writer, id: NodeId) {
+    writer.byte(node.role as u8);
+    writer.unsigned(node.actions as u64);
+    writer.unsigned(node.child_actions as u64);
+    writer.unsigned(node.flags as u64);
+    encode_properties(&node.properties, writer, id);
+}
+
+fn decode_node(
+    reader: &mut Reader,
+    id: NodeId,
+    options: DecodeOptions,
+) -> Result<Node, DecodeError> {
+    let role = match Role::n(reader.byte()?) {
+        Some(role) => role,
+        None if options.newer_schema => Role::Unknown,
+        None => return Err(DecodeError::InvalidValue),
+    };
+    Ok(Node {
+        role,
+        actions: reader.u32()?,
+        child_actions: reader.u32()?,
+        flags: reader.u32()?,
+        properties: decode_properties(reader, id, options)?,
+    })
+}
+
+fn encode_optional_str(writer: &mut Writer, value: Option<&str>) {
+    match value {
+        Some(value) => {
+            writer.byte(1);
+            writer.str(value);
+        }
+        None => writer.byte(0),
+    }
+}
+
+fn decode_optional_string(reader: &mut Reader) -> Result<Option<String>, DecodeError> {
+    match reader.byte()? {
+        0 => Ok(None),
+        1 => reader.string().map(Some),
+        _ => Err(DecodeError::InvalidValue),
+    }
+}
+
+/// Encodes a tree update in the binary format described in the
+/// [module documentation](self).
+pub fn encode(update: &TreeUpdate) -> Vec<u8> {
+    let mut writer = Writer(Vec::new());
+    writer.0.extend_from_slice(MAGIC);
+    writer.byte(FORMAT_VERSION);
+    writer.unsigned(SCHEMA_VERSION as u64);
+
+    writer.unsigned(update.nodes.len() as u64);
+    let mut previous_id = NodeId(0);
+    for (id, node) in &update.nodes {
+        writer.node_id(*id, previous_id);
+        previous_id = *id;
+        encode_node(node, &mut writer, *id);
+    }
+
+    match &update.tree {
+        Some(tree) => {
+            writer.byte(1);
+            writer.unsigned(tree.root.0);
+            encode_optional_str(&mut writer, tree.toolkit_name.as_deref());
+            encode_optional_str(&mut writer, tree.toolkit_version.as_deref());
+        }
+        None => writer.byte(0),
+    }
+    writer.unsigned(update.focus.0);
+    writer.0
+}
+
+/// Decodes a tree update that was encoded with [`encode`].
+pub fn decode(bytes: &[u8]) -> Result<TreeUpdate, DecodeError> {
+    let mut reader = Reader(bytes);
+    if reader.bytes(MAGIC.len()).ok() != Some(&MAGIC[..]) {
+        return Err(DecodeError::NotAnUpdate);
+    }
+    let format_version = reader.byte()?;
+    if format_version != FORMAT_VERSION {
+        return Err(DecodeError::UnsupportedFormatVersion(format_version));
+    }
+    let options = DecodeOptions {
+        newer_schema: reader.u32()? > SCHEMA_VERSION,
+    };
+
+    // Each node takes at least six bytes: its ID, role, actions,
+    // child actions, flags, and property count.
+    let len = reader.len(6)?;
+    let mut nodes = Vec::with_capacity(len);
+    let mut previous_id = NodeId(0);
+    for _ in 0..len {
+        let id = reader.node_id(previous_id)?;
+        previous_id = id;
+        nodes.push((id, decode_node(&mut reader, id, options)?));
+    }
+
+    let tree = match reader.byte()? {
+        0 => None,
+        1 => Some(Tree {
+            root: NodeId(reader.unsigned()?),
+            toolkit_name: decode_optional_string(&mut reader)?,
+            toolkit_version: decode_optional_string(&mut reader)?,
+        }),
+        _ => return Err(DecodeError::InvalidValue),
+    };
+    let focus = NodeId(reader.unsigned()?);
+    if !reader.0.is_empty() {
+        return Err(DecodeError::TrailingBytes);
+    }
+    Ok(TreeUpdate { nodes, tree, focus })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Action;
+    use alloc::vec;
+
+    fn sample_update() -> TreeUpdate {
+        let mut root = Node::new(Role::Window);
+        root.set_children(vec![NodeId(1), NodeId(2)]);
+        root.set_label("Example");
+        root.set_bounds(Rect::new(0.0, 0.0, 800.0, 600.0));
+        let mut input = Node::new(Role::TextInput);
+        input.set_value("Hello");
+        input.set_character_lengths([1, 1, 1, 1, 1]);
+        input.set_character_positions([0.0, 7.5, 15.0, 22.5, 30.0]);
+        input.set_text_selection(TextSelection {
+            anchor: TextPosition {
+                node: NodeId(1),
+                character_index: 0,
+            },
+            focus: TextPosition {
+                node: NodeId(1),
+                character_index: 5,
+            },
+        });
+        input.set_transform(Affine::scale(2.0));
+        input.set_live(Live::Polite);
+        input.add_action(Action::Focus);
+        input.set_required();
+        let mut slider = Node::new(Role::Slider);
+        slider.set_numeric_value(-2.5);
+        slider.set_foreground_color(0xff00_00ff);
+        slider.set_expanded(false);
+        slider.set_labelled_by(vec![NodeId(u64::MAX)]);
+        slider.set_custom_actions(vec![
+            CustomAction {
+                id: 1.into(),
+                description: "Reset".into(),
+            },
+            CustomAction {
+                id: "snap".into(),
+                description: "Snap to grid".into(),
+            },
+        ]);
+        TreeUpdate {
+            nodes: vec![(NodeId(0), root), (NodeId(2), slider), (NodeId(1), input)],
+            tree: Some(Tree {
+                root: NodeId(0),
+                toolkit_name: Some("Example".into()),
+                toolkit_version: None,
+            }),
+            focus: NodeId(1),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let update = sample_update();
+        let bytes = encode(&update);
+        assert_eq!(decode(&bytes), Ok(update));
+
+        let empty = TreeUpdate {
+            nodes: vec![],
+            tree: None,
+            focus: NodeId(u64::MAX),
+        };
+        assert_eq!(decode(&encode(&empty)), Ok(empty));
+    }
+
+    #[test]
+    fn invalid_data() {
+        let bytes = encode(&sample_update());
+        assert_eq!(decode(b"{}"), Err(DecodeError::NotAnUpdate));
+        for len in 4..bytes.len() {
+            assert!(decode(&bytes[..len]).is_err());
+        }
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(decode(&trailing), Err(DecodeError::TrailingBytes));
+        let mut newer_format = bytes;
+        newer_format[4] = FORMAT_VERSION + 1;
+        assert_eq!(
+            decode(&newer_format),
+            Err(DecodeError::UnsupportedFormatVersion(FORMAT_VERSION + 1))
+        );
+    }
+
+    /// Encodes an update with a single node that has the given role,
+    /// a label, and one more property with the given ID and value.
+    fn encode_with_property(schema_version: u32, role: u8, id: u8, value: &[u8]) -> Vec<u8> {
+        let mut writer = Writer(Vec::new());
+        writer.0.extend_from_slice(MAGIC);
+        writer.byte(FORMAT_VERSION);
+        writer.unsigned(schema_version as u64);
+        writer.unsigned(1);
+        writer.node_id(NodeId(0), NodeId(0));
+        writer.byte(role);
+        writer.unsigned(0);
+        writer.unsigned(0);
+        writer.unsigned(0);
+        writer.unsigned(2);
+        writer.unsigned(PropertyId::Label as u64);
+        let mut label = Writer(Vec::new());
+        label.str("Root");
+        writer.unsigned(label.0.len() as u64);
+        writer.0.extend_from_slice(&label.0);
+        writer.unsigned((id - PropertyId::Label as u8) as u64);
+        writer.unsigned(value.len() as u64);
+        writer.0.extend_from_slice(value);
+        writer.byte(0);
+        writer.unsigned(0);
+        writer.0
+    }
+
+    fn decoded_root(bytes: &[u8]) -> Result<Node, DecodeError> {
+        decode(bytes).map(|mut update| update.nodes.remove(0).1)
+    }
+
+    #[test]
+    fn unknown_values_from_newer_schema() {
+        let unknown_id = PropertyId::Unset as u8 + 1;
+        let role = Role::Window as u8;
+
+        let node = decoded_root(&encode_with_property(
+            SCHEMA_VERSION + 1,
+            role,
+            unknown_id,
+            &[1, 2, 3],
+        ))
+        .unwrap();
+        assert_eq!(node.role(), Role::Window);
+        assert_eq!(node.label(), Some("Root"));
+        assert_eq!(
+            decoded_root(&encode_with_property(
+                SCHEMA_VERSION,
+                role,
+                unknown_id,
+                &[1, 2, 3]
+            )),
+            Err(DecodeError::InvalidValue)
+        );
+
+        let live = PropertyId::Live as u8;
+        let node =
+            decoded_root(&encode_with_property(SCHEMA_VERSION + 1, role, live, &[99])).unwrap();
+        assert_eq!(node.live(), None);
+        assert_eq!(node.label(), Some("Root"));
+        assert_eq!(
+            decoded_root(&encode_with_property(SCHEMA_VERSION, role, live, &[99])),
+            Err(DecodeError::InvalidValue)
+        );
+        let polite = Live::Polite as u8;
+        let node =
+            decoded_root(&encode_with_property(SCHEMA_VERSION, role, live, &[polite])).unwrap();
+        assert_eq!(node.live(), Some(Live::Polite));
+
+        let node = decoded_root(&encode_with_property(
+            SCHEMA_VERSION + 1,
+            u8::MAX,
+            live,
+            &[polite],
+        ))
+        .unwrap();
+        assert_eq!(node.role(), Role::Unknown);
+        assert_eq!(
+            decoded_root(&encode_with_property(
+                SCHEMA_VERSION,
+                u8::MAX,
+                live,
+                &[polite]
+            )),
+            Err(DecodeError::InvalidValue)
+        );
+    }
+}
@@ -9,10 +9,9 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-use core::{
-    fmt,
-    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
-};
+#[cfg(feature = "geometry")]
+use core::ops::{AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use core::{fmt, ops::Add};
 
 /// A 2D affine transform. Derived from [kurbo](https://github.com/linebender/kurbo).
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -78,6 +77,7 @@ impl Affine {
     ///
     /// Useful when you want to draw into the unit square but have your output fill any rectangle.
     /// In this case push the `Affine` onto the transform stack.
+    #[cfg(feature = "geometry")]
     pub fn map_unit_square(rect: Rect) -> Affine {
         Affine([rect.width(), 0., 0., rect.height(), rect.x0, rect.y0])
     }
@@ -89,6 +89,7 @@ impl Affine {
     }
 
     /// Compute the determinant of this transform.
+    #[cfg(feature = "geometry")]
     pub fn determinant(self) -> f64 {
         self.0[0] * self.0[3] - self.0[1] * self.0[2]
     }
@@ -96,6 +97,7 @@ impl Affine {
     /// Compute the inverse transform.
     ///
     /// Produces NaN values when the determinant is zero.
+    #[cfg(feature = "geometry")]
     pub fn inverse(self) -> Affine {
         let inv_det = self.determinant().recip();
         Affine([
@@ -115,6 +117,7 @@ impl Affine {
     /// returned `Rect` is the transformed rectangle.
     ///
     /// The returned rectangle always has non-negative width and height.
+    #[cfg(feature = "geometry")]
     pub fn transform_rect_bbox(self, rect: Rect) -> Rect {
         let p00 = self * Point::new(rect.x0, rect.y0);
         let p01 = self * Point::new(rect.x0, rect.y1);
@@ -124,6 +127,7 @@ impl Affine {
     }
 
     /// Is this map finite?
+    #[cfg(feature = "geometry")]
     #[inline]
     pub fn is_finite(&self) -> bool {
         self.0[0].is_finite()
@@ -135,6 +139,7 @@ impl Affine {
     }
 
     /// Is this map NaN?
+    #[cfg(feature = "geometry")]
     #[inline]
     pub fn is_nan(&self) -> bool {
         self.0[0].is_nan()
@@ -153,6 +158,7 @@ impl Default for Affine {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Mul<Point> for Affine {
     type Output = Point;
 
@@ -165,6 +171,7 @@ impl Mul<Point> for Affine {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Mul for Affine {
     type Output = Affine;
 
@@ -181,6 +188,7 @@ impl Mul for Affine {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl MulAssign for Affine {
     #[inline]
     fn mul_assign(&mut self, other: Affine) {
@@ -188,6 +196,7 @@ impl MulAssign for Affine {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Mul<Affine> for f64 {
     type Output = Affine;
 
@@ -259,6 +268,7 @@ impl Add<Vec2> for Point {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl AddAssign<Vec2> for Point {
     #[inline]
     fn add_assign(&mut self, other: Vec2) {
@@ -266,6 +276,7 @@ impl AddAssign<Vec2> for Point {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Sub<Vec2> for Point {
     type Output = Point;
 
@@ -275,6 +286,7 @@ impl Sub<Vec2> for Point {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl SubAssign<Vec2> for Point {
     #[inline]
     fn sub_assign(&mut self, other: Vec2) {
@@ -282,6 +294,7 @@ impl SubAssign<Vec2> for Point {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Add<(f64, f64)> for Point {
     type Output = Point;
 
@@ -291,6 +304,7 @@ impl Add<(f64, f64)> for Point {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl AddAssign<(f64, f64)> for Point {
     #[inline]
     fn add_assign(&mut self, (x, y): (f64, f64)) {
@@ -298,6 +312,7 @@ impl AddAssign<(f64, f64)> for Point {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Sub<(f64, f64)> for Point {
     type Output = Point;
 
@@ -307,6 +322,7 @@ impl Sub<(f64, f64)> for Point {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl SubAssign<(f64, f64)> for Point {
     #[inline]
     fn sub_assign(&mut self, (x, y): (f64, f64)) {
@@ -314,6 +330,7 @@ impl SubAssign<(f64, f64)> for Point {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Sub<Point> for Point {
     type Output = Vec2;
 
@@ -357,6 +374,7 @@ impl From<(Point, Size)> for Rect {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Add<Vec2> for Rect {
     type Output = Rect;
 
@@ -366,6 +384,7 @@ impl Add<Vec2> for Rect {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Sub<Vec2> for Rect {
     type Output = Rect;
 
@@ -501,6 +520,7 @@ impl Rect {
     }
 
     /// The area of the rectangle.
+    #[cfg(feature = "geometry")]
     #[inline]
     pub fn area(&self) -> f64 {
         self.width() * self.height()
@@ -509,12 +529,14 @@ impl Rect {
     /// Whether this rectangle has zero area.
     ///
     /// Note: a rectangle with negative area is not considered empty.
+    #[cfg(feature = "geometry")]
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.area() == 0.0
     }
 
     /// Returns `true` if `point` lies within `self`.
+    #[cfg(feature = "geometry")]
     #[inline]
     pub fn contains(&self, point: Point) -> bool {
         point.x >= self.x0 && point.x < self.x1 && point.y >= self.y0 && point.y < self.y1
@@ -523,6 +545,7 @@ impl Rect {
     /// The smallest rectangle enclosing two rectangles.
     ///
     /// Results are valid only if width and height are non-negative.
+    #[cfg(feature = "geometry")]
     #[inline]
     pub fn union(&self, other: Rect) -> Rect {
         Rect::new(
@@ -540,6 +563,7 @@ impl Rect {
     /// points yields their enclosing rectangle.
     ///
     /// Results are valid only if width and height are non-negative.
+    #[cfg(feature = "geometry")]
     pub fn union_pt(&self, pt: Point) -> Rect {
         Rect::new(
             self.x0.min(pt.x),
@@ -553,6 +577,7 @@ impl Rect {
     ///
     /// The result is zero-area if either input has negative width or
     /// height. The result always has non-negative width and height.
+    #[cfg(feature = "geometry")]
     #[inline]
     pub fn intersect(&self, other: Rect) -> Rect {
         let x0 = self.x0.max(other.x0);
@@ -599,6 +624,7 @@ impl fmt::Debug for Size {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl MulAssign<f64> for Size {
     #[inline]
     fn mul_assign(&mut self, other: f64) {
@@ -609,6 +635,7 @@ impl MulAssign<f64> for Size {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Mul<Size> for f64 {
     type Output = Size;
 
@@ -618,6 +645,7 @@ impl Mul<Size> for f64 {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Mul<f64> for Size {
     type Output = Size;
 
@@ -630,6 +658,7 @@ impl Mul<f64> for Size {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl DivAssign<f64> for Size {
     #[inline]
     fn div_assign(&mut self, other: f64) {
@@ -640,6 +669,7 @@ impl DivAssign<f64> for Size {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Div<f64> for Size {
     type Output = Size;
 
@@ -652,6 +682,7 @@ impl Div<f64> for Size {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Add<Size> for Size {
     type Output = Size;
     #[inline]
@@ -663,6 +694,7 @@ impl Add<Size> for Size {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl AddAssign<Size> for Size {
     #[inline]
     fn add_assign(&mut self, other: Size) {
@@ -670,6 +702,7 @@ impl AddAssign<Size> for Size {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Sub<Size> for Size {
     type Output = Size;
     #[inline]
@@ -681,6 +714,7 @@ impl Sub<Size> for Size {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl SubAssign<Size> for Size {
     #[inline]
     fn sub_assign(&mut self, other: Size) {
@@ -758,6 +792,7 @@ impl From<Vec2> for (f64, f64) {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Add for Vec2 {
     type Output = Vec2;
 
@@ -770,6 +805,7 @@ impl Add for Vec2 {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl AddAssign for Vec2 {
     #[inline]
     fn add_assign(&mut self, other: Vec2) {
@@ -780,6 +816,7 @@ impl AddAssign for Vec2 {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Sub for Vec2 {
     type Output = Vec2;
 
@@ -792,6 +829,7 @@ impl Sub for Vec2 {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl SubAssign for Vec2 {
     #[inline]
     fn sub_assign(&mut self, other: Vec2) {
@@ -802,6 +840,7 @@ impl SubAssign for Vec2 {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Mul<f64> for Vec2 {
     type Output = Vec2;
 
@@ -814,6 +853,7 @@ impl Mul<f64> for Vec2 {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl MulAssign<f64> for Vec2 {
     #[inline]
     fn mul_assign(&mut self, other: f64) {
@@ -824,6 +864,7 @@ impl MulAssign<f64> for Vec2 {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Mul<Vec2> for f64 {
     type Output = Vec2;
 
@@ -833,6 +874,7 @@ impl Mul<Vec2> for f64 {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Div<f64> for Vec2 {
     type Output = Vec2;
 
@@ -846,6 +888,7 @@ impl Div<f64> for Vec2 {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl DivAssign<f64> for Vec2 {
     #[inline]
     fn div_assign(&mut self, other: f64) {
@@ -853,6 +896,7 @@ impl DivAssign<f64> for Vec2 {
     }
 }
 
+#[cfg(feature = "geometry")]
 impl Neg for Vec2 {
     type Output = Vec2;
 
@@ -13,7 +13,7 @@
 extern crate alloc;
 
 use alloc::{boxed::Box, string::String, vec::Vec};
-use core::fmt;
+use core::{fmt, future::Future, mem::size_of, pin::Pin};
 #[cfg(feature = "pyo3")]
 use pyo3::pyclass;
 #[cfg(feature = "schemars")]
@@ -32,6 +32,58 @@ use serde::{
 mod geometry;
 pub use geometry::{Affine, Point, Rect, Size, Vec2};
 
+#[cfg(feature = "binary")]
+pub mod binary;
+pub mod media;
+#[cfg(feature = "metadata")]
+pub mod metadata;
+
+/// The version of the AccessKit schema defined by this crate.
+///
+/// This is incremented whenever the meaning of the data structures in this
+/// crate changes in a way that the consumer library or a platform adapter
+/// must know about, independently of the crate's own semantic version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The error returned by [`check_schema_version`] when a crate was built
+/// against a different version of the AccessKit schema than the one
+/// defined by this copy of the `accesskit` crate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SchemaVersionMismatch {
+    /// The schema version that the calling crate was built against.
+    pub expected: u32,
+    /// The schema version defined by this copy of the `accesskit` crate.
+    pub actual: u32,
+}
+
+impl fmt::Display for SchemaVersionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AccessKit schema version mismatch: expected version {}, but the accesskit crate \
+            provides version {}. This usually means that the application's dependency graph \
+            includes incompatible versions of AccessKit crates.",
+            self.expected, self.actual
+        )
+    }
+}
+
+/// Checks that the schema version that a crate was built against
+/// matches the one defined by this crate. Crates that interpret
+/// AccessKit trees, such as `accesskit_consumer`, should call this
+/// during initialization.
+#[inline]
+pub fn check_schema_version(expected: u32) -> Result<(), SchemaVersionMismatch> {
+    if expected == SCHEMA_VERSION {
+        Ok(())
+    } else {
+        Err(SchemaVersionMismatch {
+            expected,
+            actual: SCHEMA_VERSION,
+        })
+    }
+}
+
 /// The type of an accessibility node.
 ///
 /// The majority of these roles come from the ARIA specification. Reference
@@ -41,7 +93,9 @@ pub use geometry::{Affine, Point, Rect, Size, Vec2};
 /// from Chromium. However, unlike Chromium's alphabetized list, this list
 /// is ordered roughly by expected usage frequency (with the notable exception
 /// of [`Role::Unknown`]). This is more efficient in serialization formats
-/// where integers use a variable-length encoding.
+/// where integers use a variable-length encoding. New roles are added
+/// at the end, though, so that the numeric value of an existing role
+/// never changes.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "enumn", derive(enumn::N))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -55,6 +109,11 @@ pub use geometry::{Affine, Point, Rect, Size, Vec2};
 pub enum Role {
     #[default]
     Unknown,
+    /// A run of text within a single line, with uniform formatting.
+    /// Where the formatting changes, e.g. for a bold word within
+    /// a sentence, the text must be split into separate runs, whose
+    /// formatting properties tell assistive technologies where each
+    /// format run starts and ends.
     TextRun,
     Cell,
     Label,
@@ -103,11 +162,33 @@ pub enum Role {
     TimeInput,
     EmailInput,
     NumberInput,
+
+    /// A text input whose content is secret. Platform adapters never expose
+    /// the actual value or text content of a password input to assistive
+    /// technologies. Instead, each character is replaced with a bullet
+    /// (U+2022), following the platform convention, and the input is marked
+    /// as a password field where the platform supports it. If the toolkit
+    /// already provides masked text, it should set
+    /// [`Node::set_is_value_masked`] so that the adapters expose the text
+    /// as is.
     PasswordInput,
     PhoneNumberInput,
     UrlInput,
 
     Abbr,
+    /// An important, usually time-sensitive message, such as an error
+    /// or a toast notification.
+    ///
+    /// To show a notification, add a node with this role, or with
+    /// [`Role::Status`] for less urgent messages, to the tree in an update
+    /// after the initial one, and give it a label containing the message.
+    /// Unless [`Node::set_live`] says otherwise, these roles are implicitly
+    /// live regions, assertive and polite respectively, so the platform
+    /// adapters announce the message as soon as the node is added, and
+    /// again if its label changes. Remove the node when the notification
+    /// is dismissed. Where the platform has a dedicated event for
+    /// notifications, such as the system alert event in UI Automation,
+    /// the adapters raise it as well.
     Alert,
     AlertDialog,
     Application,
@@ -117,6 +198,11 @@ pub enum Role {
     Blockquote,
     Canvas,
     Caption,
+    /// The insertion point in an editable text node, if the toolkit exposes
+    /// it as a node of its own. Consumers only report changes to this node
+    /// when the text selection of the text node that contains it changes,
+    /// so toolkits that update it every time the caret blinks don't
+    /// produce a stream of events.
     Caret,
     Code,
     ColorWell,
@@ -183,7 +269,25 @@ pub enum Role {
     Section,
     Slider,
     SpinButton,
+
+    /// A separator between panes that the user may be able to move.
+    /// Equivalent to the ARIA `separator` role on a focusable element.
+    ///
+    /// A movable splitter should be focusable, and should report its
+    /// position through [`Node::numeric_value`], with the allowed range
+    /// in [`Node::min_numeric_value`] and [`Node::max_numeric_value`].
+    /// The position is typically the size of the pane before the splitter,
+    /// either in pixels or as a percentage of the available space.
+    /// The splitter should also set [`Node::orientation`] and support
+    /// [`Action::Increment`] and [`Action::Decrement`], [`Action::SetValue`],
+    /// or both. Platform adapters that only offer a way to set the value
+    /// translate such requests into increments or decrements if the splitter
+    /// doesn't support [`Action::SetValue`].
     Splitter,
+
+    /// An advisory message, such as a status bar or a toast notification
+    /// that doesn't need immediate attention. This is implicitly a polite
+    /// live region. See [`Role::Alert`] for how to show notifications.
     Status,
     Strong,
     Suggestion,
@@ -267,7 +371,207 @@ pub enum Role {
     Terminal,
 }
 
+impl Role {
+    /// Returns the role with the given numeric value, if any.
+    #[cfg(not(feature = "enumn"))]
+    pub fn n(value: u8) -> Option<Self> {
+        // See the equivalent function for `Action`.
+        match value {
+            0 => Some(Role::Unknown),
+            1 => Some(Role::TextRun),
+            2 => Some(Role::Cell),
+            3 => Some(Role::Label),
+            4 => Some(Role::Image),
+            5 => Some(Role::Link),
+            6 => Some(Role::Row),
+            7 => Some(Role::ListItem),
+            8 => Some(Role::ListMarker),
+            9 => Some(Role::TreeItem),
+            10 => Some(Role::ListBoxOption),
+            11 => Some(Role::MenuItem),
+            12 => Some(Role::MenuListOption),
+            13 => Some(Role::Paragraph),
+            14 => Some(Role::GenericContainer),
+            15 => Some(Role::CheckBox),
+            16 => Some(Role::RadioButton),
+            17 => Some(Role::TextInput),
+            18 => Some(Role::Button),
+            19 => Some(Role::DefaultButton),
+            20 => Some(Role::Pane),
+            21 => Some(Role::RowHeader),
+            22 => Some(Role::ColumnHeader),
+            23 => Some(Role::RowGroup),
+            24 => Some(Role::List),
+            25 => Some(Role::Table),
+            26 => Some(Role::LayoutTableCell),
+            27 => Some(Role::LayoutTableRow),
+            28 => Some(Role::LayoutTable),
+            29 => Some(Role::Switch),
+            30 => Some(Role::Menu),
+            31 => Some(Role::MultilineTextInput),
+            32 => Some(Role::SearchInput),
+            33 => Some(Role::DateInput),
+            34 => Some(Role::DateTimeInput),
+            35 => Some(Role::WeekInput),
+            36 => Some(Role::MonthInput),
+            37 => Some(Role::TimeInput),
+            38 => Some(Role::EmailInput),
+            39 => Some(Role::NumberInput),
+            40 => Some(Role::PasswordInput),
+            41 => Some(Role::PhoneNumberInput),
+            42 => Some(Role::UrlInput),
+            43 => Some(Role::Abbr),
+            44 => Some(Role::Alert),
+            45 => Some(Role::AlertDialog),
+            46 => Some(Role::Application),
+            47 => Some(Role::Article),
+            48 => Some(Role::Audio),
+            49 => Some(Role::Banner),
+            50 => Some(Role::Blockquote),
+            51 => Some(Role::Canvas),
+            52 => Some(Role::Caption),
+            53 => Some(Role::Caret),
+            54 => Some(Role::Code),
+            55 => Some(Role::ColorWell),
+            56 => Some(Role::ComboBox),
+            57 => Some(Role::EditableComboBox),
+            58 => Some(Role::Complementary),
+            59 => Some(Role::Comment),
+            60 => Some(Role::ContentDeletion),
+            61 => Some(Role::ContentInsertion),
+            62 => Some(Role::ContentInfo),
+            63 => Some(Role::Definition),
+            64 => Some(Role::DescriptionList),
+            65 => Some(Role::DescriptionListDetail),
+            66 => Some(Role::DescriptionListTerm),
+            67 => Some(Role::Details),
+            68 => Some(Role::Dialog),
+            69 => Some(Role::Directory),
+            70 => Some(Role::DisclosureTriangle),
+            71 => Some(Role::Document),
+            72 => Some(Role::EmbeddedObject),
+            73 => Some(Role::Emphasis),
+            74 => Some(Role::Feed),
+            75 => Some(Role::FigureCaption),
+            76 => Some(Role::Figure),
+            77 => Some(Role::Footer),
+            78 => Some(Role::FooterAsNonLandmark),
+            79 => Some(Role::Form),
+            80 => Some(Role::Grid),
+            81 => Some(Role::Group),
+            82 => Some(Role::Header),
+            83 => Some(Role::HeaderAsNonLandmark),
+            84 => Some(Role::Heading),
+            85 => Some(Role::Iframe),
+            86 => Some(Role::IframePresentational),
+            87 => Some(Role::ImeCandidate),
+            88 => Some(Role::Keyboard),
+            89 => Some(Role::Legend),
+            90 => Some(Role::LineBreak),
+            91 => Some(Role::ListBox),
+            92 => Some(Role::Log),
+            93 => Some(Role::Main),
+            94 => Some(Role::Mark),
+            95 => Some(Role::Marquee),
+            96 => Some(Role::Math),
+            97 => Some(Role::MenuBar),
+            98 => Some(Role::MenuItemCheckBox),
+            99 => Some(Role::MenuItemRadio),
+            100 => Some(Role::MenuListPopup),
+            101 => Some(Role::Meter),
+            102 => Some(Role::Navigation),
+            103 => Some(Role::Note),
+            104 => Some(Role::PluginObject),
+            105 => Some(Role::Portal),
+            106 => Some(Role::Pre),
+            107 => Some(Role::ProgressIndicator),
+            108 => Some(Role::RadioGroup),
+            109 => Some(Role::Region),
+            110 => Some(Role::RootWebArea),
+            111 => Some(Role::Ruby),
+            112 => Some(Role::RubyAnnotation),
+            113 => Some(Role::ScrollBar),
+            114 => Some(Role::ScrollView),
+            115 => Some(Role::Search),
+            116 => Some(Role::Section),
+            117 => Some(Role::Slider),
+            118 => Some(Role::SpinButton),
+            119 => Some(Role::Splitter),
+            120 => Some(Role::Status),
+            121 => Some(Role::Strong),
+            122 => Some(Role::Suggestion),
+            123 => Some(Role::SvgRoot),
+            124 => Some(Role::Tab),
+            125 => Some(Role::TabList),
+            126 => Some(Role::TabPanel),
+            127 => Some(Role::Term),
+            128 => Some(Role::Time),
+            129 => Some(Role::Timer),
+            130 => Some(Role::TitleBar),
+            131 => Some(Role::Toolbar),
+            132 => Some(Role::Tooltip),
+            133 => Some(Role::Tree),
+            134 => Some(Role::TreeGrid),
+            135 => Some(Role::Video),
+            136 => Some(Role::WebView),
+            137 => Some(Role::Window),
+            138 => Some(Role::PdfActionableHighlight),
+            139 => Some(Role::PdfRoot),
+            140 => Some(Role::GraphicsDocument),
+            141 => Some(Role::GraphicsObject),
+            142 => Some(Role::GraphicsSymbol),
+            143 => Some(Role::DocAbstract),
+            144 => Some(Role::DocAcknowledgements),
+            145 => Some(Role::DocAfterword),
+            146 => Some(Role::DocAppendix),
+            147 => Some(Role::DocBackLink),
+            148 => Some(Role::DocBiblioEntry),
+            149 => Some(Role::DocBibliography),
+            150 => Some(Role::DocBiblioRef),
+            151 => Some(Role::DocChapter),
+            152 => Some(Role::DocColophon),
+            153 => Some(Role::DocConclusion),
+            154 => Some(Role::DocCover),
+            155 => Some(Role::DocCredit),
+            156 => Some(Role::DocCredits),
+            157 => Some(Role::DocDedication),
+            158 => Some(Role::DocEndnote),
+            159 => Some(Role::DocEndnotes),
+            160 => Some(Role::DocEpigraph),
+            161 => Some(Role::DocEpilogue),
+            162 => Some(Role::DocErrata),
+            163 => Some(Role::DocExample),
+            164 => Some(Role::DocFootnote),
+            165 => Some(Role::DocForeword),
+            166 => Some(Role::DocGlossary),
+            167 => Some(Role::DocGlossRef),
+            168 => Some(Role::DocIndex),
+            169 => Some(Role::DocIntroduction),
+            170 => Some(Role::DocNoteRef),
+            171 => Some(Role::DocNotice),
+            172 => Some(Role::DocPageBreak),
+            173 => Some(Role::DocPageFooter),
+            174 => Some(Role::DocPageHeader),
+            175 => Some(Role::DocPageList),
+            176 => Some(Role::DocPart),
+            177 => Some(Role::DocPreface),
+            178 => Some(Role::DocPrologue),
+            179 => Some(Role::DocPullquote),
+            180 => Some(Role::DocQna),
+            181 => Some(Role::DocSubtitle),
+            182 => Some(Role::DocTip),
+            183 => Some(Role::DocToc),
+            184 => Some(Role::ListGrid),
+            185 => Some(Role::Terminal),
+            _ => None,
+        }
+    }
+}
+
 /// An action to be taken on an accessibility node.
+///
+/// New actions are added at the end, so that the numeric value of
+/// an existing action never changes.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "enumn", derive(enumn::N))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -341,6 +645,32 @@ pub enum Action {
     SetValue,
 
     ShowContextMenu,
+
+    /// Change a numeric value by an arbitrary amount, like when adjusting
+    /// a slider with an analog stick. Unlike [`Action::Increment`] and
+    /// [`Action::Decrement`], the change isn't limited to a single step.
+    /// Requires [`ActionRequest::data`] to be set to
+    /// [`ActionData::NumericValue`], giving the amount to add to
+    /// the current value, which is negative to decrease it.
+    SetNumericValueRelative,
+
+    /// Select this node within its container, such as an item in
+    /// a list box or a tab in a tab list. If the container is
+    /// multiselectable, this adds the node to the existing selection.
+    Select,
+
+    /// Remove this node from its container's selection.
+    Deselect,
+
+    /// Start dragging this node, as in a drag-and-drop operation.
+    /// While it's being dragged, the node should be marked as grabbed
+    /// with [`Node::set_grabbed`].
+    Drag,
+
+    /// Drop the node that is currently being dragged onto this node,
+    /// which should be marked as a drop target with
+    /// [`Node::set_drop_target`].
+    Drop,
 }
 
 impl Action {
@@ -378,6 +708,11 @@ impl Action {
             21 => Some(Action::SetSequentialFocusNavigationStartingPoint),
             22 => Some(Action::SetValue),
             23 => Some(Action::ShowContextMenu),
+            24 => Some(Action::SetNumericValueRelative),
+            25 => Some(Action::Select),
+            26 => Some(Action::Deselect),
+            27 => Some(Action::Drag),
+            28 => Some(Action::Drop),
             _ => None,
         }
     }
@@ -533,6 +868,32 @@ pub enum Live {
     Assertive,
 }
 
+/// How urgently an announcement made outside the tree, for example with
+/// the `announce` method of a platform adapter, should be spoken.
+///
+/// This is the counterpart of [`Live`] for text that isn't associated with
+/// any node. Use it when the application needs a screen reader to speak
+/// something, such as the result of a background operation, and there is
+/// no visible content that would make a natural live region.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "enumn", derive(enumn::N))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "pyo3",
+    pyclass(module = "accesskit", rename_all = "SCREAMING_SNAKE_CASE")
+)]
+#[repr(u8)]
+pub enum AnnouncementKind {
+    /// The announcement is queued after whatever the screen reader
+    /// is currently speaking.
+    Polite,
+    /// The announcement interrupts whatever the screen reader
+    /// is currently speaking.
+    Assertive,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "enumn", derive(enumn::N))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -604,6 +965,27 @@ pub enum VerticalOffset {
     Superscript,
 }
 
+/// The cause of the most recent change to a node's value or text.
+/// See [`Node::change_reason`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "enumn", derive(enumn::N))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "pyo3",
+    pyclass(module = "accesskit", rename_all = "SCREAMING_SNAKE_CASE")
+)]
+#[repr(u8)]
+pub enum ChangeReason {
+    /// The change was made directly by the user, e.g. by typing,
+    /// pasting, or dragging a slider.
+    User,
+    /// The change was made by the application, e.g. by resetting a form
+    /// or filling in a field automatically.
+    Programmatic,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "enumn", derive(enumn::N))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -645,6 +1027,69 @@ impl From<NodeId> for NodeIdContent {
     }
 }
 
+/// The identifier of a [`CustomAction`], unique among the custom actions
+/// of a single node.
+///
+/// Toolkits that already maintain a registry of integer action IDs can
+/// keep using them, while others can use a stable string instead.
+/// Either way, the ID is passed back unchanged in
+/// [`ActionData::CustomAction`] when an assistive technology requests
+/// the action.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum CustomActionId {
+    Integer(i32),
+    String(Box<str>),
+}
+
+impl CustomActionId {
+    #[inline]
+    pub fn as_i32(&self) -> Option<i32> {
+        match self {
+            Self::Integer(id) => Some(*id),
+            Self::String(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Integer(_) => None,
+            Self::String(id) => Some(id),
+        }
+    }
+}
+
+impl From<i32> for CustomActionId {
+    #[inline]
+    fn from(id: i32) -> Self {
+        Self::Integer(id)
+    }
+}
+
+impl From<&str> for CustomActionId {
+    #[inline]
+    fn from(id: &str) -> Self {
+        Self::String(id.into())
+    }
+}
+
+impl From<String> for CustomActionId {
+    #[inline]
+    fn from(id: String) -> Self {
+        Self::String(id.into())
+    }
+}
+
+impl From<Box<str>> for CustomActionId {
+    #[inline]
+    fn from(id: Box<str>) -> Self {
+        Self::String(id)
+    }
+}
+
 /// Defines a custom action for a UI element.
 ///
 /// For example, a list UI can allow a user to reorder items in the list by dragging the
@@ -655,7 +1100,7 @@ impl From<NodeId> for NodeIdContent {
 #[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct CustomAction {
-    pub id: i32,
+    pub id: CustomActionId,
     pub description: Box<str>,
 }
 
@@ -690,7 +1135,7 @@ pub struct TextSelection {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize, enumn::N))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[repr(u8)]
@@ -715,6 +1160,11 @@ enum Flag {
     IsGrammarError,
     IsSearchMatch,
     IsSuggestion,
+    IsValueMasked,
+    Inert,
+    Offscreen,
+    Grabbed,
+    DropTarget,
 }
 
 impl Flag {
@@ -751,18 +1201,47 @@ enum PropertyValue {
     ListStyle(ListStyle),
     TextAlign(TextAlign),
     VerticalOffset(VerticalOffset),
+    ChangeReason(ChangeReason),
     Affine(Box<Affine>),
     Rect(Rect),
     TextSelection(Box<TextSelection>),
     CustomActionVec(Vec<CustomAction>),
+    TextSelectionVec(Vec<TextSelection>),
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize, enumn::N))]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
-#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
-#[repr(u8)]
-enum PropertyId {
+// The property IDs are listed through a macro so that serialization can map
+// an index back to an ID without depending on the enumn crate.
+macro_rules! property_ids {
+    ($($id:ident),+) => {
+        /// Identifies a property of a node that is stored in the node's
+        /// property table, such as the label or the numeric value.
+        /// The role, actions, and boolean flags aren't identified
+        /// by this type.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        #[cfg_attr(feature = "schemars", derive(JsonSchema))]
+        #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+        #[non_exhaustive]
+        #[repr(u8)]
+        pub enum PropertyId {
+            $($id,)+
+            // This MUST be last.
+            #[doc(hidden)]
+            Unset,
+        }
+
+        impl PropertyId {
+            const ALL: [Self; Self::Unset as usize] = [$(Self::$id),+];
+
+            #[cfg(any(feature = "serde", feature = "binary"))]
+            fn n(value: u8) -> Option<Self> {
+                Self::ALL.get(value as usize).copied()
+            }
+        }
+    };
+}
+
+property_ids! {
     // NodeIdVec
     Children,
     Controls,
@@ -801,6 +1280,9 @@ enum PropertyId {
     Url,
     RowIndexText,
     ColumnIndexText,
+    Earcon,
+    HelpText,
+    Pronunciation,
 
     // f64
     ScrollX,
@@ -816,6 +1298,13 @@ enum PropertyId {
     NumericValueJump,
     FontSize,
     FontWeight,
+    LetterSpacing,
+    LineHeight,
+    TextIndent,
+    MarginTop,
+    MarginBottom,
+    MarginLeading,
+    MarginTrailing,
 
     // usize
     RowCount,
@@ -863,15 +1352,52 @@ enum PropertyId {
     ListStyle,
     TextAlign,
     VerticalOffset,
+    ChangeReason,
 
     // Other
     Transform,
     Bounds,
     TextSelection,
     CustomActions,
+    TextSelections
+}
 
-    // This MUST be last.
-    Unset,
+impl PropertyValue {
+    /// Returns true if this value is equivalent to the property not being
+    /// set at all. Empty vectors and slices are indistinguishable from unset
+    /// properties through the getters, so they're treated as unset.
+    fn is_unset(&self) -> bool {
+        match self {
+            PropertyValue::None => true,
+            PropertyValue::NodeIdVec(value) => value.is_empty(),
+            PropertyValue::LengthSlice(value) => value.is_empty(),
+            PropertyValue::CustomActionVec(value) => value.is_empty(),
+            PropertyValue::TextSelectionVec(value) => value.is_empty(),
+            _ => false,
+        }
+    }
+
+    fn heap_size(&self) -> usize {
+        match self {
+            PropertyValue::NodeIdVec(value) => value.capacity() * size_of::<NodeId>(),
+            PropertyValue::String(value) => value.len(),
+            PropertyValue::LengthSlice(value) => value.len(),
+            PropertyValue::CoordSlice(value) => value.len() * size_of::<f32>(),
+            PropertyValue::Affine(_) => size_of::<Affine>(),
+            PropertyValue::TextSelection(_) => size_of::<TextSelection>(),
+            PropertyValue::CustomActionVec(value) => {
+                value.capacity() * size_of::<CustomAction>()
+                    + value
+                        .iter()
+                        .map(|action| {
+                            action.description.len() + action.id.as_str().map_or(0, str::len)
+                        })
+                        .sum::<usize>()
+            }
+            PropertyValue::TextSelectionVec(value) => value.capacity() * size_of::<TextSelection>(),
+            _ => 0,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -884,12 +1410,19 @@ impl Default for PropertyIndices {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 struct FrozenProperties {
     indices: PropertyIndices,
     values: Box<[PropertyValue]>,
 }
 
+impl PartialEq for FrozenProperties {
+    fn eq(&self, other: &Self) -> bool {
+        self.indices
+            .canonical_eq(&self.values, &other.indices, &other.values)
+    }
+}
+
 /// An accessibility node snapshot that can't be modified. This is not used by
 /// toolkits or applications, but only by code that retains an AccessKit tree
 /// in memory, such as the `accesskit_consumer` crate.
@@ -897,16 +1430,24 @@ struct FrozenProperties {
 pub struct FrozenNode {
     role: Role,
     actions: u32,
+    child_actions: u32,
     flags: u32,
     properties: FrozenProperties,
 }
 
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default)]
 struct Properties {
     indices: PropertyIndices,
     values: Vec<PropertyValue>,
 }
 
+impl PartialEq for Properties {
+    fn eq(&self, other: &Self) -> bool {
+        self.indices
+            .canonical_eq(&self.values, &other.indices, &other.values)
+    }
+}
+
 /// A single accessible object. A complete UI is represented as a tree of these.
 ///
 /// For brevity, and to make more of the documentation usable in bindings
@@ -921,19 +1462,45 @@ struct Properties {
 pub struct Node {
     role: Role,
     actions: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    child_actions: u32,
     flags: u32,
     properties: Properties,
 }
 
 impl PropertyIndices {
     fn get<'a>(&self, values: &'a [PropertyValue], id: PropertyId) -> &'a PropertyValue {
-        let index = self.0[id as usize];
+        self.get_by_raw_id(values, id as usize)
+    }
+
+    fn get_by_raw_id<'a>(&self, values: &'a [PropertyValue], id: usize) -> &'a PropertyValue {
+        let index = self.0[id];
         if index == PropertyId::Unset as u8 {
             &PropertyValue::None
         } else {
             &values[index as usize]
         }
     }
+
+    /// Compares two sets of properties independently of the order in which
+    /// the properties were set, treating cleared properties and empty
+    /// vectors the same as properties that were never set.
+    fn canonical_eq(
+        &self,
+        values: &[PropertyValue],
+        other: &PropertyIndices,
+        other_values: &[PropertyValue],
+    ) -> bool {
+        (0..(PropertyId::Unset as usize)).all(|id| {
+            let value = self.get_by_raw_id(values, id);
+            let other_value = other.get_by_raw_id(other_values, id);
+            if value.is_unset() {
+                other_value.is_unset()
+            } else {
+                value == other_value
+            }
+        })
+    }
 }
 
 fn unexpected_property_type() -> ! {
@@ -957,6 +1524,10 @@ impl Properties {
     }
 
     fn set(&mut self, id: PropertyId, value: PropertyValue) {
+        if value.is_unset() {
+            self.clear(id);
+            return;
+        }
         let index = self.indices.0[id as usize];
         if index == PropertyId::Unset as u8 {
             self.values.push(value);
@@ -983,10 +1554,71 @@ impl From<Properties> for FrozenProperties {
     }
 }
 
+impl From<&FrozenProperties> for Properties {
+    fn from(props: &FrozenProperties) -> Self {
+        Self {
+            indices: props.indices,
+            values: props.values.to_vec(),
+        }
+    }
+}
+
+impl FrozenProperties {
+    /// Returns a copy of these properties with the given values replaced,
+    /// only growing the values for properties that weren't already set.
+    fn with_values<const N: usize>(&self, replacements: [(PropertyId, PropertyValue); N]) -> Self {
+        let mut indices = self.indices;
+        let added = replacements
+            .iter()
+            .filter(|(id, value)| {
+                indices.0[*id as usize] == PropertyId::Unset as u8 && !value.is_unset()
+            })
+            .count();
+        let mut values = Vec::with_capacity(self.values.len() + added);
+        values.extend_from_slice(&self.values);
+        for (id, value) in replacements {
+            let index = indices.0[id as usize];
+            if index != PropertyId::Unset as u8 {
+                values[index as usize] = value;
+            } else if !value.is_unset() {
+                values.push(value);
+                indices.0[id as usize] = (values.len() - 1) as u8;
+            }
+        }
+        Self {
+            indices,
+            values: values.into_boxed_slice(),
+        }
+    }
+}
+
+macro_rules! property_info_list {
+    ($list:ident, $value_type:ident, [$(($id:ident, $getter:ident),)*]) => {
+        property_info_list! { $list, [$(($id, $getter, metadata::ValueType::$value_type),)*] }
+    };
+    ($list:ident, [$(($id:ident, $getter:ident, $value_type:expr),)*]) => {
+        #[cfg(feature = "metadata")]
+        impl metadata::PropertyInfo {
+            pub(crate) const $list: &'static [Self] = &[$(Self {
+                name: stringify!($id),
+                getter: stringify!($getter),
+                value_type: $value_type,
+                doc: metadata::Docs::$getter,
+            },)*];
+        }
+    };
+}
+
 macro_rules! flag_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+        #[cfg(feature = "metadata")]
+        impl metadata::Docs {
+            $(#[allow(non_upper_case_globals)]
+            pub(crate) const $getter: &'static str = concat!($($doc, "\n",)*);)*
+        }
+        property_info_list! { FLAGS, Flag, [$(($id, $getter),)*] }
         impl FrozenNode {
-            $($(#[$doc])*
+            $($(#[doc = $doc])*
             #[inline]
             pub fn $getter(&self) -> bool {
                 (self.flags & (Flag::$id).mask()) != 0
@@ -1000,7 +1632,7 @@ macro_rules! flag_methods {
             }
         }
         impl Node {
-            $($(#[$doc])*
+            $($(#[doc = $doc])*
             #[inline]
             pub fn $getter(&self) -> bool {
                 (self.flags & (Flag::$id).mask()) != 0
@@ -1021,6 +1653,13 @@ macro_rules! flag_methods {
                 )*
             }
         }
+        impl NodeBuilder {
+            $(#[inline]
+            pub fn $setter(mut self) -> Self {
+                self.0.$setter();
+                self
+            })*
+        }
     }
 }
 
@@ -1108,16 +1747,21 @@ macro_rules! vec_type_methods {
 }
 
 macro_rules! property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $type_getter:ident, $getter_result:ty, $setter:ident, $type_setter:ident, $setter_param:ty, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $type_getter:ident, $getter_result:ty, $setter:ident, $type_setter:ident, $setter_param:ty, $clearer:ident)),+) => {
+        #[cfg(feature = "metadata")]
+        impl metadata::Docs {
+            $(#[allow(non_upper_case_globals)]
+            pub(crate) const $getter: &'static str = concat!($($doc, "\n",)*);)*
+        }
         impl FrozenNode {
-            $($(#[$doc])*
+            $($(#[doc = $doc])*
             #[inline]
             pub fn $getter(&self) -> $getter_result {
                 self.properties.indices.$type_getter(&self.properties.values, PropertyId::$id)
             })*
         }
         impl Node {
-            $($(#[$doc])*
+            $($(#[doc = $doc])*
             #[inline]
             pub fn $getter(&self) -> $getter_result {
                 self.properties.indices.$type_getter(&self.properties.values, PropertyId::$id)
@@ -1131,13 +1775,20 @@ macro_rules! property_methods {
                 self.properties.clear(PropertyId::$id);
             })*
         }
+        impl NodeBuilder {
+            $(#[inline]
+            pub fn $setter(mut self, value: $setter_param) -> Self {
+                self.0.$setter(value);
+                self
+            })*
+        }
     }
 }
 
 macro_rules! vec_property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $item_type:ty, $getter:ident, $type_getter:ident, $setter:ident, $type_setter:ident, $pusher:ident, $type_pusher:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $item_type:ty, $getter:ident, $type_getter:ident, $setter:ident, $type_setter:ident, $pusher:ident, $type_pusher:ident, $clearer:ident)),+) => {
         $(property_methods! {
-            $(#[$doc])*
+            $(#[doc = $doc])*
             ($id, $getter, $type_getter, &[$item_type], $setter, $type_setter, impl Into<Vec<$item_type>>, $clearer)
         }
         impl Node {
@@ -1145,6 +1796,13 @@ macro_rules! vec_property_methods {
             pub fn $pusher(&mut self, item: $item_type) {
                 self.$type_pusher(PropertyId::$id, item);
             }
+        }
+        impl NodeBuilder {
+            #[inline]
+            pub fn $pusher(mut self, item: $item_type) -> Self {
+                self.0.$pusher(item);
+                self
+            }
         })*
     }
 }
@@ -1163,11 +1821,12 @@ macro_rules! slice_properties_debug_method {
 }
 
 macro_rules! node_id_vec_property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $setter:ident, $pusher:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $setter:ident, $pusher:ident, $clearer:ident)),+) => {
         $(vec_property_methods! {
-            $(#[$doc])*
+            $(#[doc = $doc])*
             ($id, NodeId, $getter, get_node_id_vec, $setter, set_node_id_vec, $pusher, push_to_node_id_vec, $clearer)
         })*
+        property_info_list! { NODE_ID_VEC_PROPERTIES, NodeIdVec, [$(($id, $getter),)*] }
         impl FrozenNode {
             slice_properties_debug_method! { debug_node_id_vec_properties, [$($getter,)*] }
         }
@@ -1190,11 +1849,12 @@ macro_rules! option_properties_debug_method {
 }
 
 macro_rules! node_id_property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
         $(property_methods! {
-            $(#[$doc])*
+            $(#[doc = $doc])*
             ($id, $getter, get_node_id_property, Option<NodeId>, $setter, set_node_id_property, NodeId, $clearer)
         })*
+        property_info_list! { NODE_ID_PROPERTIES, NodeId, [$(($id, $getter),)*] }
         impl FrozenNode {
             option_properties_debug_method! { debug_node_id_properties, [$($getter,)*] }
         }
@@ -1205,11 +1865,12 @@ macro_rules! node_id_property_methods {
 }
 
 macro_rules! string_property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
         $(property_methods! {
-            $(#[$doc])*
+            $(#[doc = $doc])*
             ($id, $getter, get_string_property, Option<&str>, $setter, set_string_property, impl Into<Box<str>>, $clearer)
         })*
+        property_info_list! { STRING_PROPERTIES, String, [$(($id, $getter),)*] }
         impl FrozenNode {
             option_properties_debug_method! { debug_string_properties, [$($getter,)*] }
         }
@@ -1220,11 +1881,12 @@ macro_rules! string_property_methods {
 }
 
 macro_rules! f64_property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
         $(property_methods! {
-            $(#[$doc])*
+            $(#[doc = $doc])*
             ($id, $getter, get_f64_property, Option<f64>, $setter, set_f64_property, f64, $clearer)
         })*
+        property_info_list! { F64_PROPERTIES, F64, [$(($id, $getter),)*] }
         impl FrozenNode {
             option_properties_debug_method! { debug_f64_properties, [$($getter,)*] }
         }
@@ -1235,11 +1897,12 @@ macro_rules! f64_property_methods {
 }
 
 macro_rules! usize_property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
         $(property_methods! {
-            $(#[$doc])*
+            $(#[doc = $doc])*
             ($id, $getter, get_usize_property, Option<usize>, $setter, set_usize_property, usize, $clearer)
         })*
+        property_info_list! { USIZE_PROPERTIES, Usize, [$(($id, $getter),)*] }
         impl FrozenNode {
             option_properties_debug_method! { debug_usize_properties, [$($getter,)*] }
         }
@@ -1250,11 +1913,12 @@ macro_rules! usize_property_methods {
 }
 
 macro_rules! color_property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
         $(property_methods! {
-            $(#[$doc])*
+            $(#[doc = $doc])*
             ($id, $getter, get_color_property, Option<u32>, $setter, set_color_property, u32, $clearer)
         })*
+        property_info_list! { COLOR_PROPERTIES, Color, [$(($id, $getter),)*] }
         impl FrozenNode {
             option_properties_debug_method! { debug_color_properties, [$($getter,)*] }
         }
@@ -1265,11 +1929,12 @@ macro_rules! color_property_methods {
 }
 
 macro_rules! text_decoration_property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
         $(property_methods! {
-            $(#[$doc])*
+            $(#[doc = $doc])*
             ($id, $getter, get_text_decoration_property, Option<TextDecoration>, $setter, set_text_decoration_property, TextDecoration, $clearer)
         })*
+        property_info_list! { TEXT_DECORATION_PROPERTIES, TextDecoration, [$(($id, $getter),)*] }
         impl FrozenNode {
             option_properties_debug_method! { debug_text_decoration_properties, [$($getter,)*] }
         }
@@ -1280,11 +1945,12 @@ macro_rules! text_decoration_property_methods {
 }
 
 macro_rules! length_slice_property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
         $(property_methods! {
-            $(#[$doc])*
+            $(#[doc = $doc])*
             ($id, $getter, get_length_slice_property, &[u8], $setter, set_length_slice_property, impl Into<Box<[u8]>>, $clearer)
         })*
+        property_info_list! { LENGTH_SLICE_PROPERTIES, LengthSlice, [$(($id, $getter),)*] }
         impl FrozenNode {
             slice_properties_debug_method! { debug_length_slice_properties, [$($getter,)*] }
         }
@@ -1295,11 +1961,12 @@ macro_rules! length_slice_property_methods {
 }
 
 macro_rules! coord_slice_property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
         $(property_methods! {
-            $(#[$doc])*
+            $(#[doc = $doc])*
             ($id, $getter, get_coord_slice_property, Option<&[f32]>, $setter, set_coord_slice_property, impl Into<Box<[f32]>>, $clearer)
         })*
+        property_info_list! { COORD_SLICE_PROPERTIES, CoordSlice, [$(($id, $getter),)*] }
         impl FrozenNode {
             option_properties_debug_method! { debug_coord_slice_properties, [$($getter,)*] }
         }
@@ -1310,11 +1977,12 @@ macro_rules! coord_slice_property_methods {
 }
 
 macro_rules! bool_property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
         $(property_methods! {
-            $(#[$doc])*
+            $(#[doc = $doc])*
             ($id, $getter, get_bool_property, Option<bool>, $setter, set_bool_property, bool, $clearer)
         })*
+        property_info_list! { BOOL_PROPERTIES, Bool, [$(($id, $getter),)*] }
         impl FrozenNode {
             option_properties_debug_method! { debug_bool_properties, [$($getter,)*] }
         }
@@ -1325,9 +1993,15 @@ macro_rules! bool_property_methods {
 }
 
 macro_rules! unique_enum_property_methods {
-    ($($(#[$doc:meta])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+    ($($(#[doc = $doc:literal])* ($id:ident, $getter:ident, $setter:ident, $clearer:ident)),+) => {
+        #[cfg(feature = "metadata")]
+        impl metadata::Docs {
+            $(#[allow(non_upper_case_globals)]
+            pub(crate) const $getter: &'static str = concat!($($doc, "\n",)*);)*
+        }
+        property_info_list! { UNIQUE_ENUM_PROPERTIES, [$(($id, $getter, metadata::ValueType::Enum(stringify!($id))),)*] }
         impl FrozenNode {
-            $($(#[$doc])*
+            $($(#[doc = $doc])*
             #[inline]
             pub fn $getter(&self) -> Option<$id> {
                 match self.properties.indices.get(&self.properties.values, PropertyId::$id) {
@@ -1339,7 +2013,7 @@ macro_rules! unique_enum_property_methods {
             option_properties_debug_method! { debug_unique_enum_properties, [$($getter,)*] }
         }
         impl Node {
-            $($(#[$doc])*
+            $($(#[doc = $doc])*
             #[inline]
             pub fn $getter(&self) -> Option<$id> {
                 match self.properties.indices.get(&self.properties.values, PropertyId::$id) {
@@ -1358,6 +2032,13 @@ macro_rules! unique_enum_property_methods {
             })*
             option_properties_debug_method! { debug_unique_enum_properties, [$($getter,)*] }
         }
+        impl NodeBuilder {
+            $(#[inline]
+            pub fn $setter(mut self, value: $id) -> Self {
+                self.0.$setter(value);
+                self
+            })*
+        }
     }
 }
 
@@ -1369,6 +2050,187 @@ impl Node {
             ..Default::default()
         }
     }
+
+    /// Replaces every node ID referenced by this node, including its
+    /// children, relations, and text selection, with the result of `f`.
+    /// This is useful when combining trees whose IDs may collide.
+    pub fn map_node_ids(&mut self, mut f: impl FnMut(NodeId) -> NodeId) {
+        for value in &mut self.properties.values {
+            match value {
+                PropertyValue::NodeIdVec(ids) => {
+                    for id in ids {
+                        *id = f(*id);
+                    }
+                }
+                PropertyValue::NodeId(id) => *id = f(*id),
+                PropertyValue::TextSelection(selection) => {
+                    selection.anchor.node = f(selection.anchor.node);
+                    selection.focus.node = f(selection.focus.node);
+                }
+                PropertyValue::TextSelectionVec(selections) => {
+                    for selection in selections {
+                        selection.anchor.node = f(selection.anchor.node);
+                        selection.focus.node = f(selection.focus.node);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Builds a [`Node`] with chained method calls, optionally checking
+/// that the properties it sets are meaningful for its role.
+///
+/// The builder has the same setters as [`Node`], each of which takes
+/// and returns the builder, e.g.:
+///
+/// ```
+/// # use accesskit::{Node, Role};
+/// let node = Node::builder(Role::Slider)
+///     .set_label("Volume")
+///     .set_numeric_value(5.0)
+///     .set_min_numeric_value(0.0)
+///     .set_max_numeric_value(10.0)
+///     .try_build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct NodeBuilder(Node);
+
+impl NodeBuilder {
+    #[inline]
+    pub fn new(role: Role) -> Self {
+        Self(Node::new(role))
+    }
+
+    /// Returns the node without checking it for problems.
+    #[inline]
+    pub fn build(self) -> Node {
+        self.0
+    }
+
+    /// Returns the node, or the problems reported by [`Node::warnings`]
+    /// if there are any.
+    pub fn try_build(self) -> Result<Node, Vec<NodeWarning>> {
+        let warnings = self.0.warnings();
+        if warnings.is_empty() {
+            Ok(self.0)
+        } else {
+            Err(warnings)
+        }
+    }
+}
+
+/// A problem found by [`Node::warnings`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum NodeWarning {
+    /// The property is set, but platform adapters ignore it on nodes
+    /// with this role.
+    IgnoredProperty(&'static str),
+    /// The node has a numeric value, but not both a minimum and a maximum,
+    /// which its role requires.
+    MissingRange,
+    /// The minimum numeric value is greater than the maximum.
+    InvertedRange { min: f64, max: f64 },
+    /// The numeric value is outside the range given by the minimum
+    /// and maximum.
+    ValueOutOfRange { value: f64, min: f64, max: f64 },
+    /// The position in the set is greater than the size of the set.
+    PositionOutOfRange { position: usize, size: usize },
+}
+
+impl fmt::Display for NodeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IgnoredProperty(name) => {
+                write!(f, "`{}` is ignored on nodes with this role", name)
+            }
+            Self::MissingRange => write!(
+                f,
+                "`numeric_value` is set without `min_numeric_value` and `max_numeric_value`"
+            ),
+            Self::InvertedRange { min, max } => {
+                write!(
+                    f,
+                    "the minimum value {} is greater than the maximum {}",
+                    min, max
+                )
+            }
+            Self::ValueOutOfRange { value, min, max } => {
+                write!(
+                    f,
+                    "the value {} is outside the range {}..={}",
+                    value, min, max
+                )
+            }
+            Self::PositionOutOfRange { position, size } => {
+                write!(f, "position {} is outside a set of size {}", position, size)
+            }
+        }
+    }
+}
+
+impl Node {
+    /// Starts building a node with the given role. See [`NodeBuilder`].
+    #[inline]
+    pub fn builder(role: Role) -> NodeBuilder {
+        NodeBuilder::new(role)
+    }
+
+    /// Checks for combinations of role and properties that platform adapters
+    /// silently ignore or can't present correctly, such as a row index on
+    /// a node that isn't a row or a cell, or a slider with a value but no
+    /// range. An empty list doesn't guarantee that the node is correct.
+    pub fn warnings(&self) -> Vec<NodeWarning> {
+        let mut warnings = Vec::new();
+        let role = self.role;
+        let is_table = matches!(
+            role,
+            Role::Table | Role::Grid | Role::TreeGrid | Role::ListGrid
+        );
+        let is_cell = matches!(role, Role::Cell | Role::RowHeader | Role::ColumnHeader);
+        let is_row = role == Role::Row;
+
+        let mut check = |is_set: bool, is_allowed: bool, name: &'static str| {
+            if is_set && !is_allowed {
+                warnings.push(NodeWarning::IgnoredProperty(name));
+            }
+        };
+        check(self.row_count().is_some(), is_table, "row_count");
+        check(self.column_count().is_some(), is_table, "column_count");
+        check(self.row_index().is_some(), is_row || is_cell, "row_index");
+        check(self.column_index().is_some(), is_cell, "column_index");
+        check(self.row_span().is_some(), is_cell, "row_span");
+        check(self.column_span().is_some(), is_cell, "column_span");
+
+        let min = self.min_numeric_value();
+        let max = self.max_numeric_value();
+        if let Some(value) = self.numeric_value() {
+            let requires_range = matches!(role, Role::Slider | Role::ScrollBar | Role::Meter);
+            match (min, max) {
+                (Some(min), Some(max)) if min <= max && !(min..=max).contains(&value) => {
+                    warnings.push(NodeWarning::ValueOutOfRange { value, min, max });
+                }
+                (Some(_), Some(_)) => (),
+                _ if requires_range => warnings.push(NodeWarning::MissingRange),
+                _ => (),
+            }
+        }
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                warnings.push(NodeWarning::InvertedRange { min, max });
+            }
+        }
+
+        if let (Some(position), Some(size)) = (self.position_in_set(), self.size_of_set()) {
+            if position > size {
+                warnings.push(NodeWarning::PositionOutOfRange { position, size });
+            }
+        }
+        warnings
+    }
 }
 
 impl From<Node> for FrozenNode {
@@ -1376,17 +2238,80 @@ impl From<Node> for FrozenNode {
         Self {
             role: node.role,
             actions: node.actions,
+            child_actions: node.child_actions,
             flags: node.flags,
             properties: node.properties.into(),
         }
     }
 }
 
+/// Copies a retained node back into a mutable one, for example to
+/// reconstruct a [`TreeUpdate`] from the state held by a consumer.
+impl From<&FrozenNode> for Node {
+    fn from(node: &FrozenNode) -> Self {
+        Self {
+            role: node.role,
+            actions: node.actions,
+            child_actions: node.child_actions,
+            flags: node.flags,
+            properties: (&node.properties).into(),
+        }
+    }
+}
+
 impl FrozenNode {
     #[inline]
     pub fn role(&self) -> Role {
         self.role
     }
+
+    /// Returns a copy of this node with only its bounds and transform
+    /// replaced, as when applying a [`GeometryUpdate`]. `None` clears
+    /// the property.
+    pub fn with_geometry(&self, bounds: Option<Rect>, transform: Option<Affine>) -> Self {
+        let bounds = bounds.map_or(PropertyValue::None, PropertyValue::Rect);
+        let transform = transform.map_or(PropertyValue::None, |transform| {
+            PropertyValue::Affine(Box::new(transform))
+        });
+        Self {
+            properties: self.properties.with_values([
+                (PropertyId::Bounds, bounds),
+                (PropertyId::Transform, transform),
+            ]),
+            ..*self
+        }
+    }
+
+    /// Returns the IDs of the properties whose values differ between
+    /// this node and `other`, in ascending order. As with equality,
+    /// cleared properties and empty vectors are treated the same as
+    /// properties that were never set.
+    pub fn changed_properties<'a>(
+        &'a self,
+        other: &'a FrozenNode,
+    ) -> impl Iterator<Item = PropertyId> + 'a {
+        PropertyId::ALL.into_iter().filter(move |id| {
+            let value = self.properties.indices.get(&self.properties.values, *id);
+            let other_value = other.properties.indices.get(&other.properties.values, *id);
+            if value.is_unset() {
+                !other_value.is_unset()
+            } else {
+                value != other_value
+            }
+        })
+    }
+
+    /// Returns an approximation of the number of bytes owned by this node
+    /// on the heap, not including the size of the node itself.
+    pub fn heap_size(&self) -> usize {
+        self.properties.values.len() * size_of::<PropertyValue>()
+            + self
+                .properties
+                .values
+                .iter()
+                .map(PropertyValue::heap_size)
+                .sum::<usize>()
+    }
 }
 
 impl Node {
@@ -1398,6 +2323,18 @@ impl Node {
     pub fn set_role(&mut self, value: Role) {
         self.role = value;
     }
+
+    /// Returns an approximation of the number of bytes owned by this node
+    /// on the heap, not including the size of the node itself.
+    pub fn heap_size(&self) -> usize {
+        self.properties.values.capacity() * size_of::<PropertyValue>()
+            + self
+                .properties
+                .values
+                .iter()
+                .map(PropertyValue::heap_size)
+                .sum::<usize>()
+    }
 }
 
 impl FrozenNode {
@@ -1405,6 +2342,17 @@ impl FrozenNode {
     pub fn supports_action(&self, action: Action) -> bool {
         (self.actions & action.mask()) != 0
     }
+    #[inline]
+    pub fn child_supports_action(&self, action: Action) -> bool {
+        (self.child_actions & action.mask()) != 0
+    }
+    /// Returns whether this node declares the same child actions
+    /// as the other node, so consumers can tell when the effective
+    /// actions of this node's children have changed.
+    #[inline]
+    pub fn has_same_child_actions(&self, other: &FrozenNode) -> bool {
+        self.child_actions == other.child_actions
+    }
 }
 
 impl Node {
@@ -1424,6 +2372,48 @@ impl Node {
     pub fn clear_actions(&mut self) {
         self.actions = 0;
     }
+
+    /// Returns whether the given action is supported by all of this node's
+    /// children, in addition to any actions that the children themselves
+    /// declare. See [`Node::add_child_action`].
+    #[inline]
+    pub fn child_supports_action(&self, action: Action) -> bool {
+        (self.child_actions & action.mask()) != 0
+    }
+    /// Declare that all children of this node support the given action,
+    /// without having to add the action to each child. This is useful
+    /// for containers such as list boxes, whose items can typically all be
+    /// clicked or focused.
+    ///
+    /// Platform adapters treat an action supported by a node's nearest
+    /// ancestor that isn't filtered out of the platform tree (such as a
+    /// [`Role::GenericContainer`]) the same as an action supported by
+    /// the node itself. The action request is still sent to the child.
+    #[inline]
+    pub fn add_child_action(&mut self, action: Action) {
+        self.child_actions |= action.mask();
+    }
+    #[inline]
+    pub fn remove_child_action(&mut self, action: Action) {
+        self.child_actions &= !(action.mask());
+    }
+    #[inline]
+    pub fn clear_child_actions(&mut self) {
+        self.child_actions = 0;
+    }
+}
+
+impl NodeBuilder {
+    #[inline]
+    pub fn add_action(mut self, action: Action) -> Self {
+        self.0.add_action(action);
+        self
+    }
+    #[inline]
+    pub fn add_child_action(mut self, action: Action) -> Self {
+        self.0.add_child_action(action);
+        self
+    }
 }
 
 flag_methods! {
@@ -1459,7 +2449,33 @@ flag_methods! {
     (IsSpellingError, is_spelling_error, set_is_spelling_error, clear_is_spelling_error),
     (IsGrammarError, is_grammar_error, set_is_grammar_error, clear_is_grammar_error),
     (IsSearchMatch, is_search_match, set_is_search_match, clear_is_search_match),
-    (IsSuggestion, is_suggestion, set_is_suggestion, clear_is_suggestion)
+    (IsSuggestion, is_suggestion, set_is_suggestion, clear_is_suggestion),
+    /// Indicates that the value and text content of a
+    /// [`Role::PasswordInput`] node have already been masked by the toolkit,
+    /// so platform adapters must not mask them again.
+    (IsValueMasked, is_value_masked, set_is_value_masked, clear_is_value_masked),
+    /// Temporarily make this node and its descendants inert, for example
+    /// while the user drags an object or draws a selection marquee.
+    /// Inert nodes are excluded from the tree presented to assistive
+    /// technologies, as with [`Node::set_hidden`], and platform adapters
+    /// don't forward action requests targeting them. Unlike rebuilding
+    /// the tree, setting and clearing this flag only requires updating
+    /// a single node; platform adapters report the removal and
+    /// restoration of the subtree where the platform supports it.
+    (Inert, is_inert, set_inert, clear_inert),
+    /// Indicates that this node and its descendants are outside the visible
+    /// area, for example the items of a carousel that are scrolled out of
+    /// view, but should remain reachable by assistive technologies.
+    /// Unlike [`Node::set_hidden`], this doesn't exclude anything from the
+    /// tree presented to assistive technologies; platform adapters expose
+    /// such nodes as offscreen instead.
+    (Offscreen, is_offscreen, set_offscreen, clear_offscreen),
+    /// Indicates that this node is being dragged, as in
+    /// a drag-and-drop operation.
+    (Grabbed, is_grabbed, set_grabbed, clear_grabbed),
+    /// Indicates that the node that is being dragged can be dropped
+    /// onto this node.
+    (DropTarget, is_drop_target, set_drop_target, clear_drop_target)
 }
 
 option_ref_type_getters! {
@@ -1503,7 +2519,8 @@ copy_type_setters! {
 
 vec_type_methods! {
     (NodeId, NodeIdVec, get_node_id_vec, set_node_id_vec, push_to_node_id_vec),
-    (CustomAction, CustomActionVec, get_custom_action_vec, set_custom_action_vec, push_to_custom_action_vec)
+    (CustomAction, CustomActionVec, get_custom_action_vec, set_custom_action_vec, push_to_custom_action_vec),
+    (TextSelection, TextSelectionVec, get_text_selection_vec, set_text_selection_vec, push_to_text_selection_vec)
 }
 
 node_id_vec_property_methods! {
@@ -1552,6 +2569,11 @@ string_property_methods! {
     (AccessKey, access_key, set_access_key, clear_access_key),
     /// A way for application authors to identify this node for automated
     /// testing purpose. The value must be unique among this node's siblings.
+    ///
+    /// Platform adapters expose this to UI test frameworks as the
+    /// `AutomationId` property on Windows, `accessibilityIdentifier`
+    /// on macOS, and both the accessible ID and the `id` object attribute
+    /// in AT-SPI.
     (AuthorId, author_id, set_author_id, clear_author_id),
     (ClassName, class_name, set_class_name, clear_class_name),
     /// Only present when different from parent.
@@ -1587,7 +2609,37 @@ string_property_methods! {
     (Tooltip, tooltip, set_tooltip, clear_tooltip),
     (Url, url, set_url, clear_url),
     (RowIndexText, row_index_text, set_row_index_text, clear_row_index_text),
-    (ColumnIndexText, column_index_text, set_column_index_text, clear_column_index_text)
+    (ColumnIndexText, column_index_text, set_column_index_text, clear_column_index_text),
+    /// An identifier for a sound, or earcon, that an audio-first interface
+    /// may play when this node is presented, for example when it receives
+    /// focus. The meaning of the identifier is defined by the application,
+    /// e.g. a name from the application's sound theme. Platform adapters
+    /// expose it through vendor-specific attributes where available, such as
+    /// the `earcon` AT-SPI object attribute or the `earcon` entry in UIA's
+    /// `AriaProperties`.
+    (Earcon, earcon, set_earcon, clear_earcon),
+    /// Additional guidance about how to use this node, such as the format
+    /// expected by a text input, that assistive technologies present
+    /// on request rather than along with the node's label and description.
+    /// Platform adapters expose it as UIA's `HelpText`, falling back to
+    /// [`placeholder`] if this isn't set, as AT-SPI's `HelpText` property,
+    /// and as macOS's `accessibilityHelp`, falling back to [`description`].
+    ///
+    /// [`placeholder`]: Node::placeholder
+    /// [`description`]: Node::description
+    (HelpText, help_text, set_help_text, clear_help_text),
+    /// How the node's name should be spoken, if speech synthesizers are
+    /// likely to mispronounce it, such as "Doctor Smith" for "Dr. Smith" or
+    /// "get user I D" for a code identifier like `getUserId`. This is
+    /// a plain-text respelling in the node's language, not phonetic notation
+    /// or markup, and it only affects speech; the name is still shown
+    /// as is, for example on a braille display. Platform adapters expose it
+    /// through vendor-specific attributes, such as the `pronunciation`
+    /// AT-SPI object attribute or the `pronunciation` entry in UIA's
+    /// `AriaProperties`, and as the `AXAccessKitPronunciation` attribute
+    /// on macOS. UIAccessibility only accepts phonetic notation, so the iOS
+    /// adapter doesn't expose it.
+    (Pronunciation, pronunciation, set_pronunciation, clear_pronunciation)
 }
 
 f64_property_methods! {
@@ -1606,7 +2658,32 @@ f64_property_methods! {
     (FontSize, font_size, set_font_size, clear_font_size),
     /// Font weight can take on any arbitrary numeric value. Increments of 100 in
     /// range `[0, 900]` represent keywords such as light, normal, bold, etc.
-    (FontWeight, font_weight, set_font_weight, clear_font_weight)
+    (FontWeight, font_weight, set_font_weight, clear_font_weight),
+    /// Extra space between characters, in pixels. Only present when
+    /// different from parent. UI Automation has no equivalent, so this is
+    /// only exposed on AT-SPI, as the `letter-spacing` text attribute.
+    (LetterSpacing, letter_spacing, set_letter_spacing, clear_letter_spacing),
+    /// The height of each line of text, in pixels. Only present when
+    /// different from parent.
+    (LineHeight, line_height, set_line_height, clear_line_height),
+    /// The indentation of the first line of a paragraph, in pixels,
+    /// relative to the paragraph's leading margin. Only present when
+    /// different from parent.
+    (TextIndent, text_indent, set_text_indent, clear_text_indent),
+    /// The space above a paragraph, in pixels. Only present when
+    /// different from parent.
+    (MarginTop, margin_top, set_margin_top, clear_margin_top),
+    /// The space below a paragraph, in pixels. Only present when
+    /// different from parent.
+    (MarginBottom, margin_bottom, set_margin_bottom, clear_margin_bottom),
+    /// The space before the start of each line of a paragraph, in pixels,
+    /// that is, on the left for left-to-right text. Only present when
+    /// different from parent.
+    (MarginLeading, margin_leading, set_margin_leading, clear_margin_leading),
+    /// The space after the end of each line of a paragraph, in pixels,
+    /// that is, on the right for left-to-right text. Only present when
+    /// different from parent.
+    (MarginTrailing, margin_trailing, set_margin_trailing, clear_margin_trailing)
 }
 
 usize_property_methods! {
@@ -1754,7 +2831,15 @@ unique_enum_property_methods! {
     /// The list style type. Only available on list items.
     (ListStyle, list_style, set_list_style, clear_list_style),
     (TextAlign, text_align, set_text_align, clear_text_align),
-    (VerticalOffset, vertical_offset, set_vertical_offset, clear_vertical_offset)
+    (VerticalOffset, vertical_offset, set_vertical_offset, clear_vertical_offset),
+    /// The cause of the change to this node's value or text that is
+    /// included in the current tree update. Platform adapters may use it
+    /// when deciding which events to raise; for example, assistive
+    /// technologies are told when a change wasn't made by the user, so they
+    /// don't echo it as if it had been typed. A toolkit should set this
+    /// in the same update as the change. It doesn't apply to later updates
+    /// that don't include the node, so there's no need to clear it.
+    (ChangeReason, change_reason, set_change_reason, clear_change_reason)
 }
 
 property_methods! {
@@ -1769,6 +2854,14 @@ property_methods! {
     /// to the origin of the tree's container (e.g. window), in physical
     /// pixels, with the y coordinate being top-down.
     ///
+    /// Because descendants inherit the transform, a scrolling container
+    /// should wrap its content in a node with a translation by the negated
+    /// scroll offset, and express the bounds of the content in unscrolled
+    /// coordinates. Scrolling then only requires updating the wrapping node,
+    /// rather than the bounds of every descendant. The transform shouldn't
+    /// be set on the container itself, since it would move the container's
+    /// own bounds along with the content.
+    ///
     /// [`bounds`]: Node::bounds
     (Transform, transform, get_affine_property, Option<&Affine>, set_transform, set_affine_property, impl Into<Box<Affine>>, clear_transform),
 
@@ -1795,10 +2888,33 @@ impl Node {
     option_properties_debug_method! { debug_option_properties, [transform, bounds, text_selection,] }
 }
 
+property_info_list! {
+    OPTION_PROPERTIES,
+    [
+        (Transform, transform, metadata::ValueType::Affine),
+        (Bounds, bounds, metadata::ValueType::Rect),
+        (TextSelection, text_selection, metadata::ValueType::TextSelection),
+    ]
+}
+
 vec_property_methods! {
     (CustomActions, CustomAction, custom_actions, get_custom_action_vec, set_custom_actions, set_custom_action_vec, push_custom_action, push_to_custom_action_vec, clear_custom_actions)
 }
 
+property_info_list! { CUSTOM_ACTION_VEC_PROPERTIES, CustomActionVec, [(CustomActions, custom_actions),] }
+
+vec_property_methods! {
+    /// Selections in addition to the primary [`Node::text_selection`],
+    /// for editors that support multiple cursors. Each item follows the
+    /// same rules as the primary selection. The primary selection must
+    /// be set whenever this is non-empty, and it shouldn't be repeated here.
+    /// Platform adapters that can only expose a single selection ignore
+    /// this property.
+    (TextSelections, TextSelection, text_selections, get_text_selection_vec, set_text_selections, set_text_selection_vec, push_text_selection, push_to_text_selection_vec, clear_text_selections)
+}
+
+property_info_list! { TEXT_SELECTION_VEC_PROPERTIES, TextSelectionVec, [(TextSelections, text_selections),] }
+
 impl fmt::Debug for FrozenNode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut fmt = f.debug_struct("FrozenNode");
@@ -1810,6 +2926,11 @@ impl fmt::Debug for FrozenNode {
             fmt.field("actions", &supported_actions);
         }
 
+        let child_actions = action_mask_to_action_vec(self.child_actions);
+        if !child_actions.is_empty() {
+            fmt.field("child_actions", &child_actions);
+        }
+
         self.debug_flag_properties(&mut fmt);
         self.debug_node_id_vec_properties(&mut fmt);
         self.debug_node_id_properties(&mut fmt);
@@ -1829,6 +2950,11 @@ impl fmt::Debug for FrozenNode {
             fmt.field("custom_actions", &custom_actions);
         }
 
+        let text_selections = self.text_selections();
+        if !text_selections.is_empty() {
+            fmt.field("text_selections", &text_selections);
+        }
+
         fmt.finish()
     }
 }
@@ -1844,6 +2970,11 @@ impl fmt::Debug for Node {
             fmt.field("actions", &supported_actions);
         }
 
+        let child_actions = action_mask_to_action_vec(self.child_actions);
+        if !child_actions.is_empty() {
+            fmt.field("child_actions", &child_actions);
+        }
+
         self.debug_flag_properties(&mut fmt);
         self.debug_node_id_vec_properties(&mut fmt);
         self.debug_node_id_properties(&mut fmt);
@@ -1863,6 +2994,11 @@ impl fmt::Debug for Node {
             fmt.field("custom_actions", &custom_actions);
         }
 
+        let text_selections = self.text_selections();
+        if !text_selections.is_empty() {
+            fmt.field("text_selections", &text_selections);
+        }
+
         fmt.finish()
     }
 }
@@ -1871,6 +3007,7 @@ impl fmt::Debug for Node {
 macro_rules! serialize_property {
     ($self:ident, $map:ident, $index:ident, $id:ident, { $($variant:ident),+ }) => {
         match &$self.values[$index as usize] {
+            value if value.is_unset() => (),
             PropertyValue::None => (),
             $(PropertyValue::$variant(value) => {
                 $map.serialize_entry(&$id, &value)?;
@@ -1902,7 +3039,7 @@ impl Serialize for Properties {
     {
         let mut len = 0;
         for value in &*self.values {
-            if !matches!(*value, PropertyValue::None) {
+            if !value.is_unset() {
                 len += 1;
             }
         }
@@ -1935,10 +3072,12 @@ impl Serialize for Properties {
                 ListStyle,
                 TextAlign,
                 VerticalOffset,
+                ChangeReason,
                 Affine,
                 Rect,
                 TextSelection,
-                CustomActionVec
+                CustomActionVec,
+                TextSelectionVec
             });
         }
         map.end()
@@ -2001,7 +3140,10 @@ impl<'de> Visitor<'de> for PropertiesVisitor {
                     Tooltip,
                     Url,
                     RowIndexText,
-                    ColumnIndexText
+                    ColumnIndexText,
+                    Earcon,
+                    HelpText,
+                    Pronunciation
                 },
                 F64 {
                     ScrollX,
@@ -2016,7 +3158,14 @@ impl<'de> Visitor<'de> for PropertiesVisitor {
                     NumericValueStep,
                     NumericValueJump,
                     FontSize,
-                    FontWeight
+                    FontWeight,
+                    LetterSpacing,
+                    LineHeight,
+                    TextIndent,
+                    MarginTop,
+                    MarginBottom,
+                    MarginLeading,
+                    MarginTrailing
                 },
                 Usize {
                     RowCount,
@@ -2063,10 +3212,12 @@ impl<'de> Visitor<'de> for PropertiesVisitor {
                 ListStyle { ListStyle },
                 TextAlign { TextAlign },
                 VerticalOffset { VerticalOffset },
+                ChangeReason { ChangeReason },
                 Affine { Transform },
                 Rect { Bounds },
                 TextSelection { TextSelection },
-                CustomActionVec { CustomActions }
+                CustomActionVec { CustomActions },
+                TextSelectionVec { TextSelections }
             });
         }
 
@@ -2148,7 +3299,10 @@ impl JsonSchema for Properties {
                 Tooltip,
                 Url,
                 RowIndexText,
-                ColumnIndexText
+                ColumnIndexText,
+                Earcon,
+                HelpText,
+                Pronunciation
             },
             f64 {
                 ScrollX,
@@ -2163,7 +3317,14 @@ impl JsonSchema for Properties {
                 NumericValueStep,
                 NumericValueJump,
                 FontSize,
-                FontWeight
+                FontWeight,
+                LetterSpacing,
+                LineHeight,
+                TextIndent,
+                MarginTop,
+                MarginBottom,
+                MarginLeading,
+                MarginTrailing
             },
             usize {
                 RowCount,
@@ -2210,10 +3371,12 @@ impl JsonSchema for Properties {
             ListStyle { ListStyle },
             TextAlign { TextAlign },
             VerticalOffset { VerticalOffset },
+            ChangeReason { ChangeReason },
             Affine { Transform },
             Rect { Bounds },
             TextSelection { TextSelection },
-            Vec<CustomAction> { CustomActions }
+            Vec<CustomAction> { CustomActions },
+            Vec<TextSelection> { TextSelections }
         });
         SchemaObject {
             instance_type: Some(InstanceType::Object.into()),
@@ -2310,13 +3473,210 @@ pub struct TreeUpdate {
     pub focus: NodeId,
 }
 
+/// A change to the geometry of nodes that are already in the tree,
+/// for toolkits that move nodes on every frame while scrolling or
+/// animating. Unlike a [`TreeUpdate`], it doesn't repeat the other
+/// properties of the nodes, and platform adapters apply it without
+/// comparing those properties.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct GeometryUpdate {
+    /// The new [`Node::bounds`] and [`Node::transform`] of each node
+    /// whose geometry changed. `None` clears the property. It is an error
+    /// for any node in this list to not already be in the tree; consumers
+    /// ignore such nodes.
+    pub nodes: Vec<(NodeId, Option<Rect>, Option<Affine>)>,
+}
+
+/// A problem found by [`TreeUpdate::validate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreeUpdateError {
+    /// The node appears more than once in [`TreeUpdate::nodes`].
+    DuplicateNode(NodeId),
+    /// The node is listed as a child more than once, either by two
+    /// different nodes in the update or twice by the same node.
+    MultipleParents {
+        child: NodeId,
+        first_parent: NodeId,
+        second_parent: NodeId,
+    },
+    /// The root node is listed as a child of another node.
+    RootHasParent { root: NodeId, parent: NodeId },
+    /// The node is new, but isn't the root and isn't a child of any node
+    /// in the update.
+    Orphan(NodeId),
+    /// The node is listed as a child, but is neither in the update
+    /// nor an existing node.
+    MissingChild { parent: NodeId, child: NodeId },
+    /// The root specified by [`TreeUpdate::tree`] is neither in the update
+    /// nor an existing node.
+    MissingRoot(NodeId),
+    /// The focused node is neither in the update nor an existing node.
+    MissingFocus(NodeId),
+}
+
+const COLLISION_HINT: &str =
+    "; if node IDs are derived from hashes, two different nodes may have the same ID";
+
+impl fmt::Display for TreeUpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateNode(id) => {
+                write!(
+                    f,
+                    "node #{} is included more than once{}",
+                    id.0, COLLISION_HINT
+                )
+            }
+            Self::MultipleParents {
+                child,
+                first_parent,
+                second_parent,
+            } => write!(
+                f,
+                "node #{} is a child of both #{} and #{}{}",
+                child.0, first_parent.0, second_parent.0, COLLISION_HINT
+            ),
+            Self::RootHasParent { root, parent } => {
+                write!(f, "root node #{} is a child of #{}", root.0, parent.0)
+            }
+            Self::Orphan(id) => write!(
+                f,
+                "new node #{} is neither the root nor a child of another node",
+                id.0
+            ),
+            Self::MissingChild { parent, child } => write!(
+                f,
+                "node #{} has child #{}, which is neither in the update nor in the tree",
+                parent.0, child.0
+            ),
+            Self::MissingRoot(id) => write!(
+                f,
+                "root node #{} is neither in the update nor in the tree",
+                id.0
+            ),
+            Self::MissingFocus(id) => write!(
+                f,
+                "focused node #{} is neither in the update nor in the tree",
+                id.0
+            ),
+        }
+    }
+}
+
+impl TreeUpdate {
+    /// Checks that this update is internally consistent, without applying it.
+    ///
+    /// `is_existing_node` must return whether a node with the given ID is
+    /// in the tree that the update will be applied to. When validating
+    /// the initial update for a tree, pass `|_| false`.
+    ///
+    /// This verifies that no node is included more than once, that no node
+    /// is listed as a child of more than one node in the update, that every
+    /// new node other than the root has a parent in the update, and that
+    /// every child, the root, and the focus refer to a node that is either
+    /// in the update or already in the tree. It can't detect problems that
+    /// depend on the parts of the tree that the update doesn't touch, such
+    /// as a child being moved without being removed from its old parent.
+    pub fn validate(
+        &self,
+        is_existing_node: impl Fn(NodeId) -> bool,
+    ) -> Result<(), TreeUpdateError> {
+        use alloc::collections::{BTreeMap, BTreeSet};
+
+        let mut ids = BTreeSet::new();
+        for (id, _) in &self.nodes {
+            if !ids.insert(*id) {
+                return Err(TreeUpdateError::DuplicateNode(*id));
+            }
+        }
+        let is_known = |id: NodeId| ids.contains(&id) || is_existing_node(id);
+
+        let root = self.tree.as_ref().map(|tree| tree.root);
+        let mut parents = BTreeMap::new();
+        for (parent, node) in &self.nodes {
+            for child in node.children() {
+                if root == Some(*child) {
+                    return Err(TreeUpdateError::RootHasParent {
+                        root: *child,
+                        parent: *parent,
+                    });
+                }
+                if let Some(first_parent) = parents.insert(*child, *parent) {
+                    return Err(TreeUpdateError::MultipleParents {
+                        child: *child,
+                        first_parent,
+                        second_parent: *parent,
+                    });
+                }
+                if !is_known(*child) {
+                    return Err(TreeUpdateError::MissingChild {
+                        parent: *parent,
+                        child: *child,
+                    });
+                }
+            }
+        }
+
+        for (id, _) in &self.nodes {
+            if root != Some(*id) && !parents.contains_key(id) && !is_existing_node(*id) {
+                return Err(TreeUpdateError::Orphan(*id));
+            }
+        }
+        if let Some(root) = root {
+            if !is_known(root) {
+                return Err(TreeUpdateError::MissingRoot(root));
+            }
+        }
+        if !is_known(self.focus) {
+            return Err(TreeUpdateError::MissingFocus(self.focus));
+        }
+        Ok(())
+    }
+
+    /// Computes an update containing only the nodes of a new snapshot
+    /// of the tree that are either new or different from their previous
+    /// version, for toolkits that rebuild their whole accessibility tree
+    /// on each frame.
+    ///
+    /// `old` must return the node with the given ID in the previous snapshot,
+    /// if any, and `new` must yield every node of the new snapshot. Both can
+    /// be derived from a map, e.g. `|id| old.get(&id)` and `&new` for
+    /// a `HashMap<NodeId, Node>` or a `BTreeMap<NodeId, Node>`.
+    ///
+    /// Nodes that are only in the previous snapshot don't need to be
+    /// mentioned, because removing them from the children of their parents
+    /// is enough to remove them from the tree. The returned update leaves
+    /// [`TreeUpdate::tree`] unset; set it if the tree information changed.
+    pub fn diff<'a, 'b>(
+        old: impl Fn(NodeId) -> Option<&'a Node>,
+        new: impl IntoIterator<Item = (&'b NodeId, &'b Node)>,
+        focus: NodeId,
+    ) -> Self {
+        let nodes = new
+            .into_iter()
+            .filter(|(id, node)| old(**id) != Some(*node))
+            .map(|(id, node)| (*id, node.clone()))
+            .collect();
+        Self {
+            nodes,
+            tree: None,
+            focus,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[repr(C)]
 pub enum ActionData {
-    CustomAction(i32),
+    /// The ID of the requested action, as specified in [`CustomAction::id`].
+    CustomAction(CustomActionId),
     Value(Box<str>),
     NumericValue(f64),
     /// Optional target rectangle for [`Action::ScrollIntoView`], in
@@ -2342,6 +3702,109 @@ pub struct ActionRequest {
     pub data: Option<ActionData>,
 }
 
+impl ActionRequest {
+    /// Returns an error if the action requires [`ActionRequest::data`]
+    /// and the data is missing or of the wrong kind.
+    pub fn check_data(&self) -> Result<(), ActionRequestError> {
+        let is_valid = match self.action {
+            Action::CustomAction => matches!(self.data, Some(ActionData::CustomAction(_))),
+            Action::ReplaceSelectedText => matches!(self.data, Some(ActionData::Value(_))),
+            Action::ScrollToPoint => matches!(self.data, Some(ActionData::ScrollToPoint(_))),
+            Action::SetScrollOffset => matches!(self.data, Some(ActionData::SetScrollOffset(_))),
+            Action::SetTextSelection => {
+                matches!(self.data, Some(ActionData::SetTextSelection(_)))
+            }
+            Action::SetValue => matches!(
+                self.data,
+                Some(ActionData::Value(_) | ActionData::NumericValue(_))
+            ),
+            Action::SetNumericValueRelative => {
+                matches!(self.data, Some(ActionData::NumericValue(_)))
+            }
+            _ => true,
+        };
+        if is_valid {
+            Ok(())
+        } else {
+            Err(ActionRequestError::MissingData)
+        }
+    }
+}
+
+/// The reason that a platform adapter dropped an [`ActionRequest`] instead
+/// of passing it to [`ActionHandler::do_action`], as reported to
+/// [`ActionHandler::action_request_dropped`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActionRequestError {
+    /// The target node isn't in the tree, usually because it was removed
+    /// after the assistive technology last saw it.
+    UnknownTarget,
+    /// The target node, or one of its ancestors, is inert.
+    /// See [`Node::is_inert`].
+    InertTarget,
+    /// The target node doesn't support the action.
+    /// See [`Node::add_action`].
+    UnsupportedAction,
+    /// The action requires [`ActionRequest::data`], but it's missing
+    /// or of the wrong kind.
+    MissingData,
+}
+
+impl fmt::Display for ActionRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnknownTarget => "the target node isn't in the tree",
+            Self::InertTarget => "the target node is inert",
+            Self::UnsupportedAction => "the target node doesn't support the action",
+            Self::MissingData => "the request is missing required data",
+        })
+    }
+}
+
+/// A report of which parts of this schema a platform adapter maps to
+/// the platform's accessibility API. Each adapter provides one, so that
+/// toolkit developers and tools can find out which of the information
+/// they provide is dropped on a given platform.
+///
+/// With the `serde` feature, the report can be serialized, e.g. to JSON.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AdapterCapabilities {
+    /// The name of the platform accessibility API, e.g. `"AT-SPI"`.
+    pub api: &'static str,
+    /// Roles that have no equivalent in the platform API, and are exposed
+    /// as an unknown or generic element. All other roles are mapped.
+    pub unmapped_roles: &'static [Role],
+    /// The properties and flags that are exposed to assistive technologies,
+    /// named as in the [`Node`] getters without any `is_` prefix,
+    /// e.g. `"label"` or `"required"`.
+    pub properties: &'static [&'static str],
+    /// The actions that assistive technologies can request.
+    pub actions: &'static [Action],
+    /// The names of the platform events that the adapter raises,
+    /// as they're known in the platform API.
+    pub events: &'static [&'static str],
+}
+
+impl AdapterCapabilities {
+    /// Returns true if the given role has an equivalent in the platform API.
+    pub fn maps_role(&self, role: Role) -> bool {
+        !self.unmapped_roles.contains(&role)
+    }
+
+    /// Returns true if the given property or flag, named as in
+    /// [`AdapterCapabilities::properties`], is exposed.
+    pub fn maps_property(&self, name: &str) -> bool {
+        self.properties.contains(&name)
+    }
+
+    /// Returns true if assistive technologies can request the given action.
+    pub fn maps_action(&self, action: Action) -> bool {
+        self.actions.contains(&action)
+    }
+}
+
 /// Handles activation of the application's accessibility implementation.
 pub trait ActivationHandler {
     /// Requests a [`TreeUpdate`] with a full tree. If the application
@@ -2359,9 +3822,12 @@ pub trait ActivationHandler {
     /// this method may be called consecutively without any call to
     /// [`DeactivationHandler::deactivate_accessibility`]; this typically happens
     /// if the platform adapter merely forwards tree updates to assistive
-    /// technologies without maintaining any state. A call to this method
-    /// must always generate a [`TreeUpdate`] with a full tree, even if
-    /// the application normally sends incremental updates.
+    /// technologies without maintaining any state. Some platform adapters
+    /// also call it again when an assistive technology asks for the tree
+    /// to be rebuilt, e.g. to recover from an application that sent
+    /// inconsistent updates. A call to this method must always generate
+    /// a [`TreeUpdate`] with a full tree, even if the application normally
+    /// sends incremental updates.
     ///
     /// The thread on which this method is called is platform-dependent.
     /// Refer to the platform adapter documentation for more details.
@@ -2380,6 +3846,77 @@ pub trait ActionHandler {
     /// This behavior is preferred over blocking, e.g. when dispatching
     /// the request to another thread.
     fn do_action(&mut self, request: ActionRequest);
+
+    /// Called instead of [`ActionHandler::do_action`] when the platform
+    /// adapter drops a request, for example because its target was removed
+    /// from the tree, or because the target doesn't support the action.
+    /// Applications can implement this to log requests they would
+    /// otherwise never see. The default implementation does nothing.
+    ///
+    /// This method is called on the same thread as
+    /// [`ActionHandler::do_action`].
+    fn action_request_dropped(&mut self, _request: &ActionRequest, _error: ActionRequestError) {}
+}
+
+/// Inspects, modifies, or vetoes action requests before they reach
+/// an [`ActionHandler`]. See [`InterceptingActionHandler`].
+pub trait ActionInterceptor {
+    /// Returns the request that should be passed on to the next handler,
+    /// or `None` to drop the request.
+    ///
+    /// This method is called on the same thread as
+    /// [`ActionHandler::do_action`], and should return quickly.
+    fn intercept(&mut self, request: ActionRequest) -> Option<ActionRequest>;
+}
+
+impl<F: FnMut(ActionRequest) -> Option<ActionRequest>> ActionInterceptor for F {
+    fn intercept(&mut self, request: ActionRequest) -> Option<ActionRequest> {
+        self(request)
+    }
+}
+
+/// An [`ActionHandler`] that passes each request through an
+/// [`ActionInterceptor`] before forwarding it to another handler. Because
+/// the inner handler can itself be an `InterceptingActionHandler`,
+/// this can be used to build a chain of middleware, for example to log
+/// every request and then veto some of them, without modifying
+/// the application's own handler. The outermost interceptor sees
+/// each request first.
+///
+/// Since platform adapters accept any [`ActionHandler`], the resulting
+/// handler can be passed to any adapter.
+pub struct InterceptingActionHandler<I: ActionInterceptor, H: ActionHandler> {
+    interceptor: I,
+    handler: H,
+}
+
+impl<I: ActionInterceptor, H: ActionHandler> InterceptingActionHandler<I, H> {
+    pub fn new(interceptor: I, handler: H) -> Self {
+        Self {
+            interceptor,
+            handler,
+        }
+    }
+
+    pub fn inner(&self) -> &H {
+        &self.handler
+    }
+
+    pub fn inner_mut(&mut self) -> &mut H {
+        &mut self.handler
+    }
+}
+
+impl<I: ActionInterceptor, H: ActionHandler> ActionHandler for InterceptingActionHandler<I, H> {
+    fn do_action(&mut self, request: ActionRequest) {
+        if let Some(request) = self.interceptor.intercept(request) {
+            self.handler.do_action(request);
+        }
+    }
+
+    fn action_request_dropped(&mut self, request: &ActionRequest, error: ActionRequestError) {
+        self.handler.action_request_dropped(request, error);
+    }
 }
 
 /// Handles deactivation of the application's accessibility implementation.
@@ -2394,9 +3931,43 @@ pub trait DeactivationHandler {
     fn deactivate_accessibility(&mut self);
 }
 
+/// A future returned by the asynchronous handler traits. It can't borrow
+/// the handler, so that the platform adapter can run it on its own executor
+/// while the handler is called again.
+pub type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send + 'static>>;
+
+/// An asynchronous counterpart of [`ActivationHandler`], for applications
+/// built on an async runtime. Only platform adapters that run an executor
+/// accept it, which currently means only the Unix adapter. The Windows,
+/// macOS and iOS adapters call their handlers on the UI thread and only
+/// accept the synchronous traits.
+pub trait AsyncActivationHandler {
+    /// Requests a [`TreeUpdate`] with a full tree, which the returned future
+    /// resolves to. The requirements of
+    /// [`ActivationHandler::request_initial_tree`] apply, except that the
+    /// platform adapter waits for the future rather than for the next
+    /// display refresh. If the future resolves to `None`, the application
+    /// must send the tree to the platform adapter itself.
+    fn request_initial_tree(&mut self) -> BoxFuture<Option<TreeUpdate>>;
+}
+
+/// An asynchronous counterpart of [`ActionHandler`], for applications
+/// built on an async runtime. Only platform adapters that run an executor
+/// accept it, which currently means only the Unix adapter. The Windows,
+/// macOS and iOS adapters call their handlers on the UI thread and only
+/// accept the synchronous traits.
+pub trait AsyncActionHandler {
+    /// Returns a future that performs the requested action. The platform
+    /// adapter runs each future to completion on its executor. Futures for
+    /// consecutive requests may run concurrently, so an application that
+    /// relies on requests being handled in order must serialize them itself.
+    fn do_action(&mut self, request: ActionRequest) -> BoxFuture<()>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn action_n() {
@@ -2427,7 +3998,153 @@ mod tests {
         );
         assert_eq!(Action::n(22), Some(Action::SetValue));
         assert_eq!(Action::n(23), Some(Action::ShowContextMenu));
-        assert_eq!(Action::n(24), None);
+        assert_eq!(Action::n(24), Some(Action::SetNumericValueRelative));
+        assert_eq!(Action::n(25), Some(Action::Select));
+        assert_eq!(Action::n(26), Some(Action::Deselect));
+        assert_eq!(Action::n(27), Some(Action::Drag));
+        assert_eq!(Action::n(28), Some(Action::Drop));
+        assert_eq!(Action::n(29), None);
+    }
+
+    // Property IDs and flags are encoded as numbers by the binary format,
+    // so their values must not change when variants are added.
+    #[test]
+    fn property_id_values() {
+        assert_eq!(PropertyId::Children as u8, 0);
+        assert_eq!(PropertyId::Controls as u8, 1);
+        assert_eq!(PropertyId::Details as u8, 2);
+        assert_eq!(PropertyId::DescribedBy as u8, 3);
+        assert_eq!(PropertyId::FlowTo as u8, 4);
+        assert_eq!(PropertyId::LabelledBy as u8, 5);
+        assert_eq!(PropertyId::Owns as u8, 6);
+        assert_eq!(PropertyId::RadioGroup as u8, 7);
+        assert_eq!(PropertyId::ActiveDescendant as u8, 8);
+        assert_eq!(PropertyId::ErrorMessage as u8, 9);
+        assert_eq!(PropertyId::InPageLinkTarget as u8, 10);
+        assert_eq!(PropertyId::MemberOf as u8, 11);
+        assert_eq!(PropertyId::NextOnLine as u8, 12);
+        assert_eq!(PropertyId::PreviousOnLine as u8, 13);
+        assert_eq!(PropertyId::PopupFor as u8, 14);
+        assert_eq!(PropertyId::Label as u8, 15);
+        assert_eq!(PropertyId::Description as u8, 16);
+        assert_eq!(PropertyId::Value as u8, 17);
+        assert_eq!(PropertyId::AccessKey as u8, 18);
+        assert_eq!(PropertyId::AuthorId as u8, 19);
+        assert_eq!(PropertyId::ClassName as u8, 20);
+        assert_eq!(PropertyId::FontFamily as u8, 21);
+        assert_eq!(PropertyId::HtmlTag as u8, 22);
+        assert_eq!(PropertyId::InnerHtml as u8, 23);
+        assert_eq!(PropertyId::KeyboardShortcut as u8, 24);
+        assert_eq!(PropertyId::Language as u8, 25);
+        assert_eq!(PropertyId::Placeholder as u8, 26);
+        assert_eq!(PropertyId::RoleDescription as u8, 27);
+        assert_eq!(PropertyId::StateDescription as u8, 28);
+        assert_eq!(PropertyId::Tooltip as u8, 29);
+        assert_eq!(PropertyId::Url as u8, 30);
+        assert_eq!(PropertyId::RowIndexText as u8, 31);
+        assert_eq!(PropertyId::ColumnIndexText as u8, 32);
+        assert_eq!(PropertyId::Earcon as u8, 33);
+        assert_eq!(PropertyId::HelpText as u8, 34);
+        assert_eq!(PropertyId::Pronunciation as u8, 35);
+        assert_eq!(PropertyId::ScrollX as u8, 36);
+        assert_eq!(PropertyId::ScrollXMin as u8, 37);
+        assert_eq!(PropertyId::ScrollXMax as u8, 38);
+        assert_eq!(PropertyId::ScrollY as u8, 39);
+        assert_eq!(PropertyId::ScrollYMin as u8, 40);
+        assert_eq!(PropertyId::ScrollYMax as u8, 41);
+        assert_eq!(PropertyId::NumericValue as u8, 42);
+        assert_eq!(PropertyId::MinNumericValue as u8, 43);
+        assert_eq!(PropertyId::MaxNumericValue as u8, 44);
+        assert_eq!(PropertyId::NumericValueStep as u8, 45);
+        assert_eq!(PropertyId::NumericValueJump as u8, 46);
+        assert_eq!(PropertyId::FontSize as u8, 47);
+        assert_eq!(PropertyId::FontWeight as u8, 48);
+        assert_eq!(PropertyId::LetterSpacing as u8, 49);
+        assert_eq!(PropertyId::LineHeight as u8, 50);
+        assert_eq!(PropertyId::TextIndent as u8, 51);
+        assert_eq!(PropertyId::MarginTop as u8, 52);
+        assert_eq!(PropertyId::MarginBottom as u8, 53);
+        assert_eq!(PropertyId::MarginLeading as u8, 54);
+        assert_eq!(PropertyId::MarginTrailing as u8, 55);
+        assert_eq!(PropertyId::RowCount as u8, 56);
+        assert_eq!(PropertyId::ColumnCount as u8, 57);
+        assert_eq!(PropertyId::RowIndex as u8, 58);
+        assert_eq!(PropertyId::ColumnIndex as u8, 59);
+        assert_eq!(PropertyId::RowSpan as u8, 60);
+        assert_eq!(PropertyId::ColumnSpan as u8, 61);
+        assert_eq!(PropertyId::Level as u8, 62);
+        assert_eq!(PropertyId::SizeOfSet as u8, 63);
+        assert_eq!(PropertyId::PositionInSet as u8, 64);
+        assert_eq!(PropertyId::ColorValue as u8, 65);
+        assert_eq!(PropertyId::BackgroundColor as u8, 66);
+        assert_eq!(PropertyId::ForegroundColor as u8, 67);
+        assert_eq!(PropertyId::Overline as u8, 68);
+        assert_eq!(PropertyId::Strikethrough as u8, 69);
+        assert_eq!(PropertyId::Underline as u8, 70);
+        assert_eq!(PropertyId::CharacterLengths as u8, 71);
+        assert_eq!(PropertyId::WordLengths as u8, 72);
+        assert_eq!(PropertyId::CharacterPositions as u8, 73);
+        assert_eq!(PropertyId::CharacterWidths as u8, 74);
+        assert_eq!(PropertyId::Expanded as u8, 75);
+        assert_eq!(PropertyId::Selected as u8, 76);
+        assert_eq!(PropertyId::Invalid as u8, 77);
+        assert_eq!(PropertyId::Toggled as u8, 78);
+        assert_eq!(PropertyId::Live as u8, 79);
+        assert_eq!(PropertyId::TextDirection as u8, 80);
+        assert_eq!(PropertyId::Orientation as u8, 81);
+        assert_eq!(PropertyId::SortDirection as u8, 82);
+        assert_eq!(PropertyId::AriaCurrent as u8, 83);
+        assert_eq!(PropertyId::AutoComplete as u8, 84);
+        assert_eq!(PropertyId::HasPopup as u8, 85);
+        assert_eq!(PropertyId::ListStyle as u8, 86);
+        assert_eq!(PropertyId::TextAlign as u8, 87);
+        assert_eq!(PropertyId::VerticalOffset as u8, 88);
+        assert_eq!(PropertyId::ChangeReason as u8, 89);
+        assert_eq!(PropertyId::Transform as u8, 90);
+        assert_eq!(PropertyId::Bounds as u8, 91);
+        assert_eq!(PropertyId::TextSelection as u8, 92);
+        assert_eq!(PropertyId::CustomActions as u8, 93);
+        assert_eq!(PropertyId::TextSelections as u8, 94);
+        assert_eq!(PropertyId::Unset as u8, 95);
+    }
+
+    #[test]
+    fn flag_values() {
+        assert_eq!(Flag::Hidden as u8, 0);
+        assert_eq!(Flag::Linked as u8, 1);
+        assert_eq!(Flag::Multiselectable as u8, 2);
+        assert_eq!(Flag::Required as u8, 3);
+        assert_eq!(Flag::Visited as u8, 4);
+        assert_eq!(Flag::Busy as u8, 5);
+        assert_eq!(Flag::LiveAtomic as u8, 6);
+        assert_eq!(Flag::Modal as u8, 7);
+        assert_eq!(Flag::TouchTransparent as u8, 8);
+        assert_eq!(Flag::ReadOnly as u8, 9);
+        assert_eq!(Flag::Disabled as u8, 10);
+        assert_eq!(Flag::Bold as u8, 11);
+        assert_eq!(Flag::Italic as u8, 12);
+        assert_eq!(Flag::ClipsChildren as u8, 13);
+        assert_eq!(Flag::IsLineBreakingObject as u8, 14);
+        assert_eq!(Flag::IsPageBreakingObject as u8, 15);
+        assert_eq!(Flag::IsSpellingError as u8, 16);
+        assert_eq!(Flag::IsGrammarError as u8, 17);
+        assert_eq!(Flag::IsSearchMatch as u8, 18);
+        assert_eq!(Flag::IsSuggestion as u8, 19);
+        assert_eq!(Flag::IsValueMasked as u8, 20);
+        assert_eq!(Flag::Inert as u8, 21);
+        assert_eq!(Flag::Offscreen as u8, 22);
+        assert_eq!(Flag::Grabbed as u8, 23);
+        assert_eq!(Flag::DropTarget as u8, 24);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn property_id_n() {
+        for (i, id) in PropertyId::ALL.iter().enumerate() {
+            assert_eq!(PropertyId::n(i as u8), Some(*id));
+            assert_eq!(*id as usize, i);
+        }
+        assert_eq!(PropertyId::n(PropertyId::Unset as u8), None);
     }
 
     #[test]
@@ -2468,4 +4185,427 @@ mod tests {
             action_mask_to_action_vec(node.actions).as_slice()
         );
     }
+
+    #[test]
+    fn role_n() {
+        let roles = (0..=u8::MAX).map_while(Role::n).collect::<Vec<_>>();
+        assert_eq!(roles.len(), Role::Terminal as usize + 1);
+        for (value, role) in roles.into_iter().enumerate() {
+            assert_eq!(role as usize, value);
+        }
+        assert_eq!(Role::n(Role::Terminal as u8 + 1), None);
+    }
+
+    #[test]
+    fn child_actions() {
+        let mut node = Node::new(Role::ListBox);
+        assert!(!node.child_supports_action(Action::Click));
+        node.add_child_action(Action::Click);
+        node.add_child_action(Action::Focus);
+        assert!(node.child_supports_action(Action::Click));
+        assert!(node.child_supports_action(Action::Focus));
+        assert!(!node.supports_action(Action::Click));
+        node.remove_child_action(Action::Focus);
+        assert!(!node.child_supports_action(Action::Focus));
+        let frozen = FrozenNode::from(node.clone());
+        assert!(frozen.child_supports_action(Action::Click));
+        node.clear_child_actions();
+        assert!(!node.child_supports_action(Action::Click));
+    }
+
+    #[test]
+    fn equality_ignores_property_order() {
+        let mut a = Node::new(Role::Button);
+        a.set_label("OK");
+        a.set_description("Confirm");
+        let mut b = Node::new(Role::Button);
+        b.set_description("Confirm");
+        b.set_label("OK");
+        assert_eq!(a, b);
+        assert_eq!(FrozenNode::from(a), FrozenNode::from(b));
+    }
+
+    #[test]
+    fn cleared_property_equals_unset() {
+        let mut a = Node::new(Role::Button);
+        a.set_label("OK");
+        a.clear_label();
+        let b = Node::new(Role::Button);
+        assert_eq!(a, b);
+        assert_eq!(FrozenNode::from(a), FrozenNode::from(b));
+    }
+
+    #[test]
+    fn empty_vec_equals_unset() {
+        let mut a = Node::new(Role::Window);
+        a.set_children(Vec::new());
+        a.set_custom_actions(Vec::new());
+        a.set_character_lengths(Vec::new());
+        let b = Node::new(Role::Window);
+        assert_eq!(a, b);
+        assert_eq!(FrozenNode::from(a.clone()), FrozenNode::from(b));
+        a.push_child(NodeId(1));
+        assert_eq!(a.children(), &[NodeId(1)]);
+    }
+
+    #[test]
+    fn changed_properties() {
+        let mut a = Node::new(Role::Slider);
+        a.set_label("Volume");
+        a.set_numeric_value(10.0);
+        a.set_children(Vec::new());
+        let mut b = Node::new(Role::Slider);
+        b.set_numeric_value(20.0);
+        b.set_label("Volume");
+        b.set_description("Loud");
+        let a = FrozenNode::from(a);
+        let b = FrozenNode::from(b);
+        assert_eq!(
+            a.changed_properties(&b).collect::<Vec<_>>(),
+            [PropertyId::Description, PropertyId::NumericValue]
+        );
+        assert_eq!(a.changed_properties(&a).next(), None);
+    }
+
+    #[test]
+    fn with_geometry() {
+        let mut node = Node::new(Role::Button);
+        node.set_label("OK");
+        node.set_bounds(Rect::new(0.0, 0.0, 10.0, 10.0));
+        let frozen = FrozenNode::from(node.clone());
+
+        let moved = frozen.with_geometry(
+            Some(Rect::new(5.0, 0.0, 15.0, 10.0)),
+            Some(Affine::scale(2.0)),
+        );
+        node.set_bounds(Rect::new(5.0, 0.0, 15.0, 10.0));
+        node.set_transform(Affine::scale(2.0));
+        assert_eq!(moved, FrozenNode::from(node.clone()));
+
+        let cleared = moved.with_geometry(None, None);
+        node.clear_bounds();
+        node.clear_transform();
+        assert_eq!(cleared, FrozenNode::from(node));
+        assert_eq!(cleared.label(), Some("OK"));
+    }
+
+    #[test]
+    fn heap_size() {
+        let node = Node::new(Role::Button);
+        assert_eq!(node.heap_size(), 0);
+        assert_eq!(FrozenNode::from(node).heap_size(), 0);
+
+        let mut node = Node::new(Role::Button);
+        node.set_label("OK");
+        let frozen = FrozenNode::from(node.clone());
+        assert_eq!(frozen.heap_size(), size_of::<PropertyValue>() + 2);
+        assert!(node.heap_size() >= frozen.heap_size());
+
+        node.clear_label();
+        node.set_children([NodeId(1), NodeId(2)]);
+        assert!(node.heap_size() >= size_of::<PropertyValue>() + 2 * size_of::<NodeId>());
+    }
+
+    #[test]
+    fn custom_action_id() {
+        let id = CustomActionId::from(42);
+        assert_eq!(id.as_i32(), Some(42));
+        assert_eq!(id.as_str(), None);
+
+        let id = CustomActionId::from("reorder");
+        assert_eq!(id.as_i32(), None);
+        assert_eq!(id.as_str(), Some("reorder"));
+
+        let mut node = Node::new(Role::ListItem);
+        node.push_custom_action(CustomAction {
+            id: 1.into(),
+            description: "Delete".into(),
+        });
+        node.push_custom_action(CustomAction {
+            id: "move-up".into(),
+            description: "Move up".into(),
+        });
+        assert_eq!(
+            &[
+                CustomActionId::Integer(1),
+                CustomActionId::String("move-up".into())
+            ],
+            node.custom_actions()
+                .iter()
+                .map(|action| action.id.clone())
+                .collect::<Vec<_>>()
+                .as_slice()
+        );
+    }
+
+    #[test]
+    fn intercepting_action_handler() {
+        struct Recorder(Vec<ActionRequest>);
+        impl ActionHandler for Recorder {
+            fn do_action(&mut self, request: ActionRequest) {
+                self.0.push(request);
+            }
+        }
+
+        let mut log = Vec::new();
+        let mut handler = InterceptingActionHandler::new(
+            |request: ActionRequest| {
+                log.push(request.action);
+                Some(request)
+            },
+            InterceptingActionHandler::new(
+                |request: ActionRequest| (request.action != Action::Click).then_some(request),
+                Recorder(Vec::new()),
+            ),
+        );
+        for action in [Action::Click, Action::Focus] {
+            handler.do_action(ActionRequest {
+                action,
+                target: NodeId(1),
+                data: None,
+            });
+        }
+        let recorded = &handler.inner().inner().0;
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].action, Action::Focus);
+        drop(handler);
+        assert_eq!(log, [Action::Click, Action::Focus]);
+    }
+
+    #[test]
+    fn map_node_ids() {
+        let mut node = Node::new(Role::TextInput);
+        node.set_children(vec![NodeId(1), NodeId(2)]);
+        node.set_labelled_by(vec![NodeId(3)]);
+        node.set_active_descendant(NodeId(4));
+        node.set_text_selection(TextSelection {
+            anchor: TextPosition {
+                node: NodeId(1),
+                character_index: 0,
+            },
+            focus: TextPosition {
+                node: NodeId(2),
+                character_index: 1,
+            },
+        });
+        node.push_text_selection(TextSelection {
+            anchor: TextPosition {
+                node: NodeId(6),
+                character_index: 0,
+            },
+            focus: TextPosition {
+                node: NodeId(6),
+                character_index: 0,
+            },
+        });
+        node.set_label("NodeId(5)");
+        node.map_node_ids(|id| NodeId(id.0 + 100));
+        assert_eq!(node.children(), &[NodeId(101), NodeId(102)]);
+        assert_eq!(node.labelled_by(), &[NodeId(103)]);
+        assert_eq!(node.active_descendant(), Some(NodeId(104)));
+        let selection = node.text_selection().unwrap();
+        assert_eq!(selection.anchor.node, NodeId(101));
+        assert_eq!(selection.focus.node, NodeId(102));
+        assert_eq!(node.text_selections()[0].anchor.node, NodeId(106));
+        assert_eq!(node.label(), Some("NodeId(5)"));
+    }
+
+    #[test]
+    fn tree_update_diff() {
+        use alloc::collections::BTreeMap;
+
+        let mut label = Node::new(Role::Label);
+        label.set_value("1");
+        let mut old = BTreeMap::new();
+        old.insert(NodeId(0), Node::new(Role::Window));
+        old.get_mut(&NodeId(0))
+            .unwrap()
+            .set_children(vec![NodeId(1), NodeId(2)]);
+        old.insert(NodeId(1), Node::new(Role::Button));
+        old.insert(NodeId(2), label.clone());
+
+        let mut new = old.clone();
+        new.remove(&NodeId(1));
+        new.get_mut(&NodeId(0))
+            .unwrap()
+            .set_children(vec![NodeId(2), NodeId(3)]);
+        new.insert(NodeId(3), Node::new(Role::Button));
+
+        let update = TreeUpdate::diff(|id| old.get(&id), &new, NodeId(3));
+        let ids = update.nodes.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        assert_eq!(ids, [NodeId(0), NodeId(3)]);
+        assert_eq!(update.tree, None);
+        assert_eq!(update.focus, NodeId(3));
+        assert_eq!(update.validate(|id| old.contains_key(&id)), Ok(()));
+
+        label.set_value("2");
+        new.insert(NodeId(2), label);
+        let update = TreeUpdate::diff(|id| old.get(&id), &new, NodeId(3));
+        assert_eq!(update.nodes.len(), 3);
+    }
+
+    #[test]
+    fn tree_update_validate() {
+        fn node(children: &[u64]) -> Node {
+            let mut node = Node::new(Role::GenericContainer);
+            node.set_children(children.iter().copied().map(NodeId).collect::<Vec<_>>());
+            node
+        }
+        let update = |nodes: Vec<(u64, Node)>, root: Option<u64>, focus: u64| TreeUpdate {
+            nodes: nodes
+                .into_iter()
+                .map(|(id, node)| (NodeId(id), node))
+                .collect(),
+            tree: root.map(|root| Tree::new(NodeId(root))),
+            focus: NodeId(focus),
+        };
+        let is_new = |_| false;
+
+        let initial = update(
+            vec![(0, node(&[1, 2])), (1, node(&[])), (2, node(&[]))],
+            Some(0),
+            1,
+        );
+        assert_eq!(initial.validate(is_new), Ok(()));
+
+        let duplicate = update(
+            vec![(0, node(&[1])), (1, node(&[])), (1, node(&[]))],
+            Some(0),
+            0,
+        );
+        assert_eq!(
+            duplicate.validate(is_new),
+            Err(TreeUpdateError::DuplicateNode(NodeId(1)))
+        );
+
+        let shared = update(
+            vec![(0, node(&[1, 2])), (1, node(&[2])), (2, node(&[]))],
+            Some(0),
+            0,
+        );
+        assert_eq!(
+            shared.validate(is_new),
+            Err(TreeUpdateError::MultipleParents {
+                child: NodeId(2),
+                first_parent: NodeId(0),
+                second_parent: NodeId(1),
+            })
+        );
+
+        let cycle = update(vec![(0, node(&[1])), (1, node(&[0]))], Some(0), 0);
+        assert_eq!(
+            cycle.validate(is_new),
+            Err(TreeUpdateError::RootHasParent {
+                root: NodeId(0),
+                parent: NodeId(1),
+            })
+        );
+
+        let orphan = update(vec![(0, node(&[])), (1, node(&[]))], Some(0), 0);
+        assert_eq!(
+            orphan.validate(is_new),
+            Err(TreeUpdateError::Orphan(NodeId(1)))
+        );
+
+        let missing_child = update(vec![(0, node(&[1]))], Some(0), 0);
+        assert_eq!(
+            missing_child.validate(is_new),
+            Err(TreeUpdateError::MissingChild {
+                parent: NodeId(0),
+                child: NodeId(1),
+            })
+        );
+
+        let missing_focus = update(vec![(0, node(&[]))], Some(0), 1);
+        assert_eq!(
+            missing_focus.validate(is_new),
+            Err(TreeUpdateError::MissingFocus(NodeId(1)))
+        );
+
+        let missing_root = update(vec![], Some(0), 0);
+        assert_eq!(
+            missing_root.validate(is_new),
+            Err(TreeUpdateError::MissingRoot(NodeId(0)))
+        );
+
+        // An incremental update may refer to nodes that are already
+        // in the tree, and may update nodes without their parents.
+        let incremental = update(vec![(1, node(&[2, 3])), (3, node(&[]))], None, 2);
+        assert_eq!(incremental.validate(|id| id.0 <= 2), Ok(()));
+        assert_eq!(
+            incremental.validate(|id| id.0 == 2),
+            Err(TreeUpdateError::Orphan(NodeId(1)))
+        );
+    }
+
+    #[test]
+    fn node_warnings() {
+        let cell = Node::builder(Role::Cell)
+            .set_row_index(1)
+            .set_column_index(2)
+            .set_column_span(2)
+            .try_build()
+            .unwrap();
+        assert!(cell.warnings().is_empty());
+
+        let mut button = Node::new(Role::Button);
+        button.set_row_index(1);
+        button.set_column_count(3);
+        assert_eq!(
+            button.warnings(),
+            vec![
+                NodeWarning::IgnoredProperty("column_count"),
+                NodeWarning::IgnoredProperty("row_index"),
+            ]
+        );
+
+        let mut slider = Node::new(Role::Slider);
+        slider.set_numeric_value(5.0);
+        assert_eq!(slider.warnings(), vec![NodeWarning::MissingRange]);
+        slider.set_min_numeric_value(0.0);
+        slider.set_max_numeric_value(4.0);
+        assert_eq!(
+            slider.warnings(),
+            vec![NodeWarning::ValueOutOfRange {
+                value: 5.0,
+                min: 0.0,
+                max: 4.0
+            }]
+        );
+        slider.set_min_numeric_value(10.0);
+        assert_eq!(
+            slider.warnings(),
+            vec![NodeWarning::InvertedRange {
+                min: 10.0,
+                max: 4.0
+            }]
+        );
+
+        // Spin buttons may be unbounded.
+        let spin_button = Node::builder(Role::SpinButton)
+            .set_numeric_value(5.0)
+            .try_build()
+            .unwrap();
+        assert!(spin_button.warnings().is_empty());
+
+        let item = Node::builder(Role::ListItem)
+            .set_position_in_set(4)
+            .set_size_of_set(3)
+            .build();
+        assert_eq!(
+            item.warnings(),
+            vec![NodeWarning::PositionOutOfRange {
+                position: 4,
+                size: 3
+            }]
+        );
+    }
+
+    #[test]
+    fn node_builder_returns_warnings() {
+        assert_eq!(
+            Node::builder(Role::Row).set_row_span(2).try_build(),
+            Err(vec![NodeWarning::IgnoredProperty("row_span")])
+        );
+    }
 }
@@ -0,0 +1,160 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+//! Helpers for exposing the controls of an audio or video player.
+//!
+//! By convention, the controls of a media player are grouped in
+//! a [`Role::Toolbar`] with a label such as "Media controls", containing:
+//!
+//! - A play/pause button, created with [`play_pause_button`]. This is
+//!   a plain button whose label names the action it performs, rather than
+//!   a toggle button, because "Pause, pressed" is confusing.
+//! - A seek slider, created with [`seek_slider`]. Its value is the playback
+//!   position in seconds, and its value text gives the position and the
+//!   duration in the same format as the visible time display. The player
+//!   handles the [`Action::Increment`], [`Action::Decrement`] and
+//!   [`Action::SetValue`] requests by seeking.
+//! - The elapsed time, created with [`elapsed_time`], if the player shows it
+//!   separately from the slider.
+//!
+//! ## Avoiding event spam
+//!
+//! The position of playing media changes continuously, but platform adapters
+//! raise events for every change to the tree. These helpers therefore round
+//! the position down to whole seconds, so that a player which rebuilds its
+//! controls on every frame only changes the tree once per second.
+//!
+//! For the same reason, the elapsed time is explicitly not a live region:
+//! announcing it every second would drown out all other speech. Users can
+//! read it on demand, or hear the seek slider's value text when they move
+//! to the slider.
+
+use alloc::{boxed::Box, format, string::String};
+
+use crate::{Action, Live, Node, Role};
+
+/// Formats a time in seconds as `m:ss`, or `h:mm:ss` if it's an hour
+/// or longer. Fractions of a second are dropped. Negative and non-finite
+/// times are formatted as `0:00`.
+pub fn format_time(seconds: f64) -> String {
+    let total = whole_seconds(seconds) as u64;
+    let (hours, minutes, seconds) = (total / 3600, (total / 60) % 60, total % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+fn whole_seconds(seconds: f64) -> f64 {
+    if seconds.is_finite() && seconds > 0.0 {
+        // `f64::floor` isn't available without the standard library.
+        (seconds as u64) as f64
+    } else {
+        0.0
+    }
+}
+
+/// Creates a play/pause button. The label is `pause_label` while the media
+/// is playing and `play_label` otherwise, so that it always names the action
+/// that clicking the button performs.
+pub fn play_pause_button(
+    is_playing: bool,
+    play_label: impl Into<Box<str>>,
+    pause_label: impl Into<Box<str>>,
+) -> Node {
+    let mut node = Node::new(Role::Button);
+    node.set_label(if is_playing {
+        pause_label.into()
+    } else {
+        play_label.into()
+    });
+    node.add_action(Action::Focus);
+    node.add_action(Action::Click);
+    node
+}
+
+/// Creates a seek slider for the given position and duration, in seconds.
+/// If the duration isn't known or is infinite, as for a live stream,
+/// the slider has no maximum and its value text only gives the position.
+pub fn seek_slider(label: impl Into<Box<str>>, position: f64, duration: Option<f64>) -> Node {
+    let position = whole_seconds(position);
+    let mut node = Node::new(Role::Slider);
+    node.set_label(label);
+    node.set_min_numeric_value(0.0);
+    node.set_numeric_value(position);
+    node.set_numeric_value_step(1.0);
+    match duration.filter(|duration| duration.is_finite()) {
+        Some(duration) => {
+            let duration = whole_seconds(duration);
+            node.set_max_numeric_value(duration);
+            node.set_value(format!(
+                "{} / {}",
+                format_time(position),
+                format_time(duration)
+            ));
+        }
+        None => node.set_value(format_time(position)),
+    }
+    node.add_action(Action::Focus);
+    node.add_action(Action::Increment);
+    node.add_action(Action::Decrement);
+    node.add_action(Action::SetValue);
+    node
+}
+
+/// Creates a timer showing the elapsed playback time. See the
+/// [module documentation](self) for why this isn't a live region.
+pub fn elapsed_time(position: f64) -> Node {
+    let mut node = Node::new(Role::Timer);
+    node.set_value(format_time(position));
+    node.set_live(Live::Off);
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format() {
+        assert_eq!(format_time(0.0), "0:00");
+        assert_eq!(format_time(65.9), "1:05");
+        assert_eq!(format_time(3723.0), "1:02:03");
+        assert_eq!(format_time(-3.0), "0:00");
+        assert_eq!(format_time(f64::NAN), "0:00");
+    }
+
+    #[test]
+    fn seek_slider_changes_once_per_second() {
+        assert_eq!(
+            seek_slider("Seek", 61.2, Some(300.5)),
+            seek_slider("Seek", 61.9, Some(300.5))
+        );
+        assert_ne!(
+            seek_slider("Seek", 61.9, Some(300.5)),
+            seek_slider("Seek", 62.0, Some(300.5))
+        );
+        let slider = seek_slider("Seek", 61.9, Some(300.5));
+        assert_eq!(slider.numeric_value(), Some(61.0));
+        assert_eq!(slider.max_numeric_value(), Some(300.0));
+        assert_eq!(slider.value(), Some("1:01 / 5:00"));
+        let live = seek_slider("Seek", 61.9, Some(f64::INFINITY));
+        assert_eq!(live.max_numeric_value(), None);
+        assert_eq!(live.value(), Some("1:01"));
+    }
+
+    #[test]
+    fn play_pause_label() {
+        assert_eq!(
+            play_pause_button(true, "Play", "Pause").label(),
+            Some("Pause")
+        );
+        assert_eq!(
+            play_pause_button(false, "Play", "Pause").label(),
+            Some("Play")
+        );
+    }
+}
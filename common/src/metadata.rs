@@ -0,0 +1,220 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+//! Structured metadata about the AccessKit schema, for use by
+//! documentation generators and language bindings.
+//!
+//! The property and flag tables are generated by the same macros that
+//! define the accessor methods on [`Node`], so they can't drift from
+//! the actual API. The enums, including [`Role`] and [`Action`], are listed
+//! with the numeric value of each variant.
+//!
+//! # Stability of numeric values
+//!
+//! The numeric value of an enum variant is its `#[repr(u8)]` discriminant.
+//! New variants are only ever added after the existing ones, so a value
+//! that has been published never changes its meaning. Renumbering or removing a variant
+//! is a breaking change that also increments [`SCHEMA_VERSION`].
+//! Code generators can therefore detect additions between two releases
+//! by comparing the highest value of each enum.
+//!
+//! See the `schema_metadata` example in this crate for a program that
+//! writes this metadata as JSON. Its output is checked in as `schema.json`
+//! at the root of this crate, and CI verifies that it's up to date,
+//! so the history of that file is a changelog of the schema.
+//!
+//! [`Node`]: crate::Node
+//! [`SCHEMA_VERSION`]: crate::SCHEMA_VERSION
+
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::Debug;
+
+use crate::{
+    Action, AnnouncementKind, AriaCurrent, AutoComplete, ChangeReason, HasPopup, Invalid,
+    ListStyle, Live, Orientation, Role, SortDirection, TextAlign, TextDecoration, TextDirection,
+    Toggled, VerticalOffset,
+};
+
+/// Holds the doc comment of each property, keyed by getter name.
+pub(crate) struct Docs;
+
+/// The type of value stored in a property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueType {
+    /// A boolean flag, which is either set or not set.
+    Flag,
+    NodeIdVec,
+    NodeId,
+    String,
+    F64,
+    Usize,
+    /// A color in RGBA, stored as a `u32`.
+    Color,
+    TextDecoration,
+    /// A slice of lengths, each stored as a `u8`.
+    LengthSlice,
+    /// A slice of coordinates, each stored as an `f32`.
+    CoordSlice,
+    /// An optional boolean, where the absence of a value is distinct
+    /// from `false`.
+    Bool,
+    /// One of the enums defined in this crate, identified by name.
+    Enum(&'static str),
+    Affine,
+    Rect,
+    TextSelection,
+    CustomActionVec,
+    TextSelectionVec,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PropertyInfo {
+    /// The name of the property, in `PascalCase`.
+    pub name: &'static str,
+    /// The name of the getter method on [`Node`](crate::Node).
+    pub getter: &'static str,
+    pub value_type: ValueType,
+    /// The doc comment of the getter method, as Markdown. Each line
+    /// is terminated by a newline and keeps its original indentation,
+    /// including the space that usually follows `///`.
+    pub doc: &'static str,
+}
+
+/// Returns all flags and properties of a node, grouped by value type.
+pub fn properties() -> impl Iterator<Item = &'static PropertyInfo> {
+    [
+        PropertyInfo::FLAGS,
+        PropertyInfo::NODE_ID_VEC_PROPERTIES,
+        PropertyInfo::NODE_ID_PROPERTIES,
+        PropertyInfo::STRING_PROPERTIES,
+        PropertyInfo::F64_PROPERTIES,
+        PropertyInfo::USIZE_PROPERTIES,
+        PropertyInfo::COLOR_PROPERTIES,
+        PropertyInfo::TEXT_DECORATION_PROPERTIES,
+        PropertyInfo::LENGTH_SLICE_PROPERTIES,
+        PropertyInfo::COORD_SLICE_PROPERTIES,
+        PropertyInfo::BOOL_PROPERTIES,
+        PropertyInfo::UNIQUE_ENUM_PROPERTIES,
+        PropertyInfo::OPTION_PROPERTIES,
+        PropertyInfo::CUSTOM_ACTION_VEC_PROPERTIES,
+        PropertyInfo::TEXT_SELECTION_VEC_PROPERTIES,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Returns all roles, in the order of their numeric values.
+pub fn roles() -> impl Iterator<Item = Role> {
+    (0..=u8::MAX).map_while(Role::n)
+}
+
+/// Returns all actions, in the order of their numeric values.
+pub fn actions() -> impl Iterator<Item = Action> {
+    (0..=u8::MAX).map_while(Action::n)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VariantInfo {
+    /// The name of the variant, in `PascalCase`.
+    pub name: String,
+    /// The numeric value of the variant, which never changes once
+    /// it has been published.
+    pub value: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumInfo {
+    /// The name of the enum, in `PascalCase`.
+    pub name: &'static str,
+    /// The variants of the enum, in the order of their numeric values.
+    pub variants: Vec<VariantInfo>,
+}
+
+fn variants<T: Debug>(n: fn(u8) -> Option<T>) -> Vec<VariantInfo> {
+    (0..=u8::MAX)
+        .map_while(|value| {
+            n(value).map(|variant| VariantInfo {
+                name: format!("{:?}", variant),
+                value,
+            })
+        })
+        .collect()
+}
+
+macro_rules! enum_info_list {
+    ($($name:ident),+) => {
+        [$(EnumInfo {
+            name: stringify!($name),
+            variants: variants($name::n),
+        }),+]
+    };
+}
+
+/// Returns all enums of the schema, with their variants.
+pub fn enums() -> impl Iterator<Item = EnumInfo> {
+    enum_info_list!(
+        Role,
+        Action,
+        Orientation,
+        TextDirection,
+        Invalid,
+        Toggled,
+        SortDirection,
+        AriaCurrent,
+        AutoComplete,
+        Live,
+        AnnouncementKind,
+        HasPopup,
+        ListStyle,
+        TextAlign,
+        VerticalOffset,
+        ChangeReason,
+        TextDecoration
+    )
+    .into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn properties_are_complete() {
+        let properties = properties().collect::<Vec<_>>();
+        let non_flag_count = properties
+            .iter()
+            .filter(|p| p.value_type != ValueType::Flag)
+            .count();
+        assert_eq!(non_flag_count, crate::PropertyId::Unset as usize);
+        let label = properties.iter().find(|p| p.name == "Label").unwrap();
+        assert_eq!(label.getter, "label");
+        assert_eq!(label.value_type, ValueType::String);
+        assert!(label.doc.starts_with(" The label of a control"));
+        let hidden = properties.iter().find(|p| p.name == "Hidden").unwrap();
+        assert_eq!(hidden.value_type, ValueType::Flag);
+        let toggled = properties.iter().find(|p| p.name == "Toggled").unwrap();
+        assert_eq!(toggled.value_type, ValueType::Enum("Toggled"));
+        assert_eq!(roles().next(), Some(Role::Unknown));
+        assert_eq!(actions().next(), Some(Action::Click));
+    }
+
+    #[test]
+    fn enums_have_numeric_values() {
+        let enums = enums().collect::<Vec<_>>();
+        let role = enums.iter().find(|e| e.name == "Role").unwrap();
+        assert_eq!(role.variants.len(), roles().count());
+        assert_eq!(role.variants[0].name, "Unknown");
+        let action = enums.iter().find(|e| e.name == "Action").unwrap();
+        let click = &action.variants[Action::Click as usize];
+        assert_eq!(click.name, "Click");
+        assert_eq!(click.value, Action::Click as u8);
+        for info in &enums {
+            for (i, variant) in info.variants.iter().enumerate() {
+                assert_eq!(usize::from(variant.value), i);
+            }
+        }
+    }
+}
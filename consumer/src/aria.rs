@@ -0,0 +1,847 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+//! Approximate conversions between AccessKit trees and ARIA-annotated HTML,
+//! for applications that mix native UI with web views and want to
+//! cross-check the semantics of both.
+//!
+//! The conversions are deliberately lossy. Only roles that have an ARIA
+//! equivalent are written as `role` attributes; other nodes become plain
+//! `div` elements. Only the properties with a direct `aria-*` counterpart
+//! are written, and bounds, actions other than focus, relations and
+//! text selection are dropped entirely. Converting a tree to HTML and back
+//! therefore doesn't produce the original tree, but it does produce one
+//! with the same ARIA semantics.
+//!
+//! Each node becomes one element, except that text runs are written as
+//! bare text and the value of a [`Role::Label`] node is written as
+//! the content of a `label` element. The output contains no whitespace
+//! between elements, and node IDs aren't preserved.
+
+use accesskit::{Action, Live, Node as NodeData, NodeId, Role, Toggled, Tree, TreeUpdate};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Write};
+
+use crate::{filters::FilterResult, node::Node, tree::State as TreeState};
+
+/// Roles with an ARIA equivalent. When several roles share an ARIA role,
+/// the first entry is used when converting from HTML.
+const ROLES: &[(Role, &str)] = &[
+    (Role::Alert, "alert"),
+    (Role::AlertDialog, "alertdialog"),
+    (Role::Application, "application"),
+    (Role::Article, "article"),
+    (Role::Banner, "banner"),
+    (Role::Blockquote, "blockquote"),
+    (Role::Button, "button"),
+    (Role::DefaultButton, "button"),
+    (Role::Caption, "caption"),
+    (Role::Cell, "cell"),
+    (Role::CheckBox, "checkbox"),
+    (Role::Code, "code"),
+    (Role::ColumnHeader, "columnheader"),
+    (Role::ComboBox, "combobox"),
+    (Role::EditableComboBox, "combobox"),
+    (Role::Comment, "comment"),
+    (Role::Complementary, "complementary"),
+    (Role::ContentInfo, "contentinfo"),
+    (Role::Definition, "definition"),
+    (Role::ContentDeletion, "deletion"),
+    (Role::Dialog, "dialog"),
+    (Role::Directory, "directory"),
+    (Role::Document, "document"),
+    (Role::Emphasis, "emphasis"),
+    (Role::Feed, "feed"),
+    (Role::Figure, "figure"),
+    (Role::Form, "form"),
+    (Role::GenericContainer, "generic"),
+    (Role::Grid, "grid"),
+    (Role::Group, "group"),
+    (Role::Heading, "heading"),
+    (Role::Image, "img"),
+    (Role::ContentInsertion, "insertion"),
+    (Role::Link, "link"),
+    (Role::List, "list"),
+    (Role::ListBox, "listbox"),
+    (Role::ListItem, "listitem"),
+    (Role::Log, "log"),
+    (Role::Main, "main"),
+    (Role::Mark, "mark"),
+    (Role::Marquee, "marquee"),
+    (Role::Math, "math"),
+    (Role::Menu, "menu"),
+    (Role::MenuBar, "menubar"),
+    (Role::MenuItem, "menuitem"),
+    (Role::MenuItemCheckBox, "menuitemcheckbox"),
+    (Role::MenuItemRadio, "menuitemradio"),
+    (Role::Meter, "meter"),
+    (Role::Navigation, "navigation"),
+    (Role::Note, "note"),
+    (Role::ListBoxOption, "option"),
+    (Role::MenuListOption, "option"),
+    (Role::Paragraph, "paragraph"),
+    (Role::ProgressIndicator, "progressbar"),
+    (Role::RadioButton, "radio"),
+    (Role::RadioGroup, "radiogroup"),
+    (Role::Region, "region"),
+    (Role::Row, "row"),
+    (Role::RowGroup, "rowgroup"),
+    (Role::RowHeader, "rowheader"),
+    (Role::ScrollBar, "scrollbar"),
+    (Role::Search, "search"),
+    (Role::SearchInput, "searchbox"),
+    (Role::Splitter, "separator"),
+    (Role::Slider, "slider"),
+    (Role::SpinButton, "spinbutton"),
+    (Role::Status, "status"),
+    (Role::Strong, "strong"),
+    (Role::Suggestion, "suggestion"),
+    (Role::Switch, "switch"),
+    (Role::Tab, "tab"),
+    (Role::Table, "table"),
+    (Role::TabList, "tablist"),
+    (Role::TabPanel, "tabpanel"),
+    (Role::Term, "term"),
+    (Role::TextInput, "textbox"),
+    (Role::MultilineTextInput, "textbox"),
+    (Role::DateInput, "textbox"),
+    (Role::DateTimeInput, "textbox"),
+    (Role::WeekInput, "textbox"),
+    (Role::MonthInput, "textbox"),
+    (Role::TimeInput, "textbox"),
+    (Role::EmailInput, "textbox"),
+    (Role::NumberInput, "textbox"),
+    (Role::PasswordInput, "textbox"),
+    (Role::PhoneNumberInput, "textbox"),
+    (Role::UrlInput, "textbox"),
+    (Role::Time, "time"),
+    (Role::Timer, "timer"),
+    (Role::Toolbar, "toolbar"),
+    (Role::Tooltip, "tooltip"),
+    (Role::Tree, "tree"),
+    (Role::TreeGrid, "treegrid"),
+    (Role::TreeItem, "treeitem"),
+    (Role::GraphicsDocument, "graphics-document"),
+    (Role::GraphicsObject, "graphics-object"),
+    (Role::GraphicsSymbol, "graphics-symbol"),
+    (Role::DocAbstract, "doc-abstract"),
+    (Role::DocAcknowledgements, "doc-acknowledgments"),
+    (Role::DocAfterword, "doc-afterword"),
+    (Role::DocAppendix, "doc-appendix"),
+    (Role::DocBackLink, "doc-backlink"),
+    (Role::DocBiblioEntry, "doc-biblioentry"),
+    (Role::DocBibliography, "doc-bibliography"),
+    (Role::DocBiblioRef, "doc-biblioref"),
+    (Role::DocChapter, "doc-chapter"),
+    (Role::DocColophon, "doc-colophon"),
+    (Role::DocConclusion, "doc-conclusion"),
+    (Role::DocCover, "doc-cover"),
+    (Role::DocCredit, "doc-credit"),
+    (Role::DocCredits, "doc-credits"),
+    (Role::DocDedication, "doc-dedication"),
+    (Role::DocEndnote, "doc-endnote"),
+    (Role::DocEndnotes, "doc-endnotes"),
+    (Role::DocEpigraph, "doc-epigraph"),
+    (Role::DocEpilogue, "doc-epilogue"),
+    (Role::DocErrata, "doc-errata"),
+    (Role::DocExample, "doc-example"),
+    (Role::DocFootnote, "doc-footnote"),
+    (Role::DocForeword, "doc-foreword"),
+    (Role::DocGlossary, "doc-glossary"),
+    (Role::DocGlossRef, "doc-glossref"),
+    (Role::DocIndex, "doc-index"),
+    (Role::DocIntroduction, "doc-introduction"),
+    (Role::DocNoteRef, "doc-noteref"),
+    (Role::DocNotice, "doc-notice"),
+    (Role::DocPageBreak, "doc-pagebreak"),
+    (Role::DocPageFooter, "doc-pagefooter"),
+    (Role::DocPageHeader, "doc-pageheader"),
+    (Role::DocPageList, "doc-pagelist"),
+    (Role::DocPart, "doc-part"),
+    (Role::DocPreface, "doc-preface"),
+    (Role::DocPrologue, "doc-prologue"),
+    (Role::DocPullquote, "doc-pullquote"),
+    (Role::DocQna, "doc-qna"),
+    (Role::DocSubtitle, "doc-subtitle"),
+    (Role::DocTip, "doc-tip"),
+    (Role::DocToc, "doc-toc"),
+];
+
+/// Returns the ARIA role that corresponds to the given role, if any.
+pub fn aria_role(role: Role) -> Option<&'static str> {
+    ROLES
+        .iter()
+        .find(|(r, _)| *r == role)
+        .map(|(_, aria)| *aria)
+}
+
+/// Returns the role that corresponds to the given ARIA role, if any.
+/// ARIA roles are compared without regard to ASCII case.
+pub fn role_from_aria(aria: &str) -> Option<Role> {
+    ROLES
+        .iter()
+        .find(|(_, r)| r.eq_ignore_ascii_case(aria))
+        .map(|(role, _)| *role)
+}
+
+fn write_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+}
+
+fn write_attribute(out: &mut String, name: &str, value: &str) {
+    out.push(' ');
+    out.push_str(name);
+    out.push_str("=\"");
+    write_escaped(out, value);
+    out.push('"');
+}
+
+fn write_number_attribute(out: &mut String, name: &str, value: f64) -> fmt::Result {
+    write!(out, " {}=\"{}\"", name, value)
+}
+
+fn write_element(
+    out: &mut String,
+    node: &Node,
+    filter: &impl Fn(&Node) -> FilterResult,
+) -> fmt::Result {
+    let role = node.role();
+    if role == Role::TextRun {
+        if let Some(value) = node.value() {
+            write_escaped(out, &value);
+        }
+        return Ok(());
+    }
+    let tag = if role == Role::Label { "label" } else { "div" };
+    out.push('<');
+    out.push_str(tag);
+    if let Some(aria) = aria_role(role) {
+        write_attribute(out, "role", aria);
+    }
+    if node.is_multiline() {
+        write_attribute(out, "aria-multiline", "true");
+    }
+    if let Some(label) = node.label() {
+        write_attribute(out, "aria-label", &label);
+    }
+    if let Some(description) = node.description() {
+        write_attribute(out, "aria-description", &description);
+    }
+    if let Some(placeholder) = node.placeholder() {
+        write_attribute(out, "aria-placeholder", placeholder);
+    }
+    let value = node.value();
+    if role != Role::Label {
+        if let Some(value) = &value {
+            write_attribute(out, "aria-valuetext", value);
+        }
+    }
+    if let Some(value) = node.numeric_value() {
+        write_number_attribute(out, "aria-valuenow", value)?;
+    }
+    if let Some(value) = node.min_numeric_value() {
+        write_number_attribute(out, "aria-valuemin", value)?;
+    }
+    if let Some(value) = node.max_numeric_value() {
+        write_number_attribute(out, "aria-valuemax", value)?;
+    }
+    if let Some(level) = node.data().level() {
+        write!(out, " aria-level=\"{}\"", level)?;
+    }
+    if let Some(toggled) = node.toggled() {
+        let name = if role == Role::Button {
+            "aria-pressed"
+        } else {
+            "aria-checked"
+        };
+        let toggled = match toggled {
+            Toggled::True => "true",
+            Toggled::False => "false",
+            Toggled::Mixed => "mixed",
+        };
+        write_attribute(out, name, toggled);
+    }
+    if let Some(expanded) = node.is_expanded() {
+        write_attribute(
+            out,
+            "aria-expanded",
+            if expanded { "true" } else { "false" },
+        );
+    }
+    if let Some(selected) = node.is_selected() {
+        write_attribute(
+            out,
+            "aria-selected",
+            if selected { "true" } else { "false" },
+        );
+    }
+    let states = [
+        ("aria-disabled", node.is_disabled()),
+        ("aria-readonly", node.data().is_read_only()),
+        ("aria-required", node.is_required()),
+        ("aria-hidden", node.is_hidden()),
+    ];
+    for (name, _) in states.iter().filter(|(_, is_set)| *is_set) {
+        write_attribute(out, name, "true");
+    }
+    if let Some(shortcut) = node.keyboard_shortcut() {
+        write_attribute(out, "aria-keyshortcuts", shortcut);
+    }
+    if let Some(live) = node.data().live() {
+        let live = match live {
+            Live::Off => "off",
+            Live::Polite => "polite",
+            Live::Assertive => "assertive",
+        };
+        write_attribute(out, "aria-live", live);
+    }
+    if node.is_focusable() {
+        write_attribute(out, "tabindex", "0");
+    }
+    out.push('>');
+    if role == Role::Label {
+        if let Some(value) = &value {
+            write_escaped(out, value);
+        }
+    }
+    for child in node.filtered_children(filter) {
+        write_element(out, &child, filter)?;
+    }
+    out.push_str("</");
+    out.push_str(tag);
+    out.push('>');
+    Ok(())
+}
+
+impl Node<'_> {
+    /// Returns an approximate ARIA-annotated HTML snippet for this node
+    /// and those of its descendants that pass the given filter.
+    ///
+    /// Only roles and properties with a direct ARIA counterpart are
+    /// written, so the snippet is suitable for comparing semantics with
+    /// a web view, but not for reconstructing the original tree.
+    pub fn to_aria_html(&self, filter: &impl Fn(&Node) -> FilterResult) -> String {
+        let mut out = String::new();
+        write_element(&mut out, self, filter).unwrap();
+        out
+    }
+}
+
+impl TreeState {
+    /// Returns an approximate ARIA-annotated HTML snippet for the nodes
+    /// that pass the given filter, such as
+    /// [`common_filter`](crate::common_filter). The children of excluded
+    /// nodes take their place in the output.
+    pub fn to_aria_html(&self, filter: &impl Fn(&Node) -> FilterResult) -> String {
+        let mut out = String::new();
+        let root = self.root();
+        let result = match filter(&root) {
+            FilterResult::Include => write_element(&mut out, &root, filter),
+            FilterResult::ExcludeNode => root
+                .filtered_children(filter)
+                .try_for_each(|child| write_element(&mut out, &child, filter)),
+            FilterResult::ExcludeSubtree => Ok(()),
+        };
+        result.unwrap();
+        out
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The byte offset in the snippet at which the error was detected.
+    pub offset: usize,
+    pub message: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.offset)
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &'static str) -> ParseError {
+        ParseError {
+            offset: self.offset,
+            message,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.offset..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.offset += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, prefix: &str) -> bool {
+        if self.rest().starts_with(prefix) {
+            self.offset += prefix.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.offset += len;
+        &rest[..len]
+    }
+
+    fn name(&mut self) -> Result<&'a str, ParseError> {
+        let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if name.is_empty() {
+            Err(self.error("expected a name"))
+        } else {
+            Ok(name)
+        }
+    }
+
+    fn attribute_value(&mut self) -> Result<String, ParseError> {
+        for quote in ['"', '\''] {
+            if self.rest().starts_with(quote) {
+                self.offset += 1;
+                let start = self.offset;
+                let value = self.take_while(|c| c != quote);
+                if !self.eat(if quote == '"' { "\"" } else { "'" }) {
+                    return Err(self.error("unterminated attribute value"));
+                }
+                return decode(value, start);
+            }
+        }
+        let start = self.offset;
+        let value = self.take_while(|c| !c.is_whitespace() && c != '>' && c != '/');
+        decode(value, start)
+    }
+}
+
+fn decode(text: &str, offset: usize) -> Result<String, ParseError> {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let error = ParseError {
+            offset: offset + text.len() - rest.len(),
+            message: "invalid character reference",
+        };
+        let end = rest.find(';').ok_or(error.clone())?;
+        let c = match &rest[1..end] {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            "nbsp" => '\u{a0}',
+            reference => {
+                let code = if let Some(hex) = reference
+                    .strip_prefix("#x")
+                    .or_else(|| reference.strip_prefix("#X"))
+                {
+                    u32::from_str_radix(hex, 16).ok()
+                } else if let Some(decimal) = reference.strip_prefix('#') {
+                    decimal.parse().ok()
+                } else {
+                    None
+                };
+                code.and_then(char::from_u32).ok_or(error)?
+            }
+        };
+        out.push(c);
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+struct OpenElement<'a> {
+    tag: &'a str,
+    index: usize,
+}
+
+fn boolean(value: &str) -> bool {
+    value.eq_ignore_ascii_case("true")
+}
+
+fn build_node(tag: &str, attributes: &[(&str, String)]) -> Result<NodeData, &'static str> {
+    let attribute = |name: &str| {
+        attributes
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    };
+    let number = |name: &str| {
+        attribute(name)
+            .map(|value| value.trim().parse::<f64>())
+            .transpose()
+            .map_err(|_| "invalid number")
+    };
+    let role = attribute("role")
+        .and_then(|roles| roles.split_ascii_whitespace().find_map(role_from_aria))
+        .unwrap_or(if tag.eq_ignore_ascii_case("label") {
+            Role::Label
+        } else {
+            Role::GenericContainer
+        });
+    let role = if role == Role::TextInput && attribute("aria-multiline").is_some_and(boolean) {
+        Role::MultilineTextInput
+    } else {
+        role
+    };
+    let mut node = NodeData::new(role);
+    if let Some(label) = attribute("aria-label") {
+        node.set_label(label);
+    }
+    if let Some(description) = attribute("aria-description") {
+        node.set_description(description);
+    }
+    if let Some(placeholder) = attribute("aria-placeholder") {
+        node.set_placeholder(placeholder);
+    }
+    if let Some(value) = attribute("aria-valuetext") {
+        node.set_value(value);
+    }
+    if let Some(value) = number("aria-valuenow")? {
+        node.set_numeric_value(value);
+    }
+    if let Some(value) = number("aria-valuemin")? {
+        node.set_min_numeric_value(value);
+    }
+    if let Some(value) = number("aria-valuemax")? {
+        node.set_max_numeric_value(value);
+    }
+    if let Some(level) = attribute("aria-level") {
+        node.set_level(level.trim().parse().map_err(|_| "invalid level")?);
+    }
+    if let Some(toggled) = attribute("aria-checked").or_else(|| attribute("aria-pressed")) {
+        node.set_toggled(if toggled.eq_ignore_ascii_case("mixed") {
+            Toggled::Mixed
+        } else if boolean(toggled) {
+            Toggled::True
+        } else {
+            Toggled::False
+        });
+    }
+    if let Some(expanded) = attribute("aria-expanded") {
+        node.set_expanded(boolean(expanded));
+    }
+    if let Some(selected) = attribute("aria-selected") {
+        node.set_selected(boolean(selected));
+    }
+    if attribute("aria-disabled").is_some_and(boolean) {
+        node.set_disabled();
+    }
+    if attribute("aria-readonly").is_some_and(boolean) {
+        node.set_read_only();
+    }
+    if attribute("aria-required").is_some_and(boolean) {
+        node.set_required();
+    }
+    if attribute("aria-hidden").is_some_and(boolean) {
+        node.set_hidden();
+    }
+    if let Some(shortcut) = attribute("aria-keyshortcuts") {
+        node.set_keyboard_shortcut(shortcut);
+    }
+    if let Some(live) = attribute("aria-live") {
+        node.set_live(match live {
+            "polite" => Live::Polite,
+            "assertive" => Live::Assertive,
+            _ => Live::Off,
+        });
+    }
+    if attribute("tabindex").is_some() {
+        node.add_action(Action::Focus);
+    }
+    Ok(node)
+}
+
+/// Builds a tree update from an ARIA-annotated HTML snippet, such as one
+/// produced by [`TreeState::to_aria_html`], mainly for use in tests.
+///
+/// The snippet must contain exactly one root element. Only the subset of
+/// HTML produced by [`TreeState::to_aria_html`] is understood: elements,
+/// attributes, text and character references. Comments, doctypes and
+/// void elements aren't supported, and tag names other than `label` are
+/// ignored. Node IDs are assigned in document order starting from 0,
+/// and the root node has focus.
+pub fn tree_update_from_aria_html(html: &str) -> Result<TreeUpdate, ParseError> {
+    let mut parser = Parser {
+        input: html,
+        offset: 0,
+    };
+    let mut nodes: Vec<(NodeId, NodeData)> = Vec::new();
+    let mut stack: Vec<OpenElement> = Vec::new();
+    while !parser.rest().is_empty() {
+        let start = parser.offset;
+        if parser.eat("</") {
+            let tag = parser.name()?;
+            parser.skip_whitespace();
+            if !parser.eat(">") {
+                return Err(parser.error("expected '>'"));
+            }
+            match stack.pop() {
+                Some(element) if element.tag.eq_ignore_ascii_case(tag) => (),
+                _ => {
+                    return Err(ParseError {
+                        offset: start,
+                        message: "unexpected end tag",
+                    })
+                }
+            }
+        } else if parser.eat("<") {
+            if stack.is_empty() && !nodes.is_empty() {
+                return Err(ParseError {
+                    offset: start,
+                    message: "more than one root element",
+                });
+            }
+            let tag = parser.name()?;
+            let mut attributes = Vec::new();
+            let self_closing = loop {
+                parser.skip_whitespace();
+                if parser.eat(">") {
+                    break false;
+                }
+                if parser.eat("/>") {
+                    break true;
+                }
+                if parser.rest().is_empty() {
+                    return Err(parser.error("unexpected end of input"));
+                }
+                let name = parser.name()?;
+                parser.skip_whitespace();
+                let value = if parser.eat("=") {
+                    parser.skip_whitespace();
+                    parser.attribute_value()?
+                } else {
+                    String::new()
+                };
+                attributes.push((name, value));
+            };
+            let node = build_node(tag, &attributes).map_err(|message| ParseError {
+                offset: start,
+                message,
+            })?;
+            let id = NodeId(nodes.len() as u64);
+            if let Some(parent) = stack.last() {
+                nodes[parent.index].1.push_child(id);
+            }
+            nodes.push((id, node));
+            if !self_closing {
+                stack.push(OpenElement {
+                    tag,
+                    index: nodes.len() - 1,
+                });
+            }
+        } else {
+            let text = parser.take_while(|c| c != '<');
+            if text.trim().is_empty() {
+                continue;
+            }
+            let Some(parent) = stack.last() else {
+                return Err(ParseError {
+                    offset: start,
+                    message: "text outside of the root element",
+                });
+            };
+            let text = decode(text, start)?;
+            let id = NodeId(nodes.len() as u64);
+            let parent = &mut nodes[parent.index].1;
+            if parent.role() == Role::Label && parent.children().is_empty() {
+                let value = match parent.value() {
+                    Some(value) => value.to_string() + &text,
+                    None => text,
+                };
+                parent.set_value(value);
+            } else {
+                parent.push_child(id);
+                let mut node = NodeData::new(Role::TextRun);
+                node.set_value(text);
+                nodes.push((id, node));
+            }
+        }
+    }
+    if !stack.is_empty() {
+        return Err(parser.error("unexpected end of input"));
+    }
+    if nodes.is_empty() {
+        return Err(parser.error("expected a root element"));
+    }
+    Ok(TreeUpdate {
+        nodes,
+        tree: Some(Tree::new(NodeId(0))),
+        focus: NodeId(0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{Node, NodeId, Role, Toggled, Tree, TreeUpdate};
+    use alloc::vec;
+
+    use super::{tree_update_from_aria_html, ParseError};
+
+    #[test]
+    fn filtered() {
+        let tree = crate::tests::test_tree();
+        assert_eq!(
+            tree.state().to_aria_html(&crate::tests::test_tree_filter),
+            concat!(
+                "<div tabindex=\"0\">",
+                "<div role=\"paragraph\"></div>",
+                "<label>label_1_1</label>",
+                "<div role=\"paragraph\"><label aria-label=\"label_2_0\"></label></div>",
+                "<label>label_3_1_0</label>",
+                "<div role=\"button\" aria-label=\"button_3_2\"></div>",
+                "</div>"
+            )
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2), NodeId(3)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::CheckBox);
+                    node.set_label("Say \"hi\" & <wave>");
+                    node.set_toggled(Toggled::Mixed);
+                    node.set_disabled();
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Slider);
+                    node.add_action(accesskit::Action::Focus);
+                    node.set_numeric_value(5.0);
+                    node.set_min_numeric_value(0.0);
+                    node.set_max_numeric_value(10.5);
+                    node
+                }),
+                (NodeId(3), {
+                    let mut node = Node::new(Role::Paragraph);
+                    node.set_children(vec![NodeId(4)]);
+                    node
+                }),
+                (NodeId(4), {
+                    let mut node = Node::new(Role::TextRun);
+                    node.set_value("Hello ");
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(2),
+        };
+        let tree = crate::Tree::new(update, true);
+        let html = tree
+            .state()
+            .to_aria_html(&|_: &crate::Node| crate::FilterResult::Include);
+        assert_eq!(
+            html,
+            concat!(
+                "<div>",
+                "<div role=\"checkbox\" aria-label=\"Say &quot;hi&quot; &amp; &lt;wave&gt;\" ",
+                "aria-checked=\"mixed\" aria-disabled=\"true\"></div>",
+                "<div role=\"slider\" aria-valuenow=\"5\" aria-valuemin=\"0\" ",
+                "aria-valuemax=\"10.5\" tabindex=\"0\"></div>",
+                "<div role=\"paragraph\">Hello </div>",
+                "</div>"
+            )
+        );
+        let tree = crate::Tree::new(tree_update_from_aria_html(&html).unwrap(), true);
+        assert_eq!(
+            tree.state().dump(),
+            r#"genericContainer focused focusable
+  checkBox label="Say \"hi\" & <wave>" checked=mixed disabled
+  slider numericValue=5 minNumericValue=0 maxNumericValue=10.5 focusable
+  paragraph
+    textRun value="Hello "
+"#
+        );
+    }
+
+    #[test]
+    fn parse_hand_written_snippet() {
+        let update = tree_update_from_aria_html(
+            "<div role='dialog' aria-label=Settings>\n  \
+             <label>Name&#x3a;</label>\n  \
+             <input role=\"textbox\" aria-multiline=\"true\" tabindex=\"-1\"/>\n  \
+             <button role=\"bogus button\" aria-pressed=\"true\">OK</button>\n\
+             </div>",
+        )
+        .unwrap();
+        let tree = crate::Tree::new(update, true);
+        assert_eq!(
+            tree.state().dump(),
+            r#"dialog label="Settings" focused focusable
+  label value="Name:"
+  multilineTextInput focusable multiline
+  button checked=true
+    textRun value="OK"
+"#
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |html| tree_update_from_aria_html(html).unwrap_err();
+        assert_eq!(
+            error("<div></span>"),
+            ParseError {
+                offset: 5,
+                message: "unexpected end tag"
+            }
+        );
+        assert_eq!(
+            error("<div></div><div></div>"),
+            ParseError {
+                offset: 11,
+                message: "more than one root element"
+            }
+        );
+        assert_eq!(
+            error("text"),
+            ParseError {
+                offset: 0,
+                message: "text outside of the root element"
+            }
+        );
+        assert_eq!(
+            error("<div aria-valuenow=\"five\">"),
+            ParseError {
+                offset: 0,
+                message: "invalid number"
+            }
+        );
+        assert_eq!(error("<div>").message, "unexpected end of input");
+        assert_eq!(error("").message, "expected a root element");
+    }
+}
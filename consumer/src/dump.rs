@@ -0,0 +1,226 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Role, Toggled};
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::{filters::FilterResult, node::Node, tree::State as TreeState};
+
+fn write_role(out: &mut String, role: Role) -> fmt::Result {
+    let start = out.len();
+    write!(out, "{:?}", role)?;
+    out[start..start + 1].make_ascii_lowercase();
+    Ok(())
+}
+
+fn write_quoted(out: &mut String, name: &str, value: &str) -> fmt::Result {
+    write!(out, " {}=\"", name)?;
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    Ok(())
+}
+
+fn write_node(out: &mut String, node: &Node, depth: usize) -> fmt::Result {
+    for _ in 0..depth {
+        out.push_str("  ");
+    }
+    write_role(out, node.role())?;
+    if let Some(label) = node.label() {
+        write_quoted(out, "label", &label)?;
+    }
+    if let Some(value) = node.value() {
+        write_quoted(out, "value", &value)?;
+    }
+    if let Some(description) = node.description() {
+        write_quoted(out, "description", &description)?;
+    }
+    if let Some(placeholder) = node.placeholder() {
+        write_quoted(out, "placeholder", placeholder)?;
+    }
+    if let Some(value) = node.numeric_value() {
+        write!(out, " numericValue={}", value)?;
+    }
+    if let Some(value) = node.min_numeric_value() {
+        write!(out, " minNumericValue={}", value)?;
+    }
+    if let Some(value) = node.max_numeric_value() {
+        write!(out, " maxNumericValue={}", value)?;
+    }
+    if let Some(toggled) = node.toggled() {
+        let toggled = match toggled {
+            Toggled::True => "true",
+            Toggled::False => "false",
+            Toggled::Mixed => "mixed",
+        };
+        write!(out, " checked={}", toggled)?;
+    }
+    if let Some(expanded) = node.data().is_expanded() {
+        write!(out, " expanded={}", expanded)?;
+    }
+    if let Some(selected) = node.is_selected() {
+        write!(out, " selected={}", selected)?;
+    }
+    let states = [
+        ("focused", node.is_focused()),
+        ("focusable", node.is_focusable()),
+        ("disabled", node.is_disabled()),
+        ("readOnly", node.data().is_read_only()),
+        ("required", node.is_required()),
+        ("hidden", node.is_hidden()),
+        ("offscreen", node.data().is_offscreen()),
+        ("multiline", node.is_multiline()),
+        ("clickable", node.is_clickable()),
+    ];
+    for (name, _) in states.iter().filter(|(_, is_set)| *is_set) {
+        write!(out, " {}", name)?;
+    }
+    out.push('\n');
+    Ok(())
+}
+
+fn write_subtree(
+    out: &mut String,
+    node: &Node,
+    depth: usize,
+    filter: &impl Fn(&Node) -> FilterResult,
+) -> fmt::Result {
+    write_node(out, node, depth)?;
+    for child in node.filtered_children(filter) {
+        write_subtree(out, &child, depth + 1, filter)?;
+    }
+    Ok(())
+}
+
+impl TreeState {
+    /// Returns a canonical textual dump of every node in the tree, in the
+    /// spirit of the accessibility tree dumps used by browser engines,
+    /// for use in golden (snapshot) tests.
+    ///
+    /// Each node is written on its own line, indented by two spaces per
+    /// level, starting with its role in camel case. The role is followed by
+    /// the node's text properties, quoted and escaped, and then by its
+    /// states, always in the same order. Node IDs are omitted, so that dumps
+    /// don't change when a toolkit allocates IDs differently. For example:
+    ///
+    /// ```text
+    /// window label="Hello"
+    ///   button label="Save" focusable
+    ///   checkBox label="Remember me" checked=true
+    /// ```
+    pub fn dump(&self) -> String {
+        self.dump_filtered(&|_: &Node| FilterResult::Include)
+    }
+
+    /// Returns a dump of the nodes that pass the given filter, such as
+    /// [`common_filter`](crate::common_filter), as they would be exposed
+    /// by a platform adapter. The children of excluded nodes take their
+    /// place in the dump.
+    pub fn dump_filtered(&self, filter: &impl Fn(&Node) -> FilterResult) -> String {
+        let mut out = String::new();
+        let root = self.root();
+        let result = match filter(&root) {
+            FilterResult::Include => write_subtree(&mut out, &root, 0, filter),
+            FilterResult::ExcludeNode => root
+                .filtered_children(filter)
+                .try_for_each(|child| write_subtree(&mut out, &child, 0, filter)),
+            FilterResult::ExcludeSubtree => Ok(()),
+        };
+        result.unwrap();
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{Node, NodeId, Role, Toggled, Tree, TreeUpdate};
+    use alloc::vec;
+
+    #[test]
+    fn unfiltered() {
+        let tree = crate::tests::test_tree();
+        assert_eq!(
+            tree.state().dump(),
+            r#"rootWebArea focusable
+  paragraph
+    label value="label_0_0_ignored"
+  paragraph
+    button label="button_1_0_hidden" hidden
+      genericContainer hidden
+    label value="label_1_1"
+    button label="button_1_2_hidden" hidden
+      genericContainer hidden
+  paragraph
+    label label="label_2_0"
+  paragraph
+    genericContainer
+    link label="label_3_1_0"
+      label value="label_3_1_0"
+    button label="button_3_2"
+    genericContainer
+"#
+        );
+    }
+
+    #[test]
+    fn filtered() {
+        let tree = crate::tests::test_tree();
+        assert_eq!(
+            tree.state().dump_filtered(&crate::tests::test_tree_filter),
+            r#"rootWebArea focusable
+  paragraph
+  label value="label_1_1"
+  paragraph
+    label label="label_2_0"
+  label value="label_3_1_0"
+  button label="button_3_2"
+"#
+        );
+    }
+
+    #[test]
+    fn properties_and_states() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::CheckBox);
+                    node.set_label("Say \"hi\"");
+                    node.set_toggled(Toggled::Mixed);
+                    node.set_disabled();
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Slider);
+                    node.set_numeric_value(5.0);
+                    node.set_min_numeric_value(0.0);
+                    node.set_max_numeric_value(10.5);
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(2),
+        };
+        let tree = crate::Tree::new(update, true);
+        assert_eq!(
+            tree.state().dump(),
+            r#"window
+  checkBox label="Say \"hi\"" checked=mixed disabled
+  slider numericValue=5 minNumericValue=0 maxNumericValue=10.5 focused focusable
+"#
+        );
+    }
+}
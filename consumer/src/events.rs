@@ -0,0 +1,220 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::PropertyId;
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{node::Node, tree::ChangeHandler};
+
+/// A typed change to the tree, as delivered to subscribers registered
+/// with [`Tree::subscribe`].
+///
+/// For each update, events are delivered in the same order as the calls
+/// to a [`TreeChangeHandler`]: added nodes, then updated nodes, then
+/// the focus change, then removed nodes. For each updated node,
+/// [`Event::NodeUpdated`] comes first, followed by
+/// [`Event::PropertyChanged`] for each changed property and then
+/// [`Event::TextSelectionChanged`] if the selection changed.
+///
+/// [`Tree::subscribe`]: crate::Tree::subscribe
+/// [`TreeChangeHandler`]: crate::TreeChangeHandler
+#[derive(Clone, Copy)]
+pub enum Event<'a> {
+    NodeAdded(Node<'a>),
+    /// A node was updated. This is delivered even if only the role,
+    /// actions, or boolean flags of the node changed, or if the node's
+    /// focus state changed, none of which are reported through
+    /// [`Event::PropertyChanged`].
+    NodeUpdated {
+        old_node: Node<'a>,
+        new_node: Node<'a>,
+    },
+    PropertyChanged {
+        old_node: Node<'a>,
+        new_node: Node<'a>,
+        property: PropertyId,
+    },
+    /// The primary or additional text selections of a node changed.
+    TextSelectionChanged {
+        old_node: Node<'a>,
+        new_node: Node<'a>,
+    },
+    FocusChanged {
+        old_node: Option<Node<'a>>,
+        new_node: Option<Node<'a>>,
+    },
+    /// A node was removed. The node is the last version of it
+    /// that was in the tree.
+    NodeRemoved(Node<'a>),
+}
+
+/// Identifies a subscriber registered with [`Tree::subscribe`], so it can
+/// be removed with [`Tree::unsubscribe`].
+///
+/// [`Tree::subscribe`]: crate::Tree::subscribe
+/// [`Tree::unsubscribe`]: crate::Tree::unsubscribe
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(pub(crate) u64);
+
+pub(crate) type Subscriber = Box<dyn FnMut(&Event) + Send + Sync>;
+
+pub(crate) struct EventDispatcher<'a>(pub(crate) &'a mut Vec<(SubscriptionId, Subscriber)>);
+
+impl EventDispatcher<'_> {
+    fn dispatch(&mut self, event: Event) {
+        for (_, subscriber) in self.0.iter_mut() {
+            subscriber(&event);
+        }
+    }
+}
+
+impl ChangeHandler for EventDispatcher<'_> {
+    fn node_added(&mut self, node: &Node) {
+        self.dispatch(Event::NodeAdded(*node));
+    }
+
+    fn node_updated(&mut self, old_node: &Node, new_node: &Node) {
+        let (old_node, new_node) = (*old_node, *new_node);
+        self.dispatch(Event::NodeUpdated { old_node, new_node });
+        let mut selection_changed = false;
+        for property in old_node.data().changed_properties(new_node.data()) {
+            if matches!(
+                property,
+                PropertyId::TextSelection | PropertyId::TextSelections
+            ) {
+                selection_changed = true;
+            }
+            self.dispatch(Event::PropertyChanged {
+                old_node,
+                new_node,
+                property,
+            });
+        }
+        if selection_changed {
+            self.dispatch(Event::TextSelectionChanged { old_node, new_node });
+        }
+    }
+
+    fn focus_moved(&mut self, old_node: Option<&Node>, new_node: Option<&Node>) {
+        self.dispatch(Event::FocusChanged {
+            old_node: old_node.copied(),
+            new_node: new_node.copied(),
+        });
+    }
+
+    fn node_removed(&mut self, node: &Node) {
+        self.dispatch(Event::NodeRemoved(*node));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use accesskit::{Node, NodeId, Role, TextPosition, TextSelection, Tree, TreeUpdate};
+    use alloc::{format, string::String, sync::Arc, vec, vec::Vec};
+    use std::sync::Mutex;
+
+    use super::Event;
+
+    fn describe(event: &Event) -> String {
+        match event {
+            Event::NodeAdded(node) => format!("added {:?}", node.id()),
+            Event::NodeUpdated { new_node, .. } => format!("updated {:?}", new_node.id()),
+            Event::PropertyChanged {
+                new_node, property, ..
+            } => format!("{:?} changed on {:?}", property, new_node.id()),
+            Event::TextSelectionChanged { new_node, .. } => {
+                format!("selection changed on {:?}", new_node.id())
+            }
+            Event::FocusChanged { old_node, new_node } => format!(
+                "focus {:?} -> {:?}",
+                old_node.map(|node| node.id()),
+                new_node.map(|node| node.id())
+            ),
+            Event::NodeRemoved(node) => format!("removed {:?}", node.id()),
+        }
+    }
+
+    fn subscribe(tree: &mut crate::Tree) -> (super::SubscriptionId, Arc<Mutex<Vec<String>>>) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let id = tree.subscribe({
+            let events = Arc::clone(&events);
+            move |event| events.lock().unwrap().push(describe(event))
+        });
+        (id, events)
+    }
+
+    #[test]
+    fn typed_events() {
+        let mut root = Node::new(Role::Window);
+        root.set_children(vec![NodeId(1)]);
+        let mut tree = crate::Tree::new(
+            TreeUpdate {
+                nodes: vec![
+                    (NodeId(0), root.clone()),
+                    (NodeId(1), Node::new(Role::Button)),
+                ],
+                tree: Some(Tree::new(NodeId(0))),
+                focus: NodeId(0),
+            },
+            true,
+        );
+        let (first_id, first) = subscribe(&mut tree);
+        let (_, second) = subscribe(&mut tree);
+
+        let mut input = Node::new(Role::TextInput);
+        input.set_value("hello");
+        let position = TextPosition {
+            node: NodeId(2),
+            character_index: 5,
+        };
+        input.set_text_selection(TextSelection {
+            anchor: position,
+            focus: position,
+        });
+        root.set_children(vec![NodeId(2)]);
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(0), root), (NodeId(2), input)],
+            tree: None,
+            focus: NodeId(2),
+        });
+        let expected = [
+            "added NodeId(2)",
+            "updated NodeId(0)",
+            "Children changed on NodeId(0)",
+            "focus Some(NodeId(0)) -> Some(NodeId(2))",
+            "removed NodeId(1)",
+        ];
+        assert_eq!(*first.lock().unwrap(), expected);
+        assert_eq!(*second.lock().unwrap(), expected);
+
+        assert!(tree.unsubscribe(first_id));
+        assert!(!tree.unsubscribe(first_id));
+        second.lock().unwrap().clear();
+        let mut input = Node::from(tree.state().node_by_id(NodeId(2)).unwrap().data());
+        input.set_text_selection(TextSelection {
+            anchor: TextPosition {
+                node: NodeId(2),
+                character_index: 0,
+            },
+            focus: position,
+        });
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(2), input)],
+            tree: None,
+            focus: NodeId(2),
+        });
+        assert_eq!(first.lock().unwrap().len(), 5);
+        assert_eq!(
+            *second.lock().unwrap(),
+            [
+                "updated NodeId(2)",
+                "TextSelection changed on NodeId(2)",
+                "selection changed on NodeId(2)",
+            ]
+        );
+    }
+}
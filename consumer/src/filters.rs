@@ -19,7 +19,7 @@ pub fn common_filter(node: &Node) -> FilterResult {
         return FilterResult::Include;
     }
 
-    if node.is_hidden() {
+    if node.is_hidden() || node.data().is_inert() {
         return FilterResult::ExcludeSubtree;
     }
 
@@ -8,16 +8,50 @@
 extern crate alloc;
 
 pub(crate) mod tree;
-pub use tree::{ChangeHandler as TreeChangeHandler, State as TreeState, Tree};
+pub use tree::{
+    ChangeHandler as TreeChangeHandler, FocusEventPosition, State as TreeState, Tree,
+    SCHEMA_VERSION,
+};
+
+pub(crate) mod events;
+pub use events::{Event as TreeEvent, SubscriptionId};
 
 pub(crate) mod node;
 pub use node::Node;
 
+pub(crate) mod multi_tree;
+pub use multi_tree::{MultiTree, SubtreeId};
+
 pub(crate) mod filters;
 pub use filters::{common_filter, common_filter_with_root_exception, FilterResult};
 
 pub(crate) mod iterators;
 
+pub(crate) mod aria;
+pub use aria::{
+    aria_role, role_from_aria, tree_update_from_aria_html, ParseError as AriaParseError,
+};
+
+pub(crate) mod dump;
+
+pub(crate) mod live;
+pub use live::LiveRegionChange;
+
+pub(crate) mod progress;
+pub use progress::ProgressThrottle;
+
+pub(crate) mod query;
+pub use query::{ParseError as QueryParseError, Query};
+
+pub(crate) mod shortcuts;
+pub use shortcuts::KeyboardShortcut;
+
+pub(crate) mod spatial;
+pub use spatial::Direction;
+
+pub(crate) mod table;
+pub use table::TableCellPosition;
+
 pub(crate) mod text;
 pub use text::{
     AttributeValue as TextAttributeValue, Position as TextPosition, Range as TextRange,
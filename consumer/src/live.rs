@@ -0,0 +1,163 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use alloc::vec::Vec;
+
+/// A change to the text of a live region, as it should be announced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiveRegionChange<'a> {
+    /// Text was appended to the region, as in a chat log or terminal.
+    /// Lines may also have been removed from the start of the region,
+    /// for example to limit its scrollback. Only the appended text,
+    /// without leading line breaks, should be announced.
+    ///
+    /// Text that extends the last word of the old text, as when
+    /// "Score: 1" becomes "Score: 15", isn't considered appended,
+    /// since announcing only the new characters would be misleading.
+    Appended(&'a str),
+    /// The text of the region was replaced, and all of the new text
+    /// should be announced.
+    Replaced(&'a str),
+}
+
+impl<'a> LiveRegionChange<'a> {
+    /// Compares the old and new text of a live region. Returns `None`
+    /// if there is nothing to announce, that is, if the new text is empty,
+    /// unchanged, or only had lines removed from its start.
+    pub fn new(old: &str, new: &'a str) -> Option<Self> {
+        if new.trim().is_empty() || old == new {
+            return None;
+        }
+        let overlap = line_overlap(old.as_bytes(), new.as_bytes());
+        if overlap == 0 || continues_word(&new[..overlap], &new[overlap..]) {
+            return Some(Self::Replaced(new));
+        }
+        let appended = new[overlap..].trim_start_matches(['\r', '\n']);
+        if appended.trim().is_empty() {
+            None
+        } else {
+            Some(Self::Appended(appended))
+        }
+    }
+
+    /// Returns the text that should be announced.
+    pub fn text(&self) -> &'a str {
+        match self {
+            Self::Appended(text) | Self::Replaced(text) => text,
+        }
+    }
+}
+
+/// Returns true if the text on either side of the boundary between
+/// the old and appended text belongs to the same word.
+fn continues_word(before: &str, after: &str) -> bool {
+    match (before.chars().next_back(), after.chars().next()) {
+        (Some(before), Some(after)) => before.is_alphanumeric() && after.is_alphanumeric(),
+        _ => false,
+    }
+}
+
+/// Returns the length of the longest prefix of `new` that is also
+/// a suffix of `old` starting at the beginning of a line.
+fn line_overlap(old: &[u8], new: &[u8]) -> usize {
+    // The failure function of the Knuth-Morris-Pratt algorithm:
+    // `failure[i]` is the length of the longest proper prefix of
+    // `new[..=i]` that is also a suffix of it.
+    let mut failure = Vec::with_capacity(new.len());
+    failure.push(0);
+    let mut len = 0;
+    for &b in &new[1..] {
+        while len > 0 && new[len] != b {
+            len = failure[len - 1];
+        }
+        if new[len] == b {
+            len += 1;
+        }
+        failure.push(len);
+    }
+
+    // Find the longest prefix of `new` that is a suffix of `old`.
+    let mut len = 0;
+    for &b in old {
+        if len == new.len() {
+            len = failure[len - 1];
+        }
+        while len > 0 && new[len] != b {
+            len = failure[len - 1];
+        }
+        if new[len] == b {
+            len += 1;
+        }
+    }
+
+    // Shorter overlaps are also suffixes of `old`; take the longest
+    // one that starts a line.
+    while len > 0 && len < old.len() && old[old.len() - len - 1] != b'\n' {
+        len = failure[len - 1];
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LiveRegionChange;
+
+    #[test]
+    fn appended_lines() {
+        assert_eq!(
+            LiveRegionChange::new("alice: hi", "alice: hi\nbob: hello"),
+            Some(LiveRegionChange::Appended("bob: hello"))
+        );
+        assert_eq!(
+            LiveRegionChange::new("$ make", "$ make\r\nok\r\n"),
+            Some(LiveRegionChange::Appended("ok\r\n"))
+        );
+        assert_eq!(
+            LiveRegionChange::new("Loading", "Loading..."),
+            Some(LiveRegionChange::Appended("..."))
+        );
+    }
+
+    #[test]
+    fn scrolled_lines() {
+        assert_eq!(
+            LiveRegionChange::new("one\ntwo\nthree", "two\nthree\nfour"),
+            Some(LiveRegionChange::Appended("four"))
+        );
+        assert_eq!(LiveRegionChange::new("one\ntwo", "two"), None);
+        // An overlap that doesn't start a line in the old text isn't
+        // a scrolled line.
+        assert_eq!(
+            LiveRegionChange::new("attempt 1", "1 failed"),
+            Some(LiveRegionChange::Replaced("1 failed"))
+        );
+    }
+
+    #[test]
+    fn extended_word() {
+        assert_eq!(
+            LiveRegionChange::new("Score: 1", "Score: 15"),
+            Some(LiveRegionChange::Replaced("Score: 15"))
+        );
+        assert_eq!(
+            LiveRegionChange::new("Score: 1", "Score: 1 point"),
+            Some(LiveRegionChange::Appended(" point"))
+        );
+    }
+
+    #[test]
+    fn replaced() {
+        assert_eq!(
+            LiveRegionChange::new("", "Saved"),
+            Some(LiveRegionChange::Replaced("Saved"))
+        );
+        assert_eq!(
+            LiveRegionChange::new("Saving", "Saved"),
+            Some(LiveRegionChange::Replaced("Saved"))
+        );
+        assert_eq!(LiveRegionChange::new("Saved", "Saved"), None);
+        assert_eq!(LiveRegionChange::new("Saved", ""), None);
+    }
+}
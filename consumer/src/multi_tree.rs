@@ -0,0 +1,519 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{ActionData, ActionRequest, Node, NodeId, Tree, TreeUpdate};
+use alloc::vec::Vec;
+use hashbrown::{HashMap, HashSet};
+
+/// Identifies one of the trees combined by a [`MultiTree`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SubtreeId(u64);
+
+impl SubtreeId {
+    /// The host tree, which contains the root of the combined tree.
+    pub const HOST: Self = Self(0);
+}
+
+struct TreeData {
+    /// The tree and the node within it that this tree is grafted onto,
+    /// or `None` for the host tree.
+    anchor: Option<(SubtreeId, NodeId)>,
+    tree: Option<Tree>,
+    focus: Option<NodeId>,
+    nodes: HashMap<NodeId, Node>,
+}
+
+impl TreeData {
+    fn new(anchor: Option<(SubtreeId, NodeId)>) -> Self {
+        Self {
+            anchor,
+            tree: None,
+            focus: None,
+            nodes: HashMap::new(),
+        }
+    }
+
+    fn root(&self) -> Option<NodeId> {
+        self.tree.as_ref().map(|tree| tree.root)
+    }
+}
+
+/// Combines the trees of several independent sources, such as a host UI
+/// and the web views embedded in it, into a single tree that can be given
+/// to a platform adapter.
+///
+/// Each subtree is grafted onto an anchor node in another tree, and its root
+/// appears as the last child of that node. Every source keeps using its own
+/// node IDs; the IDs in the combined tree are allocated here, so they don't
+/// need to be coordinated between sources. Use [`MultiTree::resolve`] or
+/// [`MultiTree::route_action_request`] to map them back.
+///
+/// When the focus of a tree is on the anchor of a subtree that has been
+/// initialized, the focus of the combined tree is that of the subtree.
+///
+/// Nodes that are removed from a tree are forgotten. If the anchor
+/// of a subtree is removed, the subtree is kept but is no longer part of
+/// the combined tree, until the anchor is added again.
+pub struct MultiTree {
+    trees: HashMap<SubtreeId, TreeData>,
+    next_subtree_id: u64,
+    global_ids: HashMap<(SubtreeId, NodeId), NodeId>,
+    local_ids: HashMap<NodeId, (SubtreeId, NodeId)>,
+    next_global_id: u64,
+}
+
+impl Default for MultiTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MultiTree {
+    pub fn new() -> Self {
+        let mut trees = HashMap::new();
+        trees.insert(SubtreeId::HOST, TreeData::new(None));
+        Self {
+            trees,
+            next_subtree_id: 1,
+            global_ids: HashMap::new(),
+            local_ids: HashMap::new(),
+            next_global_id: 0,
+        }
+    }
+
+    /// Registers a new subtree, grafted onto the node with the given ID
+    /// in the `parent` tree. The subtree isn't part of the combined tree
+    /// until its first update, which must include [`TreeUpdate::tree`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parent` isn't a known tree.
+    pub fn add_subtree(&mut self, parent: SubtreeId, anchor: NodeId) -> SubtreeId {
+        assert!(
+            self.trees.contains_key(&parent),
+            "unknown tree {:?}",
+            parent
+        );
+        let id = SubtreeId(self.next_subtree_id);
+        self.next_subtree_id += 1;
+        self.trees.insert(id, TreeData::new(Some((parent, anchor))));
+        id
+    }
+
+    /// Removes a subtree and every subtree grafted onto it. Returns
+    /// the update to apply to the combined tree, if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `subtree` is the host tree or isn't a known tree.
+    pub fn remove_subtree(&mut self, subtree: SubtreeId) -> Option<TreeUpdate> {
+        assert_ne!(subtree, SubtreeId::HOST, "the host tree can't be removed");
+        let (parent, anchor) = self.trees[&subtree].anchor.unwrap();
+        let was_attached = self.is_attached(subtree);
+        let mut to_remove = Vec::from([subtree]);
+        while let Some(id) = to_remove.pop() {
+            self.trees.remove(&id);
+            to_remove.extend(self.subtrees_of(id));
+        }
+        self.global_ids.retain(|(tree, _), global_id| {
+            let keep = self.trees.contains_key(tree);
+            if !keep {
+                self.local_ids.remove(global_id);
+            }
+            keep
+        });
+        if !was_attached {
+            return None;
+        }
+        let nodes = Vec::from([self.translate(parent, anchor)]);
+        self.finish_update(nodes, None)
+    }
+
+    /// Applies an update from one of the trees, and returns the update
+    /// to apply to the combined tree. Returns `None` if the combined tree
+    /// isn't affected, either because the host tree hasn't been initialized
+    /// yet or because the given tree isn't part of it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `subtree` isn't a known tree.
+    pub fn update(&mut self, subtree: SubtreeId, update: TreeUpdate) -> Option<TreeUpdate> {
+        let was_attached = self.attached_trees();
+        let data = self.trees.get_mut(&subtree).expect("unknown tree");
+        let old_root = data.root();
+        let mut removed = Vec::new();
+        let new_children = update
+            .nodes
+            .iter()
+            .flat_map(|(_, node)| node.children().iter().copied())
+            .collect::<HashSet<_>>();
+        for (id, _) in &update.nodes {
+            if let Some(old_node) = data.nodes.get(id) {
+                removed.extend(
+                    old_node
+                        .children()
+                        .iter()
+                        .filter(|child| !new_children.contains(*child)),
+                );
+            }
+        }
+        for (id, node) in update.nodes.iter().cloned() {
+            data.nodes.insert(id, node);
+        }
+        if let Some(tree) = &update.tree {
+            if let Some(old_root) = old_root.filter(|old_root| *old_root != tree.root) {
+                removed.push(old_root);
+            }
+            data.tree = Some(tree.clone());
+        }
+        data.focus = Some(update.focus);
+        while let Some(id) = removed.pop() {
+            let data = self.trees.get_mut(&subtree).unwrap();
+            if let Some(node) = data.nodes.remove(&id) {
+                removed.extend(node.children().iter().copied());
+            }
+            if let Some(global_id) = self.global_ids.remove(&(subtree, id)) {
+                self.local_ids.remove(&global_id);
+            }
+        }
+
+        let is_attached = self.attached_trees();
+        if !is_attached.contains(&subtree) {
+            return None;
+        }
+        let mut nodes = update
+            .nodes
+            .iter()
+            .map(|(id, _)| self.translate(subtree, *id))
+            .collect::<Vec<_>>();
+        let root_changed =
+            update.tree.is_some() && old_root != update.tree.as_ref().map(|t| t.root);
+        if root_changed {
+            if let Some((parent, anchor)) = self.trees[&subtree].anchor {
+                nodes.push(self.translate(parent, anchor));
+            }
+        }
+        let mut newly_attached = is_attached
+            .difference(&was_attached)
+            .copied()
+            .filter(|id| *id != subtree)
+            .collect::<Vec<_>>();
+        newly_attached.sort();
+        for id in newly_attached {
+            let local_ids = self.trees[&id].nodes.keys().copied().collect::<Vec<_>>();
+            nodes.extend(
+                local_ids
+                    .into_iter()
+                    .map(|local_id| self.translate(id, local_id)),
+            );
+            let (parent, anchor) = self.trees[&id].anchor.unwrap();
+            if !is_attached.contains(&parent) || parent == subtree {
+                continue;
+            }
+            nodes.push(self.translate(parent, anchor));
+        }
+        let tree = if subtree == SubtreeId::HOST {
+            update.tree.map(|tree| Tree {
+                root: self.global_id(SubtreeId::HOST, tree.root),
+                ..tree
+            })
+        } else {
+            None
+        };
+        self.finish_update(nodes, tree)
+    }
+
+    /// Returns the tree and local ID of a node in the combined tree.
+    pub fn resolve(&self, id: NodeId) -> Option<(SubtreeId, NodeId)> {
+        self.local_ids.get(&id).copied()
+    }
+
+    /// Translates an action request on the combined tree into a request
+    /// for the tree that contains its target. Returns `None` if the target
+    /// is unknown, or if the request refers to nodes in different trees.
+    pub fn route_action_request(
+        &self,
+        mut request: ActionRequest,
+    ) -> Option<(SubtreeId, ActionRequest)> {
+        let (subtree, target) = self.resolve(request.target)?;
+        request.target = target;
+        if let Some(ActionData::SetTextSelection(selection)) = &mut request.data {
+            for position in [&mut selection.anchor, &mut selection.focus] {
+                let (position_subtree, node) = self.resolve(position.node)?;
+                if position_subtree != subtree {
+                    return None;
+                }
+                position.node = node;
+            }
+        }
+        Some((subtree, request))
+    }
+
+    fn subtrees_of(&self, parent: SubtreeId) -> Vec<SubtreeId> {
+        self.trees
+            .iter()
+            .filter(|(_, data)| data.anchor.is_some_and(|(tree, _)| tree == parent))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    fn is_attached(&self, subtree: SubtreeId) -> bool {
+        let data = &self.trees[&subtree];
+        if data.tree.is_none() {
+            return false;
+        }
+        match data.anchor {
+            None => true,
+            Some((parent, anchor)) => {
+                self.trees[&parent].nodes.contains_key(&anchor) && self.is_attached(parent)
+            }
+        }
+    }
+
+    fn attached_trees(&self) -> HashSet<SubtreeId> {
+        self.trees
+            .keys()
+            .copied()
+            .filter(|id| self.is_attached(*id))
+            .collect()
+    }
+
+    fn global_id(&mut self, subtree: SubtreeId, id: NodeId) -> NodeId {
+        *self.global_ids.entry((subtree, id)).or_insert_with(|| {
+            let global_id = NodeId(self.next_global_id);
+            self.next_global_id += 1;
+            self.local_ids.insert(global_id, (subtree, id));
+            global_id
+        })
+    }
+
+    /// Returns the version of a node for the combined tree, with its IDs
+    /// translated and the roots of attached subtrees added to its children.
+    fn translate(&mut self, subtree: SubtreeId, id: NodeId) -> (NodeId, Node) {
+        let mut node = self.trees[&subtree].nodes[&id].clone();
+        node.map_node_ids(|id| self.global_id(subtree, id));
+        let mut grafted = self
+            .trees
+            .iter()
+            .filter(|(_, data)| data.anchor == Some((subtree, id)))
+            .filter_map(|(child, data)| Some((*child, data.root()?)))
+            .collect::<Vec<_>>();
+        grafted.sort();
+        for (child, root) in grafted {
+            if self.is_attached(child) {
+                let root = self.global_id(child, root);
+                node.push_child(root);
+            }
+        }
+        (self.global_id(subtree, id), node)
+    }
+
+    fn focus(&mut self) -> Option<NodeId> {
+        let mut subtree = SubtreeId::HOST;
+        let mut focus = self.trees[&subtree].focus?;
+        'outer: loop {
+            let mut grafted = self
+                .trees
+                .iter()
+                .filter(|(_, data)| data.anchor == Some((subtree, focus)))
+                .map(|(child, _)| *child)
+                .collect::<Vec<_>>();
+            grafted.sort();
+            for child in grafted {
+                if self.is_attached(child) {
+                    if let Some(child_focus) = self.trees[&child].focus {
+                        subtree = child;
+                        focus = child_focus;
+                        continue 'outer;
+                    }
+                }
+            }
+            break;
+        }
+        Some(self.global_id(subtree, focus))
+    }
+
+    fn finish_update(
+        &mut self,
+        nodes: Vec<(NodeId, Node)>,
+        tree: Option<Tree>,
+    ) -> Option<TreeUpdate> {
+        let focus = self.focus()?;
+        let mut seen = HashSet::new();
+        let nodes = nodes
+            .into_iter()
+            .rev()
+            .filter(|(id, _)| seen.insert(*id))
+            .collect::<Vec<_>>();
+        Some(TreeUpdate { nodes, tree, focus })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{Action, ActionRequest, Node, NodeId, Role, Tree, TreeUpdate};
+    use alloc::{vec, vec::Vec};
+
+    use super::{MultiTree, SubtreeId};
+
+    fn node(role: Role, children: &[u64]) -> Node {
+        let mut node = Node::new(role);
+        node.set_children(children.iter().copied().map(NodeId).collect::<Vec<_>>());
+        node
+    }
+
+    fn initial(nodes: Vec<(u64, Node)>, focus: u64) -> TreeUpdate {
+        TreeUpdate {
+            nodes: nodes
+                .into_iter()
+                .map(|(id, node)| (NodeId(id), node))
+                .collect(),
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(focus),
+        }
+    }
+
+    fn apply(tree: &mut Option<crate::Tree>, update: TreeUpdate) {
+        match tree {
+            Some(tree) => tree.update(update),
+            None => *tree = Some(crate::Tree::new(update, true)),
+        }
+    }
+
+    #[test]
+    fn grafting() {
+        let mut multi = MultiTree::new();
+        let mut combined = None;
+        let web_view = multi.add_subtree(SubtreeId::HOST, NodeId(1));
+        assert!(multi
+            .update(
+                web_view,
+                initial(
+                    vec![
+                        (0, node(Role::Document, &[1])),
+                        (1, node(Role::Button, &[]))
+                    ],
+                    1
+                )
+            )
+            .is_none());
+
+        let host = initial(
+            vec![(0, node(Role::Window, &[1])), (1, node(Role::WebView, &[]))],
+            1,
+        );
+        apply(&mut combined, multi.update(SubtreeId::HOST, host).unwrap());
+        let state = combined.as_ref().unwrap().state();
+        let focus = state.focus().unwrap();
+        assert_eq!(focus.role(), Role::Button);
+        let button_id = focus.id();
+        assert_eq!(multi.resolve(button_id), Some((web_view, NodeId(1))));
+        let web_view_node = state.root().children().next().unwrap();
+        assert_eq!(web_view_node.role(), Role::WebView);
+        assert_eq!(
+            web_view_node.children().next().unwrap().role(),
+            Role::Document
+        );
+
+        let request = ActionRequest {
+            action: Action::Click,
+            target: button_id,
+            data: None,
+        };
+        let (target_tree, request) = multi.route_action_request(request).unwrap();
+        assert_eq!(target_tree, web_view);
+        assert_eq!(request.target, NodeId(1));
+
+        let update = multi
+            .update(
+                web_view,
+                TreeUpdate {
+                    nodes: vec![(NodeId(0), node(Role::Document, &[]))],
+                    tree: None,
+                    focus: NodeId(0),
+                },
+            )
+            .unwrap();
+        apply(&mut combined, update);
+        let state = combined.as_ref().unwrap().state();
+        assert_eq!(state.focus().unwrap().role(), Role::Document);
+        assert_eq!(multi.resolve(button_id), None);
+
+        apply(&mut combined, multi.remove_subtree(web_view).unwrap());
+        let state = combined.as_ref().unwrap().state();
+        assert_eq!(state.focus().unwrap().role(), Role::WebView);
+        assert_eq!(
+            state.root().children().next().unwrap().children().count(),
+            0
+        );
+    }
+
+    #[test]
+    fn reattaching_anchor() {
+        let mut multi = MultiTree::new();
+        let mut combined = None;
+        let host = initial(
+            vec![(0, node(Role::Window, &[1])), (1, node(Role::WebView, &[]))],
+            0,
+        );
+        apply(&mut combined, multi.update(SubtreeId::HOST, host).unwrap());
+        let web_view = multi.add_subtree(SubtreeId::HOST, NodeId(1));
+        let update = multi
+            .update(web_view, initial(vec![(0, node(Role::Document, &[]))], 0))
+            .unwrap();
+        apply(&mut combined, update);
+        assert_eq!(
+            combined
+                .as_ref()
+                .unwrap()
+                .state()
+                .root()
+                .deepest_last_child()
+                .unwrap()
+                .role(),
+            Role::Document
+        );
+
+        let update = multi
+            .update(
+                SubtreeId::HOST,
+                TreeUpdate {
+                    nodes: vec![(NodeId(0), node(Role::Window, &[]))],
+                    tree: None,
+                    focus: NodeId(0),
+                },
+            )
+            .unwrap();
+        apply(&mut combined, update);
+        assert_eq!(
+            combined.as_ref().unwrap().state().root().children().count(),
+            0
+        );
+        assert!(multi
+            .update(web_view, initial(vec![(0, node(Role::Document, &[]))], 0))
+            .is_none());
+
+        let update = multi
+            .update(
+                SubtreeId::HOST,
+                TreeUpdate {
+                    nodes: vec![
+                        (NodeId(0), node(Role::Window, &[1])),
+                        (NodeId(1), node(Role::WebView, &[])),
+                    ],
+                    tree: None,
+                    focus: NodeId(1),
+                },
+            )
+            .unwrap();
+        apply(&mut combined, update);
+        let state = combined.as_ref().unwrap().state();
+        assert_eq!(
+            state.root().deepest_last_child().unwrap().role(),
+            Role::Document
+        );
+        assert_eq!(state.focus().unwrap().role(), Role::Document);
+    }
+}
@@ -9,17 +9,13 @@
 // found in the LICENSE.chromium file.
 
 use accesskit::{
-    Action, Affine, FrozenNode as NodeData, Live, NodeId, Orientation, Point, Rect, Role,
-    TextSelection, Toggled,
-};
-use alloc::{
-    string::{String, ToString},
-    sync::Arc,
-    vec::Vec,
+    Action, ActionData, Affine, ChangeReason, FrozenNode as NodeData, Live, NodeId, Orientation,
+    Point, Rect, Role, TextDecoration, TextSelection, Toggled, VerticalOffset,
 };
+use alloc::{string::String, sync::Arc, vec, vec::Vec};
 use core::{fmt, iter::FusedIterator};
 
-use crate::filters::FilterResult;
+use crate::filters::{common_filter, FilterResult};
 use crate::iterators::{
     FilteredChildren, FollowingFilteredSiblings, FollowingSiblings, LabelledBy,
     PrecedingFilteredSiblings, PrecedingSiblings,
@@ -51,12 +47,18 @@ impl<'a> Node<'a> {
         self.tree_state.focus_id() == Some(self.id())
     }
 
+    /// Returns whether this node has focus when the host has focus.
+    /// See [`TreeState::focus_id_in_tree`].
+    ///
+    /// [`TreeState::focus_id_in_tree`]: crate::TreeState::focus_id_in_tree
     pub fn is_focused_in_tree(&self) -> bool {
-        self.tree_state.focus == self.id()
+        self.tree_state.focus_id_in_tree() == self.id()
     }
 
     pub fn is_focusable(&self) -> bool {
-        self.supports_action(Action::Focus) || self.is_focused_in_tree()
+        self.supports_action(Action::Focus)
+            || self.tree_state.focus == self.id()
+            || self.is_focused_in_tree()
     }
 
     pub fn is_root(&self) -> bool {
@@ -290,10 +292,16 @@ impl<'a> Node<'a> {
             return None;
         }
 
-        for child in self.children().rev() {
-            let point = child.direct_transform().inverse() * point;
-            if let Some(result) = child.hit_test(point, filter) {
-                return Some(result);
+        // Parts of the children outside a clipping node aren't visible,
+        // so they can't be hit.
+        let is_clipped =
+            self.clips_children() && self.raw_bounds().is_some_and(|rect| !rect.contains(point));
+        if !is_clipped {
+            for child in self.children().rev() {
+                let point = child.direct_transform().inverse() * point;
+                if let Some(result) = child.hit_test(point, filter) {
+                    return Some(result);
+                }
             }
         }
 
@@ -309,7 +317,8 @@ impl<'a> Node<'a> {
     }
 
     /// Returns the deepest filtered node, either this node or a descendant,
-    /// at the given point in this node's coordinate space.
+    /// at the given point in this node's coordinate space. Descendants of
+    /// a node that clips its children are only hit inside that node's bounds.
     pub fn node_at_point(
         &self,
         point: Point,
@@ -318,6 +327,171 @@ impl<'a> Node<'a> {
         self.hit_test(point, filter).map(|(node, _)| node)
     }
 
+    pub fn clips_children(&self) -> bool {
+        self.data().clips_children()
+    }
+
+    /// Returns the children of this node, filtered with the given filter,
+    /// that are at least partly inside this node's bounds. If this node
+    /// doesn't clip its children or doesn't have bounds, all filtered
+    /// children are considered visible, as are children without bounds.
+    /// A filtered child may be a deeper descendant, so its bounds are
+    /// compared in this node's coordinate space, including the transforms
+    /// of any nodes in between.
+    ///
+    /// None of the platform adapters in this repository use this yet;
+    /// it's provided for adapters and assistive tools that expose
+    /// the visible range of a collection.
+    pub fn visible_filtered_children(
+        &self,
+        filter: impl Fn(&Node) -> FilterResult + 'a,
+    ) -> impl DoubleEndedIterator<Item = Node<'a>> + 'a {
+        let parent = *self;
+        let viewport = self.raw_bounds().filter(|_| self.clips_children());
+        self.filtered_children(filter).filter(move |child| {
+            let Some(viewport) = viewport else {
+                return true;
+            };
+            let Some(bounds) = child.bounding_box_in_coordinate_space(&parent) else {
+                return true;
+            };
+            let visible = viewport.intersect(bounds);
+            visible.width() > 0.0 && visible.height() > 0.0
+        })
+    }
+
+    /// Returns the first and last children of this node, filtered with the
+    /// given filter, that are visible within this node's viewport.
+    /// See [`Node::visible_filtered_children`].
+    pub fn visible_filtered_child_range(
+        &self,
+        filter: impl Fn(&Node) -> FilterResult + 'a,
+    ) -> Option<(Node<'a>, Node<'a>)> {
+        let mut visible = self.visible_filtered_children(filter);
+        let first = visible.next()?;
+        let last = visible.next_back().unwrap_or(first);
+        Some((first, last))
+    }
+
+    pub fn scroll_x(&self) -> Option<f64> {
+        self.data().scroll_x()
+    }
+
+    pub fn scroll_x_min(&self) -> Option<f64> {
+        self.data().scroll_x_min()
+    }
+
+    pub fn scroll_x_max(&self) -> Option<f64> {
+        self.data().scroll_x_max()
+    }
+
+    pub fn scroll_y(&self) -> Option<f64> {
+        self.data().scroll_y()
+    }
+
+    pub fn scroll_y_min(&self) -> Option<f64> {
+        self.data().scroll_y_min()
+    }
+
+    pub fn scroll_y_max(&self) -> Option<f64> {
+        self.data().scroll_y_max()
+    }
+
+    fn scroll_range(min: Option<f64>, max: Option<f64>) -> Option<(f64, f64)> {
+        let min = min.unwrap_or(0.0);
+        let max = max?;
+        (max > min).then_some((min, max))
+    }
+
+    fn horizontal_scroll_range(&self) -> Option<(f64, f64)> {
+        Self::scroll_range(self.scroll_x_min(), self.scroll_x_max())
+    }
+
+    fn vertical_scroll_range(&self) -> Option<(f64, f64)> {
+        Self::scroll_range(self.scroll_y_min(), self.scroll_y_max())
+    }
+
+    pub fn is_horizontally_scrollable(&self) -> bool {
+        self.horizontal_scroll_range().is_some()
+    }
+
+    pub fn is_vertically_scrollable(&self) -> bool {
+        self.vertical_scroll_range().is_some()
+    }
+
+    fn scroll_percent(value: Option<f64>, (min, max): (f64, f64)) -> f64 {
+        let value = value.unwrap_or(min).clamp(min, max);
+        (value - min) / (max - min) * 100.0
+    }
+
+    /// Returns the horizontal scroll position as a percentage of the
+    /// scrollable range, or `None` if the node isn't horizontally
+    /// scrollable.
+    pub fn horizontal_scroll_percent(&self) -> Option<f64> {
+        self.horizontal_scroll_range()
+            .map(|range| Self::scroll_percent(self.scroll_x(), range))
+    }
+
+    /// Returns the vertical scroll position as a percentage of the
+    /// scrollable range, or `None` if the node isn't vertically
+    /// scrollable.
+    pub fn vertical_scroll_percent(&self) -> Option<f64> {
+        self.vertical_scroll_range()
+            .map(|range| Self::scroll_percent(self.scroll_y(), range))
+    }
+
+    fn view_size(extent: Option<f64>, range: Option<(f64, f64)>) -> f64 {
+        match (extent, range) {
+            (Some(extent), Some((min, max))) if extent > 0.0 => {
+                extent / (extent + max - min) * 100.0
+            }
+            _ => 100.0,
+        }
+    }
+
+    /// Returns the width of this node's viewport as a percentage of the
+    /// total width of its content, computed from the node's bounds and
+    /// its horizontal scroll range.
+    pub fn horizontal_view_size(&self) -> f64 {
+        Self::view_size(
+            self.raw_bounds().map(|rect| rect.width()),
+            self.horizontal_scroll_range(),
+        )
+    }
+
+    /// Returns the height of this node's viewport as a percentage of the
+    /// total height of its content, computed from the node's bounds and
+    /// its vertical scroll range.
+    pub fn vertical_view_size(&self) -> f64 {
+        Self::view_size(
+            self.raw_bounds().map(|rect| rect.height()),
+            self.vertical_scroll_range(),
+        )
+    }
+
+    /// Returns the scroll offset corresponding to the given percentages
+    /// of the horizontal and vertical scroll ranges. If a percentage
+    /// is `None`, or the node isn't scrollable in that direction,
+    /// the current offset in that direction is kept.
+    pub fn scroll_offset_for_percent(
+        &self,
+        horizontal: Option<f64>,
+        vertical: Option<f64>,
+    ) -> Point {
+        fn offset(current: Option<f64>, percent: Option<f64>, range: Option<(f64, f64)>) -> f64 {
+            match (percent, range) {
+                (Some(percent), Some((min, max))) => {
+                    min + (max - min) * percent.clamp(0.0, 100.0) / 100.0
+                }
+                _ => current.unwrap_or_default(),
+            }
+        }
+        Point::new(
+            offset(self.scroll_x(), horizontal, self.horizontal_scroll_range()),
+            offset(self.scroll_y(), vertical, self.vertical_scroll_range()),
+        )
+    }
+
     pub fn id(&self) -> NodeId {
         self.id
     }
@@ -338,6 +512,19 @@ impl<'a> Node<'a> {
         self.data().is_hidden()
     }
 
+    /// Returns true if this node or any of its ancestors is marked inert.
+    /// Platform adapters must not forward action requests targeting
+    /// an inert node.
+    pub fn is_inert(&self) -> bool {
+        self.data().is_inert() || self.parent().is_some_and(|parent| parent.is_inert())
+    }
+
+    /// Returns true if this node or any of its ancestors is marked
+    /// as offscreen but still reachable. See [`accesskit::Node::set_offscreen`].
+    pub fn is_offscreen(&self) -> bool {
+        self.data().is_offscreen() || self.parent().is_some_and(|parent| parent.is_offscreen())
+    }
+
     pub fn is_disabled(&self) -> bool {
         self.data().is_disabled()
     }
@@ -425,6 +612,10 @@ impl<'a> Node<'a> {
         self.toggled().is_some()
     }
 
+    pub fn is_expanded(&self) -> Option<bool> {
+        self.data().is_expanded()
+    }
+
     pub fn supports_expand_collapse(&self) -> bool {
         self.data().is_expanded().is_some()
     }
@@ -447,9 +638,19 @@ impl<'a> Node<'a> {
     }
 
     // The future of the `Action` enum is undecided, so keep the following
-    // function private for now.
-    fn supports_action(&self, action: Action) -> bool {
-        self.data().supports_action(action)
+    // function private to this crate for now.
+    pub(crate) fn supports_action(&self, action: Action) -> bool {
+        if self.is_inert() {
+            return false;
+        }
+        if self.data().supports_action(action) {
+            return true;
+        }
+        // All platform adapters currently use the common filter, so child
+        // actions are inherited from the nearest parent that is exposed
+        // to assistive technologies.
+        self.filtered_parent(&common_filter)
+            .is_some_and(|parent| parent.data().child_supports_action(action))
     }
 
     pub fn supports_increment(&self) -> bool {
@@ -459,6 +660,86 @@ impl<'a> Node<'a> {
     pub fn supports_decrement(&self) -> bool {
         self.supports_action(Action::Decrement)
     }
+
+    pub fn supports_replace_selected_text(&self) -> bool {
+        self.supports_action(Action::ReplaceSelectedText)
+    }
+
+    pub fn supports_set_text_selection(&self) -> bool {
+        self.supports_action(Action::SetTextSelection)
+    }
+
+    pub fn supports_set_value(&self) -> bool {
+        self.supports_action(Action::SetValue)
+    }
+
+    pub fn supports_select(&self) -> bool {
+        self.supports_action(Action::Select)
+    }
+
+    pub fn supports_deselect(&self) -> bool {
+        self.supports_action(Action::Deselect)
+    }
+
+    pub fn supports_scroll_into_view(&self) -> bool {
+        self.supports_action(Action::ScrollIntoView)
+    }
+
+    /// Returns true if any action can scroll this node horizontally.
+    pub fn supports_horizontal_scrolling(&self) -> bool {
+        self.supports_action(Action::ScrollLeft)
+            || self.supports_action(Action::ScrollRight)
+            || self.supports_action(Action::SetScrollOffset)
+    }
+
+    /// Returns true if any action can scroll this node vertically.
+    pub fn supports_vertical_scrolling(&self) -> bool {
+        self.supports_action(Action::ScrollUp)
+            || self.supports_action(Action::ScrollDown)
+            || self.supports_action(Action::SetScrollOffset)
+    }
+
+    pub fn supports_drag(&self) -> bool {
+        self.supports_action(Action::Drag)
+    }
+
+    pub fn supports_drop(&self) -> bool {
+        self.supports_action(Action::Drop)
+    }
+
+    /// Returns the action, and its data, that best moves this node's
+    /// numeric value to the given value. If the node doesn't support
+    /// setting its value directly, but supports changing it by a relative
+    /// amount, this is a relative change to the given value. Otherwise,
+    /// if it supports incrementing or decrementing it, as is common for
+    /// splitters, this is an increment or decrement toward the given value.
+    pub fn set_numeric_value_action(&self, value: f64) -> (Action, Option<ActionData>) {
+        if !self.supports_action(Action::SetValue) {
+            if let Some(current) = self.numeric_value() {
+                if self.supports_action(Action::SetNumericValueRelative) {
+                    return (
+                        Action::SetNumericValueRelative,
+                        Some(ActionData::NumericValue(value - current)),
+                    );
+                }
+                if value > current && self.supports_increment() {
+                    return (Action::Increment, None);
+                }
+                if value < current && self.supports_decrement() {
+                    return (Action::Decrement, None);
+                }
+            }
+        }
+        (Action::SetValue, Some(ActionData::NumericValue(value)))
+    }
+
+    pub fn supports_show_tooltip(&self) -> bool {
+        self.supports_action(Action::ShowTooltip)
+    }
+
+    pub fn supports_hide_tooltip(&self) -> bool {
+        self.supports_action(Action::HideTooltip)
+    }
 }
 
 fn descendant_label_filter(node: &Node) -> FilterResult {
@@ -535,16 +816,139 @@ impl<'a> Node<'a> {
         }
     }
 
+    /// Returns the accessible name of this node, as platform adapters
+    /// should expose it. See [`Node::write_name`] for how it's computed.
+    pub fn name(&self) -> Option<String> {
+        let mut result = String::new();
+        self.write_name(&mut result).unwrap().then_some(result)
+    }
+
+    /// Writes the accessible name of this node, which is the first of
+    /// the following that is present:
+    ///
+    /// 1. The node's [`label`](accesskit::Node::label).
+    /// 2. The names of the nodes that label this node, as returned by
+    ///    [`Node::labelled_by`], separated by spaces. Each of these
+    ///    contributes its value if [`Node::label_comes_from_value`],
+    ///    its label otherwise, or else the names of the nodes that label
+    ///    it in turn. A node that has already contributed isn't visited
+    ///    again, so cycles in the `labelled_by` relation are harmless.
+    /// 3. The node's value, if [`Node::label_comes_from_value`].
+    /// 4. The node's [`tooltip`](accesskit::Node::tooltip).
+    /// 5. The node's [`placeholder`](accesskit::Node::placeholder).
+    ///
+    /// Returns whether anything was written.
+    pub fn write_name<W: fmt::Write>(&self, mut writer: W) -> Result<bool, fmt::Error> {
+        if self.write_label_direct(&mut writer)? {
+            return Ok(true);
+        }
+        let mut visited = vec![self.id];
+        if self.write_name_from_labelled_by(&mut writer, &mut visited)? {
+            return Ok(true);
+        }
+        if self.label_comes_from_value() && self.write_value(&mut writer)? {
+            return Ok(true);
+        }
+        if let Some(text) = self.data().tooltip().or_else(|| self.placeholder()) {
+            writer.write_str(text)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn write_name_from_labelled_by(
+        &self,
+        writer: &mut dyn fmt::Write,
+        visited: &mut Vec<NodeId>,
+    ) -> Result<bool, fmt::Error> {
+        let mut wrote_one = false;
+        for node in self.labelled_by() {
+            if visited.contains(&node.id) {
+                continue;
+            }
+            visited.push(node.id);
+            let mut writer = SpacePrefixingWriter {
+                inner: &mut *writer,
+                need_prefix: wrote_one,
+            };
+            let wrote_this_time = if node.label_comes_from_value() {
+                node.write_value(&mut writer)?
+            } else {
+                node.write_label_direct(&mut writer)?
+                    || node.write_name_from_labelled_by(&mut writer, visited)?
+            };
+            wrote_one = wrote_one || wrote_this_time;
+        }
+        Ok(wrote_one)
+    }
+
+    /// Returns the nodes that describe this node, as set with
+    /// [`accesskit::Node::set_described_by`]. IDs that don't refer to
+    /// nodes in the tree are skipped.
+    pub fn described_by(&self) -> impl Iterator<Item = Node<'a>> + 'a {
+        let tree_state = self.tree_state;
+        self.state
+            .data
+            .described_by()
+            .iter()
+            .filter_map(move |id| tree_state.node_by_id(*id))
+    }
+
+    /// Returns the accessible description of this node. This is the names
+    /// of the nodes that describe this node, as returned by
+    /// [`Node::described_by`], separated by spaces, or if there are none,
+    /// the node's [`description`](accesskit::Node::description).
+    /// Text that merely repeats the node's [name](Node::name) is left out.
     pub fn description(&self) -> Option<String> {
-        self.data()
-            .description()
-            .map(|description| description.to_string())
+        let name = self.name();
+        let is_new = |text: &str| !text.is_empty() && name.as_deref() != Some(text);
+        let mut result = String::new();
+        for node in self.described_by() {
+            if node.id == self.id {
+                continue;
+            }
+            if let Some(text) = node.name().filter(|text| is_new(text)) {
+                if !result.is_empty() {
+                    result.push(' ');
+                }
+                result.push_str(&text);
+            }
+        }
+        if result.is_empty() {
+            if let Some(description) = self.data().description().filter(|text| is_new(text)) {
+                result.push_str(description);
+            }
+        }
+        (!result.is_empty()).then_some(result)
     }
 
     pub fn placeholder(&self) -> Option<&str> {
         self.data().placeholder()
     }
 
+    /// Returns the placeholder, unless it's also the node's name,
+    /// as returned by [`Node::name`]. Platform adapters that expose
+    /// the placeholder separately from the name use this, so that
+    /// it isn't announced twice.
+    pub fn placeholder_unless_name(&self) -> Option<&str> {
+        let placeholder = self.placeholder()?;
+        (self.name().as_deref() != Some(placeholder)).then_some(placeholder)
+    }
+
+    pub fn help_text(&self) -> Option<&str> {
+        self.data().help_text()
+    }
+
+    pub fn keyboard_shortcut(&self) -> Option<&str> {
+        self.data().keyboard_shortcut()
+    }
+
+    /// Returns true if this node's value and text content must be masked
+    /// before they are exposed. See [`Role::PasswordInput`].
+    pub fn is_password_masked(&self) -> bool {
+        self.role() == Role::PasswordInput && !self.data().is_value_masked()
+    }
+
     pub fn value(&self) -> Option<String> {
         let mut result = String::new();
         self.write_value(&mut result).unwrap().then_some(result)
@@ -552,7 +956,17 @@ impl<'a> Node<'a> {
 
     pub fn write_value<W: fmt::Write>(&self, mut writer: W) -> Result<bool, fmt::Error> {
         if let Some(value) = &self.data().value() {
-            writer.write_str(value)?;
+            if self.is_password_masked() {
+                // Without text runs, there are no character lengths
+                // to tell which code points make up a single character.
+                if self.supports_text_ranges() {
+                    self.document_range().write_text(writer)?;
+                } else {
+                    write_masked(&mut writer, value.chars().count())?;
+                }
+            } else {
+                writer.write_str(value)?;
+            }
             Ok(true)
         } else if self.supports_text_ranges() && !self.is_multiline() {
             self.document_range().write_text(writer)?;
@@ -581,6 +995,7 @@ impl<'a> Node<'a> {
                     | Role::RadioButton
                     | Role::RadioGroup
                     | Role::Slider
+                    | Role::Splitter
                     | Role::Switch
                     | Role::TreeGrid
             )
@@ -613,20 +1028,157 @@ impl<'a> Node<'a> {
         self.data().is_required()
     }
 
+    /// Returns the live region politeness of this node. If it isn't set
+    /// explicitly, [`Role::Alert`] and [`Role::Status`] are implicitly
+    /// assertive and polite respectively, as in ARIA, and other nodes
+    /// inherit the politeness of their parent.
     pub fn live(&self) -> Live {
-        self.data()
-            .live()
-            .unwrap_or_else(|| self.parent().map_or(Live::Off, |parent| parent.live()))
+        self.data().live().unwrap_or_else(|| match self.role() {
+            Role::Alert => Live::Assertive,
+            Role::Status => Live::Polite,
+            _ => self.parent().map_or(Live::Off, |parent| parent.live()),
+        })
+    }
+
+    /// Returns true if this node is a notification that should be announced
+    /// as soon as it's added to the tree. See [`Role::Alert`].
+    pub fn is_notification(&self) -> bool {
+        matches!(self.role(), Role::Alert | Role::Status)
+    }
+
+    fn inherited<T>(&self, f: impl Fn(&'a NodeData) -> Option<T>) -> Option<T> {
+        let mut node = *self;
+        loop {
+            if let Some(value) = f(&node.state.data) {
+                return Some(value);
+            }
+            node = node.parent()?;
+        }
+    }
+
+    /// Returns the letter spacing of this node in pixels, inherited from
+    /// the nearest ancestor that sets it.
+    pub fn letter_spacing(&self) -> Option<f64> {
+        self.inherited(NodeData::letter_spacing)
+    }
+
+    /// Returns the line height of this node in pixels, inherited from
+    /// the nearest ancestor that sets it.
+    pub fn line_height(&self) -> Option<f64> {
+        self.inherited(NodeData::line_height)
+    }
+
+    /// Returns the first-line indentation of this node in pixels, inherited
+    /// from the nearest ancestor that sets it.
+    pub fn text_indent(&self) -> Option<f64> {
+        self.inherited(NodeData::text_indent)
+    }
+
+    pub fn margin_top(&self) -> Option<f64> {
+        self.inherited(NodeData::margin_top)
+    }
+
+    pub fn margin_bottom(&self) -> Option<f64> {
+        self.inherited(NodeData::margin_bottom)
+    }
+
+    pub fn margin_leading(&self) -> Option<f64> {
+        self.inherited(NodeData::margin_leading)
+    }
+
+    pub fn margin_trailing(&self) -> Option<f64> {
+        self.inherited(NodeData::margin_trailing)
+    }
+
+    /// Returns the font family of this node, inherited from the nearest
+    /// ancestor that sets it.
+    pub fn font_family(&self) -> Option<&'a str> {
+        self.inherited(NodeData::font_family)
+    }
+
+    /// Returns the language of this node, inherited from the nearest
+    /// ancestor that sets it.
+    pub fn language(&self) -> Option<&'a str> {
+        self.inherited(NodeData::language)
+    }
+
+    /// Returns the font size of this node in pixels, inherited from
+    /// the nearest ancestor that sets it.
+    pub fn font_size(&self) -> Option<f64> {
+        self.inherited(NodeData::font_size)
+    }
+
+    pub fn font_weight(&self) -> Option<f64> {
+        self.inherited(NodeData::font_weight)
+    }
+
+    pub fn background_color(&self) -> Option<u32> {
+        self.inherited(NodeData::background_color)
+    }
+
+    pub fn foreground_color(&self) -> Option<u32> {
+        self.inherited(NodeData::foreground_color)
+    }
+
+    pub fn overline(&self) -> Option<TextDecoration> {
+        self.inherited(NodeData::overline)
+    }
+
+    pub fn strikethrough(&self) -> Option<TextDecoration> {
+        self.inherited(NodeData::strikethrough)
+    }
+
+    pub fn underline(&self) -> Option<TextDecoration> {
+        self.inherited(NodeData::underline)
+    }
+
+    pub fn vertical_offset(&self) -> Option<VerticalOffset> {
+        self.inherited(NodeData::vertical_offset)
+    }
+
+    pub fn is_bold(&self) -> bool {
+        self.data().is_bold()
+    }
+
+    pub fn is_italic(&self) -> bool {
+        self.data().is_italic()
+    }
+
+    /// Returns the reason for the change to this node in the latest
+    /// tree update, or `None` if the update didn't include this node
+    /// or didn't give a reason.
+    pub fn change_reason(&self) -> Option<ChangeReason> {
+        if self.tree_state.change_reason_ids.contains(&self.id) {
+            self.data().change_reason()
+        } else {
+            None
+        }
     }
 
     pub fn is_selected(&self) -> Option<bool> {
         self.data().is_selected()
     }
 
+    pub fn is_multiselectable(&self) -> bool {
+        self.data().is_multiselectable()
+    }
+
+    pub fn is_grabbed(&self) -> bool {
+        self.data().is_grabbed()
+    }
+
+    pub fn is_drop_target(&self) -> bool {
+        self.data().is_drop_target()
+    }
+
     pub fn raw_text_selection(&self) -> Option<&TextSelection> {
         self.data().text_selection()
     }
 
+    pub fn raw_text_selections(&self) -> &[TextSelection] {
+        self.data().text_selections()
+    }
+
     pub fn raw_value(&self) -> Option<&str> {
         self.data().value()
     }
@@ -635,10 +1187,33 @@ impl<'a> Node<'a> {
         self.data().author_id()
     }
 
+    pub fn url(&self) -> Option<&str> {
+        self.data().url()
+    }
+
+    pub fn is_link(&self) -> bool {
+        matches!(
+            self.role(),
+            Role::Link
+                | Role::DocBackLink
+                | Role::DocBiblioRef
+                | Role::DocGlossRef
+                | Role::DocNoteRef
+        )
+    }
+
     pub fn class_name(&self) -> Option<&str> {
         self.data().class_name()
     }
 
+    pub fn earcon(&self) -> Option<&str> {
+        self.data().earcon()
+    }
+
+    pub fn pronunciation(&self) -> Option<&str> {
+        self.data().pronunciation()
+    }
+
     pub fn index_path(&self) -> Vec<usize> {
         self.relative_index_path(self.tree_state.root_id())
     }
@@ -692,6 +1267,14 @@ impl<'a> Node<'a> {
     }
 }
 
+/// Writes the given number of bullets, one for each masked character.
+pub(crate) fn write_masked<W: fmt::Write>(mut writer: W, count: usize) -> fmt::Result {
+    for _ in 0..count {
+        writer.write_char('\u{2022}')?;
+    }
+    Ok(())
+}
+
 struct SpacePrefixingWriter<W: fmt::Write> {
     inner: W,
     need_prefix: bool,
@@ -721,7 +1304,10 @@ impl<W: fmt::Write> fmt::Write for SpacePrefixingWriter<W> {
 
 #[cfg(test)]
 mod tests {
-    use accesskit::{Node, NodeId, Point, Rect, Role, Tree, TreeUpdate};
+    use crate::FilterResult;
+    use accesskit::{
+        Action, ActionData, Affine, Live, Node, NodeId, Point, Rect, Role, Tree, TreeUpdate,
+    };
     use alloc::vec;
 
     use crate::tests::*;
@@ -780,6 +1366,36 @@ mod tests {
             .is_none());
     }
 
+    #[test]
+    fn child_actions_through_ignored_parent() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::ListBox);
+                    node.set_children(vec![NodeId(1)]);
+                    node.add_child_action(Action::Click);
+                    node.add_child_action(Action::Focus);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::GenericContainer);
+                    node.set_children(vec![NodeId(2)]);
+                    node
+                }),
+                (NodeId(2), Node::new(Role::ListBoxOption)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = crate::Tree::new(update, false);
+        let state = tree.state();
+        assert!(!state.root().is_clickable());
+        let option = state.node_by_id(NodeId(2)).unwrap();
+        assert!(option.is_clickable());
+        assert!(option.is_focusable());
+        assert!(!option.supports_increment());
+    }
+
     #[test]
     fn filtered_parent() {
         let tree = test_tree();
@@ -984,45 +1600,194 @@ mod tests {
     }
 
     #[test]
-    fn no_label_or_labelled_by() {
-        let update = TreeUpdate {
+    fn node_at_point_with_clipping() {
+        let update = |clips_children| TreeUpdate {
             nodes: vec![
                 (NodeId(0), {
                     let mut node = Node::new(Role::Window);
+                    node.set_transform(Affine::translate((10.0, 10.0)));
                     node.set_children(vec![NodeId(1)]);
                     node
                 }),
-                (NodeId(1), Node::new(Role::Button)),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::ScrollView);
+                    node.set_bounds(Rect::new(0.0, 0.0, 100.0, 100.0));
+                    if clips_children {
+                        node.set_clips_children();
+                    }
+                    node.set_children(vec![NodeId(2)]);
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Button);
+                    node.set_bounds(Rect::new(0.0, 50.0, 100.0, 150.0));
+                    node
+                }),
             ],
             tree: Some(Tree::new(NodeId(0))),
             focus: NodeId(0),
         };
-        let tree = crate::Tree::new(update, false);
-        assert_eq!(None, tree.state().node_by_id(NodeId(1)).unwrap().label());
+        let hit = |clips_children, x, y| {
+            let tree = crate::Tree::new(update(clips_children), false);
+            tree.state()
+                .node_at_point(Point::new(x, y), &crate::common_filter)
+                .map(|node| node.id())
+        };
+        assert_eq!(hit(true, 50.0, 80.0), Some(NodeId(2)));
+        assert_eq!(hit(true, 50.0, 50.0), Some(NodeId(1)));
+        assert_eq!(hit(true, 50.0, 130.0), None);
+        assert_eq!(hit(false, 50.0, 130.0), Some(NodeId(2)));
     }
 
     #[test]
-    fn label_from_labelled_by() {
-        // The following mock UI probably isn't very localization-friendly,
-        // but it's good for this test.
-        const LABEL_1: &str = "Check email every";
-        const LABEL_2: &str = "minutes";
-
+    fn scrolling_by_transform() {
+        let content = |scroll_y: f64| {
+            let mut node = Node::new(Role::GenericContainer);
+            node.set_transform(Affine::translate((0.0, -scroll_y)));
+            node.set_children(vec![NodeId(3)]);
+            node
+        };
         let update = TreeUpdate {
             nodes: vec![
                 (NodeId(0), {
                     let mut node = Node::new(Role::Window);
-                    node.set_children(vec![NodeId(1), NodeId(2), NodeId(3), NodeId(4)]);
+                    node.set_children(vec![NodeId(1)]);
                     node
                 }),
                 (NodeId(1), {
-                    let mut node = Node::new(Role::CheckBox);
-                    node.set_labelled_by(vec![NodeId(2), NodeId(4)]);
+                    let mut node = Node::new(Role::ScrollView);
+                    node.set_bounds(Rect::new(0.0, 0.0, 100.0, 100.0));
+                    node.set_children(vec![NodeId(2)]);
                     node
                 }),
-                (NodeId(2), {
-                    let mut node = Node::new(Role::Label);
-                    node.set_value(LABEL_1);
+                (NodeId(2), content(0.0)),
+                (NodeId(3), {
+                    let mut node = Node::new(Role::Button);
+                    node.set_bounds(Rect::new(0.0, 150.0, 100.0, 170.0));
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut tree = crate::Tree::new(update, false);
+        // Only the node with the transform changes when scrolling.
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(2), content(100.0))],
+            tree: None,
+            focus: NodeId(0),
+        });
+        assert_eq!(
+            tree.state().node_by_id(NodeId(3)).unwrap().bounding_box(),
+            Some(Rect::new(0.0, 50.0, 100.0, 70.0))
+        );
+    }
+
+    #[test]
+    fn nested_transforms() {
+        // A quarter turn counterclockwise, written out exactly.
+        const ROTATE_90: Affine = Affine::new([0.0, 1.0, -1.0, 0.0, 0.0, 0.0]);
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_transform(Affine::scale(1.5));
+                    node.set_children(vec![NodeId(1)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Canvas);
+                    node.set_transform(Affine::translate((100.0, 50.0)) * Affine::scale(2.0));
+                    node.set_children(vec![NodeId(2)]);
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::GenericContainer);
+                    node.set_transform(ROTATE_90 * Affine::scale_non_uniform(1.0, 0.5));
+                    node.set_children(vec![NodeId(3)]);
+                    node
+                }),
+                (NodeId(3), {
+                    let mut node = Node::new(Role::Button);
+                    node.set_bounds(Rect {
+                        x0: 0.0,
+                        y0: 0.0,
+                        x1: 10.0,
+                        y1: 40.0,
+                    });
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = crate::Tree::new(update, false);
+        let state = tree.state();
+        let button = state.node_by_id(NodeId(3)).unwrap();
+        // In the rotated container, the button spans x -20..0 and y 0..10.
+        // The canvas scales that to x -40..0 and y 0..20 and moves it to
+        // x 60..100 and y 50..70, and the window scales everything by 1.5.
+        assert_eq!(
+            Some(Rect {
+                x0: 90.0,
+                y0: 75.0,
+                x1: 150.0,
+                y1: 105.0,
+            }),
+            button.bounding_box()
+        );
+        let root = state.root();
+        let to_root = |x, y| root.direct_transform().inverse() * Point::new(x, y);
+        assert_eq!(
+            Some(NodeId(3)),
+            root.node_at_point(to_root(120.0, 90.0), &crate::common_filter)
+                .map(|node| node.id())
+        );
+        assert!(root
+            .node_at_point(to_root(120.0, 110.0), &crate::common_filter)
+            .is_none());
+    }
+
+    #[test]
+    fn no_label_or_labelled_by() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1)]);
+                    node
+                }),
+                (NodeId(1), Node::new(Role::Button)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = crate::Tree::new(update, false);
+        assert_eq!(None, tree.state().node_by_id(NodeId(1)).unwrap().label());
+    }
+
+    #[test]
+    fn label_from_labelled_by() {
+        // The following mock UI probably isn't very localization-friendly,
+        // but it's good for this test.
+        const LABEL_1: &str = "Check email every";
+        const LABEL_2: &str = "minutes";
+
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2), NodeId(3), NodeId(4)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::CheckBox);
+                    node.set_labelled_by(vec![NodeId(2), NodeId(4)]);
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Label);
+                    node.set_value(LABEL_1);
                     node
                 }),
                 (NodeId(3), {
@@ -1050,6 +1815,115 @@ mod tests {
         );
     }
 
+    #[test]
+    fn name_computation() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![
+                        NodeId(1),
+                        NodeId(2),
+                        NodeId(3),
+                        NodeId(4),
+                        NodeId(5),
+                        NodeId(6),
+                    ]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::TextInput);
+                    node.set_placeholder("Search");
+                    node.set_tooltip("Search the site");
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::TextInput);
+                    node.set_placeholder("Search");
+                    node
+                }),
+                (NodeId(3), {
+                    let mut node = Node::new(Role::Label);
+                    node.set_value("Visible text");
+                    node
+                }),
+                // These two label each other, and the first one is also
+                // labelled by a group that is labelled by a label.
+                (NodeId(4), {
+                    let mut node = Node::new(Role::TextInput);
+                    node.set_labelled_by(vec![NodeId(5), NodeId(6)]);
+                    node
+                }),
+                (NodeId(5), {
+                    let mut node = Node::new(Role::Group);
+                    node.set_labelled_by(vec![NodeId(4), NodeId(3)]);
+                    node
+                }),
+                (NodeId(6), {
+                    let mut node = Node::new(Role::Group);
+                    node.set_label("Group");
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = crate::Tree::new(update, false);
+        let name = |id| tree.state().node_by_id(id).unwrap().name();
+        assert_eq!(name(NodeId(0)), None);
+        assert_eq!(name(NodeId(1)), Some("Search the site".into()));
+        assert_eq!(name(NodeId(2)), Some("Search".into()));
+        assert_eq!(name(NodeId(3)), Some("Visible text".into()));
+        assert_eq!(name(NodeId(4)), Some("Visible text Group".into()));
+        assert_eq!(name(NodeId(5)), Some("Group Visible text".into()));
+        let node = |id| tree.state().node_by_id(id).unwrap();
+        assert_eq!(node(NodeId(1)).placeholder_unless_name(), Some("Search"));
+        assert_eq!(node(NodeId(2)).placeholder_unless_name(), None);
+    }
+
+    #[test]
+    fn description_computation() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2), NodeId(3), NodeId(4)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::TextInput);
+                    node.set_label("Password");
+                    node.set_description("Ignored");
+                    node.set_described_by(vec![NodeId(2), NodeId(3), NodeId(5)]);
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Label);
+                    node.set_value("At least 8 characters");
+                    node
+                }),
+                (NodeId(3), {
+                    let mut node = Node::new(Role::Label);
+                    node.set_value("Password");
+                    node
+                }),
+                (NodeId(4), {
+                    let mut node = Node::new(Role::Button);
+                    node.set_label("Save");
+                    node.set_description("Save");
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = crate::Tree::new(update, false);
+        let description = |id| tree.state().node_by_id(id).unwrap().description();
+        assert_eq!(description(NodeId(1)), Some("At least 8 characters".into()));
+        assert_eq!(description(NodeId(2)), None);
+        assert_eq!(description(NodeId(4)), None);
+    }
+
     #[test]
     fn label_from_descendant_label() {
         const ROOT_ID: NodeId = NodeId(0);
@@ -1226,4 +2100,312 @@ mod tests {
             tree.state().node_by_id(MENU_ITEM_RADIO_ID).unwrap().label()
         );
     }
+
+    #[test]
+    fn set_numeric_value_action() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2), NodeId(3)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Splitter);
+                    node.set_numeric_value(50.0);
+                    node.add_action(Action::Increment);
+                    node.add_action(Action::Decrement);
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Slider);
+                    node.set_numeric_value(50.0);
+                    node.add_action(Action::Increment);
+                    node.add_action(Action::SetValue);
+                    node
+                }),
+                (NodeId(3), {
+                    let mut node = Node::new(Role::Slider);
+                    node.set_numeric_value(50.0);
+                    node.add_action(Action::Increment);
+                    node.add_action(Action::SetNumericValueRelative);
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = crate::Tree::new(update, false);
+        let state = tree.state();
+        let splitter = state.node_by_id(NodeId(1)).unwrap();
+        assert!(!splitter.is_read_only());
+        assert_eq!(
+            splitter.set_numeric_value_action(60.0),
+            (Action::Increment, None)
+        );
+        assert_eq!(
+            splitter.set_numeric_value_action(40.0),
+            (Action::Decrement, None)
+        );
+        let slider = state.node_by_id(NodeId(2)).unwrap();
+        assert_eq!(
+            slider.set_numeric_value_action(60.0),
+            (Action::SetValue, Some(ActionData::NumericValue(60.0)))
+        );
+        let analog_slider = state.node_by_id(NodeId(3)).unwrap();
+        assert_eq!(
+            analog_slider.set_numeric_value_action(35.0),
+            (
+                Action::SetNumericValueRelative,
+                Some(ActionData::NumericValue(-15.0))
+            )
+        );
+    }
+
+    #[test]
+    fn viewport() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::ScrollView);
+                    node.set_bounds(Rect::new(0.0, 0.0, 100.0, 100.0));
+                    node.set_clips_children();
+                    node.set_scroll_y(150.0);
+                    node.set_scroll_y_max(300.0);
+                    node.set_children(vec![NodeId(1), NodeId(2), NodeId(3), NodeId(4)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::ListItem);
+                    node.set_bounds(Rect::new(0.0, -100.0, 100.0, -50.0));
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::ListItem);
+                    node.set_bounds(Rect::new(0.0, -50.0, 100.0, 0.0));
+                    node
+                }),
+                (NodeId(3), {
+                    let mut node = Node::new(Role::ListItem);
+                    node.set_bounds(Rect::new(0.0, 0.0, 100.0, 50.0));
+                    node
+                }),
+                (NodeId(4), {
+                    let mut node = Node::new(Role::ListItem);
+                    node.set_bounds(Rect::new(0.0, 50.0, 100.0, 100.0));
+                    node.set_transform(Affine::translate((0.0, 20.0)));
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = crate::Tree::new(update, false);
+        let root = tree.state().root();
+        let (first, last) = root
+            .visible_filtered_child_range(|_| FilterResult::Include)
+            .unwrap();
+        assert_eq!(first.id(), NodeId(3));
+        assert_eq!(last.id(), NodeId(4));
+        assert!(!root.is_horizontally_scrollable());
+        assert!(root.is_vertically_scrollable());
+        assert!(!root.supports_horizontal_scrolling());
+        assert!(!root.supports_vertical_scrolling());
+        assert_eq!(root.horizontal_scroll_percent(), None);
+        assert_eq!(root.vertical_scroll_percent(), Some(50.0));
+        assert_eq!(root.horizontal_view_size(), 100.0);
+        assert_eq!(root.vertical_view_size(), 25.0);
+        assert_eq!(
+            root.scroll_offset_for_percent(Some(10.0), Some(100.0)),
+            Point::new(0.0, 300.0)
+        );
+    }
+
+    #[test]
+    fn viewport_through_filtered_container() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::ScrollView);
+                    node.set_bounds(Rect::new(0.0, 0.0, 100.0, 100.0));
+                    node.set_clips_children();
+                    node.set_children(vec![NodeId(1)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::GenericContainer);
+                    node.set_transform(Affine::translate((0.0, -100.0)));
+                    node.set_children(vec![NodeId(2), NodeId(3), NodeId(4)]);
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::ListItem);
+                    node.set_bounds(Rect::new(0.0, 0.0, 100.0, 100.0));
+                    node
+                }),
+                (NodeId(3), {
+                    let mut node = Node::new(Role::ListItem);
+                    node.set_bounds(Rect::new(0.0, 100.0, 100.0, 200.0));
+                    node
+                }),
+                (NodeId(4), {
+                    let mut node = Node::new(Role::ListItem);
+                    node.set_bounds(Rect::new(0.0, 200.0, 100.0, 300.0));
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = crate::Tree::new(update, false);
+        let root = tree.state().root();
+        let filter = |node: &crate::Node| {
+            if node.role() == Role::GenericContainer {
+                FilterResult::ExcludeNode
+            } else {
+                FilterResult::Include
+            }
+        };
+        let (first, last) = root.visible_filtered_child_range(filter).unwrap();
+        assert_eq!(first.id(), NodeId(3));
+        assert_eq!(last.id(), NodeId(3));
+    }
+
+    #[test]
+    fn scrolling_actions() {
+        let update = |actions: &[Action]| TreeUpdate {
+            nodes: vec![(NodeId(0), {
+                let mut node = Node::new(Role::ScrollView);
+                for action in actions {
+                    node.add_action(*action);
+                }
+                node
+            })],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let supports = |actions: &[Action]| {
+            let tree = crate::Tree::new(update(actions), false);
+            let root = tree.state().root();
+            (
+                root.supports_horizontal_scrolling(),
+                root.supports_vertical_scrolling(),
+            )
+        };
+        assert_eq!(supports(&[Action::ScrollRight]), (true, false));
+        assert_eq!(supports(&[Action::ScrollUp]), (false, true));
+        assert_eq!(supports(&[Action::SetScrollOffset]), (true, true));
+        assert_eq!(supports(&[Action::ScrollIntoView]), (false, false));
+    }
+
+    #[test]
+    fn inert_subtree() {
+        let update = |inert| TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Group);
+                    node.set_children(vec![NodeId(2)]);
+                    if inert {
+                        node.set_inert();
+                    }
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Button);
+                    node.add_action(Action::Click);
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut tree = crate::Tree::new(update(false), false);
+        let button = tree.state().node_by_id(NodeId(2)).unwrap();
+        assert!(!button.is_inert());
+        assert!(button.is_clickable());
+        assert_eq!(crate::common_filter(&button), FilterResult::Include);
+
+        tree.update(update(true));
+        let button = tree.state().node_by_id(NodeId(2)).unwrap();
+        assert!(button.is_inert());
+        assert!(!button.is_clickable());
+        assert_eq!(crate::common_filter(&button), FilterResult::ExcludeSubtree);
+        assert_eq!(
+            tree.state()
+                .root()
+                .filtered_children(crate::common_filter)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn implicit_live_regions() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(3), NodeId(4)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Alert);
+                    node.set_children(vec![NodeId(2)]);
+                    node
+                }),
+                (NodeId(2), Node::new(Role::Label)),
+                (NodeId(3), Node::new(Role::Status)),
+                (NodeId(4), {
+                    let mut node = Node::new(Role::Status);
+                    node.set_live(Live::Off);
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = crate::Tree::new(update, false);
+        let state = tree.state();
+        let live = |id| state.node_by_id(NodeId(id)).unwrap().live();
+        assert_eq!(live(0), Live::Off);
+        assert_eq!(live(1), Live::Assertive);
+        assert_eq!(live(2), Live::Assertive);
+        assert_eq!(live(3), Live::Polite);
+        assert_eq!(live(4), Live::Off);
+        assert!(state.node_by_id(NodeId(1)).unwrap().is_notification());
+        assert!(!state.node_by_id(NodeId(2)).unwrap().is_notification());
+    }
+
+    #[test]
+    fn inherited_text_formatting() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::TextInput);
+                    node.set_children(vec![NodeId(1)]);
+                    node.set_line_height(20.0);
+                    node.set_margin_leading(8.0);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::TextRun);
+                    node.set_line_height(24.0);
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = crate::Tree::new(update, false);
+        let run = tree.state().node_by_id(NodeId(1)).unwrap();
+        assert_eq!(run.line_height(), Some(24.0));
+        assert_eq!(run.margin_leading(), Some(8.0));
+        assert_eq!(run.letter_spacing(), None);
+        assert_eq!(tree.state().root().line_height(), Some(20.0));
+    }
 }
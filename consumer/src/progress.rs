@@ -0,0 +1,187 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Live, NodeId, Role};
+use alloc::{format, string::String};
+use core::time::Duration;
+use hashbrown::HashMap;
+
+use crate::node::Node;
+
+/// Decides when changes to a progress indicator should be announced,
+/// so that long operations are reported periodically without
+/// overwhelming the user.
+///
+/// A progress indicator is announced if it is a live region, or if it is
+/// inside a container that is marked as busy. Announcements of the same
+/// indicator are at least [`interval`](Self::new) apart, except that
+/// reaching the maximum value is always announced.
+///
+/// This type doesn't read the clock itself. The caller passes the time
+/// elapsed since an arbitrary fixed point, such as the creation of
+/// the adapter.
+pub struct ProgressThrottle {
+    interval: Duration,
+    last_announced: HashMap<NodeId, Duration>,
+}
+
+impl Default for ProgressThrottle {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_INTERVAL)
+    }
+}
+
+impl ProgressThrottle {
+    /// The default minimum time between announcements of the same
+    /// progress indicator.
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(3);
+
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_announced: HashMap::new(),
+        }
+    }
+
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Returns the text to announce for a change to the given node,
+    /// if any. This must be called for every updated node, though it
+    /// returns `None` immediately for nodes other than progress indicators.
+    pub fn node_updated(
+        &mut self,
+        old_node: &Node,
+        new_node: &Node,
+        now: Duration,
+    ) -> Option<String> {
+        if new_node.role() != Role::ProgressIndicator
+            || new_node.is_hidden()
+            || new_node.numeric_value() == old_node.numeric_value()
+            || !should_announce(new_node)
+        {
+            return None;
+        }
+        let is_complete = new_node.numeric_value().is_some()
+            && new_node.numeric_value() == new_node.max_numeric_value();
+        if let Some(last) = self.last_announced.get(&new_node.id()) {
+            if !is_complete && now.saturating_sub(*last) < self.interval {
+                return None;
+            }
+        }
+        self.last_announced.insert(new_node.id(), now);
+        progress_text(new_node)
+    }
+
+    /// Forgets the given node. This should be called for every
+    /// removed node.
+    pub fn node_removed(&mut self, node: &Node) {
+        self.last_announced.remove(&node.id());
+    }
+}
+
+fn should_announce(node: &Node) -> bool {
+    if node.live() != Live::Off {
+        return true;
+    }
+    let mut ancestor = node.parent();
+    while let Some(node) = ancestor {
+        if node.data().is_busy() {
+            return true;
+        }
+        ancestor = node.parent();
+    }
+    false
+}
+
+fn progress_text(node: &Node) -> Option<String> {
+    if let Some(value) = node.value() {
+        return Some(value);
+    }
+    let value = node.numeric_value()?;
+    let min = node.min_numeric_value().unwrap_or(0.0);
+    let max = node.max_numeric_value().unwrap_or(100.0);
+    if max <= min {
+        return None;
+    }
+    let percent = ((value - min) / (max - min) * 100.0).clamp(0.0, 100.0);
+    Some(format!("{}%", (percent + 0.5) as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{Live, Node, NodeId, Role, Tree, TreeUpdate};
+    use alloc::{string::String, vec, vec::Vec};
+    use core::time::Duration;
+
+    use super::ProgressThrottle;
+
+    fn update(progress: f64, is_busy: bool) -> TreeUpdate {
+        TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1)]);
+                    if is_busy {
+                        node.set_busy();
+                    }
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::ProgressIndicator);
+                    node.set_numeric_value(progress);
+                    node.set_min_numeric_value(0.0);
+                    node.set_max_numeric_value(200.0);
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        }
+    }
+
+    fn announcements(steps: &[(u64, f64)], is_busy: bool) -> Vec<String> {
+        let mut throttle = ProgressThrottle::default();
+        let mut tree = crate::Tree::new(update(0.0, is_busy), true);
+        let mut result = Vec::new();
+        for (secs, progress) in steps {
+            let old_state = tree.state().clone();
+            tree.update(update(*progress, is_busy));
+            let old_node = old_state.node_by_id(NodeId(1)).unwrap();
+            let new_node = tree.state().node_by_id(NodeId(1)).unwrap();
+            let now = Duration::from_secs(*secs);
+            result.extend(throttle.node_updated(&old_node, &new_node, now));
+        }
+        result
+    }
+
+    #[test]
+    fn throttled_in_busy_container() {
+        let steps = [(0, 20.0), (1, 40.0), (3, 61.0), (4, 200.0)];
+        assert_eq!(announcements(&steps, true), ["10%", "31%", "100%"]);
+    }
+
+    #[test]
+    fn not_announced_outside_busy_container() {
+        assert!(announcements(&[(0, 20.0)], false).is_empty());
+    }
+
+    #[test]
+    fn live_progress_indicator() {
+        let mut throttle = ProgressThrottle::new(Duration::from_secs(1));
+        let mut update = update(0.0, false);
+        update.nodes[1].1.set_live(Live::Polite);
+        let tree = crate::Tree::new(update.clone(), true);
+        update.nodes[1].1.set_numeric_value(50.0);
+        let new_tree = crate::Tree::new(update, true);
+        let old_node = tree.state().node_by_id(NodeId(1)).unwrap();
+        let new_node = new_tree.state().node_by_id(NodeId(1)).unwrap();
+        assert_eq!(
+            throttle.node_updated(&old_node, &new_node, Duration::ZERO),
+            Some("25%".into())
+        );
+    }
+}
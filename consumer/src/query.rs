@@ -0,0 +1,560 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+//! A small selector language for finding nodes in a tree, mainly intended
+//! for toolkit test suites and inspection tools.
+//!
+//! A query is a sequence of compound selectors separated by whitespace,
+//! where each selector after the first must match a descendant of a node
+//! matched by the previous one. A compound selector is an optional role name
+//! (or `*`), followed by any number of bracketed conditions:
+//!
+//! - `[label=Save]` matches the computed label exactly.
+//! - `[label*=Sav]` matches if the computed label contains the given text.
+//! - `[disabled]` matches a boolean state; `[!disabled]` negates it.
+//!
+//! Role names are the names of the [`Role`] variants, compared without
+//! regard to ASCII case, so `button`, `Button` and `BUTTON` are equivalent.
+//! A name that isn't a role is a parse error.
+//! Values may be quoted with single or double quotes if they contain
+//! spaces or brackets, as in `button[label="Save as"]`.
+//!
+//! The supported text properties are `label` (also available as `name`),
+//! `value`, `description`, `placeholder`, `author_id` and `class_name`.
+//! The `id` property compares against the numeric node ID.
+//!
+//! The supported states are `disabled`, `focused`, `focusable`, `hidden`,
+//! `read_only`, `required`, `selected`, `expanded`, `checked`, `multiline`
+//! and `clickable`.
+//!
+//! Queries consider every node in the tree, including nodes that platform
+//! adapters would filter out.
+//!
+//! [`Role`]: accesskit::Role
+
+use accesskit::{Role, Toggled};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, fmt::Write};
+
+use crate::{node::Node, tree::State as TreeState};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The byte offset in the query at which the error was detected.
+    pub offset: usize,
+    pub message: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.message, self.offset)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TextProperty {
+    Label,
+    Value,
+    Description,
+    Placeholder,
+    AuthorId,
+    ClassName,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Disabled,
+    Focused,
+    Focusable,
+    Hidden,
+    ReadOnly,
+    Required,
+    Selected,
+    Expanded,
+    Checked,
+    Multiline,
+    Clickable,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Equals,
+    Contains,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Condition {
+    Text(TextProperty, Operator, Box<str>),
+    Id(u64),
+    State(State, bool),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Compound {
+    role: Option<Role>,
+    conditions: Vec<Condition>,
+}
+
+/// A parsed selector that can be evaluated against a tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    compounds: Vec<Compound>,
+}
+
+fn role_from_name(name: &str) -> Option<Role> {
+    let mut buf = String::new();
+    (0..=u8::MAX).map_while(Role::n).find(|role| {
+        buf.clear();
+        write!(buf, "{:?}", role).unwrap();
+        buf.eq_ignore_ascii_case(name)
+    })
+}
+
+impl TextProperty {
+    fn get(self, node: &Node) -> Option<String> {
+        match self {
+            Self::Label => node.label(),
+            Self::Value => node.value(),
+            Self::Description => node.description(),
+            Self::Placeholder => node.placeholder().map(ToString::to_string),
+            Self::AuthorId => node.author_id().map(ToString::to_string),
+            Self::ClassName => node.class_name().map(ToString::to_string),
+        }
+    }
+}
+
+impl State {
+    fn get(self, node: &Node) -> bool {
+        match self {
+            Self::Disabled => node.is_disabled(),
+            Self::Focused => node.is_focused(),
+            Self::Focusable => node.is_focusable(),
+            Self::Hidden => node.is_hidden(),
+            Self::ReadOnly => node.is_read_only(),
+            Self::Required => node.is_required(),
+            Self::Selected => node.is_selected() == Some(true),
+            Self::Expanded => node.data().is_expanded() == Some(true),
+            Self::Checked => node.toggled() == Some(Toggled::True),
+            Self::Multiline => node.is_multiline(),
+            Self::Clickable => node.is_clickable(),
+        }
+    }
+}
+
+impl Condition {
+    fn matches(&self, node: &Node) -> bool {
+        match self {
+            Self::Text(property, operator, expected) => {
+                let Some(actual) = property.get(node) else {
+                    return false;
+                };
+                match operator {
+                    Operator::Equals => actual == **expected,
+                    Operator::Contains => actual.contains(&**expected),
+                }
+            }
+            Self::Id(id) => node.id().0 == *id,
+            Self::State(state, expected) => state.get(node) == *expected,
+        }
+    }
+}
+
+impl Compound {
+    fn matches(&self, node: &Node) -> bool {
+        if let Some(role) = self.role {
+            if node.role() != role {
+                return false;
+            }
+        }
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(node))
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &'static str) -> ParseError {
+        ParseError {
+            offset: self.offset,
+            message,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.offset..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+        Some(c)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.offset += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.offset;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+        self.offset != start
+    }
+
+    fn identifier(&mut self) -> &'a str {
+        let start = self.offset;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            self.bump();
+        }
+        &self.input[start..self.offset]
+    }
+
+    fn value(&mut self) -> Result<&'a str, ParseError> {
+        if let Some(quote @ ('"' | '\'')) = self.peek() {
+            self.bump();
+            let start = self.offset;
+            loop {
+                match self.bump() {
+                    Some(c) if c == quote => {
+                        return Ok(&self.input[start..self.offset - quote.len_utf8()]);
+                    }
+                    Some(_) => (),
+                    None => return Err(self.error("unterminated quoted value")),
+                }
+            }
+        }
+        let start = self.offset;
+        while self.peek().is_some_and(|c| c != ']') {
+            self.bump();
+        }
+        Ok(self.input[start..self.offset].trim())
+    }
+
+    fn condition(&mut self) -> Result<Condition, ParseError> {
+        self.skip_whitespace();
+        let negated = self.eat('!');
+        let name_offset = self.offset;
+        let name = self.identifier();
+        if name.is_empty() {
+            return Err(self.error("expected a property or state name"));
+        }
+        self.skip_whitespace();
+        let operator = if self.eat('=') {
+            Some(Operator::Equals)
+        } else if self.eat('*') {
+            if !self.eat('=') {
+                return Err(self.error("expected '=' after '*'"));
+            }
+            Some(Operator::Contains)
+        } else {
+            None
+        };
+        let condition = match operator {
+            Some(operator) => {
+                if negated {
+                    return Err(ParseError {
+                        offset: name_offset,
+                        message: "only states can be negated",
+                    });
+                }
+                self.skip_whitespace();
+                let value = self.value()?;
+                let property = match name {
+                    "label" | "name" => TextProperty::Label,
+                    "value" => TextProperty::Value,
+                    "description" => TextProperty::Description,
+                    "placeholder" => TextProperty::Placeholder,
+                    "author_id" => TextProperty::AuthorId,
+                    "class_name" => TextProperty::ClassName,
+                    "id" if operator == Operator::Equals => {
+                        let id = value.parse().map_err(|_| self.error("invalid node ID"))?;
+                        return self.close(Condition::Id(id));
+                    }
+                    _ => {
+                        return Err(ParseError {
+                            offset: name_offset,
+                            message: "unknown property",
+                        })
+                    }
+                };
+                Condition::Text(property, operator, value.into())
+            }
+            None => {
+                let state = match name {
+                    "disabled" => State::Disabled,
+                    "focused" => State::Focused,
+                    "focusable" => State::Focusable,
+                    "hidden" => State::Hidden,
+                    "read_only" => State::ReadOnly,
+                    "required" => State::Required,
+                    "selected" => State::Selected,
+                    "expanded" => State::Expanded,
+                    "checked" => State::Checked,
+                    "multiline" => State::Multiline,
+                    "clickable" => State::Clickable,
+                    _ => {
+                        return Err(ParseError {
+                            offset: name_offset,
+                            message: "unknown state",
+                        })
+                    }
+                };
+                Condition::State(state, !negated)
+            }
+        };
+        self.close(condition)
+    }
+
+    fn close(&mut self, condition: Condition) -> Result<Condition, ParseError> {
+        self.skip_whitespace();
+        if !self.eat(']') {
+            return Err(self.error("expected ']'"));
+        }
+        Ok(condition)
+    }
+
+    fn compound(&mut self) -> Result<Compound, ParseError> {
+        let role = if self.eat('*') {
+            None
+        } else {
+            let name_offset = self.offset;
+            let name = self.identifier();
+            if name.is_empty() {
+                None
+            } else {
+                let role = role_from_name(name).ok_or(ParseError {
+                    offset: name_offset,
+                    message: "unknown role",
+                })?;
+                Some(role)
+            }
+        };
+        let mut conditions = Vec::new();
+        while self.eat('[') {
+            conditions.push(self.condition()?);
+        }
+        Ok(Compound { role, conditions })
+    }
+
+    fn query(mut self) -> Result<Query, ParseError> {
+        let mut compounds = Vec::new();
+        self.skip_whitespace();
+        while self.peek().is_some() {
+            let start = self.offset;
+            let compound = self.compound()?;
+            if self.offset == start {
+                return Err(self.error("unexpected character"));
+            }
+            compounds.push(compound);
+            if !self.skip_whitespace() && self.peek().is_some() {
+                return Err(self.error("unexpected character"));
+            }
+        }
+        if compounds.is_empty() {
+            return Err(self.error("empty query"));
+        }
+        Ok(Query { compounds })
+    }
+}
+
+impl Query {
+    pub fn parse(query: &str) -> Result<Self, ParseError> {
+        Parser {
+            input: query,
+            offset: 0,
+        }
+        .query()
+    }
+
+    /// Returns true if the given node matches this query. When the query
+    /// has multiple selectors, the node must match the last one, and
+    /// its ancestors must match the preceding ones in order.
+    pub fn matches(&self, node: &Node) -> bool {
+        let (last, rest) = self.compounds.split_last().unwrap();
+        if !last.matches(node) {
+            return false;
+        }
+        let mut remaining = rest;
+        let mut ancestor = node.parent();
+        while let Some((compound, rest)) = remaining.split_last() {
+            let Some(current) = ancestor else {
+                return false;
+            };
+            if compound.matches(&current) {
+                remaining = rest;
+            }
+            ancestor = current.parent();
+        }
+        true
+    }
+
+    /// Returns all nodes in the tree that match this query, in depth-first
+    /// order starting at the root.
+    pub fn find_all<'a>(&self, state: &'a TreeState) -> Vec<Node<'a>> {
+        let mut result = Vec::new();
+        let mut stack = Vec::from([state.root()]);
+        while let Some(node) = stack.pop() {
+            if self.matches(&node) {
+                result.push(node);
+            }
+            let start = stack.len();
+            stack.extend(node.children());
+            stack[start..].reverse();
+        }
+        result
+    }
+
+    /// Returns the first node, in depth-first order, that matches this query.
+    pub fn find_first<'a>(&self, state: &'a TreeState) -> Option<Node<'a>> {
+        let mut stack = Vec::from([state.root()]);
+        while let Some(node) = stack.pop() {
+            if self.matches(&node) {
+                return Some(node);
+            }
+            let start = stack.len();
+            stack.extend(node.children());
+            stack[start..].reverse();
+        }
+        None
+    }
+}
+
+impl TreeState {
+    /// Parses the given query and returns all matching nodes. See the
+    /// [`Query`] documentation for the syntax.
+    pub fn query(&self, query: &str) -> Result<Vec<Node<'_>>, ParseError> {
+        Ok(Query::parse(query)?.find_all(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::NodeId;
+    use alloc::vec::Vec;
+
+    use super::{ParseError, Query};
+
+    fn ids(tree: &crate::tree::Tree, query: &str) -> Vec<NodeId> {
+        tree.state()
+            .query(query)
+            .unwrap()
+            .iter()
+            .map(|node| node.id())
+            .collect()
+    }
+
+    #[test]
+    fn role() {
+        let tree = crate::tests::test_tree();
+        assert_eq!(
+            ids(&tree, "button"),
+            [
+                crate::tests::BUTTON_1_0_HIDDEN_ID,
+                crate::tests::BUTTON_1_2_HIDDEN_ID,
+                crate::tests::BUTTON_3_2_ID,
+            ]
+        );
+        assert_eq!(ids(&tree, "RootWebArea"), [crate::tests::ROOT_ID]);
+        assert_eq!(ids(&tree, "*").len(), 17);
+    }
+
+    #[test]
+    fn properties_and_states() {
+        let tree = crate::tests::test_tree();
+        assert_eq!(
+            ids(&tree, "button[label=button_3_2]"),
+            [crate::tests::BUTTON_3_2_ID]
+        );
+        assert_eq!(
+            ids(&tree, "button[name='button_3_2'][!hidden]"),
+            [crate::tests::BUTTON_3_2_ID]
+        );
+        assert_eq!(
+            ids(&tree, "button[hidden]"),
+            [
+                crate::tests::BUTTON_1_0_HIDDEN_ID,
+                crate::tests::BUTTON_1_2_HIDDEN_ID,
+            ]
+        );
+        assert_eq!(ids(&tree, "[value*=label_1]"), [crate::tests::LABEL_1_1_ID]);
+        assert_eq!(ids(&tree, "[id=9]"), [crate::tests::PARAGRAPH_2_ID]);
+        assert!(ids(&tree, "button[disabled]").is_empty());
+    }
+
+    #[test]
+    fn descendants() {
+        let tree = crate::tests::test_tree();
+        assert_eq!(ids(&tree, "link label"), [crate::tests::LABEL_3_1_0_ID]);
+        assert_eq!(
+            ids(&tree, "RootWebArea paragraph [hidden]"),
+            [
+                crate::tests::BUTTON_1_0_HIDDEN_ID,
+                crate::tests::CONTAINER_1_0_0_HIDDEN_ID,
+                crate::tests::BUTTON_1_2_HIDDEN_ID,
+                crate::tests::CONTAINER_1_2_0_HIDDEN_ID,
+            ]
+        );
+        let query = Query::parse("paragraph label").unwrap();
+        assert_eq!(
+            query.find_first(tree.state()).map(|node| node.id()),
+            Some(crate::tests::LABEL_0_0_IGNORED_ID)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Query::parse("").unwrap_err(),
+            ParseError {
+                offset: 0,
+                message: "empty query"
+            }
+        );
+        assert_eq!(
+            Query::parse("button[bogus]").unwrap_err(),
+            ParseError {
+                offset: 7,
+                message: "unknown state"
+            }
+        );
+        assert_eq!(
+            Query::parse("link buton").unwrap_err(),
+            ParseError {
+                offset: 5,
+                message: "unknown role"
+            }
+        );
+        assert_eq!(
+            Query::parse("button[label=\"Save").unwrap_err().message,
+            "unterminated quoted value"
+        );
+        assert_eq!(
+            Query::parse("button[disabled").unwrap_err().message,
+            "expected ']'"
+        );
+        assert_eq!(
+            Query::parse("button[!label=Save]").unwrap_err().message,
+            "only states can be negated"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::NodeId;
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{filters::FilterResult, node::Node, tree::State as TreeState};
+
+/// A keyboard shortcut defined on a node in the tree, as collected by
+/// [`TreeState::keyboard_shortcuts`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyboardShortcut {
+    pub node_id: NodeId,
+    pub shortcut: String,
+    /// The computed label of the node that owns the shortcut.
+    pub label: Option<String>,
+}
+
+impl KeyboardShortcut {
+    fn conflicts_with(&self, other: &Self) -> bool {
+        self.shortcut.eq_ignore_ascii_case(&other.shortcut)
+    }
+}
+
+fn collect(
+    node: &Node,
+    filter: &impl Fn(&Node) -> FilterResult,
+    shortcuts: &mut Vec<KeyboardShortcut>,
+) {
+    if let Some(shortcut) = node.keyboard_shortcut() {
+        shortcuts.push(KeyboardShortcut {
+            node_id: node.id(),
+            shortcut: shortcut.into(),
+            label: node.label(),
+        });
+    }
+    for child in node.filtered_children(filter) {
+        collect(&child, filter, shortcuts);
+    }
+}
+
+impl TreeState {
+    /// Returns the keyboard shortcuts of all nodes that pass the given
+    /// filter, such as [`common_filter`](crate::common_filter), in
+    /// depth-first order. Applications can use this to render a list
+    /// of all available shortcuts.
+    pub fn keyboard_shortcuts(
+        &self,
+        filter: &impl Fn(&Node) -> FilterResult,
+    ) -> Vec<KeyboardShortcut> {
+        let mut shortcuts = Vec::new();
+        let root = self.root();
+        match filter(&root) {
+            FilterResult::Include => collect(&root, filter, &mut shortcuts),
+            FilterResult::ExcludeNode => {
+                for child in root.filtered_children(filter) {
+                    collect(&child, filter, &mut shortcuts);
+                }
+            }
+            FilterResult::ExcludeSubtree => (),
+        }
+        shortcuts
+    }
+
+    /// Returns the groups of nodes, among those that pass the given filter,
+    /// that define the same keyboard shortcut, compared without regard
+    /// to ASCII case. Each group is in depth-first order, and the groups are
+    /// ordered by the position of their first node.
+    pub fn keyboard_shortcut_conflicts(
+        &self,
+        filter: &impl Fn(&Node) -> FilterResult,
+    ) -> Vec<Vec<KeyboardShortcut>> {
+        let mut groups: Vec<Vec<KeyboardShortcut>> = Vec::new();
+        for shortcut in self.keyboard_shortcuts(filter) {
+            if let Some(group) = groups
+                .iter_mut()
+                .find(|group| group[0].conflicts_with(&shortcut))
+            {
+                group.push(shortcut);
+            } else {
+                groups.push(vec![shortcut]);
+            }
+        }
+        groups.retain(|group| group.len() > 1);
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{Node, NodeId, Role, Tree, TreeUpdate};
+    use alloc::{string::String, vec};
+
+    use crate::common_filter;
+
+    #[test]
+    fn shortcuts_and_conflicts() {
+        let button = |label: &str, shortcut: &str| {
+            let mut node = Node::new(Role::Button);
+            node.set_label(label);
+            node.set_keyboard_shortcut(shortcut);
+            node
+        };
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2), NodeId(3), NodeId(4)]);
+                    node
+                }),
+                (NodeId(1), button("Save", "Ctrl+S")),
+                (NodeId(2), button("Open", "Ctrl+O")),
+                (NodeId(3), button("Sort", "ctrl+s")),
+                (NodeId(4), {
+                    let mut node = button("Secret", "Ctrl+O");
+                    node.set_hidden();
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = crate::Tree::new(update, false);
+        let state = tree.state();
+
+        let shortcuts = state.keyboard_shortcuts(&common_filter);
+        assert_eq!(
+            shortcuts
+                .iter()
+                .map(|shortcut| (shortcut.shortcut.as_str(), shortcut.label.as_deref()))
+                .collect::<vec::Vec<_>>(),
+            [
+                ("Ctrl+S", Some("Save")),
+                ("Ctrl+O", Some("Open")),
+                ("ctrl+s", Some("Sort")),
+            ]
+        );
+        assert_eq!(shortcuts[0].node_id, NodeId(1));
+
+        let conflicts = state.keyboard_shortcut_conflicts(&common_filter);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0]
+                .iter()
+                .map(|shortcut| shortcut.label.clone())
+                .collect::<vec::Vec<_>>(),
+            [Some(String::from("Save")), Some(String::from("Sort"))]
+        );
+        assert_eq!(
+            state
+                .keyboard_shortcut_conflicts(&|_: &crate::Node| crate::FilterResult::Include)
+                .len(),
+            2
+        );
+    }
+}
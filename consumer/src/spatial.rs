@@ -0,0 +1,173 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::Rect;
+use core::cmp::Ordering;
+
+use crate::{filters::FilterResult, node::Node};
+
+/// A direction on the screen, for [`Node::neighbor_in_direction`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Maps a rectangle into a coordinate space in which this direction
+    /// points towards positive x, so that only that case has to be handled.
+    fn rotate(self, rect: Rect) -> Rect {
+        match self {
+            Self::Right => rect,
+            Self::Left => Rect::new(-rect.x1, rect.y0, -rect.x0, rect.y1),
+            Self::Down => Rect::new(rect.y0, rect.x0, rect.y1, rect.x1),
+            Self::Up => Rect::new(-rect.y1, rect.x0, -rect.y0, rect.x1),
+        }
+    }
+}
+
+/// How well a candidate fits as the neighbor of a node to its right,
+/// after rotating both rectangles. Lower is better.
+fn score(origin: Rect, candidate: Rect) -> Option<(bool, f64)> {
+    // The candidate must extend further right than the origin, and must
+    // start to the right of the origin's left edge.
+    if !(origin.x0 < candidate.x0 || origin.x1 <= candidate.x0) || origin.x1 >= candidate.x1 {
+        return None;
+    }
+    // Candidates that overlap the origin vertically, i.e. that can be
+    // reached by moving in a straight line, always win over the others.
+    let is_outside_beam = candidate.y1 <= origin.y0 || origin.y1 <= candidate.y0;
+    let major = (candidate.x0 - origin.x1).max(0.0);
+    let minor = ((candidate.y0 + candidate.y1) - (origin.y0 + origin.y1)) / 2.0;
+    // Weighting the distance in the direction of movement, as Android's
+    // focus finder does, favors the nearest row or column.
+    Some((is_outside_beam, 13.0 * major * major + minor * minor))
+}
+
+fn find_best<'a>(
+    node: Node<'a>,
+    origin: &Node,
+    origin_rect: Rect,
+    direction: Direction,
+    filter: &impl Fn(&Node) -> FilterResult,
+    best: &mut Option<(Node<'a>, (bool, f64))>,
+) {
+    let filter_result = filter(&node);
+    if filter_result == FilterResult::ExcludeSubtree || node.id() == origin.id() {
+        return;
+    }
+    if filter_result == FilterResult::Include && !origin.is_descendant_of(&node) {
+        if let Some(candidate_score) = node
+            .bounding_box()
+            .and_then(|rect| score(origin_rect, direction.rotate(rect)))
+        {
+            let is_better = best.as_ref().map_or(true, |(_, best_score)| {
+                candidate_score.partial_cmp(best_score) == Some(Ordering::Less)
+            });
+            if is_better {
+                *best = Some((node, candidate_score));
+            }
+        }
+    }
+    for child in node.children() {
+        find_best(child, origin, origin_rect, direction, filter, best);
+    }
+}
+
+impl<'a> Node<'a> {
+    /// Returns the node that a user would expect to move to when moving
+    /// from this node in the given direction, e.g. with the arrow keys of
+    /// a remote control or a gamepad, based on the bounding boxes of
+    /// the filtered nodes in the tree.
+    ///
+    /// Nodes in a straight line from this node in that direction are
+    /// preferred, then nodes closer in that direction, and then nodes
+    /// closer to this node's center line. This node's ancestors and
+    /// descendants are never returned. Returns `None` if this node
+    /// doesn't have bounds or if there's no node in that direction.
+    pub fn neighbor_in_direction(
+        &self,
+        direction: Direction,
+        filter: &impl Fn(&Node) -> FilterResult,
+    ) -> Option<Node<'a>> {
+        let origin_rect = direction.rotate(self.bounding_box()?);
+        let mut best = None;
+        find_best(
+            self.tree_state.root(),
+            self,
+            origin_rect,
+            direction,
+            filter,
+            &mut best,
+        );
+        best.map(|(node, _)| node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{Node, NodeId, Rect, Role, Tree, TreeUpdate};
+    use alloc::vec;
+
+    use super::Direction;
+
+    // A window with a row of three buttons above a wide text input,
+    // and a button in the middle of the row that contains an image.
+    fn test_tree() -> crate::Tree {
+        let button = |x0| {
+            let mut node = Node::new(Role::Button);
+            node.set_bounds(Rect::new(x0, 0.0, x0 + 40.0, 20.0));
+            node
+        };
+        let mut root = Node::new(Role::Window);
+        root.set_bounds(Rect::new(0.0, 0.0, 200.0, 100.0));
+        root.set_children(vec![NodeId(1), NodeId(2), NodeId(3), NodeId(4)]);
+        let mut middle = button(60.0);
+        middle.set_children(vec![NodeId(5)]);
+        let mut input = Node::new(Role::TextInput);
+        input.set_bounds(Rect::new(0.0, 40.0, 200.0, 60.0));
+        let mut image = Node::new(Role::Image);
+        image.set_bounds(Rect::new(70.0, 5.0, 90.0, 15.0));
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), root),
+                (NodeId(1), button(0.0)),
+                (NodeId(2), middle),
+                (NodeId(3), button(120.0)),
+                (NodeId(4), input),
+                (NodeId(5), image),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(1),
+        };
+        crate::Tree::new(update, true)
+    }
+
+    #[test]
+    fn neighbor_in_direction() {
+        let tree = test_tree();
+        let state = tree.state();
+        let neighbor = |id, direction| {
+            state
+                .node_by_id(NodeId(id))
+                .unwrap()
+                .neighbor_in_direction(direction, &crate::common_filter)
+                .map(|node| node.id().0)
+        };
+        assert_eq!(neighbor(1, Direction::Right), Some(2));
+        assert_eq!(neighbor(2, Direction::Right), Some(3));
+        assert_eq!(neighbor(3, Direction::Right), None);
+        assert_eq!(neighbor(3, Direction::Left), Some(2));
+        assert_eq!(neighbor(1, Direction::Left), None);
+        assert_eq!(neighbor(1, Direction::Up), None);
+        assert_eq!(neighbor(2, Direction::Down), Some(4));
+        // The middle button is closest to the center of the input.
+        assert_eq!(neighbor(4, Direction::Up), Some(2));
+        assert_eq!(neighbor(5, Direction::Right), Some(3));
+        assert_eq!(neighbor(5, Direction::Left), Some(1));
+    }
+}
@@ -0,0 +1,460 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{NodeId, Role};
+use alloc::{vec, vec::Vec};
+use hashbrown::HashMap;
+
+use crate::node::Node;
+
+/// The position of a cell within a table, as computed by
+/// [`Node::table_cell_position`]. Indices are zero-based.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TableCellPosition {
+    pub row_index: usize,
+    pub column_index: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+}
+
+impl TableCellPosition {
+    fn contains(&self, row: usize, column: usize) -> bool {
+        self.contains_row(row) && self.contains_column(column)
+    }
+
+    fn contains_row(&self, row: usize) -> bool {
+        (self.row_index..self.row_index + self.row_span).contains(&row)
+    }
+
+    fn contains_column(&self, column: usize) -> bool {
+        (self.column_index..self.column_index + self.column_span).contains(&column)
+    }
+
+    fn overlaps_rows(&self, other: &Self) -> bool {
+        self.row_index < other.row_index + other.row_span
+            && other.row_index < self.row_index + self.row_span
+    }
+
+    fn overlaps_columns(&self, other: &Self) -> bool {
+        self.column_index < other.column_index + other.column_span
+            && other.column_index < self.column_index + self.column_span
+    }
+}
+
+/// The rows and cells of a table. Layouts are computed when the tree is
+/// updated, so that queries on large tables don't have to walk the table
+/// again for every cell.
+#[derive(Debug)]
+pub(crate) struct TableLayout {
+    rows: Vec<(NodeId, usize)>,
+    cells: Vec<(NodeId, TableCellPosition)>,
+    cell_indices: HashMap<NodeId, usize>,
+    row_count: usize,
+    column_count: usize,
+}
+
+impl TableLayout {
+    /// Computes the positions of the cells in the given table. Rows and
+    /// cells that don't specify their index are assumed to follow
+    /// the previous row or cell, taking the previous cell's column span
+    /// into account.
+    pub(crate) fn new(table: &Node) -> Self {
+        let mut rows = vec![];
+        let mut cells = vec![];
+        let mut cell_indices = HashMap::new();
+        let mut row_count = 0;
+        let mut column_count = 0;
+        let mut next_row = 0;
+        for row in table.table_rows_uncached() {
+            let row_index = row.data().row_index().unwrap_or(next_row);
+            next_row = row_index + 1;
+            row_count = row_count.max(next_row);
+            rows.push((row.id(), row_index));
+            let mut next_column = 0;
+            for cell in row.children().filter(Node::is_table_cell) {
+                let data = cell.data();
+                let position = TableCellPosition {
+                    row_index: data.row_index().unwrap_or(row_index),
+                    column_index: data.column_index().unwrap_or(next_column),
+                    row_span: data.row_span().unwrap_or(1).max(1),
+                    column_span: data.column_span().unwrap_or(1).max(1),
+                };
+                next_column = position.column_index + position.column_span;
+                row_count = row_count.max(position.row_index + position.row_span);
+                column_count = column_count.max(next_column);
+                cell_indices.insert(cell.id(), cells.len());
+                cells.push((cell.id(), position));
+            }
+        }
+        Self {
+            rows,
+            cells,
+            cell_indices,
+            row_count: table.data().row_count().unwrap_or(row_count),
+            column_count: table.data().column_count().unwrap_or(column_count),
+        }
+    }
+}
+
+fn collect_rows<'a>(node: &Node<'a>, rows: &mut Vec<Node<'a>>) {
+    for child in node.children() {
+        match child.role() {
+            Role::Row => rows.push(child),
+            Role::RowGroup | Role::GenericContainer => collect_rows(&child, rows),
+            _ => (),
+        }
+    }
+}
+
+impl<'a> Node<'a> {
+    pub fn is_table(&self) -> bool {
+        matches!(
+            self.role(),
+            Role::Table | Role::Grid | Role::TreeGrid | Role::ListGrid
+        )
+    }
+
+    pub fn is_table_cell(&self) -> bool {
+        matches!(
+            self.role(),
+            Role::Cell | Role::RowHeader | Role::ColumnHeader
+        )
+    }
+
+    fn table_rows_uncached(&self) -> Vec<Node<'a>> {
+        let mut rows = vec![];
+        if self.is_table() {
+            collect_rows(self, &mut rows);
+        }
+        rows
+    }
+
+    fn table_layout(&self) -> Option<&'a TableLayout> {
+        self.tree_state
+            .table_layouts
+            .get(&self.id)
+            .map(|layout| &**layout)
+    }
+
+    fn table_cells(&self) -> impl Iterator<Item = (Node<'a>, TableCellPosition)> + 'a {
+        let tree_state = self.tree_state;
+        self.table_layout()
+            .into_iter()
+            .flat_map(|layout| layout.cells.iter())
+            .map(move |(id, position)| (tree_state.node_by_id(*id).unwrap(), *position))
+    }
+
+    /// Returns the rows of this table, including rows within row groups.
+    /// Returns an empty list if this node isn't a table.
+    pub fn table_rows(&self) -> Vec<Node<'a>> {
+        let tree_state = self.tree_state;
+        self.table_layout()
+            .into_iter()
+            .flat_map(|layout| layout.rows.iter())
+            .map(|(id, _)| tree_state.node_by_id(*id).unwrap())
+            .collect()
+    }
+
+    /// Returns the number of rows in this table, which is the explicit
+    /// row count if there is one.
+    pub fn table_row_count(&self) -> usize {
+        self.table_layout().map_or_else(
+            || self.data().row_count().unwrap_or(0),
+            |layout| layout.row_count,
+        )
+    }
+
+    /// Returns the number of columns in this table, which is the explicit
+    /// column count if there is one.
+    pub fn table_column_count(&self) -> usize {
+        self.table_layout().map_or_else(
+            || self.data().column_count().unwrap_or(0),
+            |layout| layout.column_count,
+        )
+    }
+
+    /// Returns the cell of this table that covers the given row and column.
+    pub fn table_cell_at(&self, row: usize, column: usize) -> Option<Node<'a>> {
+        self.table_cells()
+            .find(|(_, position)| position.contains(row, column))
+            .map(|(cell, _)| cell)
+    }
+
+    /// Returns the row header cells of this table that cover the given row.
+    pub fn table_row_headers(&self, row: usize) -> Vec<Node<'a>> {
+        self.table_cells()
+            .filter(|(cell, position)| cell.role() == Role::RowHeader && position.contains_row(row))
+            .map(|(cell, _)| cell)
+            .collect()
+    }
+
+    /// Returns the column header cells of this table that cover
+    /// the given column.
+    pub fn table_column_headers(&self, column: usize) -> Vec<Node<'a>> {
+        self.table_cells()
+            .filter(|(cell, position)| {
+                cell.role() == Role::ColumnHeader && position.contains_column(column)
+            })
+            .map(|(cell, _)| cell)
+            .collect()
+    }
+
+    /// Returns the header cells with the given role, which must be
+    /// [`Role::RowHeader`] or [`Role::ColumnHeader`]. For a table, these are
+    /// all of its header cells with that role; for a cell, they're the
+    /// headers that share any of its rows or columns, other than the
+    /// cell itself.
+    pub fn table_headers(&self, role: Role) -> Vec<Node<'a>> {
+        if self.is_table() {
+            return self
+                .table_cells()
+                .filter(|(cell, _)| cell.role() == role)
+                .map(|(cell, _)| cell)
+                .collect();
+        }
+        let (Some(table), Some(position)) = (self.containing_table(), self.table_cell_position())
+        else {
+            return Vec::new();
+        };
+        table
+            .table_cells()
+            .filter(|(header, header_position)| {
+                header.role() == role
+                    && header.id() != self.id()
+                    && match role {
+                        Role::RowHeader => header_position.overlaps_rows(&position),
+                        Role::ColumnHeader => header_position.overlaps_columns(&position),
+                        _ => false,
+                    }
+            })
+            .map(|(header, _)| header)
+            .collect()
+    }
+
+    /// Returns the cells of this table that cover the given column,
+    /// in row order.
+    pub fn table_column_cells(&self, column: usize) -> Vec<Node<'a>> {
+        self.table_cells()
+            .filter(|(_, position)| position.contains_column(column))
+            .map(|(cell, _)| cell)
+            .collect()
+    }
+
+    /// Returns the indices of the selected rows of this table.
+    pub fn table_selected_rows(&self) -> Vec<usize> {
+        let tree_state = self.tree_state;
+        self.table_layout()
+            .into_iter()
+            .flat_map(|layout| layout.rows.iter())
+            .filter(|(id, _)| tree_state.node_by_id(*id).unwrap().is_selected() == Some(true))
+            .map(|(_, index)| *index)
+            .collect()
+    }
+
+    pub fn table_caption(&self) -> Option<Node<'a>> {
+        if !self.is_table() {
+            return None;
+        }
+        self.children().find(|child| child.role() == Role::Caption)
+    }
+
+    /// Returns the nearest ancestor of this node that is a table.
+    pub fn containing_table(&self) -> Option<Node<'a>> {
+        let mut current = self.parent();
+        while let Some(node) = current {
+            if node.is_table() {
+                return Some(node);
+            }
+            current = node.parent();
+        }
+        None
+    }
+
+    /// Returns the position of this cell within its table, or `None`
+    /// if this node isn't a cell in a table.
+    pub fn table_cell_position(&self) -> Option<TableCellPosition> {
+        if !self.is_table_cell() {
+            return None;
+        }
+        let layout = self.containing_table()?.table_layout()?;
+        let index = layout.cell_indices.get(&self.id)?;
+        Some(layout.cells[*index].1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{Node, NodeId, Role, Tree, TreeUpdate};
+    use alloc::{vec, vec::Vec};
+
+    use super::TableCellPosition;
+
+    const TABLE_ID: NodeId = NodeId(1);
+
+    // A 3x3 table with a caption, a header row in a row group,
+    // and a cell that spans two columns.
+    fn test_tree() -> crate::Tree {
+        let mut root = Node::new(Role::Window);
+        root.set_children(vec![TABLE_ID]);
+        let mut table = Node::new(Role::Table);
+        table.set_children(vec![NodeId(2), NodeId(3), NodeId(10), NodeId(20)]);
+        let mut caption = Node::new(Role::Caption);
+        caption.set_label("Scores");
+        let mut header_group = Node::new(Role::RowGroup);
+        header_group.set_children(vec![NodeId(4)]);
+        let mut header_row = Node::new(Role::Row);
+        header_row.set_children(vec![NodeId(5), NodeId(6), NodeId(7)]);
+        let mut first_row = Node::new(Role::Row);
+        first_row.set_children(vec![NodeId(11), NodeId(12)]);
+        let mut spanning_cell = Node::new(Role::Cell);
+        spanning_cell.set_column_span(2);
+        let mut second_row = Node::new(Role::Row);
+        second_row.set_children(vec![NodeId(21), NodeId(22)]);
+        let mut skipped_cell = Node::new(Role::Cell);
+        skipped_cell.set_column_index(2);
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), root),
+                (TABLE_ID, table),
+                (NodeId(2), caption),
+                (NodeId(3), header_group),
+                (NodeId(4), header_row),
+                (NodeId(5), Node::new(Role::ColumnHeader)),
+                (NodeId(6), Node::new(Role::ColumnHeader)),
+                (NodeId(7), Node::new(Role::ColumnHeader)),
+                (NodeId(10), first_row),
+                (NodeId(11), Node::new(Role::RowHeader)),
+                (NodeId(12), spanning_cell),
+                (NodeId(20), second_row),
+                (NodeId(21), Node::new(Role::RowHeader)),
+                (NodeId(22), skipped_cell),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        crate::Tree::new(update, false)
+    }
+
+    #[test]
+    fn table_dimensions() {
+        let tree = test_tree();
+        let table = tree.state().node_by_id(TABLE_ID).unwrap();
+        assert!(table.is_table());
+        assert_eq!(table.table_rows().len(), 3);
+        assert_eq!(table.table_row_count(), 3);
+        assert_eq!(table.table_column_count(), 3);
+        assert_eq!(table.table_caption().unwrap().id(), NodeId(2));
+        assert!(tree.state().root().table_rows().is_empty());
+    }
+
+    #[test]
+    fn cells_and_headers() {
+        let tree = test_tree();
+        let table = tree.state().node_by_id(TABLE_ID).unwrap();
+        let cell_at = |row, column| table.table_cell_at(row, column).map(|cell| cell.id());
+        assert_eq!(cell_at(0, 2), Some(NodeId(7)));
+        assert_eq!(cell_at(1, 1), Some(NodeId(12)));
+        assert_eq!(cell_at(1, 2), Some(NodeId(12)));
+        assert_eq!(cell_at(2, 1), None);
+        assert_eq!(cell_at(2, 2), Some(NodeId(22)));
+        assert_eq!(cell_at(3, 0), None);
+        let ids = |nodes: Vec<crate::Node>| nodes.iter().map(|node| node.id()).collect::<Vec<_>>();
+        assert_eq!(ids(table.table_row_headers(2)), [NodeId(21)]);
+        assert_eq!(ids(table.table_column_headers(1)), [NodeId(6)]);
+        assert!(table.table_row_headers(0).is_empty());
+        assert!(table.table_selected_rows().is_empty());
+        assert_eq!(
+            ids(table.table_headers(Role::RowHeader)),
+            [NodeId(11), NodeId(21)]
+        );
+        assert_eq!(
+            ids(table.table_column_cells(2)),
+            [NodeId(7), NodeId(12), NodeId(22)]
+        );
+
+        let cell = tree.state().node_by_id(NodeId(12)).unwrap();
+        assert_eq!(
+            ids(cell.table_headers(Role::ColumnHeader)),
+            [NodeId(6), NodeId(7)]
+        );
+        assert!(tree
+            .state()
+            .node_by_id(NodeId(11))
+            .unwrap()
+            .table_headers(Role::RowHeader)
+            .is_empty());
+        assert_eq!(cell.containing_table().unwrap().id(), TABLE_ID);
+        assert_eq!(
+            cell.table_cell_position(),
+            Some(TableCellPosition {
+                row_index: 1,
+                column_index: 1,
+                row_span: 1,
+                column_span: 2,
+            })
+        );
+        let row = tree.state().node_by_id(NodeId(10)).unwrap();
+        assert_eq!(row.table_cell_position(), None);
+    }
+
+    #[test]
+    fn explicit_counts() {
+        let mut tree = test_tree();
+        let mut table = Node::from(tree.state().node_by_id(TABLE_ID).unwrap().data());
+        table.set_row_count(100);
+        table.set_column_count(4);
+        tree.update(TreeUpdate {
+            nodes: vec![(TABLE_ID, table)],
+            tree: None,
+            focus: NodeId(0),
+        });
+        let table = tree.state().node_by_id(TABLE_ID).unwrap();
+        assert_eq!(table.table_row_count(), 100);
+        assert_eq!(table.table_column_count(), 4);
+    }
+
+    #[test]
+    fn layout_follows_updates() {
+        let mut tree = test_tree();
+        // Add a fourth cell to the last row, without updating the table.
+        let mut second_row = Node::from(tree.state().node_by_id(NodeId(20)).unwrap().data());
+        second_row.push_child(NodeId(23));
+        tree.update(TreeUpdate {
+            nodes: vec![
+                (NodeId(20), second_row),
+                (NodeId(23), Node::new(Role::Cell)),
+            ],
+            tree: None,
+            focus: NodeId(0),
+        });
+        let state = tree.state();
+        let table = state.node_by_id(TABLE_ID).unwrap();
+        assert_eq!(table.table_column_count(), 4);
+        assert_eq!(table.table_cell_at(2, 3).unwrap().id(), NodeId(23));
+        assert_eq!(
+            state
+                .node_by_id(NodeId(23))
+                .unwrap()
+                .table_cell_position()
+                .map(|position| position.column_index),
+            Some(3)
+        );
+
+        // A node that stops being a table no longer has a layout.
+        let mut group = Node::from(table.data());
+        group.set_role(Role::Group);
+        tree.update(TreeUpdate {
+            nodes: vec![(TABLE_ID, group)],
+            tree: None,
+            focus: NodeId(0),
+        });
+        let state = tree.state();
+        assert!(state.node_by_id(TABLE_ID).unwrap().table_rows().is_empty());
+        assert_eq!(
+            state.node_by_id(NodeId(23)).unwrap().table_cell_position(),
+            None
+        );
+    }
+}
@@ -9,7 +9,7 @@ use accesskit::{
 use alloc::{string::String, vec::Vec};
 use core::{cmp::Ordering, fmt, iter::FusedIterator};
 
-use crate::{FilterResult, Node, TreeState};
+use crate::{node::write_masked, FilterResult, Node, TreeState};
 
 #[derive(Clone, Copy)]
 pub(crate) struct InnerPosition<'a> {
@@ -86,6 +86,15 @@ impl<'a> InnerPosition<'a> {
         self.is_run_end() && self.node.following_text_runs(root_node).next().is_none()
     }
 
+    fn is_format_start(&self, root_node: &Node) -> bool {
+        self.is_run_start()
+            && self
+                .node
+                .preceding_text_runs(root_node)
+                .next()
+                .map_or(true, |node| !node.has_same_format(&self.node))
+    }
+
     fn biased_to_start(&self, root_node: &Node) -> Self {
         if self.is_run_end() {
             if let Some(node) = self.node.following_text_runs(root_node).next() {
@@ -147,6 +156,34 @@ impl<'a> InnerPosition<'a> {
         }
     }
 
+    fn format_start(&self, root_node: &Node) -> Self {
+        let mut node = self.node;
+        for preceding in self.node.preceding_text_runs(root_node) {
+            if !preceding.has_same_format(&node) {
+                break;
+            }
+            node = preceding;
+        }
+        Self {
+            node,
+            character_index: 0,
+        }
+    }
+
+    fn format_end(&self, root_node: &Node) -> Self {
+        let mut node = self.node;
+        for following in self.node.following_text_runs(root_node) {
+            if !following.has_same_format(&node) {
+                break;
+            }
+            node = following;
+        }
+        Self {
+            node,
+            character_index: node.data().character_lengths().len(),
+        }
+    }
+
     fn line_start(&self) -> Self {
         let mut node = self.node;
         while let Some(id) = node.data().previous_on_line() {
@@ -197,8 +234,9 @@ impl<'a> Position<'a> {
     }
 
     pub fn is_format_start(&self) -> bool {
-        // TODO: support variable text formatting (part of rich text)
-        self.is_document_start()
+        self.inner
+            .biased_to_start(&self.root_node)
+            .is_format_start(&self.root_node)
     }
 
     pub fn is_word_start(&self) -> bool {
@@ -328,18 +366,31 @@ impl<'a> Position<'a> {
     }
 
     pub fn forward_to_format_start(&self) -> Self {
-        // TODO: support variable text formatting (part of rich text)
-        self.document_end()
+        let pos = self.inner.biased_to_start(&self.root_node);
+        Self {
+            root_node: self.root_node,
+            inner: pos
+                .format_end(&self.root_node)
+                .biased_to_start(&self.root_node),
+        }
     }
 
     pub fn forward_to_format_end(&self) -> Self {
-        // TODO: support variable text formatting (part of rich text)
-        self.document_end()
+        let pos = self.inner.biased_to_start(&self.root_node);
+        Self {
+            root_node: self.root_node,
+            inner: pos.format_end(&self.root_node),
+        }
     }
 
     pub fn backward_to_format_start(&self) -> Self {
-        // TODO: support variable text formatting (part of rich text)
-        self.document_start()
+        let pos = self.inner.biased_to_end(&self.root_node);
+        Self {
+            root_node: self.root_node,
+            inner: pos
+                .format_start(&self.root_node)
+                .biased_to_start(&self.root_node),
+        }
     }
 
     pub fn forward_to_word_start(&self) -> Self {
@@ -585,7 +636,11 @@ impl<'a> Range<'a> {
                         .sum::<usize>();
                 &value[slice_start..slice_end]
             };
-            writer.write_str(s).err()
+            if self.node.is_password_masked() {
+                write_masked(&mut writer, end_index - start_index).err()
+            } else {
+                writer.write_str(s).err()
+            }
         }) {
             Err(err)
         } else {
@@ -703,6 +758,29 @@ impl<'a> Range<'a> {
         .unwrap_or_else(|| AttributeValue::Single(value.unwrap()))
     }
 
+    /// Returns the parts of this range that fall within each format run,
+    /// i.e. each sequence of adjacent text runs with the same formatting.
+    /// A degenerate range yields itself.
+    pub fn format_runs(&self) -> impl Iterator<Item = Range<'a>> + 'a {
+        let node = self.node;
+        let end = self.end();
+        let mut next_start = Some(self.start());
+        core::iter::from_fn(move || {
+            let start = next_start?;
+            let run_end = start.forward_to_format_end();
+            let run_end = if run_end >= end {
+                next_start = None;
+                end
+            } else {
+                next_start = Some(run_end);
+                run_end
+            };
+            let mut range = Range::new(node, start.inner, run_end.inner);
+            range.fix_start_bias();
+            Some(range)
+        })
+    }
+
     fn fix_start_bias(&mut self) {
         if !self.is_degenerate() {
             self.start = self.start.biased_to_start(&self.node);
@@ -801,6 +879,16 @@ fn text_node_filter(root_id: NodeId, node: &Node) -> FilterResult {
     }
 }
 
+fn link_filter(node: &Node) -> FilterResult {
+    if node.is_link() {
+        FilterResult::Include
+    } else if node.role() == Role::TextRun {
+        FilterResult::ExcludeSubtree
+    } else {
+        FilterResult::ExcludeNode
+    }
+}
+
 fn character_index_at_point(node: &Node, point: Point) -> usize {
     // We know the node has a bounding rectangle because it was returned
     // by a hit test.
@@ -865,6 +953,25 @@ impl<'a> Node<'a> {
         self.preceding_filtered_siblings(move |node| text_node_filter(id, node))
     }
 
+    /// Returns whether this text run has the same formatting as `other`,
+    /// so that both are part of the same format run if they're adjacent.
+    fn has_same_format(&self, other: &Node) -> bool {
+        self.font_family() == other.font_family()
+            && self.language() == other.language()
+            && self.font_size() == other.font_size()
+            && self.font_weight() == other.font_weight()
+            && self.is_bold() == other.is_bold()
+            && self.is_italic() == other.is_italic()
+            && self.background_color() == other.background_color()
+            && self.foreground_color() == other.foreground_color()
+            && self.overline() == other.overline()
+            && self.strikethrough() == other.strikethrough()
+            && self.underline() == other.underline()
+            && self.vertical_offset() == other.vertical_offset()
+            && self.data().is_spelling_error() == other.data().is_spelling_error()
+            && self.data().is_grammar_error() == other.data().is_grammar_error()
+    }
+
     pub fn supports_text_ranges(&self) -> bool {
         (self.is_text_input()
             || matches!(self.role(), Role::Label | Role::Document | Role::Terminal))
@@ -893,11 +1000,43 @@ impl<'a> Node<'a> {
         Range::new(*self, start, end)
     }
 
+    /// Returns the range of this node's text that is within the given
+    /// descendant, or `None` if the descendant isn't within this node
+    /// or contains no text.
+    pub fn text_range_of_descendant(&self, descendant: &Node<'a>) -> Option<Range<'a>> {
+        if !descendant.is_descendant_of(self) {
+            return None;
+        }
+        let mut runs = descendant.text_runs();
+        let first = runs.next()?;
+        let last = runs.next_back().unwrap_or(first);
+        let start = InnerPosition {
+            node: first,
+            character_index: 0,
+        };
+        let end = InnerPosition {
+            node: last,
+            character_index: last.data().character_lengths().len(),
+        };
+        Some(Range::new(*self, start, end))
+    }
+
+    /// Returns the links within this node's text, in document order,
+    /// along with the range of text that each one covers. Links that
+    /// contain no text are skipped, as are links nested within other links.
+    pub fn text_links(&self) -> impl Iterator<Item = (Node<'a>, Range<'a>)> + 'a {
+        let node = *self;
+        self.filtered_children(link_filter).filter_map(move |link| {
+            let range = node.text_range_of_descendant(&link)?;
+            Some((link, range))
+        })
+    }
+
     pub fn has_text_selection(&self) -> bool {
         self.data().text_selection().is_some()
     }
 
-    pub fn text_selection(&self) -> Option<Range> {
+    pub fn text_selection(&self) -> Option<Range<'a>> {
         self.data().text_selection().map(|selection| {
             let anchor = InnerPosition::clamped_upgrade(self.tree_state, selection.anchor).unwrap();
             let focus = InnerPosition::clamped_upgrade(self.tree_state, selection.focus).unwrap();
@@ -905,6 +1044,22 @@ impl<'a> Node<'a> {
         })
     }
 
+    /// Returns the primary selection, if any, followed by the additional
+    /// selections of an editor with multiple cursors. Additional selections
+    /// whose positions don't refer to text runs are skipped.
+    pub fn text_selections(&self) -> impl Iterator<Item = Range<'a>> + 'a {
+        let node = *self;
+        let tree_state = self.tree_state;
+        let additional = self.state.data.text_selections();
+        self.text_selection()
+            .into_iter()
+            .chain(additional.iter().filter_map(move |selection| {
+                let anchor = InnerPosition::clamped_upgrade(tree_state, selection.anchor)?;
+                let focus = InnerPosition::clamped_upgrade(tree_state, selection.focus)?;
+                Some(Range::new(node, anchor, focus))
+            }))
+    }
+
     pub fn text_selection_focus(&self) -> Option<Position> {
         self.data().text_selection().map(|selection| {
             let focus = InnerPosition::clamped_upgrade(self.tree_state, selection.focus).unwrap();
@@ -1097,7 +1252,7 @@ impl<'a> Node<'a> {
 #[cfg(test)]
 mod tests {
     use accesskit::{NodeId, Point, Rect, TextSelection};
-    use alloc::vec;
+    use alloc::{vec, vec::Vec};
 
     // This is based on an actual tree produced by egui.
     fn main_multiline_tree(selection: Option<TextSelection>) -> crate::Tree {
@@ -2026,4 +2181,245 @@ mod tests {
         let node = state.node_by_id(NodeId(1)).unwrap();
         let _ = node.text_selection().unwrap();
     }
+
+    #[test]
+    fn multiple_selections() {
+        use accesskit::{Node, Role, TextPosition, Tree, TreeUpdate};
+
+        let caret = |character_index| TextSelection {
+            anchor: TextPosition {
+                node: NodeId(2),
+                character_index,
+            },
+            focus: TextPosition {
+                node: NodeId(2),
+                character_index,
+            },
+        };
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::TextInput);
+                    node.set_children(vec![NodeId(2)]);
+                    node.set_text_selection(caret(1));
+                    node.push_text_selection(caret(3));
+                    node.push_text_selection(TextSelection {
+                        anchor: TextPosition {
+                            node: NodeId(3),
+                            character_index: 0,
+                        },
+                        focus: TextPosition {
+                            node: NodeId(3),
+                            character_index: 0,
+                        },
+                    });
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::TextRun);
+                    node.set_value("abcd");
+                    node.set_character_lengths([1, 1, 1, 1]);
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(1),
+        };
+        let tree = crate::Tree::new(update, true);
+        let state = tree.state();
+        let node = state.node_by_id(NodeId(1)).unwrap();
+        let offsets = node
+            .text_selections()
+            .map(|range| range.start().to_global_utf16_index())
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, [1, 3]);
+    }
+
+    #[test]
+    fn password_masking() {
+        use accesskit::{Node, Role, Tree, TreeUpdate};
+
+        let update = |is_value_masked| TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::PasswordInput);
+                    node.set_children(vec![NodeId(2)]);
+                    if is_value_masked {
+                        node.set_is_value_masked();
+                    }
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::TextRun);
+                    // The third character is an "e" followed by
+                    // a combining acute accent.
+                    node.set_value("pae\u{301}s");
+                    node.set_character_lengths([1, 1, 3, 1]);
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(1),
+        };
+
+        let tree = crate::Tree::new(update(false), false);
+        let state = tree.state();
+        let node = state.node_by_id(NodeId(1)).unwrap();
+        assert!(node.is_password_masked());
+        assert_eq!(
+            node.value().as_deref(),
+            Some("\u{2022}\u{2022}\u{2022}\u{2022}")
+        );
+        assert_eq!(
+            node.document_range().text(),
+            "\u{2022}\u{2022}\u{2022}\u{2022}"
+        );
+
+        let tree = crate::Tree::new(update(true), false);
+        let state = tree.state();
+        let node = state.node_by_id(NodeId(1)).unwrap();
+        assert!(!node.is_password_masked());
+        assert_eq!(node.value().as_deref(), Some("pae\u{301}s"));
+    }
+
+    fn formatted_tree() -> crate::Tree {
+        use accesskit::{Node, Role, Tree, TreeUpdate};
+
+        let runs = [
+            ("Hello ", false),
+            ("there ", false),
+            ("bold", true),
+            ("!", false),
+        ];
+        let mut nodes = vec![(NodeId(0), {
+            let mut node = Node::new(Role::TextInput);
+            node.set_children((1..=runs.len() as u64).map(NodeId).collect::<Vec<_>>());
+            node.set_font_family("Sans");
+            node
+        })];
+        for (i, (text, is_bold)) in runs.into_iter().enumerate() {
+            let id = i as u64 + 1;
+            let mut node = Node::new(Role::TextRun);
+            node.set_value(text);
+            node.set_character_lengths(vec![1; text.len()]);
+            node.set_word_lengths([text.len() as u8]);
+            if is_bold {
+                node.set_bold();
+            }
+            if i > 0 {
+                node.set_previous_on_line(NodeId(id - 1));
+            }
+            if i < runs.len() - 1 {
+                node.set_next_on_line(NodeId(id + 1));
+            }
+            nodes.push((NodeId(id), node));
+        }
+        crate::Tree::new(
+            TreeUpdate {
+                nodes,
+                tree: Some(Tree::new(NodeId(0))),
+                focus: NodeId(0),
+            },
+            true,
+        )
+    }
+
+    #[test]
+    fn format_runs() {
+        let tree = formatted_tree();
+        let state = tree.state();
+        let node = state.node_by_id(NodeId(0)).unwrap();
+        let texts = node
+            .document_range()
+            .format_runs()
+            .map(|range| range.text())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, ["Hello there ", "bold", "!"]);
+
+        let start = node.document_range().start();
+        assert!(start.is_format_start());
+        let bold_start = start.forward_to_format_start();
+        assert!(bold_start.is_format_start());
+        assert_eq!(bold_start.to_global_usv_index(), 12);
+        assert_eq!(start.forward_to_format_end().to_global_usv_index(), 12);
+        let in_there = node.text_position_from_global_usv_index(8).unwrap();
+        assert!(!in_there.is_format_start());
+        assert!(in_there.backward_to_format_start() == start);
+        let in_bold = node.text_position_from_global_usv_index(14).unwrap();
+        assert!(in_bold.backward_to_format_start() == bold_start);
+        assert_eq!(in_bold.forward_to_format_end().to_global_usv_index(), 16);
+        assert!(bold_start
+            .forward_to_format_start()
+            .forward_to_format_start()
+            .is_document_end());
+
+        let mut range = in_there.to_degenerate_range();
+        range.set_end(in_bold);
+        let texts = range
+            .format_runs()
+            .map(|range| range.text())
+            .collect::<Vec<_>>();
+        assert_eq!(texts, ["ere ", "bo"]);
+    }
+
+    #[test]
+    fn text_links() {
+        use accesskit::{Node, Role, Tree, TreeUpdate};
+
+        let run = |value: &str| {
+            let mut node = Node::new(Role::TextRun);
+            node.set_value(value);
+            node.set_character_lengths(vec![1; value.len()]);
+            node
+        };
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Document);
+                    node.set_children(vec![NodeId(2), NodeId(3), NodeId(5), NodeId(6)]);
+                    node
+                }),
+                (NodeId(2), run("Visit ")),
+                (NodeId(3), {
+                    let mut node = Node::new(Role::Link);
+                    node.set_url("https://accesskit.dev/");
+                    node.set_children(vec![NodeId(4)]);
+                    node
+                }),
+                (NodeId(4), run("AccessKit")),
+                (NodeId(5), run(" or ")),
+                (NodeId(6), Node::new(Role::Link)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = crate::Tree::new(update, false);
+        let state = tree.state();
+        let document = state.node_by_id(NodeId(1)).unwrap();
+        let links = document.text_links().collect::<Vec<_>>();
+        assert_eq!(links.len(), 1);
+        let (link, range) = &links[0];
+        assert_eq!(link.id(), NodeId(3));
+        assert_eq!(link.url(), Some("https://accesskit.dev/"));
+        assert_eq!(range.text(), "AccessKit");
+        assert_eq!(range.start().to_global_usv_index(), 6);
+        assert_eq!(range.end().to_global_usv_index(), 15);
+        let root = state.root();
+        assert!(document.text_range_of_descendant(&root).is_none());
+    }
 }
@@ -3,26 +3,52 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-use accesskit::{FrozenNode as NodeData, NodeId, Tree as TreeData, TreeUpdate};
-use alloc::{sync::Arc, vec};
-use core::fmt;
+use accesskit::{
+    Action, ActionRequest, ActionRequestError, FrozenNode as NodeData, GeometryUpdate, NodeId,
+    Point, Role, TextSelection, Tree as TreeData, TreeUpdate, TreeUpdateError,
+};
+use alloc::{boxed::Box, sync::Arc, vec, vec::Vec};
+use core::{
+    fmt,
+    mem::{self, size_of},
+};
 use hashbrown::{HashMap, HashSet};
 use immutable_chunkmap::map::MapM as ChunkMap;
 
-use crate::node::{Node, NodeState, ParentAndIndex};
+use crate::{
+    events::{Event, EventDispatcher, Subscriber, SubscriptionId},
+    filters::FilterResult,
+    node::{Node, NodeState, ParentAndIndex},
+    table::TableLayout,
+};
 
+/// An immutable view of the tree at a point in time.
+///
+/// The nodes are stored in a persistent map of reference-counted node data,
+/// so cloning a state is cheap, and applying an update to the tree only
+/// copies the parts of the map that the update touches. A clone is a
+/// snapshot that isn't affected by later updates, and it's `Send` and `Sync`,
+/// so a platform adapter can hand it to another thread and answer queries
+/// from it without holding a lock on the tree.
 #[derive(Clone)]
 pub struct State {
     pub(crate) nodes: ChunkMap<NodeId, NodeState>,
     pub(crate) data: TreeData,
     pub(crate) focus: NodeId,
     is_host_focused: bool,
+    /// The nodes marked as modal, in the order in which they became modal.
+    modal_ids: Vec<NodeId>,
+    pub(crate) table_layouts: ChunkMap<NodeId, Arc<TableLayout>>,
+    /// The nodes whose change reason was set in the latest update.
+    /// A change reason only applies to the update that includes it.
+    pub(crate) change_reason_ids: Vec<NodeId>,
 }
 
 #[derive(Default)]
 struct InternalChanges {
     added_node_ids: HashSet<NodeId>,
     updated_node_ids: HashSet<NodeId>,
+    reordered_node_ids: HashSet<NodeId>,
     removed_node_ids: HashSet<NodeId>,
 }
 
@@ -36,12 +62,105 @@ impl State {
         }
     }
 
+    /// Hash-based ID schemes can silently produce the same ID for two
+    /// different nodes, which shows up as a node included more than once
+    /// in an update, or as a node claimed by two parents. The second parent
+    /// may be an existing node that the update doesn't include, if the
+    /// colliding nodes are structurally distant. Rather than merging
+    /// unrelated nodes, keep the first entry or claim, skip the later one,
+    /// and report the collision.
+    fn resolve_id_collisions(&self, update: &mut TreeUpdate) {
+        let old_root = self.data.root;
+        let new_root = update.tree.as_ref().map_or(old_root, |tree| tree.root);
+
+        let mut ids = HashSet::with_capacity(update.nodes.len());
+        let mut orphans = Vec::new();
+        update.nodes.retain(|(id, node)| {
+            if ids.insert(*id) {
+                return true;
+            }
+            report_id_collision(TreeUpdateError::DuplicateNode(*id));
+            orphans.extend_from_slice(node.children());
+            false
+        });
+
+        // The existing nodes that the update detaches from their parents.
+        // They and their descendants are removed unless claimed elsewhere.
+        let mut detached = HashSet::new();
+        if new_root != old_root {
+            detached.insert(old_root);
+        }
+        for (id, node) in &update.nodes {
+            if let Some(node_state) = self.nodes.get(id) {
+                let children = node.children().iter().collect::<HashSet<_>>();
+                for child_id in node_state.data.children().iter() {
+                    if !children.contains(child_id) {
+                        detached.insert(*child_id);
+                    }
+                }
+            }
+        }
+        let is_kept = |mut id: NodeId| loop {
+            if detached.contains(&id) {
+                return false;
+            }
+            match self.nodes.get(&id).and_then(|node| node.parent_and_index) {
+                Some(ParentAndIndex(parent, _)) => id = parent,
+                None => return true,
+            }
+        };
+
+        let mut parents = HashMap::new();
+        for (id, node) in &mut update.nodes {
+            let mut children = Vec::with_capacity(node.children().len());
+            for child_id in node.children() {
+                let first_parent = parents.get(child_id).copied().or_else(|| {
+                    let parent = self.nodes.get(child_id)?.parent_and_index?.0;
+                    (parent != *id && !ids.contains(&parent) && is_kept(parent)).then_some(parent)
+                });
+                if let Some(first_parent) = first_parent {
+                    report_id_collision(TreeUpdateError::MultipleParents {
+                        child: *child_id,
+                        first_parent,
+                        second_parent: *id,
+                    });
+                } else {
+                    parents.insert(*child_id, *id);
+                    children.push(*child_id);
+                }
+            }
+            if children.len() != node.children().len() {
+                node.set_children(children);
+            }
+        }
+
+        // New nodes that only a skipped duplicate claimed are now orphans.
+        while let Some(id) = orphans.pop() {
+            if id == new_root || parents.contains_key(&id) || self.nodes.get(&id).is_some() {
+                continue;
+            }
+            let Some(index) = update.nodes.iter().position(|(node_id, _)| *node_id == id) else {
+                continue;
+            };
+            let (_, node) = update.nodes.remove(index);
+            for child_id in node.children() {
+                if parents.get(child_id) == Some(&id) {
+                    parents.remove(child_id);
+                    orphans.push(*child_id);
+                }
+            }
+        }
+    }
+
     fn update(
         &mut self,
-        update: TreeUpdate,
+        mut update: TreeUpdate,
         is_host_focused: bool,
         mut changes: Option<&mut InternalChanges>,
     ) {
+        self.resolve_id_collisions(&mut update);
+        debug_validate(&update, |id| self.has_node(id));
+
         let mut unreachable = HashSet::new();
 
         if let Some(tree) = update.tree {
@@ -52,8 +171,14 @@ impl State {
         }
 
         let root = self.data.root;
+        self.change_reason_ids.clear();
         let mut pending_nodes: HashMap<NodeId, _> = HashMap::new();
         let mut pending_children = HashMap::new();
+        let mut changed_child_actions = Vec::new();
+        let mut changed_offscreen = Vec::new();
+        let mut update_ids = Vec::with_capacity(update.nodes.len());
+        let mut update_parents = HashSet::new();
+        let mut new_modal_ids = Vec::new();
 
         fn add_node(
             nodes: &mut ChunkMap<NodeId, NodeState>,
@@ -74,21 +199,26 @@ impl State {
 
         for (node_id, node_data) in update.nodes {
             let node_data = NodeData::from(node_data);
+            update_ids.push(node_id);
 
             unreachable.remove(&node_id);
+            if node_data.change_reason().is_some() {
+                self.change_reason_ids.push(node_id);
+            }
+            if node_data.is_modal() {
+                new_modal_ids.push(node_id);
+            }
 
             let mut seen_child_ids = HashSet::with_capacity(node_data.children().len());
             for (child_index, child_id) in node_data.children().iter().enumerate() {
-                if seen_child_ids.contains(child_id) {
-                    panic!(
-                        "Node #{} of TreeUpdate includes duplicate child #{};",
-                        node_id.0, child_id.0
-                    );
-                }
+                update_parents.insert(*child_id);
                 unreachable.remove(child_id);
                 let parent_and_index = ParentAndIndex(node_id, child_index);
-                if let Some(child_state) = self.nodes.get_mut_cow(child_id) {
+                if let Some(child_state) = self.nodes.get(child_id) {
+                    // Only copy the node if it moved, so the map stays
+                    // shared with earlier snapshots of the state.
                     if child_state.parent_and_index != Some(parent_and_index) {
+                        let child_state = self.nodes.get_mut_cow(child_id).unwrap();
                         child_state.parent_and_index = Some(parent_and_index);
                     }
                 } else if let Some(child_data) = pending_nodes.remove(child_id) {
@@ -109,15 +239,34 @@ impl State {
                 if node_id == root {
                     node_state.parent_and_index = None;
                 }
-                for child_id in node_state.data.children().iter() {
-                    if !seen_child_ids.contains(child_id) {
+                let old_children = node_state.data.children();
+                for child_id in old_children.iter() {
+                    // A child that another node in this update claimed
+                    // has moved rather than become unreachable.
+                    if !seen_child_ids.contains(child_id) && !update_parents.contains(child_id) {
                         unreachable.insert(*child_id);
                     }
                 }
+                let is_reordered = old_children != node_data.children()
+                    && old_children.len() == seen_child_ids.len()
+                    && old_children
+                        .iter()
+                        .all(|child_id| seen_child_ids.contains(child_id));
                 if *node_state.data != node_data {
+                    if changes.is_some() && !node_state.data.has_same_child_actions(&node_data) {
+                        changed_child_actions.push(node_id);
+                    }
+                    if changes.is_some()
+                        && node_state.data.is_offscreen() != node_data.is_offscreen()
+                    {
+                        changed_offscreen.push(node_id);
+                    }
                     node_state.data = Arc::new(node_data);
                     if let Some(changes) = &mut changes {
                         changes.updated_node_ids.insert(node_id);
+                        if is_reordered {
+                            changes.reordered_node_ids.insert(node_id);
+                        }
                     }
                 }
             } else if let Some(parent_and_index) = pending_children.remove(&node_id) {
@@ -148,6 +297,7 @@ impl State {
         if !unreachable.is_empty() {
             fn traverse_unreachable(
                 nodes: &mut ChunkMap<NodeId, NodeState>,
+                table_layouts: &mut ChunkMap<NodeId, Arc<TableLayout>>,
                 changes: &mut Option<&mut InternalChanges>,
                 id: NodeId,
             ) {
@@ -155,19 +305,104 @@ impl State {
                     changes.removed_node_ids.insert(id);
                 }
                 let node = nodes.remove_cow(&id).unwrap();
+                table_layouts.remove_cow(&id);
                 for child_id in node.data.children().iter() {
-                    traverse_unreachable(nodes, changes, *child_id);
+                    // Skip the children that moved to another parent.
+                    let is_moved = nodes.get(child_id).is_some_and(|child| {
+                        child
+                            .parent_and_index
+                            .is_some_and(|ParentAndIndex(parent, _)| parent != id)
+                    });
+                    if !is_moved {
+                        traverse_unreachable(nodes, table_layouts, changes, *child_id);
+                    }
                 }
             }
 
             for id in unreachable {
-                traverse_unreachable(&mut self.nodes, &mut changes, id);
+                traverse_unreachable(&mut self.nodes, &mut self.table_layouts, &mut changes, id);
+            }
+        }
+
+        if let Some(changes) = &mut changes {
+            // Descendants inherit whether they're offscreen, so they're
+            // considered updated when an ancestor's flag changes.
+            // Likewise, a node's child actions apply to the children
+            // that platform adapters expose beneath it, which may be
+            // deeper descendants if the nodes in between are filtered out.
+            // Which nodes are filtered out is up to each adapter,
+            // so all descendants are considered updated.
+            fn mark_descendants_updated(
+                nodes: &ChunkMap<NodeId, NodeState>,
+                changes: &mut InternalChanges,
+                id: NodeId,
+            ) {
+                let Some(node_state) = nodes.get(&id) else {
+                    return;
+                };
+                for child_id in node_state.data.children().iter() {
+                    if !changes.added_node_ids.contains(child_id) {
+                        changes.updated_node_ids.insert(*child_id);
+                    }
+                    mark_descendants_updated(nodes, changes, *child_id);
+                }
+            }
+
+            for id in changed_offscreen.into_iter().chain(changed_child_actions) {
+                mark_descendants_updated(&self.nodes, changes, id);
+            }
+        }
+
+        self.update_table_layouts(update_ids.into_iter());
+
+        let nodes = &self.nodes;
+        self.modal_ids
+            .retain(|id| nodes.get(id).is_some_and(|node| node.data.is_modal()));
+        for id in new_modal_ids {
+            if !self.modal_ids.contains(&id) {
+                self.modal_ids.push(id);
             }
         }
 
         self.validate_global();
     }
 
+    /// Recomputes the layouts of the tables that contain the given nodes,
+    /// or that are the given nodes. A table's layout only depends on
+    /// the nodes for which it's the nearest table ancestor.
+    fn update_table_layouts(&mut self, ids: impl Iterator<Item = NodeId>) {
+        let mut table_ids = HashSet::new();
+        let mut former_table_ids = Vec::new();
+        for id in ids {
+            let Some(node) = self.node_by_id(id) else {
+                continue;
+            };
+            if node.is_table() {
+                table_ids.insert(id);
+            } else if self.table_layouts.get(&id).is_some() {
+                former_table_ids.push(id);
+            }
+            if let Some(table) = node.containing_table() {
+                table_ids.insert(table.id());
+            }
+        }
+        for id in former_table_ids {
+            self.table_layouts.remove_cow(&id);
+        }
+        let layouts = table_ids
+            .into_iter()
+            .map(|id| {
+                (
+                    id,
+                    Arc::new(TableLayout::new(&self.node_by_id(id).unwrap())),
+                )
+            })
+            .collect::<Vec<_>>();
+        for (id, layout) in layouts {
+            self.table_layouts.insert_cow(id, layout);
+        }
+    }
+
     fn update_host_focus_state(
         &mut self,
         is_host_focused: bool,
@@ -181,6 +416,33 @@ impl State {
         self.update(update, is_host_focused, changes);
     }
 
+    fn update_geometry(
+        &mut self,
+        update: GeometryUpdate,
+        mut changes: Option<&mut InternalChanges>,
+    ) {
+        for (id, bounds, transform) in update.nodes {
+            let Some(node_state) = self.nodes.get(&id) else {
+                debug_assert!(
+                    false,
+                    "GeometryUpdate includes node #{}, which isn't in the tree",
+                    id.0
+                );
+                continue;
+            };
+            if node_state.data.bounds() == bounds
+                && node_state.data.transform() == transform.as_ref()
+            {
+                continue;
+            }
+            let data = node_state.data.with_geometry(bounds, transform);
+            self.nodes.get_mut_cow(&id).unwrap().data = Arc::new(data);
+            if let Some(changes) = &mut changes {
+                changes.updated_node_ids.insert(id);
+            }
+        }
+    }
+
     pub fn has_node(&self, id: NodeId) -> bool {
         self.nodes.get(&id).is_some()
     }
@@ -193,6 +455,29 @@ impl State {
         })
     }
 
+    /// Checks whether a platform adapter should pass the given request
+    /// to the application's action handler, that is, whether its target
+    /// is in the tree and isn't inert, whether the target supports
+    /// the action, and whether it has any data that the action requires.
+    pub fn check_action_request(&self, request: &ActionRequest) -> Result<(), ActionRequestError> {
+        let node = self
+            .node_by_id(request.target)
+            .ok_or(ActionRequestError::UnknownTarget)?;
+        if node.is_inert() {
+            return Err(ActionRequestError::InertTarget);
+        }
+        let supported = match request.action {
+            // The focused node can always be focused again, even if
+            // it doesn't declare the action.
+            Action::Focus => node.is_focusable(),
+            action => node.supports_action(action),
+        };
+        if !supported {
+            return Err(ActionRequestError::UnsupportedAction);
+        }
+        request.check_data()
+    }
+
     pub fn root_id(&self) -> NodeId {
         self.data.root
     }
@@ -201,12 +486,75 @@ impl State {
         self.node_by_id(self.root_id()).unwrap()
     }
 
+    /// Returns the deepest filtered node at the given point, which is
+    /// in the coordinate space of the tree's container (e.g. window),
+    /// taking the transforms of all nodes into account.
+    /// See [`Node::node_at_point`].
+    pub fn node_at_point(
+        &self,
+        point: Point,
+        filter: &impl Fn(&Node) -> FilterResult,
+    ) -> Option<Node<'_>> {
+        let root = self.root();
+        root.node_at_point(root.direct_transform().inverse() * point, filter)
+    }
+
     pub fn is_host_focused(&self) -> bool {
         self.is_host_focused
     }
 
+    /// Returns the node that has focus, or `None` if the host doesn't.
+    /// See [`State::focus_id_in_tree`].
     pub fn focus_id(&self) -> Option<NodeId> {
-        self.is_host_focused.then_some(self.focus)
+        self.is_host_focused.then(|| self.focus_id_in_tree())
+    }
+
+    /// Returns the node that has focus when the host has focus.
+    ///
+    /// This is normally [`TreeUpdate::focus`], with two exceptions.
+    /// While a modal node such as a dialog is shown, focus is confined to
+    /// it: if the tree's focus is outside the modal node, the modal node
+    /// itself is reported as focused. And if the focused node is
+    /// a composite widget, such as a list box or a grid, whose
+    /// [`active_descendant`] is set, that descendant is reported as focused
+    /// instead. Platform adapters should all use this, so they agree
+    /// on where focus is.
+    ///
+    /// [`active_descendant`]: accesskit::Node::active_descendant
+    pub fn focus_id_in_tree(&self) -> NodeId {
+        let mut focus = self.node_by_id(self.focus).unwrap();
+        if let Some(modal) = self.active_modal() {
+            if !focus.is_descendant_of(&modal) {
+                focus = modal;
+            }
+        }
+        focus
+            .data()
+            .active_descendant()
+            .filter(|id| self.has_node(*id))
+            .unwrap_or(focus.id())
+    }
+
+    /// Returns the modal node that's currently shown, if any. If several
+    /// modal nodes are shown, e.g. a dialog opened from another dialog,
+    /// this is the one that became modal last.
+    pub fn active_modal(&self) -> Option<Node<'_>> {
+        self.modal_ids
+            .iter()
+            .rev()
+            .map(|id| self.node_by_id(*id).unwrap())
+            .find(|node| {
+                // `common_filter` can't be used here, since it depends on
+                // which node is focused.
+                let mut node = Some(*node);
+                while let Some(current) = node {
+                    if current.is_hidden() || current.data().is_inert() {
+                        return false;
+                    }
+                    node = current.parent();
+                }
+                true
+            })
     }
 
     pub fn focus(&self) -> Option<Node<'_>> {
@@ -220,22 +568,152 @@ impl State {
     pub fn toolkit_version(&self) -> Option<&str> {
         self.data.toolkit_version.as_deref()
     }
+
+    /// Returns a full tree update that reproduces this state, including
+    /// the tree data and the focus. This lets an adapter that's activated
+    /// late, or a debugging tool, obtain the complete tree without asking
+    /// the application for it again.
+    pub fn snapshot(&self) -> TreeUpdate {
+        TreeUpdate {
+            nodes: self
+                .nodes
+                .into_iter()
+                .map(|(id, node_state)| (*id, node_state.data.as_ref().into()))
+                .collect(),
+            tree: Some(self.data.clone()),
+            focus: self.focus,
+        }
+    }
+
+    /// Returns an approximation of the number of bytes used by the data
+    /// of all nodes in this tree state. Node data is immutable and
+    /// reference-counted, so it is shared with any clones of this state
+    /// rather than being duplicated.
+    pub fn node_data_size(&self) -> usize {
+        self.nodes
+            .into_iter()
+            .map(|(_, node_state)| size_of::<NodeData>() + node_state.data.heap_size())
+            .sum()
+    }
+}
+
+/// Where the focus change, if any, is reported relative to the other
+/// changes in an update. See [`ChangeHandler`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FocusEventPosition {
+    /// Report the focus change after all added and updated nodes,
+    /// so that assistive technologies see the final state of the newly
+    /// focused node when they process the focus event.
+    #[default]
+    AfterUpdates,
+    /// Report the focus change after added nodes, but before other
+    /// updated nodes.
+    BeforeUpdates,
 }
 
+/// Receives the changes resulting from a tree update.
+///
+/// The changes from a single update are reported in a fixed order,
+/// so that the events raised by platform adapters are deterministic:
+///
+/// 1. [`node_added`] for each added node, in ascending order of node ID.
+/// 2. [`node_updated`] for each updated node, in ascending order of node ID,
+///    followed by [`children_reordered`] for each node whose children were
+///    only reordered, in the same order.
+/// 3. If the focus moved, [`node_updated`] for the previously and newly
+///    focused nodes if they weren't already reported as updated, followed by
+///    [`focus_moved`].
+/// 4. [`node_removed`] for each removed node, in ascending order of node ID.
+///
+/// Steps 2 and 3 are swapped if [`focus_event_position`] returns
+/// [`FocusEventPosition::BeforeUpdates`].
+///
+/// [`node_added`]: ChangeHandler::node_added
+/// [`node_updated`]: ChangeHandler::node_updated
+/// [`children_reordered`]: ChangeHandler::children_reordered
+/// [`focus_moved`]: ChangeHandler::focus_moved
+/// [`node_removed`]: ChangeHandler::node_removed
+/// [`focus_event_position`]: ChangeHandler::focus_event_position
 pub trait ChangeHandler {
     fn node_added(&mut self, node: &Node);
     fn node_updated(&mut self, old_node: &Node, new_node: &Node);
+
+    /// Called for each node whose children were reordered, without any
+    /// being added or removed, so that platform adapters can report
+    /// the move rather than removing and adding each child.
+    /// The node is also reported through [`ChangeHandler::node_updated`].
+    fn children_reordered(&mut self, _old_node: &Node, _new_node: &Node) {}
+
+    /// Called by [`Tree::update_geometry_and_process_changes`] for each
+    /// node whose bounds or transform changed, instead of
+    /// [`ChangeHandler::node_updated`], since no other property
+    /// can have changed. The default implementation forwards
+    /// to [`ChangeHandler::node_updated`].
+    fn bounds_changed(&mut self, old_node: &Node, new_node: &Node) {
+        self.node_updated(old_node, new_node);
+    }
+
     fn focus_moved(&mut self, old_node: Option<&Node>, new_node: Option<&Node>);
     fn node_removed(&mut self, node: &Node);
+
+    fn focus_event_position(&self) -> FocusEventPosition {
+        FocusEventPosition::AfterUpdates
+    }
 }
 
 pub struct Tree {
     state: State,
+    subscribers: Vec<(SubscriptionId, Subscriber)>,
+    next_subscription_id: u64,
+    #[cfg(feature = "tracing")]
+    update_count: u64,
+}
+
+/// The version of the AccessKit schema that this crate understands.
+/// See [`accesskit::SCHEMA_VERSION`].
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[cfg(feature = "tracing")]
+fn update_span(update_id: u64, update: &TreeUpdate) -> tracing::Span {
+    tracing::debug_span!(
+        "accesskit_tree_update",
+        update_id,
+        node_count = update.nodes.len()
+    )
+}
+
+/// Panics if this crate was built against a different version of
+/// the AccessKit schema than the `accesskit` crate in use.
+fn assert_schema_version(expected: u32) {
+    if let Err(error) = accesskit::check_schema_version(expected) {
+        panic!("{}", error);
+    }
+}
+
+/// Reports an ID collision that [`State::update`] recovered from.
+fn report_id_collision(error: TreeUpdateError) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!("Invalid TreeUpdate: {}", error);
+    #[cfg(not(feature = "tracing"))]
+    let _ = error;
+}
+
+/// In debug builds, panics with a precise error if the update
+/// is inconsistent. See [`TreeUpdate::validate`].
+fn debug_validate(update: &TreeUpdate, is_existing_node: impl Fn(NodeId) -> bool) {
+    if cfg!(debug_assertions) {
+        if let Err(error) = update.validate(is_existing_node) {
+            panic!("Invalid TreeUpdate: {}", error);
+        }
+    }
 }
 
 impl Tree {
-    pub fn new(mut initial_state: TreeUpdate, is_host_focused: bool) -> Self {
-        let Some(tree) = initial_state.tree.take() else {
+    pub fn new(initial_state: TreeUpdate, is_host_focused: bool) -> Self {
+        assert_schema_version(SCHEMA_VERSION);
+        #[cfg(feature = "tracing")]
+        let _span = update_span(0, &initial_state).entered();
+        let Some(tree) = initial_state.tree.clone() else {
             panic!("Tried to initialize the accessibility tree without a root tree. TreeUpdate::tree must be Some.");
         };
         let mut state = State {
@@ -243,13 +721,42 @@ impl Tree {
             data: tree,
             focus: initial_state.focus,
             is_host_focused,
+            modal_ids: Vec::new(),
+            table_layouts: ChunkMap::new(),
+            change_reason_ids: Vec::new(),
         };
         state.update(initial_state, is_host_focused, None);
-        Self { state }
+        Self {
+            state,
+            subscribers: Vec::new(),
+            next_subscription_id: 0,
+            #[cfg(feature = "tracing")]
+            update_count: 0,
+        }
+    }
+
+    /// Starts a tracing span for the next update. Platform adapters
+    /// capture the current span when they queue platform events,
+    /// so the events can be traced back to the update that caused them,
+    /// even when they are raised later or on another thread.
+    #[cfg(feature = "tracing")]
+    fn start_update_span(&mut self, update: &TreeUpdate) -> tracing::span::EnteredSpan {
+        self.update_count += 1;
+        update_span(self.update_count, update).entered()
     }
 
     pub fn update(&mut self, update: TreeUpdate) {
-        self.state.update(update, self.state.is_host_focused, None);
+        #[cfg(feature = "tracing")]
+        let _span = self.start_update_span(&update);
+        if self.subscribers.is_empty() {
+            self.state.update(update, self.state.is_host_focused, None);
+            return;
+        }
+        let mut changes = InternalChanges::default();
+        let old_state = self.state.clone();
+        self.state
+            .update(update, self.state.is_host_focused, Some(&mut changes));
+        self.notify_subscribers(&old_state, &changes);
     }
 
     pub fn update_and_process_changes(
@@ -257,15 +764,26 @@ impl Tree {
         update: TreeUpdate,
         handler: &mut impl ChangeHandler,
     ) {
+        #[cfg(feature = "tracing")]
+        let _span = self.start_update_span(&update);
         let mut changes = InternalChanges::default();
         let old_state = self.state.clone();
         self.state
             .update(update, self.state.is_host_focused, Some(&mut changes));
-        self.process_changes(old_state, changes, handler);
+        self.process_changes(&old_state, &changes, handler);
+        self.notify_subscribers(&old_state, &changes);
     }
 
     pub fn update_host_focus_state(&mut self, is_host_focused: bool) {
-        self.state.update_host_focus_state(is_host_focused, None);
+        if self.subscribers.is_empty() {
+            self.state.update_host_focus_state(is_host_focused, None);
+            return;
+        }
+        let mut changes = InternalChanges::default();
+        let old_state = self.state.clone();
+        self.state
+            .update_host_focus_state(is_host_focused, Some(&mut changes));
+        self.notify_subscribers(&old_state, &changes);
     }
 
     pub fn update_host_focus_state_and_process_changes(
@@ -277,59 +795,173 @@ impl Tree {
         let old_state = self.state.clone();
         self.state
             .update_host_focus_state(is_host_focused, Some(&mut changes));
-        self.process_changes(old_state, changes, handler);
+        self.process_changes(&old_state, &changes, handler);
+        self.notify_subscribers(&old_state, &changes);
+    }
+
+    /// Applies a [`GeometryUpdate`], replacing the bounds and transform
+    /// of the given nodes without comparing their other properties
+    /// or their children.
+    pub fn update_geometry(&mut self, update: GeometryUpdate) {
+        if self.subscribers.is_empty() {
+            self.state.update_geometry(update, None);
+            return;
+        }
+        let mut changes = InternalChanges::default();
+        let old_state = self.state.clone();
+        self.state.update_geometry(update, Some(&mut changes));
+        self.notify_subscribers(&old_state, &changes);
+    }
+
+    pub fn update_geometry_and_process_changes(
+        &mut self,
+        update: GeometryUpdate,
+        handler: &mut impl ChangeHandler,
+    ) {
+        let mut changes = InternalChanges::default();
+        let old_state = self.state.clone();
+        self.state.update_geometry(update, Some(&mut changes));
+        let mut ids = changes.updated_node_ids.iter().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        for id in ids {
+            let old_node = old_state.node_by_id(id).unwrap();
+            let new_node = self.state.node_by_id(id).unwrap();
+            handler.bounds_changed(&old_node, &new_node);
+        }
+        self.notify_subscribers(&old_state, &changes);
+    }
+
+    /// Registers a function that will be called with a typed [`Event`]
+    /// for each change to the tree, in addition to any
+    /// [`ChangeHandler`] passed to the update methods. Any number of
+    /// subscribers can be registered; they're called in the order in
+    /// which they were registered.
+    pub fn subscribe(
+        &mut self,
+        subscriber: impl FnMut(&Event) + Send + Sync + 'static,
+    ) -> SubscriptionId {
+        let id = SubscriptionId(self.next_subscription_id);
+        self.next_subscription_id += 1;
+        self.subscribers.push((id, Box::new(subscriber)));
+        id
+    }
+
+    /// Removes a subscriber registered with [`Tree::subscribe`].
+    /// Returns false if there was no such subscriber.
+    pub fn unsubscribe(&mut self, id: SubscriptionId) -> bool {
+        let len = self.subscribers.len();
+        self.subscribers.retain(|(other_id, _)| *other_id != id);
+        self.subscribers.len() != len
+    }
+
+    fn notify_subscribers(&mut self, old_state: &State, changes: &InternalChanges) {
+        if self.subscribers.is_empty() {
+            return;
+        }
+        let mut subscribers = mem::take(&mut self.subscribers);
+        self.process_changes(old_state, changes, &mut EventDispatcher(&mut subscribers));
+        self.subscribers = subscribers;
     }
 
     fn process_changes(
         &self,
-        old_state: State,
-        changes: InternalChanges,
+        old_state: &State,
+        changes: &InternalChanges,
         handler: &mut impl ChangeHandler,
     ) {
-        for id in &changes.added_node_ids {
-            let node = self.state.node_by_id(*id).unwrap();
+        fn sorted(ids: &HashSet<NodeId>) -> Vec<NodeId> {
+            let mut ids = ids.iter().copied().collect::<Vec<_>>();
+            ids.sort_unstable();
+            ids
+        }
+
+        for id in sorted(&changes.added_node_ids) {
+            let node = self.state.node_by_id(id).unwrap();
             handler.node_added(&node);
         }
-        for id in &changes.updated_node_ids {
-            let old_node = old_state.node_by_id(*id).unwrap();
-            let new_node = self.state.node_by_id(*id).unwrap();
+        let focus_event_position = handler.focus_event_position();
+        if focus_event_position == FocusEventPosition::BeforeUpdates {
+            self.process_focus_change(old_state, changes, handler);
+        }
+        for id in sorted(&changes.updated_node_ids) {
+            let old_node = old_state.node_by_id(id).unwrap();
+            let new_node = self.state.node_by_id(id).unwrap();
+            if is_caret_blink(&old_node, &new_node) {
+                continue;
+            }
             handler.node_updated(&old_node, &new_node);
         }
-        if old_state.focus_id() != self.state.focus_id() {
-            let old_node = old_state.focus();
-            if let Some(old_node) = &old_node {
-                let id = old_node.id();
-                if !changes.updated_node_ids.contains(&id)
-                    && !changes.removed_node_ids.contains(&id)
-                {
-                    if let Some(old_node_new_version) = self.state.node_by_id(id) {
-                        handler.node_updated(old_node, &old_node_new_version);
-                    }
+        for id in sorted(&changes.reordered_node_ids) {
+            let old_node = old_state.node_by_id(id).unwrap();
+            let new_node = self.state.node_by_id(id).unwrap();
+            handler.children_reordered(&old_node, &new_node);
+        }
+        if focus_event_position == FocusEventPosition::AfterUpdates {
+            self.process_focus_change(old_state, changes, handler);
+        }
+        for id in sorted(&changes.removed_node_ids) {
+            let node = old_state.node_by_id(id).unwrap();
+            handler.node_removed(&node);
+        }
+    }
+
+    fn process_focus_change(
+        &self,
+        old_state: &State,
+        changes: &InternalChanges,
+        handler: &mut impl ChangeHandler,
+    ) {
+        if old_state.focus_id() == self.state.focus_id() {
+            return;
+        }
+        let old_node = old_state.focus();
+        if let Some(old_node) = &old_node {
+            let id = old_node.id();
+            if !changes.updated_node_ids.contains(&id) && !changes.removed_node_ids.contains(&id) {
+                if let Some(old_node_new_version) = self.state.node_by_id(id) {
+                    handler.node_updated(old_node, &old_node_new_version);
                 }
             }
-            let new_node = self.state.focus();
-            if let Some(new_node) = &new_node {
-                let id = new_node.id();
-                if !changes.added_node_ids.contains(&id) && !changes.updated_node_ids.contains(&id)
-                {
-                    if let Some(new_node_old_version) = old_state.node_by_id(id) {
-                        handler.node_updated(&new_node_old_version, new_node);
-                    }
+        }
+        let new_node = self.state.focus();
+        if let Some(new_node) = &new_node {
+            let id = new_node.id();
+            if !changes.added_node_ids.contains(&id) && !changes.updated_node_ids.contains(&id) {
+                if let Some(new_node_old_version) = old_state.node_by_id(id) {
+                    handler.node_updated(&new_node_old_version, new_node);
                 }
             }
-            handler.focus_moved(old_node.as_ref(), new_node.as_ref());
-        }
-        for id in &changes.removed_node_ids {
-            let node = old_state.node_by_id(*id).unwrap();
-            handler.node_removed(&node);
         }
+        handler.focus_moved(old_node.as_ref(), new_node.as_ref());
     }
 
+    /// Returns the current state of the tree. Clone it to keep
+    /// a snapshot; see [`State`].
     pub fn state(&self) -> &State {
         &self.state
     }
 }
 
+/// Returns whether an update to a caret node is only the caret blinking,
+/// which toolkits may implement by updating the node, rather than
+/// the caret moving. This is the case if the selection of the text
+/// node that contains the caret hasn't changed.
+fn is_caret_blink(old_node: &Node, new_node: &Node) -> bool {
+    fn text_selection(caret: &Node) -> Option<(NodeId, TextSelection)> {
+        let mut node = caret.parent()?;
+        loop {
+            if let Some(selection) = node.raw_text_selection() {
+                return Some((node.id(), *selection));
+            }
+            node = node.parent()?;
+        }
+    }
+
+    new_node.role() == Role::Caret
+        && old_node.role() == Role::Caret
+        && text_selection(old_node).is_some_and(|old| text_selection(new_node) == Some(old))
+}
+
 struct ShortNodeList<'a, T>(&'a HashMap<NodeId, T>);
 
 impl<T> fmt::Display for ShortNodeList<'_, T> {
@@ -354,8 +986,23 @@ impl<T> fmt::Display for ShortNodeList<'_, T> {
 
 #[cfg(test)]
 mod tests {
-    use accesskit::{Node, NodeId, Role, Tree, TreeUpdate};
-    use alloc::vec;
+    use accesskit::{
+        Action, ActionData, ActionRequest, ActionRequestError, Affine, ChangeReason,
+        GeometryUpdate, Node, NodeId, Rect, Role, TextPosition, TextSelection, Tree, TreeUpdate,
+    };
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn schema_version_matches_accesskit() {
+        assert_eq!(super::SCHEMA_VERSION, accesskit::SCHEMA_VERSION);
+        super::assert_schema_version(super::SCHEMA_VERSION);
+    }
+
+    #[test]
+    #[should_panic(expected = "schema version mismatch")]
+    fn schema_version_mismatch() {
+        super::assert_schema_version(super::SCHEMA_VERSION + 1);
+    }
 
     #[test]
     fn init_tree_with_root_node() {
@@ -398,6 +1045,124 @@ mod tests {
         assert_eq!(2, state.root().children().count());
     }
 
+    #[test]
+    fn snapshot_is_unaffected_by_updates() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2)]);
+                    node
+                }),
+                (NodeId(1), Node::new(Role::Button)),
+                (NodeId(2), Node::new(Role::Button)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut tree = super::Tree::new(update, false);
+        let snapshot = tree.state().clone();
+        assert_send_sync(&snapshot);
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(2), {
+                let mut node = Node::new(Role::Button);
+                node.set_label("OK");
+                node
+            })],
+            tree: None,
+            focus: NodeId(2),
+        });
+        let old_node = snapshot.node_by_id(NodeId(2)).unwrap();
+        assert_eq!(old_node.label(), None);
+        assert_eq!(snapshot.focus_id(), None);
+        let new_node = tree.state().node_by_id(NodeId(2)).unwrap();
+        assert_eq!(new_node.label().as_deref(), Some("OK"));
+        // Nodes that the update didn't touch are shared.
+        assert!(core::ptr::eq(
+            snapshot.node_by_id(NodeId(1)).unwrap().data(),
+            tree.state().node_by_id(NodeId(1)).unwrap().data()
+        ));
+    }
+
+    #[test]
+    fn change_reason_applies_to_one_update() {
+        let input = |value: &str| {
+            let mut node = Node::new(Role::TextInput);
+            node.set_value(value);
+            node.set_change_reason(ChangeReason::Programmatic);
+            node
+        };
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2)]);
+                    node
+                }),
+                (NodeId(1), input("")),
+                (NodeId(2), Node::new(Role::Button)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut tree = super::Tree::new(update, false);
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(1), input("Hello"))],
+            tree: None,
+            focus: NodeId(0),
+        });
+        let node = tree.state().node_by_id(NodeId(1)).unwrap();
+        assert_eq!(node.change_reason(), Some(ChangeReason::Programmatic));
+
+        // The toolkit didn't clear the reason, but this update
+        // doesn't include the node.
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(2), {
+                let mut node = Node::new(Role::Button);
+                node.set_label("OK");
+                node
+            })],
+            tree: None,
+            focus: NodeId(0),
+        });
+        let node = tree.state().node_by_id(NodeId(1)).unwrap();
+        assert_eq!(node.change_reason(), None);
+    }
+
+    #[test]
+    fn node_data_size() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Button);
+                    node.set_label("OK");
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = super::Tree::new(update, false);
+        let state = tree.state();
+        let expected = state
+            .node_by_id(NodeId(0))
+            .map(|node| node.data().heap_size())
+            .unwrap()
+            + state
+                .node_by_id(NodeId(1))
+                .map(|node| node.data().heap_size())
+                .unwrap()
+            + 2 * core::mem::size_of::<accesskit::FrozenNode>();
+        assert_eq!(expected, state.node_data_size());
+    }
+
     #[test]
     fn add_child_to_root_node() {
         let root_node = Node::new(Role::Window);
@@ -473,21 +1238,240 @@ mod tests {
     }
 
     #[test]
-    fn remove_child_from_root_node() {
-        let root_node = Node::new(Role::Window);
-        let first_update = TreeUpdate {
+    fn update_geometry() {
+        let update = TreeUpdate {
             nodes: vec![
                 (NodeId(0), {
-                    let mut node = root_node.clone();
-                    node.push_child(NodeId(1));
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2)]);
                     node
                 }),
-                (NodeId(1), Node::new(Role::RootWebArea)),
-            ],
-            tree: Some(Tree::new(NodeId(0))),
-            focus: NodeId(0),
-        };
-        let mut tree = super::Tree::new(first_update, false);
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Button);
+                    node.set_label("OK");
+                    node.set_bounds(Rect::new(0.0, 0.0, 10.0, 10.0));
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Button);
+                    node.set_bounds(Rect::new(0.0, 20.0, 10.0, 30.0));
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut tree = super::Tree::new(update, false);
+        struct Handler(Vec<NodeId>);
+        impl super::ChangeHandler for Handler {
+            fn node_added(&mut self, _node: &crate::Node) {
+                panic!("expected only bounds changes");
+            }
+            fn node_updated(&mut self, _old_node: &crate::Node, _new_node: &crate::Node) {
+                panic!("expected only bounds changes");
+            }
+            fn bounds_changed(&mut self, old_node: &crate::Node, new_node: &crate::Node) {
+                assert_ne!(old_node.bounding_box(), new_node.bounding_box());
+                self.0.push(new_node.id());
+            }
+            fn focus_moved(
+                &mut self,
+                _old_node: Option<&crate::Node>,
+                _new_node: Option<&crate::Node>,
+            ) {
+                panic!("expected only bounds changes");
+            }
+            fn node_removed(&mut self, _node: &crate::Node) {
+                panic!("expected only bounds changes");
+            }
+        }
+        let mut handler = Handler(Vec::new());
+        tree.update_geometry_and_process_changes(
+            GeometryUpdate {
+                nodes: vec![
+                    (
+                        NodeId(1),
+                        Some(Rect::new(0.0, 5.0, 10.0, 15.0)),
+                        Some(Affine::scale(2.0)),
+                    ),
+                    (NodeId(2), Some(Rect::new(0.0, 20.0, 10.0, 30.0)), None),
+                ],
+            },
+            &mut handler,
+        );
+        // The second node's geometry didn't change.
+        assert_eq!(handler.0, vec![NodeId(1)]);
+        let button = tree.state().node_by_id(NodeId(1)).unwrap();
+        assert_eq!(button.label().as_deref(), Some("OK"));
+        assert_eq!(
+            button.bounding_box(),
+            Some(Rect::new(0.0, 10.0, 20.0, 30.0))
+        );
+    }
+
+    #[test]
+    fn focus_confined_to_modal() {
+        let root = |children: &[u64]| {
+            let mut node = Node::new(Role::Window);
+            node.set_children(children.iter().copied().map(NodeId).collect::<Vec<_>>());
+            node
+        };
+        let dialog = |is_hidden| {
+            let mut node = Node::new(Role::Dialog);
+            node.set_modal();
+            if is_hidden {
+                node.set_hidden();
+            }
+            node.set_children(vec![NodeId(3)]);
+            node
+        };
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), root(&[1])),
+                (NodeId(1), Node::new(Role::Button)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(1),
+        };
+        let mut tree = super::Tree::new(update, true);
+        assert_eq!(tree.state().focus_id(), Some(NodeId(1)));
+
+        tree.update(TreeUpdate {
+            nodes: vec![
+                (NodeId(0), root(&[1, 2])),
+                (NodeId(2), dialog(false)),
+                (NodeId(3), Node::new(Role::Button)),
+            ],
+            tree: None,
+            focus: NodeId(1),
+        });
+        assert_eq!(tree.state().active_modal().unwrap().id(), NodeId(2));
+        assert_eq!(tree.state().focus_id(), Some(NodeId(2)));
+
+        tree.update(TreeUpdate {
+            nodes: vec![],
+            tree: None,
+            focus: NodeId(3),
+        });
+        assert_eq!(tree.state().focus_id(), Some(NodeId(3)));
+
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(2), dialog(true))],
+            tree: None,
+            focus: NodeId(1),
+        });
+        assert!(tree.state().active_modal().is_none());
+        assert_eq!(tree.state().focus_id(), Some(NodeId(1)));
+
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(2), dialog(false))],
+            tree: None,
+            focus: NodeId(1),
+        });
+        assert_eq!(tree.state().focus_id(), Some(NodeId(2)));
+        tree.update_host_focus_state(false);
+        assert_eq!(tree.state().focus_id(), None);
+        tree.update_host_focus_state(true);
+
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(0), root(&[1]))],
+            tree: None,
+            focus: NodeId(1),
+        });
+        assert!(tree.state().active_modal().is_none());
+        assert_eq!(tree.state().focus_id(), Some(NodeId(1)));
+    }
+
+    #[test]
+    fn focus_forwarded_to_active_descendant() {
+        let list_box = |active_descendant: Option<u64>| {
+            let mut node = Node::new(Role::ListBox);
+            node.set_children(vec![NodeId(2), NodeId(3)]);
+            if let Some(id) = active_descendant {
+                node.set_active_descendant(NodeId(id));
+            }
+            node
+        };
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1)]);
+                    node
+                }),
+                (NodeId(1), list_box(Some(2))),
+                (NodeId(2), Node::new(Role::ListBoxOption)),
+                (NodeId(3), Node::new(Role::ListBoxOption)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(1),
+        };
+        let mut tree = super::Tree::new(update, true);
+        let state = tree.state();
+        assert_eq!(state.focus_id(), Some(NodeId(2)));
+        let option = state.node_by_id(NodeId(2)).unwrap();
+        assert!(option.is_focused());
+        assert!(option.is_focusable());
+        assert!(!state.node_by_id(NodeId(1)).unwrap().is_focused());
+
+        struct Handler(Vec<(Option<NodeId>, Option<NodeId>)>);
+        impl super::ChangeHandler for Handler {
+            fn node_added(&mut self, _node: &crate::Node) {}
+            fn node_updated(&mut self, _old_node: &crate::Node, _new_node: &crate::Node) {}
+            fn focus_moved(
+                &mut self,
+                old_node: Option<&crate::Node>,
+                new_node: Option<&crate::Node>,
+            ) {
+                self.0.push((
+                    old_node.map(|node| node.id()),
+                    new_node.map(|node| node.id()),
+                ));
+            }
+            fn node_removed(&mut self, _node: &crate::Node) {}
+        }
+        let mut handler = Handler(Vec::new());
+        tree.update_and_process_changes(
+            TreeUpdate {
+                nodes: vec![(NodeId(1), list_box(Some(3)))],
+                tree: None,
+                focus: NodeId(1),
+            },
+            &mut handler,
+        );
+        tree.update_and_process_changes(
+            TreeUpdate {
+                nodes: vec![(NodeId(1), list_box(None))],
+                tree: None,
+                focus: NodeId(1),
+            },
+            &mut handler,
+        );
+        assert_eq!(
+            handler.0,
+            vec![
+                (Some(NodeId(2)), Some(NodeId(3))),
+                (Some(NodeId(3)), Some(NodeId(1)))
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_child_from_root_node() {
+        let root_node = Node::new(Role::Window);
+        let first_update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = root_node.clone();
+                    node.push_child(NodeId(1));
+                    node
+                }),
+                (NodeId(1), Node::new(Role::RootWebArea)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut tree = super::Tree::new(first_update, false);
         assert_eq!(1, tree.state().root().children().count());
         let second_update = TreeUpdate {
             nodes: vec![(NodeId(0), root_node)],
@@ -624,6 +1608,138 @@ mod tests {
         assert!(!tree.state().node_by_id(NodeId(1)).unwrap().is_focused());
     }
 
+    #[test]
+    fn update_child_actions() {
+        let root_node = {
+            let mut node = Node::new(Role::ListBox);
+            node.set_children(vec![NodeId(1)]);
+            node
+        };
+        let first_update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), root_node.clone()),
+                (NodeId(1), Node::new(Role::ListBoxOption)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut tree = super::Tree::new(first_update, false);
+        assert!(!tree.state().node_by_id(NodeId(1)).unwrap().is_clickable());
+        let second_update = TreeUpdate {
+            nodes: vec![(NodeId(0), {
+                let mut node = root_node;
+                node.add_child_action(Action::Click);
+                node
+            })],
+            tree: None,
+            focus: NodeId(0),
+        };
+        struct Handler {
+            got_updated_parent: bool,
+            got_updated_child: bool,
+        }
+        fn unexpected_change() {
+            panic!("expected only updated parent and child nodes");
+        }
+        impl super::ChangeHandler for Handler {
+            fn node_added(&mut self, _node: &crate::Node) {
+                unexpected_change();
+            }
+            fn node_updated(&mut self, old_node: &crate::Node, new_node: &crate::Node) {
+                if new_node.id() == NodeId(0) {
+                    self.got_updated_parent = true;
+                    return;
+                }
+                if new_node.id() == NodeId(1) && !old_node.is_clickable() && new_node.is_clickable()
+                {
+                    self.got_updated_child = true;
+                    return;
+                }
+                unexpected_change();
+            }
+            fn focus_moved(
+                &mut self,
+                _old_node: Option<&crate::Node>,
+                _new_node: Option<&crate::Node>,
+            ) {
+                unexpected_change();
+            }
+            fn node_removed(&mut self, _node: &crate::Node) {
+                unexpected_change();
+            }
+        }
+        let mut handler = Handler {
+            got_updated_parent: false,
+            got_updated_child: false,
+        };
+        tree.update_and_process_changes(second_update, &mut handler);
+        assert!(handler.got_updated_parent);
+        assert!(handler.got_updated_child);
+    }
+
+    #[test]
+    fn update_child_actions_through_filtered_container() {
+        let root_node = {
+            let mut node = Node::new(Role::ListBox);
+            node.set_children(vec![NodeId(1)]);
+            node
+        };
+        let first_update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), root_node.clone()),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::GenericContainer);
+                    node.set_children(vec![NodeId(2)]);
+                    node
+                }),
+                (NodeId(2), Node::new(Role::ListBoxOption)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut tree = super::Tree::new(first_update, false);
+        assert!(!tree.state().node_by_id(NodeId(2)).unwrap().is_clickable());
+        let second_update = TreeUpdate {
+            nodes: vec![(NodeId(0), {
+                let mut node = root_node;
+                node.add_child_action(Action::Click);
+                node
+            })],
+            tree: None,
+            focus: NodeId(0),
+        };
+        struct Handler {
+            got_updated_option: bool,
+        }
+        impl super::ChangeHandler for Handler {
+            fn node_added(&mut self, _node: &crate::Node) {
+                panic!("expected only updated nodes");
+            }
+            fn node_updated(&mut self, old_node: &crate::Node, new_node: &crate::Node) {
+                if new_node.id() == NodeId(2) {
+                    assert!(!old_node.is_clickable());
+                    assert!(new_node.is_clickable());
+                    self.got_updated_option = true;
+                }
+            }
+            fn focus_moved(
+                &mut self,
+                _old_node: Option<&crate::Node>,
+                _new_node: Option<&crate::Node>,
+            ) {
+                panic!("expected only updated nodes");
+            }
+            fn node_removed(&mut self, _node: &crate::Node) {
+                panic!("expected only updated nodes");
+            }
+        }
+        let mut handler = Handler {
+            got_updated_option: false,
+        };
+        tree.update_and_process_changes(second_update, &mut handler);
+        assert!(handler.got_updated_option);
+    }
+
     #[test]
     fn update_node() {
         let child_node = Node::new(Role::Button);
@@ -747,4 +1863,555 @@ mod tests {
         let mut handler = Handler {};
         tree.update_and_process_changes(update, &mut handler);
     }
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Added(NodeId),
+        Updated(NodeId),
+        Reordered(NodeId),
+        FocusMoved(Option<NodeId>, Option<NodeId>),
+        Removed(NodeId),
+    }
+
+    struct RecordingHandler {
+        focus_event_position: super::FocusEventPosition,
+        events: Vec<Event>,
+    }
+
+    impl super::ChangeHandler for RecordingHandler {
+        fn node_added(&mut self, node: &crate::Node) {
+            self.events.push(Event::Added(node.id()));
+        }
+        fn node_updated(&mut self, _old_node: &crate::Node, new_node: &crate::Node) {
+            self.events.push(Event::Updated(new_node.id()));
+        }
+        fn children_reordered(&mut self, _old_node: &crate::Node, new_node: &crate::Node) {
+            self.events.push(Event::Reordered(new_node.id()));
+        }
+        fn focus_moved(&mut self, old_node: Option<&crate::Node>, new_node: Option<&crate::Node>) {
+            self.events.push(Event::FocusMoved(
+                old_node.map(|node| node.id()),
+                new_node.map(|node| node.id()),
+            ));
+        }
+        fn node_removed(&mut self, node: &crate::Node) {
+            self.events.push(Event::Removed(node.id()));
+        }
+        fn focus_event_position(&self) -> super::FocusEventPosition {
+            self.focus_event_position
+        }
+    }
+
+    fn events_for_mixed_update(focus_event_position: super::FocusEventPosition) -> Vec<Event> {
+        let first_update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2), NodeId(3), NodeId(4)]);
+                    node
+                }),
+                (NodeId(1), Node::new(Role::Button)),
+                (NodeId(2), Node::new(Role::Button)),
+                (NodeId(3), Node::new(Role::Slider)),
+                (NodeId(4), Node::new(Role::Label)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(1),
+        };
+        let mut tree = super::Tree::new(first_update, true);
+        let second_update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(6), NodeId(1), NodeId(2), NodeId(3), NodeId(5)]);
+                    node
+                }),
+                (NodeId(6), Node::new(Role::Label)),
+                (NodeId(5), Node::new(Role::Label)),
+                (NodeId(3), {
+                    let mut node = Node::new(Role::Slider);
+                    node.set_numeric_value(1.0);
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Button);
+                    node.set_bounds(Rect::new(0.0, 0.0, 10.0, 10.0));
+                    node
+                }),
+            ],
+            tree: None,
+            focus: NodeId(3),
+        };
+        let mut handler = RecordingHandler {
+            focus_event_position,
+            events: Vec::new(),
+        };
+        tree.update_and_process_changes(second_update, &mut handler);
+        handler.events
+    }
+
+    #[test]
+    fn event_order_with_focus_after_updates() {
+        assert_eq!(
+            events_for_mixed_update(super::FocusEventPosition::AfterUpdates),
+            [
+                Event::Added(NodeId(5)),
+                Event::Added(NodeId(6)),
+                Event::Updated(NodeId(0)),
+                Event::Updated(NodeId(2)),
+                Event::Updated(NodeId(3)),
+                Event::Updated(NodeId(1)),
+                Event::FocusMoved(Some(NodeId(1)), Some(NodeId(3))),
+                Event::Removed(NodeId(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn event_order_with_focus_before_updates() {
+        assert_eq!(
+            events_for_mixed_update(super::FocusEventPosition::BeforeUpdates),
+            [
+                Event::Added(NodeId(5)),
+                Event::Added(NodeId(6)),
+                Event::Updated(NodeId(1)),
+                Event::FocusMoved(Some(NodeId(1)), Some(NodeId(3))),
+                Event::Updated(NodeId(0)),
+                Event::Updated(NodeId(2)),
+                Event::Updated(NodeId(3)),
+                Event::Removed(NodeId(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn caret_blink_is_not_a_change() {
+        fn text_input(caret_index: usize) -> Node {
+            let mut node = Node::new(Role::TextInput);
+            node.set_children(vec![NodeId(1)]);
+            let position = TextPosition {
+                node: NodeId(0),
+                character_index: caret_index,
+            };
+            node.set_text_selection(TextSelection {
+                anchor: position,
+                focus: position,
+            });
+            node
+        }
+        fn caret(x: f64, hidden: bool) -> Node {
+            let mut node = Node::new(Role::Caret);
+            node.set_bounds(Rect::new(x, 0.0, x + 1.0, 10.0));
+            if hidden {
+                node.set_hidden();
+            }
+            node
+        }
+        let update = TreeUpdate {
+            nodes: vec![(NodeId(0), text_input(0)), (NodeId(1), caret(0.0, false))],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut tree = super::Tree::new(update, true);
+        let mut handler = RecordingHandler {
+            focus_event_position: super::FocusEventPosition::AfterUpdates,
+            events: Vec::new(),
+        };
+        for hidden in [true, false, true] {
+            tree.update_and_process_changes(
+                TreeUpdate {
+                    nodes: vec![(NodeId(1), caret(0.0, hidden))],
+                    tree: None,
+                    focus: NodeId(0),
+                },
+                &mut handler,
+            );
+        }
+        assert!(handler.events.is_empty());
+        assert!(tree.state().node_by_id(NodeId(1)).unwrap().is_hidden());
+        tree.update_and_process_changes(
+            TreeUpdate {
+                nodes: vec![(NodeId(0), text_input(1)), (NodeId(1), caret(5.0, false))],
+                tree: None,
+                focus: NodeId(0),
+            },
+            &mut handler,
+        );
+        assert_eq!(
+            handler.events,
+            [Event::Updated(NodeId(0)), Event::Updated(NodeId(1))]
+        );
+    }
+
+    fn list_tree(items: Vec<NodeId>) -> TreeUpdate {
+        let mut nodes = vec![
+            (NodeId(0), {
+                let mut node = Node::new(Role::Window);
+                node.set_children(vec![NodeId(1), NodeId(2)]);
+                node
+            }),
+            (NodeId(1), {
+                let mut node = Node::new(Role::List);
+                node.set_children(items);
+                node
+            }),
+            (NodeId(2), Node::new(Role::List)),
+        ];
+        for id in 3..6 {
+            nodes.push((NodeId(id), Node::new(Role::ListItem)));
+        }
+        TreeUpdate {
+            nodes,
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        }
+    }
+
+    #[test]
+    fn reordered_children() {
+        let mut tree = super::Tree::new(list_tree(vec![NodeId(3), NodeId(4), NodeId(5)]), true);
+        let update = TreeUpdate {
+            nodes: vec![(NodeId(1), {
+                let mut node = Node::new(Role::List);
+                node.set_children(vec![NodeId(5), NodeId(3), NodeId(4)]);
+                node
+            })],
+            tree: None,
+            focus: NodeId(0),
+        };
+        let mut handler = RecordingHandler {
+            focus_event_position: super::FocusEventPosition::AfterUpdates,
+            events: Vec::new(),
+        };
+        tree.update_and_process_changes(update, &mut handler);
+        assert_eq!(
+            handler.events,
+            [Event::Updated(NodeId(1)), Event::Reordered(NodeId(1))]
+        );
+        let list = tree.state().node_by_id(NodeId(1)).unwrap();
+        assert_eq!(
+            list.child_ids().collect::<Vec<_>>(),
+            [NodeId(5), NodeId(3), NodeId(4)]
+        );
+        let (parent, index) = tree
+            .state()
+            .node_by_id(NodeId(3))
+            .unwrap()
+            .parent_and_index()
+            .unwrap();
+        assert_eq!(parent.id(), NodeId(1));
+        assert_eq!(index, 1);
+    }
+
+    #[test]
+    fn moved_to_parent_listed_earlier() {
+        let mut tree = super::Tree::new(list_tree(vec![NodeId(3), NodeId(4), NodeId(5)]), true);
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(2), {
+                    let mut node = Node::new(Role::List);
+                    node.set_children(vec![NodeId(4)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::List);
+                    node.set_children(vec![NodeId(3), NodeId(5)]);
+                    node
+                }),
+            ],
+            tree: None,
+            focus: NodeId(0),
+        };
+        let mut handler = RecordingHandler {
+            focus_event_position: super::FocusEventPosition::AfterUpdates,
+            events: Vec::new(),
+        };
+        tree.update_and_process_changes(update, &mut handler);
+        assert_eq!(
+            handler.events,
+            [Event::Updated(NodeId(1)), Event::Updated(NodeId(2))]
+        );
+        let moved = tree.state().node_by_id(NodeId(4)).unwrap();
+        assert_eq!(moved.parent_id(), Some(NodeId(2)));
+    }
+
+    #[test]
+    fn offscreen_change_updates_descendants() {
+        let mut tree = super::Tree::new(list_tree(vec![NodeId(3), NodeId(4), NodeId(5)]), true);
+        let update = TreeUpdate {
+            nodes: vec![(NodeId(1), {
+                let mut node = Node::new(Role::List);
+                node.set_children(vec![NodeId(3), NodeId(4), NodeId(5)]);
+                node.set_offscreen();
+                node
+            })],
+            tree: None,
+            focus: NodeId(0),
+        };
+        let mut handler = RecordingHandler {
+            focus_event_position: super::FocusEventPosition::AfterUpdates,
+            events: Vec::new(),
+        };
+        tree.update_and_process_changes(update, &mut handler);
+        assert_eq!(
+            handler.events,
+            [
+                Event::Updated(NodeId(1)),
+                Event::Updated(NodeId(3)),
+                Event::Updated(NodeId(4)),
+                Event::Updated(NodeId(5)),
+            ]
+        );
+        let state = tree.state();
+        assert!(state.node_by_id(NodeId(4)).unwrap().is_offscreen());
+        assert!(!state.node_by_id(NodeId(2)).unwrap().is_offscreen());
+    }
+
+    #[test]
+    fn colliding_node_ids() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1)]);
+                    node
+                }),
+                (NodeId(1), Node::new(Role::Button)),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Label);
+                    node.set_children(vec![NodeId(2)]);
+                    node
+                }),
+                (NodeId(2), Node::new(Role::Image)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = super::Tree::new(update, false);
+        let node = tree.state().node_by_id(NodeId(1)).unwrap();
+        assert_eq!(Role::Button, node.role());
+        assert_eq!(0, node.children().count());
+        assert!(tree.state().node_by_id(NodeId(2)).is_none());
+    }
+
+    #[test]
+    fn child_of_two_parents() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Group);
+                    node.set_children(vec![NodeId(3)]);
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Group);
+                    node.set_children(vec![NodeId(3)]);
+                    node
+                }),
+                (NodeId(3), Node::new(Role::Button)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = super::Tree::new(update, false);
+        let state = tree.state();
+        assert_eq!(
+            Some(NodeId(1)),
+            state.node_by_id(NodeId(3)).unwrap().parent_id()
+        );
+        assert_eq!(0, state.node_by_id(NodeId(2)).unwrap().children().count());
+    }
+
+    #[test]
+    fn child_of_distant_existing_parent() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Group);
+                    node.set_children(vec![NodeId(3)]);
+                    node
+                }),
+                (NodeId(2), Node::new(Role::Group)),
+                (NodeId(3), Node::new(Role::Button)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut tree = super::Tree::new(update, false);
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(2), {
+                let mut node = Node::new(Role::Group);
+                node.set_children(vec![NodeId(3)]);
+                node
+            })],
+            tree: None,
+            focus: NodeId(0),
+        });
+        let state = tree.state();
+        assert_eq!(
+            Some(NodeId(1)),
+            state.node_by_id(NodeId(3)).unwrap().parent_id()
+        );
+        assert_eq!(
+            vec![NodeId(3)],
+            state
+                .node_by_id(NodeId(1))
+                .unwrap()
+                .children()
+                .map(|child| child.id())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(0, state.node_by_id(NodeId(2)).unwrap().children().count());
+    }
+
+    #[test]
+    fn move_child_out_of_removed_parent() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Group);
+                    node.set_children(vec![NodeId(3)]);
+                    node
+                }),
+                (NodeId(2), Node::new(Role::Group)),
+                (NodeId(3), Node::new(Role::Button)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut tree = super::Tree::new(update, false);
+        tree.update(TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(2)]);
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Group);
+                    node.set_children(vec![NodeId(3)]);
+                    node
+                }),
+            ],
+            tree: None,
+            focus: NodeId(0),
+        });
+        let state = tree.state();
+        assert!(state.node_by_id(NodeId(1)).is_none());
+        assert_eq!(
+            Some(NodeId(2)),
+            state.node_by_id(NodeId(3)).unwrap().parent_id()
+        );
+    }
+
+    #[test]
+    fn check_action_request() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Slider);
+                    node.add_action(Action::Focus);
+                    node.add_action(Action::SetValue);
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Group);
+                    node.add_action(Action::Focus);
+                    node.set_inert();
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let tree = super::Tree::new(update, false);
+        let check = |target, action, data| {
+            tree.state().check_action_request(&ActionRequest {
+                action,
+                target: NodeId(target),
+                data,
+            })
+        };
+        assert_eq!(check(1, Action::Focus, None), Ok(()));
+        assert_eq!(
+            check(1, Action::SetValue, Some(ActionData::NumericValue(1.0))),
+            Ok(())
+        );
+        assert_eq!(
+            check(1, Action::SetValue, None),
+            Err(ActionRequestError::MissingData)
+        );
+        assert_eq!(
+            check(1, Action::Increment, None),
+            Err(ActionRequestError::UnsupportedAction)
+        );
+        assert_eq!(check(0, Action::Focus, None), Ok(()));
+        assert_eq!(
+            check(2, Action::Focus, None),
+            Err(ActionRequestError::InertTarget)
+        );
+        assert_eq!(
+            check(3, Action::Focus, None),
+            Err(ActionRequestError::UnknownTarget)
+        );
+    }
+
+    #[test]
+    fn snapshot() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), {
+                    let mut node = Node::new(Role::Window);
+                    node.set_children(vec![NodeId(1), NodeId(2)]);
+                    node
+                }),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Button);
+                    node.set_label("OK");
+                    node.add_action(Action::Click);
+                    node
+                }),
+                (NodeId(2), Node::new(Role::Label)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(1),
+        };
+        let tree = super::Tree::new(update.clone(), true);
+        let snapshot = tree.state().snapshot();
+        assert_eq!(snapshot, update);
+
+        let mut tree = super::Tree::new(snapshot, false);
+        tree.update(TreeUpdate {
+            nodes: vec![(NodeId(2), {
+                let mut node = Node::new(Role::Label);
+                node.set_value("Done");
+                node
+            })],
+            tree: None,
+            focus: NodeId(1),
+        });
+        let snapshot = tree.state().snapshot();
+        assert_eq!(snapshot.nodes[2].1.value(), Some("Done"));
+        assert_eq!(snapshot.focus, NodeId(1));
+    }
 }
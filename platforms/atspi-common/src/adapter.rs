@@ -8,7 +8,9 @@
 // Use of this source code is governed by a BSD-style license that can be
 // found in the LICENSE.chromium file.
 
-use accesskit::{ActionHandler, NodeId, Role, TreeUpdate};
+use accesskit::{
+    ActionHandler, AnnouncementKind, ChangeReason, GeometryUpdate, NodeId, Role, TreeUpdate,
+};
 use accesskit_consumer::{FilterResult, Node, Tree, TreeChangeHandler, TreeState};
 use atspi_common::{InterfaceSet, Live, State};
 use std::{
@@ -53,8 +55,9 @@ impl<'a> AdapterChangeHandler<'a> {
 
         let role = node.role();
         let is_root = node.is_root();
+        let is_notification = node.is_notification();
         let node = NodeWrapper(node);
-        let interfaces = node.interfaces();
+        let interfaces = node.interfaces(self.adapter.can_refresh());
         self.adapter.register_interfaces(node.id(), interfaces);
         if is_root && role == Role::Window {
             let adapter_index = self
@@ -65,6 +68,10 @@ impl<'a> AdapterChangeHandler<'a> {
                 .unwrap();
             self.adapter.window_created(adapter_index, node.id());
         }
+        if role == Role::Tooltip || is_notification {
+            self.adapter
+                .emit_object_event(node.id(), ObjectEvent::StateChanged(State::Showing, true));
+        }
 
         let live = node.live();
         if live != Live::None {
@@ -91,14 +98,19 @@ impl<'a> AdapterChangeHandler<'a> {
 
         let role = node.role();
         let is_root = node.is_root();
+        let is_notification = node.is_notification();
         let node = NodeWrapper(node);
         if is_root && role == Role::Window {
             self.adapter.window_destroyed(node.id());
         }
+        if role == Role::Tooltip || is_notification {
+            self.adapter
+                .emit_object_event(node.id(), ObjectEvent::StateChanged(State::Showing, false));
+        }
         self.adapter
             .emit_object_event(node.id(), ObjectEvent::StateChanged(State::Defunct, true));
         self.adapter
-            .unregister_interfaces(node.id(), node.interfaces());
+            .unregister_interfaces(node.id(), node.interfaces(self.adapter.can_refresh()));
     }
 
     fn remove_subtree(&mut self, node: &Node) {
@@ -117,6 +129,7 @@ impl<'a> AdapterChangeHandler<'a> {
             return;
         }
         self.checked_text_change.insert(id);
+        let is_programmatic = new_node.change_reason() == Some(ChangeReason::Programmatic);
         let old_text = old_node.document_range().text();
         let new_text = new_node.document_range().text();
 
@@ -151,6 +164,7 @@ impl<'a> AdapterChangeHandler<'a> {
                         start_index: prefix_usv_count,
                         length,
                         content: old_content.to_string(),
+                        is_programmatic,
                     },
                 );
             }
@@ -165,6 +179,7 @@ impl<'a> AdapterChangeHandler<'a> {
                         start_index: prefix_usv_count,
                         length,
                         content: new_content.to_string(),
+                        is_programmatic,
                     },
                 );
             }
@@ -203,13 +218,16 @@ impl<'a> AdapterChangeHandler<'a> {
             }
             return;
         };
-        if !old_node.is_focused() || new_node.raw_text_selection() == old_node.raw_text_selection()
+        if !old_node.is_focused()
+            || (new_node.raw_text_selection() == old_node.raw_text_selection()
+                && new_node.raw_text_selections() == old_node.raw_text_selections())
         {
             return;
         }
 
         if let Some(selection) = new_node.text_selection() {
             if !selection.is_degenerate()
+                || new_node.raw_text_selections() != old_node.raw_text_selections()
                 || old_node
                     .text_selection()
                     .map(|selection| !selection.is_degenerate())
@@ -265,8 +283,9 @@ impl TreeChangeHandler for AdapterChangeHandler<'_> {
         } else if filter_new == FilterResult::Include {
             let old_wrapper = NodeWrapper(old_node);
             let new_wrapper = NodeWrapper(new_node);
-            let old_interfaces = old_wrapper.interfaces();
-            let new_interfaces = new_wrapper.interfaces();
+            let can_refresh = self.adapter.can_refresh();
+            let old_interfaces = old_wrapper.interfaces(can_refresh);
+            let new_interfaces = new_wrapper.interfaces(can_refresh);
             let kept_interfaces = old_interfaces & new_interfaces;
             self.adapter
                 .unregister_interfaces(new_wrapper.id(), old_interfaces ^ kept_interfaces);
@@ -278,6 +297,17 @@ impl TreeChangeHandler for AdapterChangeHandler<'_> {
         }
     }
 
+    fn bounds_changed(&mut self, old_node: &Node, new_node: &Node) {
+        if filter(new_node) == FilterResult::Include {
+            let bounds = *self.adapter.context.read_root_window_bounds();
+            NodeWrapper(new_node).notify_bounds_changes(
+                &bounds,
+                self.adapter,
+                &NodeWrapper(old_node),
+            );
+        }
+    }
+
     fn focus_moved(&mut self, old_node: Option<&Node>, new_node: Option<&Node>) {
         if let (None, Some(new_node)) = (old_node, new_node) {
             if let Some(root_window) = root_window(new_node.tree_state) {
@@ -316,7 +346,7 @@ pub fn next_adapter_id() -> usize {
 
 pub struct Adapter {
     id: usize,
-    callback: Box<dyn AdapterCallback + Send + Sync>,
+    callback: Arc<dyn AdapterCallback + Send + Sync>,
     context: Arc<Context>,
 }
 
@@ -374,11 +404,18 @@ impl Adapter {
     ) -> Self {
         let tree = Tree::new(initial_state, is_window_focused);
         let focus_id = tree.state().focus_id();
-        let context = Context::new(app_context, tree, action_handler, root_window_bounds);
+        let callback: Arc<dyn AdapterCallback + Send + Sync> = Arc::new(callback);
+        let context = Context::new(
+            app_context,
+            tree,
+            action_handler,
+            root_window_bounds,
+            Arc::clone(&callback),
+        );
         context.write_app_context().push_adapter(id, &context);
         let adapter = Self {
             id,
-            callback: Box::new(callback),
+            callback,
             context,
         };
         adapter.register_tree();
@@ -393,7 +430,8 @@ impl Adapter {
             for child in node.filtered_children(&filter) {
                 let child_id = child.id();
                 let wrapper = NodeWrapper(&child);
-                let interfaces = wrapper.interfaces();
+                // Only the root can have the refresh action.
+                let interfaces = wrapper.interfaces(false);
                 to_add.push((child_id, interfaces));
                 add_children(child, to_add);
             }
@@ -405,13 +443,19 @@ impl Adapter {
             let tree = self.context.read_tree();
             let tree_state = tree.state();
             let mut app_context = self.context.write_app_context();
-            app_context.toolkit_name = tree_state.toolkit_name().map(|s| s.to_string());
-            app_context.toolkit_version = tree_state.toolkit_version().map(|s| s.to_string());
+            // The toolkit info is shared by all of the application's
+            // windows, so don't let a tree that lacks it clear it.
+            if let Some(toolkit_name) = tree_state.toolkit_name() {
+                app_context.toolkit_name = Some(toolkit_name.to_string());
+            }
+            if let Some(toolkit_version) = tree_state.toolkit_version() {
+                app_context.toolkit_version = Some(toolkit_version.to_string());
+            }
             let adapter_index = app_context.adapter_index(self.id).unwrap();
             let root = tree_state.root();
             let root_id = root.id();
             let wrapper = NodeWrapper(&root);
-            objects_to_add.push((root_id, wrapper.interfaces()));
+            objects_to_add.push((root_id, wrapper.interfaces(self.can_refresh())));
             add_children(root, &mut objects_to_add);
             (adapter_index, root_id)
         };
@@ -436,6 +480,10 @@ impl Adapter {
         PlatformRoot::new(&self.context.app_context)
     }
 
+    fn can_refresh(&self) -> bool {
+        self.callback.supports_refresh()
+    }
+
     fn register_interfaces(&self, id: NodeId, new_interfaces: InterfaceSet) {
         self.callback.register_interfaces(self, id, new_interfaces);
     }
@@ -468,12 +516,26 @@ impl Adapter {
         tree.update_and_process_changes(update, &mut handler);
     }
 
+    pub fn update_geometry(&mut self, update: GeometryUpdate) {
+        let mut handler = AdapterChangeHandler::new(self);
+        let mut tree = self.context.tree.write().unwrap();
+        tree.update_geometry_and_process_changes(update, &mut handler);
+    }
+
     pub fn update_window_focus_state(&mut self, is_focused: bool) {
         let mut handler = AdapterChangeHandler::new(self);
         let mut tree = self.context.tree.write().unwrap();
         tree.update_host_focus_state_and_process_changes(is_focused, &mut handler);
     }
 
+    pub fn announce(&self, text: &str, kind: AnnouncementKind) {
+        let live = match kind {
+            AnnouncementKind::Polite => Live::Polite,
+            AnnouncementKind::Assertive => Live::Assertive,
+        };
+        self.emit_object_event(self.root_id(), ObjectEvent::Announcement(text.into(), live));
+    }
+
     fn window_created(&self, adapter_index: usize, window: NodeId) {
         self.emit_root_object_event(ObjectEvent::ChildAdded(adapter_index, window));
     }
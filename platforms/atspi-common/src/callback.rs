@@ -12,4 +12,19 @@ pub trait AdapterCallback {
     fn register_interfaces(&self, adapter: &Adapter, id: NodeId, interfaces: InterfaceSet);
     fn unregister_interfaces(&self, adapter: &Adapter, id: NodeId, interfaces: InterfaceSet);
     fn emit_event(&self, adapter: &Adapter, event: Event);
+
+    /// Returns whether the callback handles
+    /// [`request_refresh`](Self::request_refresh). Only then does the root
+    /// of the tree expose a "refresh" action. The default implementation
+    /// returns `false`.
+    fn supports_refresh(&self) -> bool {
+        false
+    }
+
+    /// Called when an assistive technology asks for the tree of the adapter
+    /// with the given ID to be rebuilt from scratch, typically because
+    /// it got out of sync with the application. This is only called if
+    /// [`supports_refresh`](Self::supports_refresh) returns `true`.
+    /// The default implementation ignores the request.
+    fn request_refresh(&self, _adapter_id: usize) {}
 }
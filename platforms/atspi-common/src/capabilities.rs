@@ -0,0 +1,154 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Action, AdapterCapabilities, Role};
+
+/// The parts of the AccessKit schema that are exposed through AT-SPI.
+///
+/// This must be kept in sync with the mappings in the `node` module.
+/// A unit test checks that each listed property and action is handled.
+pub const CAPABILITIES: AdapterCapabilities = AdapterCapabilities {
+    api: "AT-SPI",
+    unmapped_roles: &[Role::Unknown, Role::Caret],
+    properties: &[
+        "children",
+        "bounds",
+        "transform",
+        "label",
+        "description",
+        "active_descendant",
+        "described_by",
+        "value",
+        "author_id",
+        "url",
+        "placeholder",
+        "role_description",
+        "help_text",
+        "earcon",
+        "pronunciation",
+        "numeric_value",
+        "min_numeric_value",
+        "max_numeric_value",
+        "numeric_value_step",
+        "letter_spacing",
+        "line_height",
+        "text_indent",
+        "margin_top",
+        "margin_bottom",
+        "margin_leading",
+        "margin_trailing",
+        "font_family",
+        "language",
+        "font_size",
+        "font_weight",
+        "background_color",
+        "foreground_color",
+        "underline",
+        "strikethrough",
+        "vertical_offset",
+        "bold",
+        "italic",
+        "character_lengths",
+        "word_lengths",
+        "text_selection",
+        "text_selections",
+        "row_count",
+        "column_count",
+        "row_index",
+        "column_index",
+        "row_span",
+        "column_span",
+        "toggled",
+        "selected",
+        "multiselectable",
+        "grabbed",
+        "drop_target",
+        "live",
+        "orientation",
+        "change_reason",
+        "disabled",
+        "read_only",
+        "required",
+        "offscreen",
+    ],
+    actions: &[
+        Action::Click,
+        Action::Focus,
+        Action::ShowTooltip,
+        Action::HideTooltip,
+        Action::ScrollIntoView,
+        Action::ScrollToPoint,
+        Action::SetTextSelection,
+        Action::ReplaceSelectedText,
+        Action::SetValue,
+        Action::Increment,
+        Action::Decrement,
+        Action::SetNumericValueRelative,
+        Action::Select,
+        Action::Deselect,
+        Action::Drag,
+        Action::Drop,
+    ],
+    events: &[
+        "object:active-descendant-changed",
+        "object:announcement",
+        "object:bounds-changed",
+        "object:children-changed:add",
+        "object:children-changed:remove",
+        "object:property-change:accessible-description",
+        "object:property-change:accessible-name",
+        "object:property-change:accessible-parent",
+        "object:property-change:accessible-role",
+        "object:property-change:accessible-value",
+        "object:state-changed",
+        "object:text-caret-moved",
+        "object:text-changed:delete",
+        "object:text-changed:insert",
+        "object:text-selection-changed",
+        "window:activate",
+        "window:deactivate",
+    ],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::CAPABILITIES;
+
+    #[test]
+    fn capabilities_match_mappings() {
+        accesskit_testing::check_capabilities(
+            &CAPABILITIES,
+            &[
+                include_str!("action.rs"),
+                include_str!("adapter.rs"),
+                include_str!("node.rs"),
+                include_str!("util.rs"),
+            ],
+            &[
+                ("children", &["filtered_children"]),
+                ("bounds", &["bounding_box"]),
+                ("label", &["name"]),
+                ("active_descendant", &["focus_id"]),
+                ("described_by", &["description"]),
+                ("value", &["name", "document_range"]),
+                ("placeholder", &["placeholder_unless_name"]),
+                ("character_lengths", &["document_range"]),
+                ("word_lengths", &["is_word_start"]),
+                ("row_count", &["table_row_count"]),
+                ("column_count", &["table_column_count"]),
+                ("row_index", &["table_cell_position"]),
+                ("column_index", &["table_cell_position"]),
+                ("row_span", &["table_cell_position"]),
+                ("column_span", &["table_cell_position"]),
+                ("Action::Increment", &["set_numeric_value_action"]),
+                ("Action::Decrement", &["set_numeric_value_action"]),
+                (
+                    "Action::SetNumericValueRelative",
+                    &["set_numeric_value_action"],
+                ),
+            ],
+        );
+    }
+}
@@ -3,16 +3,17 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-use accesskit::{ActionHandler, ActionRequest};
+use accesskit::{ActionHandler, ActionRequest, ActionRequestError};
 use accesskit_consumer::Tree;
 use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
-use crate::WindowBounds;
+use crate::{AdapterCallback, WindowBounds};
 
 /// This is an implementation detail of `accesskit_unix`, required for robust
 /// state transitions with minimal overhead.
 pub trait ActionHandlerNoMut {
     fn do_action(&self, request: ActionRequest);
+    fn action_request_dropped(&self, request: &ActionRequest, error: ActionRequestError);
 }
 
 /// This is an implementation detail of `accesskit_unix`, required for robust
@@ -29,6 +30,13 @@ impl<H: ActionHandler + Send> ActionHandlerNoMut for ActionHandlerWrapper<H> {
     fn do_action(&self, request: ActionRequest) {
         self.0.lock().unwrap().do_action(request)
     }
+
+    fn action_request_dropped(&self, request: &ActionRequest, error: ActionRequestError) {
+        self.0
+            .lock()
+            .unwrap()
+            .action_request_dropped(request, error)
+    }
 }
 
 pub(crate) struct Context {
@@ -36,6 +44,7 @@ pub(crate) struct Context {
     pub(crate) tree: RwLock<Tree>,
     pub(crate) action_handler: Arc<dyn ActionHandlerNoMut + Send + Sync>,
     pub(crate) root_window_bounds: RwLock<WindowBounds>,
+    pub(crate) callback: Arc<dyn AdapterCallback + Send + Sync>,
 }
 
 impl Context {
@@ -44,12 +53,14 @@ impl Context {
         tree: Tree,
         action_handler: Arc<dyn ActionHandlerNoMut + Send + Sync>,
         root_window_bounds: WindowBounds,
+        callback: Arc<dyn AdapterCallback + Send + Sync>,
     ) -> Arc<Self> {
         Arc::new(Self {
             app_context: Arc::clone(app_context),
             tree: RwLock::new(tree),
             action_handler,
             root_window_bounds: RwLock::new(root_window_bounds),
+            callback,
         })
     }
 
@@ -65,6 +76,22 @@ impl Context {
         self.action_handler.do_action(request);
     }
 
+    pub(crate) fn action_request_dropped(
+        &self,
+        request: &ActionRequest,
+        error: ActionRequestError,
+    ) {
+        self.action_handler.action_request_dropped(request, error);
+    }
+
+    pub(crate) fn request_refresh(&self, adapter_id: usize) {
+        self.callback.request_refresh(adapter_id);
+    }
+
+    pub(crate) fn can_refresh(&self) -> bool {
+        self.callback.supports_refresh()
+    }
+
     pub(crate) fn read_app_context(&self) -> RwLockReadGuard<'_, AppContext> {
         self.app_context.read().unwrap()
     }
@@ -98,7 +125,11 @@ impl AppContext {
     }
 
     pub(crate) fn push_adapter(&mut self, id: usize, context: &Arc<Context>) {
-        self.adapters.push((id, Arc::clone(context)));
+        // Adapters whose activation was deferred can be pushed after
+        // adapters that were created later, so keep the list sorted.
+        if let Err(index) = self.adapter_index(id) {
+            self.adapters.insert(index, (id, Arc::clone(context)));
+        }
     }
 
     pub(crate) fn remove_adapter(&mut self, id: usize) {
@@ -40,16 +40,24 @@ pub enum ObjectEvent {
     ChildAdded(usize, NodeId),
     ChildRemoved(NodeId),
     PropertyChanged(Property),
+    /// The selection within a selection container changed.
+    SelectionChanged,
     StateChanged(State, bool),
     TextInserted {
         start_index: i32,
         length: i32,
         content: String,
+        /// True if the change was made by the application rather than
+        /// by the user.
+        is_programmatic: bool,
     },
     TextRemoved {
         start_index: i32,
         length: i32,
         content: String,
+        /// True if the change was made by the application rather than
+        /// by the user.
+        is_programmatic: bool,
     },
     TextSelectionChanged,
 }
@@ -6,6 +6,7 @@
 mod action;
 mod adapter;
 mod callback;
+mod capabilities;
 mod context;
 mod error;
 mod events;
@@ -16,6 +17,9 @@ mod rect;
 pub mod simplified;
 mod util;
 
+#[cfg(test)]
+mod tests;
+
 pub use atspi_common::{
     CoordType, Granularity, InterfaceSet, Layer, Role, ScrollType, State, StateSet,
 };
@@ -23,6 +27,7 @@ pub use atspi_common::{
 pub use action::*;
 pub use adapter::{next_adapter_id, Adapter};
 pub use callback::AdapterCallback;
+pub use capabilities::CAPABILITIES;
 pub use context::{ActionHandlerNoMut, ActionHandlerWrapper, AppContext};
 pub use error::*;
 pub use events::*;
@@ -9,10 +9,10 @@
 // found in the LICENSE.chromium file.
 
 use accesskit::{
-    Action, ActionData, ActionRequest, Affine, Live, NodeId, Orientation, Point, Rect, Role,
-    Toggled,
+    Action, ActionData, ActionRequest, ActionRequestError, Affine, Live, NodeId, Orientation,
+    Point, Rect, Role, Toggled,
 };
-use accesskit_consumer::{FilterResult, Node, TreeState};
+use accesskit_consumer::{FilterResult, LiveRegionChange, Node, TextRange, TreeState};
 use atspi_common::{
     CoordType, Granularity, Interface, InterfaceSet, Layer, Live as AtspiLive, Role as AtspiRole,
     ScrollType, State, StateSet,
@@ -36,11 +36,7 @@ pub(crate) struct NodeWrapper<'a>(pub(crate) &'a Node<'a>);
 
 impl NodeWrapper<'_> {
     pub(crate) fn name(&self) -> Option<String> {
-        if self.0.label_comes_from_value() {
-            self.0.value()
-        } else {
-            self.0.label()
-        }
+        self.0.name()
     }
 
     pub(crate) fn description(&self) -> Option<String> {
@@ -306,7 +302,11 @@ impl NodeWrapper<'_> {
         }
         let filter_result = filter(self.0);
         if filter_result == FilterResult::Include {
-            atspi_state.insert(State::Visible | State::Showing);
+            atspi_state.insert(State::Visible);
+            // Offscreen nodes stay reachable, but aren't on the screen.
+            if !self.0.is_offscreen() {
+                atspi_state.insert(State::Showing);
+            }
         }
         if atspi_role != AtspiRole::ToggleButton && state.toggled().is_some() {
             atspi_state.insert(State::Checkable);
@@ -319,6 +319,9 @@ impl NodeWrapper<'_> {
                 atspi_state.insert(State::Selected);
             }
         }
+        if state.is_multiselectable() {
+            atspi_state.insert(State::Multiselectable);
+        }
         if state.is_text_input() {
             atspi_state.insert(State::SelectableText);
             atspi_state.insert(match state.is_multiline() {
@@ -357,9 +360,26 @@ impl NodeWrapper<'_> {
 
     fn attributes(&self) -> HashMap<&'static str, String> {
         let mut attributes = HashMap::new();
-        if let Some(placeholder) = self.0.placeholder() {
+        if let Some(author_id) = self.0.author_id() {
+            attributes.insert("id", author_id.to_string());
+        }
+        if let Some(placeholder) = self.0.placeholder_unless_name() {
             attributes.insert("placeholder-text", placeholder.to_string());
         }
+        if let Some(earcon) = self.0.earcon() {
+            attributes.insert("earcon", earcon.to_string());
+        }
+        if let Some(pronunciation) = self.0.pronunciation() {
+            attributes.insert("pronunciation", pronunciation.to_string());
+        }
+        if self.0.is_grabbed() || self.0.supports_drag() {
+            attributes.insert("grabbed", self.0.is_grabbed().to_string());
+        }
+        // AT-SPI has no state for drop targets, and the `dropeffect`
+        // attribute would need an effect that AccessKit doesn't describe.
+        if self.0.is_drop_target() || self.0.supports_drop() {
+            attributes.insert("droptarget", "true".into());
+        }
         attributes
     }
 
@@ -367,8 +387,8 @@ impl NodeWrapper<'_> {
         self.0.is_root()
     }
 
-    fn supports_action(&self) -> bool {
-        self.0.is_clickable()
+    fn supports_action(&self, can_refresh: bool) -> bool {
+        self.n_actions(can_refresh) > 0
     }
 
     fn supports_component(&self) -> bool {
@@ -379,13 +399,53 @@ impl NodeWrapper<'_> {
         self.0.supports_text_ranges()
     }
 
+    fn supports_hypertext(&self) -> bool {
+        self.supports_text()
+    }
+
+    fn supports_hyperlink(&self) -> bool {
+        self.0.is_link()
+    }
+
+    fn supports_editable_text(&self) -> bool {
+        // Inserting and deleting text selects the affected range
+        // before replacing it, so both actions are required.
+        self.supports_text()
+            && !self.0.is_read_only_or_disabled()
+            && self.0.supports_set_text_selection()
+            && self.0.supports_replace_selected_text()
+    }
+
     fn supports_value(&self) -> bool {
         self.current_value().is_some()
     }
 
-    pub(crate) fn interfaces(&self) -> InterfaceSet {
+    fn supports_selection(&self) -> bool {
+        self.0.is_multiselectable()
+            || matches!(
+                self.0.role(),
+                Role::ListBox
+                    | Role::TabList
+                    | Role::Tree
+                    | Role::Grid
+                    | Role::ListGrid
+                    | Role::TreeGrid
+            )
+    }
+
+    fn supports_table(&self) -> bool {
+        self.0.is_table()
+    }
+
+    fn supports_table_cell(&self) -> bool {
+        self.0.is_table_cell()
+    }
+
+    /// `can_refresh` tells whether the adapter's callback handles refresh
+    /// requests; see [`is_refresh_action`](Self::is_refresh_action).
+    pub(crate) fn interfaces(&self, can_refresh: bool) -> InterfaceSet {
         let mut interfaces = InterfaceSet::new(Interface::Accessible);
-        if self.supports_action() {
+        if self.supports_action(can_refresh) {
             interfaces.insert(Interface::Action);
         }
         if self.supports_component() {
@@ -394,9 +454,27 @@ impl NodeWrapper<'_> {
         if self.supports_text() {
             interfaces.insert(Interface::Text);
         }
+        if self.supports_editable_text() {
+            interfaces.insert(Interface::EditableText);
+        }
+        if self.supports_hypertext() {
+            interfaces.insert(Interface::Hypertext);
+        }
+        if self.supports_hyperlink() {
+            interfaces.insert(Interface::Hyperlink);
+        }
         if self.supports_value() {
             interfaces.insert(Interface::Value);
         }
+        if self.supports_selection() {
+            interfaces.insert(Interface::Selection);
+        }
+        if self.supports_table() {
+            interfaces.insert(Interface::Table);
+        }
+        if self.supports_table_cell() {
+            interfaces.insert(Interface::TableCell);
+        }
         interfaces
     }
 
@@ -409,19 +487,55 @@ impl NodeWrapper<'_> {
         }
     }
 
-    fn n_actions(&self) -> i32 {
+    fn available_actions(&self) -> Vec<Action> {
+        let mut actions = Vec::new();
         if self.0.is_clickable() {
-            1
-        } else {
-            0
+            actions.push(Action::Click);
+        }
+        if self.0.supports_show_tooltip() {
+            actions.push(Action::ShowTooltip);
+        }
+        if self.0.supports_hide_tooltip() {
+            actions.push(Action::HideTooltip);
+        }
+        if self.0.supports_drag() {
+            actions.push(Action::Drag);
         }
+        if self.0.supports_drop() {
+            actions.push(Action::Drop);
+        }
+        actions
+    }
+
+    fn n_actions(&self, can_refresh: bool) -> i32 {
+        self.available_actions().len() as i32 + i32::from(can_refresh && self.is_root())
+    }
+
+    /// If the adapter's callback handles refresh requests, the root of
+    /// each tree exposes one more action than its
+    /// [`available_actions`](Self::available_actions), which lets assistive
+    /// technologies ask for the tree to be rebuilt from scratch.
+    fn is_refresh_action(&self, index: i32, can_refresh: bool) -> bool {
+        can_refresh && self.is_root() && index == self.available_actions().len() as i32
+    }
+
+    fn action_at_index(&self, index: i32) -> Option<Action> {
+        let index = usize::try_from(index).ok()?;
+        self.available_actions().get(index).copied()
     }
 
-    fn get_action_name(&self, index: i32) -> String {
-        if index != 0 {
-            return String::new();
+    fn get_action_name(&self, index: i32, can_refresh: bool) -> String {
+        if self.is_refresh_action(index, can_refresh) {
+            return "refresh".into();
         }
-        String::from(if self.0.is_clickable() { "click" } else { "" })
+        String::from(match self.action_at_index(index) {
+            Some(Action::Click) => "click",
+            Some(Action::ShowTooltip) => "show-tooltip",
+            Some(Action::HideTooltip) => "hide-tooltip",
+            Some(Action::Drag) => "drag",
+            Some(Action::Drop) => "drop",
+            _ => "",
+        })
     }
 
     fn raw_bounds_and_transform(&self) -> (Option<Rect>, Affine) {
@@ -465,6 +579,7 @@ impl NodeWrapper<'_> {
         old: &NodeWrapper<'_>,
     ) {
         self.notify_state_changes(adapter, old);
+        self.notify_selection_changes(adapter, old);
         self.notify_property_changes(adapter, old);
         self.notify_bounds_changes(window_bounds, adapter, old);
         self.notify_children_changes(adapter, old);
@@ -486,9 +601,19 @@ impl NodeWrapper<'_> {
         }
     }
 
+    fn notify_selection_changes(&self, adapter: &Adapter, old: &NodeWrapper<'_>) {
+        if self.0.is_selected() == old.0.is_selected() {
+            return;
+        }
+        if let Some(container) = selection_container(self.0) {
+            adapter.emit_object_event(container.id(), ObjectEvent::SelectionChanged);
+        }
+    }
+
     fn notify_property_changes(&self, adapter: &Adapter, old: &NodeWrapper<'_>) {
         let name = self.name();
-        if name != old.name() {
+        let old_name = old.name();
+        if name != old_name {
             let name = name.unwrap_or_default();
             adapter.emit_object_event(
                 self.id(),
@@ -497,7 +622,13 @@ impl NodeWrapper<'_> {
 
             let live = self.live();
             if live != AtspiLive::None {
-                adapter.emit_object_event(self.id(), ObjectEvent::Announcement(name, live));
+                let old_name = old_name.unwrap_or_default();
+                if let Some(change) = LiveRegionChange::new(&old_name, &name) {
+                    adapter.emit_object_event(
+                        self.id(),
+                        ObjectEvent::Announcement(change.text().to_string(), live),
+                    );
+                }
             }
         }
         let description = self.description();
@@ -537,7 +668,7 @@ impl NodeWrapper<'_> {
         }
     }
 
-    fn notify_bounds_changes(
+    pub(crate) fn notify_bounds_changes(
         &self,
         window_bounds: &WindowBounds,
         adapter: &Adapter,
@@ -558,14 +689,163 @@ impl NodeWrapper<'_> {
                 adapter.emit_object_event(self.id(), ObjectEvent::ChildAdded(index, *child));
             }
         }
-        for child in old_filtered_children.into_iter() {
-            if !new_filtered_children.contains(&child) {
+        for child in old_filtered_children.iter() {
+            if !new_filtered_children.contains(child) {
+                adapter.emit_object_event(self.id(), ObjectEvent::ChildRemoved(*child));
+            }
+        }
+        // AT-SPI has no event for reordered children, so report each child
+        // that moved as removed and added again. The child itself stays
+        // alive. The children that keep their relative order are those
+        // in the longest increasing subsequence of old positions,
+        // so only the fewest possible children are reported as moved.
+        let old_positions = new_filtered_children
+            .iter()
+            .enumerate()
+            .filter_map(|(index, child)| {
+                old_filtered_children
+                    .iter()
+                    .position(|old_child| old_child == child)
+                    .map(|old_position| (index, old_position))
+            })
+            .collect::<Vec<_>>();
+        let unmoved = longest_increasing_subsequence(
+            &old_positions
+                .iter()
+                .map(|(_, old_position)| *old_position)
+                .collect::<Vec<_>>(),
+        );
+        for ((index, _), unmoved) in old_positions.iter().zip(unmoved) {
+            if !unmoved {
+                let child = new_filtered_children[*index];
                 adapter.emit_object_event(self.id(), ObjectEvent::ChildRemoved(child));
+                adapter.emit_object_event(self.id(), ObjectEvent::ChildAdded(*index, child));
             }
         }
     }
 }
 
+/// Returns whether each of the given values is part of a longest
+/// strictly increasing subsequence.
+fn longest_increasing_subsequence(values: &[usize]) -> Vec<bool> {
+    // `tails[len]` is the index of the smallest last value of any
+    // increasing subsequence of length `len + 1` found so far.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut predecessors = vec![None; values.len()];
+    for (i, value) in values.iter().enumerate() {
+        let len = tails.partition_point(|tail| values[*tail] < *value);
+        if len > 0 {
+            predecessors[i] = Some(tails[len - 1]);
+        }
+        if len == tails.len() {
+            tails.push(i);
+        } else {
+            tails[len] = i;
+        }
+    }
+    let mut result = vec![false; values.len()];
+    let mut next = tails.last().copied();
+    while let Some(i) = next {
+        result[i] = true;
+        next = predecessors[i];
+    }
+    result
+}
+
+/// Returns the nearest ancestor of the given node that supports
+/// the Selection interface, if any.
+fn selection_container<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    let mut current = node.filtered_parent(&filter);
+    while let Some(ancestor) = current {
+        if NodeWrapper(&ancestor).supports_selection() {
+            return Some(ancestor);
+        }
+        current = ancestor.filtered_parent(&filter);
+    }
+    None
+}
+
+/// Returns the items of a selection container, in tree order.
+/// In a tree or a grid, the items aren't necessarily children of
+/// the container: they may be nested in groups, in rows or in other
+/// tree items. Items of nested selection containers are excluded.
+fn selectable_items<'a>(container: &Node<'a>) -> Vec<Node<'a>> {
+    fn collect<'a>(node: &Node<'a>, items: &mut Vec<Node<'a>>) {
+        for child in node.filtered_children(filter) {
+            if child.is_selected().is_some() || child.supports_select() {
+                items.push(child);
+            }
+            if !NodeWrapper(&child).supports_selection() {
+                collect(&child, items);
+            }
+        }
+    }
+    let mut items = Vec::new();
+    collect(container, &mut items);
+    items
+}
+
+/// Requests that the given child of a selection container be selected
+/// or deselected. Returns false if the child doesn't support it.
+fn request_selection_change(context: &Context, child: &Node, select: bool) -> bool {
+    if child.is_selected() == Some(select) {
+        return true;
+    }
+    let action = if select {
+        if !child.supports_select() {
+            return false;
+        }
+        Action::Select
+    } else {
+        if !child.supports_deselect() {
+            return false;
+        }
+        Action::Deselect
+    };
+    request_action(
+        context,
+        child,
+        ActionRequest {
+            action,
+            target: child.id(),
+            data: None,
+        },
+    );
+    true
+}
+
+/// Replaces a range of text by selecting it and then replacing
+/// the selection, since there's no action that does both.
+fn replace_text_range(context: &Context, node: &Node, range: TextRange, text: &str) {
+    request_action(
+        context,
+        node,
+        ActionRequest {
+            action: Action::SetTextSelection,
+            target: node.id(),
+            data: Some(ActionData::SetTextSelection(range.to_text_selection())),
+        },
+    );
+    request_action(
+        context,
+        node,
+        ActionRequest {
+            action: Action::ReplaceSelectedText,
+            target: node.id(),
+            data: Some(ActionData::Value(text.into())),
+        },
+    );
+}
+
+/// Forwards an action request for the given node, or reports why
+/// it was dropped.
+fn request_action(context: &Context, node: &Node, request: ActionRequest) {
+    match node.tree_state.check_action_request(&request) {
+        Ok(()) => context.do_action(request),
+        Err(error) => context.action_request_dropped(&request, error),
+    }
+}
+
 #[derive(Clone)]
 pub struct PlatformNode {
     context: Weak<Context>,
@@ -654,20 +934,107 @@ impl PlatformNode {
         self.resolve_for_text_with_context(|node, _| f(node))
     }
 
+    fn resolve_for_editable_text_with_context<F, T>(&self, f: F) -> Result<T>
+    where
+        for<'a> F: FnOnce(Node<'a>, &Context) -> Result<T>,
+    {
+        self.resolve_with_context(|node, context| {
+            let wrapper = NodeWrapper(&node);
+            if wrapper.supports_editable_text() {
+                f(node, context)
+            } else {
+                Err(Error::UnsupportedInterface)
+            }
+        })
+    }
+
+    fn resolve_for_hyperlink<F, T>(&self, f: F) -> Result<T>
+    where
+        for<'a> F: FnOnce(Node<'a>) -> Result<T>,
+    {
+        self.resolve(|node| {
+            let wrapper = NodeWrapper(&node);
+            if wrapper.supports_hyperlink() {
+                f(node)
+            } else {
+                Err(Error::UnsupportedInterface)
+            }
+        })
+    }
+
+    fn resolve_for_selection<F, T>(&self, f: F) -> Result<T>
+    where
+        for<'a> F: FnOnce(Node<'a>) -> Result<T>,
+    {
+        self.resolve(|node| {
+            let wrapper = NodeWrapper(&node);
+            if wrapper.supports_selection() {
+                f(node)
+            } else {
+                Err(Error::UnsupportedInterface)
+            }
+        })
+    }
+
+    fn resolve_for_selection_with_context<F, T>(&self, f: F) -> Result<T>
+    where
+        for<'a> F: FnOnce(Node<'a>, &Context) -> Result<T>,
+    {
+        self.resolve_with_context(|node, context| {
+            let wrapper = NodeWrapper(&node);
+            if wrapper.supports_selection() {
+                f(node, context)
+            } else {
+                Err(Error::UnsupportedInterface)
+            }
+        })
+    }
+
+    fn resolve_for_table<F, T>(&self, f: F) -> Result<T>
+    where
+        for<'a> F: FnOnce(Node<'a>) -> Result<T>,
+    {
+        self.resolve(|node| {
+            if node.is_table() {
+                f(node)
+            } else {
+                Err(Error::UnsupportedInterface)
+            }
+        })
+    }
+
+    fn resolve_for_table_cell<F, T>(&self, f: F) -> Result<T>
+    where
+        for<'a> F: FnOnce(Node<'a>) -> Result<T>,
+    {
+        self.resolve(|node| {
+            if node.is_table_cell() {
+                f(node)
+            } else {
+                Err(Error::UnsupportedInterface)
+            }
+        })
+    }
+
     fn do_action_internal<F>(&self, f: F) -> Result<()>
     where
-        F: FnOnce(&TreeState, &Context) -> ActionRequest,
+        F: FnOnce(Option<Node>) -> ActionRequest,
     {
         let context = self.upgrade_context()?;
         let tree = context.read_tree();
-        if tree.state().has_node(self.id) {
-            let request = f(tree.state(), &context);
-            drop(tree);
-            context.do_action(request);
-            Ok(())
-        } else {
-            Err(Error::Defunct)
+        let request = f(tree.state().node_by_id(self.id));
+        let result = tree.state().check_action_request(&request);
+        drop(tree);
+        match result {
+            Ok(()) => context.do_action(request),
+            Err(error) => {
+                context.action_request_dropped(&request, error);
+                if error == ActionRequestError::UnknownTarget {
+                    return Err(Error::Defunct);
+                }
+            }
         }
+        Ok(())
     }
 
     pub fn name(&self) -> Result<String> {
@@ -684,6 +1051,10 @@ impl PlatformNode {
         })
     }
 
+    pub fn help_text(&self) -> Result<String> {
+        self.resolve(|node| Ok(node.help_text().unwrap_or_default().to_string()))
+    }
+
     pub fn relative(&self, id: NodeId) -> Self {
         Self {
             context: self.context.clone(),
@@ -797,9 +1168,9 @@ impl PlatformNode {
     }
 
     pub fn supports_action(&self) -> Result<bool> {
-        self.resolve(|node| {
+        self.resolve_with_context(|node, context| {
             let wrapper = NodeWrapper(&node);
-            Ok(wrapper.supports_action())
+            Ok(wrapper.supports_action(context.can_refresh()))
         })
     }
 
@@ -817,6 +1188,13 @@ impl PlatformNode {
         })
     }
 
+    pub fn supports_editable_text(&self) -> Result<bool> {
+        self.resolve(|node| {
+            let wrapper = NodeWrapper(&node);
+            Ok(wrapper.supports_editable_text())
+        })
+    }
+
     pub fn supports_value(&self) -> Result<bool> {
         self.resolve(|node| {
             let wrapper = NodeWrapper(&node);
@@ -824,35 +1202,71 @@ impl PlatformNode {
         })
     }
 
-    pub fn interfaces(&self) -> Result<InterfaceSet> {
+    pub fn supports_hypertext(&self) -> Result<bool> {
         self.resolve(|node| {
             let wrapper = NodeWrapper(&node);
-            Ok(wrapper.interfaces())
+            Ok(wrapper.supports_hypertext())
         })
     }
 
-    pub fn n_actions(&self) -> Result<i32> {
+    pub fn supports_hyperlink(&self) -> Result<bool> {
         self.resolve(|node| {
             let wrapper = NodeWrapper(&node);
-            Ok(wrapper.n_actions())
+            Ok(wrapper.supports_hyperlink())
         })
     }
 
-    pub fn action_name(&self, index: i32) -> Result<String> {
+    pub fn supports_selection(&self) -> Result<bool> {
         self.resolve(|node| {
             let wrapper = NodeWrapper(&node);
-            Ok(wrapper.get_action_name(index))
+            Ok(wrapper.supports_selection())
         })
     }
 
-    pub fn actions(&self) -> Result<Vec<AtspiAction>> {
+    pub fn supports_table(&self) -> Result<bool> {
+        self.resolve(|node| {
+            let wrapper = NodeWrapper(&node);
+            Ok(wrapper.supports_table())
+        })
+    }
+
+    pub fn supports_table_cell(&self) -> Result<bool> {
         self.resolve(|node| {
             let wrapper = NodeWrapper(&node);
-            let n_actions = wrapper.n_actions() as usize;
+            Ok(wrapper.supports_table_cell())
+        })
+    }
+
+    pub fn interfaces(&self) -> Result<InterfaceSet> {
+        self.resolve_with_context(|node, context| {
+            let wrapper = NodeWrapper(&node);
+            Ok(wrapper.interfaces(context.can_refresh()))
+        })
+    }
+
+    pub fn n_actions(&self) -> Result<i32> {
+        self.resolve_with_context(|node, context| {
+            let wrapper = NodeWrapper(&node);
+            Ok(wrapper.n_actions(context.can_refresh()))
+        })
+    }
+
+    pub fn action_name(&self, index: i32) -> Result<String> {
+        self.resolve_with_context(|node, context| {
+            let wrapper = NodeWrapper(&node);
+            Ok(wrapper.get_action_name(index, context.can_refresh()))
+        })
+    }
+
+    pub fn actions(&self) -> Result<Vec<AtspiAction>> {
+        self.resolve_with_context(|node, context| {
+            let wrapper = NodeWrapper(&node);
+            let can_refresh = context.can_refresh();
+            let n_actions = wrapper.n_actions(can_refresh) as usize;
             let mut actions = Vec::with_capacity(n_actions);
             for i in 0..n_actions {
                 actions.push(AtspiAction {
-                    localized_name: wrapper.get_action_name(i as i32),
+                    localized_name: wrapper.get_action_name(i as i32, can_refresh),
                     description: "".into(),
                     key_binding: "".into(),
                 });
@@ -862,11 +1276,22 @@ impl PlatformNode {
     }
 
     pub fn do_action(&self, index: i32) -> Result<bool> {
-        if index != 0 {
-            return Ok(false);
+        let (action, is_refresh) = self.resolve_with_context(|node, context| {
+            let wrapper = NodeWrapper(&node);
+            Ok((
+                wrapper.action_at_index(index),
+                wrapper.is_refresh_action(index, context.can_refresh()),
+            ))
+        })?;
+        if is_refresh {
+            self.upgrade_context()?.request_refresh(self.adapter_id);
+            return Ok(true);
         }
-        self.do_action_internal(|_, _| ActionRequest {
-            action: Action::Click,
+        let Some(action) = action else {
+            return Ok(false);
+        };
+        self.do_action_internal(|_| ActionRequest {
+            action,
             target: self.id,
             data: None,
         })?;
@@ -925,7 +1350,7 @@ impl PlatformNode {
     }
 
     pub fn grab_focus(&self) -> Result<bool> {
-        self.do_action_internal(|_, _| ActionRequest {
+        self.do_action_internal(|_| ActionRequest {
             action: Action::Focus,
             target: self.id,
             data: None,
@@ -933,6 +1358,17 @@ impl PlatformNode {
         Ok(true)
     }
 
+    pub fn scroll_to(&self, _: ScrollType) -> Result<bool> {
+        // AccessKit doesn't support alignment hints for scrolling,
+        // so every scroll type is treated as "anywhere".
+        self.do_action_internal(|_| ActionRequest {
+            action: Action::ScrollIntoView,
+            target: self.id,
+            data: None,
+        })?;
+        Ok(true)
+    }
+
     pub fn scroll_to_point(&self, coord_type: CoordType, x: i32, y: i32) -> Result<bool> {
         self.resolve_with_context(|node, context| {
             let window_bounds = context.read_root_window_bounds();
@@ -941,11 +1377,15 @@ impl PlatformNode {
                 node.filtered_parent(&filter),
                 coord_type,
             );
-            context.do_action(ActionRequest {
-                action: Action::ScrollToPoint,
-                target: self.id,
-                data: Some(ActionData::ScrollToPoint(point)),
-            });
+            request_action(
+                context,
+                &node,
+                ActionRequest {
+                    action: Action::ScrollToPoint,
+                    target: self.id,
+                    data: Some(ActionData::ScrollToPoint(point)),
+                },
+            );
             Ok(())
         })?;
         Ok(true)
@@ -1006,30 +1446,32 @@ impl PlatformNode {
     pub fn set_caret_offset(&self, offset: i32) -> Result<bool> {
         self.resolve_for_text_with_context(|node, context| {
             let offset = text_position_from_offset(&node, offset).ok_or(Error::IndexOutOfRange)?;
-            context.do_action(ActionRequest {
-                action: Action::SetTextSelection,
-                target: node.id(),
-                data: Some(ActionData::SetTextSelection(
-                    offset.to_degenerate_range().to_text_selection(),
-                )),
-            });
+            request_action(
+                context,
+                &node,
+                ActionRequest {
+                    action: Action::SetTextSelection,
+                    target: node.id(),
+                    data: Some(ActionData::SetTextSelection(
+                        offset.to_degenerate_range().to_text_selection(),
+                    )),
+                },
+            );
             Ok(true)
         })
     }
 
-    pub fn text_attribute_value(&self, _offset: i32, _attribute_name: &str) -> Result<String> {
-        // TODO: Implement rich text.
-        Err(Error::UnsupportedInterface)
+    pub fn text_attribute_value(&self, offset: i32, attribute_name: &str) -> Result<String> {
+        let (mut attributes, _, _) = self.text_attributes(offset)?;
+        Ok(attributes.remove(attribute_name).unwrap_or_default())
     }
 
-    pub fn text_attributes(&self, _offset: i32) -> Result<(HashMap<String, String>, i32, i32)> {
-        // TODO: Implement rich text.
-        Err(Error::UnsupportedInterface)
+    pub fn text_attributes(&self, offset: i32) -> Result<(HashMap<String, String>, i32, i32)> {
+        self.text_attribute_run(offset, false)
     }
 
     pub fn default_text_attributes(&self) -> Result<HashMap<String, String>> {
-        // TODO: Implement rich text.
-        Err(Error::UnsupportedInterface)
+        self.resolve_for_text(|node| Ok(text_attributes(&node)))
     }
 
     pub fn character_extents(&self, offset: i32, coord_type: CoordType) -> Result<AtspiRect> {
@@ -1067,21 +1509,23 @@ impl PlatformNode {
 
     pub fn n_selections(&self) -> Result<i32> {
         self.resolve_for_text(|node| {
-            match node.text_selection().filter(|range| !range.is_degenerate()) {
-                Some(_) => Ok(1),
-                None => Ok(0),
-            }
+            let count = node
+                .text_selections()
+                .filter(|range| !range.is_degenerate())
+                .count();
+            count.try_into().map_err(|_| Error::TooManyCharacters)
         })
     }
 
     pub fn selection(&self, selection_num: i32) -> Result<(i32, i32)> {
-        if selection_num != 0 {
+        let Ok(selection_num) = usize::try_from(selection_num) else {
             return Ok((-1, -1));
-        }
+        };
 
         self.resolve_for_text(|node| {
-            node.text_selection()
+            node.text_selections()
                 .filter(|range| !range.is_degenerate())
+                .nth(selection_num)
                 .map_or(Ok((-1, -1)), |range| {
                     let start = range
                         .start()
@@ -1115,13 +1559,17 @@ impl PlatformNode {
             let selection_end = node
                 .text_selection_focus()
                 .unwrap_or_else(|| node.document_range().start());
-            context.do_action(ActionRequest {
-                action: Action::SetTextSelection,
-                target: node.id(),
-                data: Some(ActionData::SetTextSelection(
-                    selection_end.to_degenerate_range().to_text_selection(),
-                )),
-            });
+            request_action(
+                context,
+                &node,
+                ActionRequest {
+                    action: Action::SetTextSelection,
+                    target: node.id(),
+                    data: Some(ActionData::SetTextSelection(
+                        selection_end.to_degenerate_range().to_text_selection(),
+                    )),
+                },
+            );
             Ok(true)
         })
     }
@@ -1139,11 +1587,107 @@ impl PlatformNode {
         self.resolve_for_text_with_context(|node, context| {
             let range = text_range_from_offsets(&node, start_offset, end_offset)
                 .ok_or(Error::IndexOutOfRange)?;
-            context.do_action(ActionRequest {
-                action: Action::SetTextSelection,
-                target: node.id(),
-                data: Some(ActionData::SetTextSelection(range.to_text_selection())),
-            });
+            request_action(
+                context,
+                &node,
+                ActionRequest {
+                    action: Action::SetTextSelection,
+                    target: node.id(),
+                    data: Some(ActionData::SetTextSelection(range.to_text_selection())),
+                },
+            );
+            Ok(true)
+        })
+    }
+
+    pub fn n_links(&self) -> Result<i32> {
+        self.resolve_for_text(|node| Ok(index_to_i32(node.text_links().count())))
+    }
+
+    pub fn link(&self, link_index: usize) -> Result<Option<NodeId>> {
+        self.resolve_for_text(|node| {
+            Ok(node.text_links().nth(link_index).map(|(link, _)| link.id()))
+        })
+    }
+
+    pub fn link_index(&self, character_index: i32) -> Result<i32> {
+        self.resolve_for_text(|node| {
+            let Ok(character_index) = usize::try_from(character_index) else {
+                return Ok(-1);
+            };
+            Ok(node
+                .text_links()
+                .position(|(_, range)| {
+                    range.start().to_global_usv_index() <= character_index
+                        && character_index < range.end().to_global_usv_index()
+                })
+                .map_or(-1, index_to_i32))
+        })
+    }
+
+    pub fn link_start_index(&self) -> Result<i32> {
+        self.resolve_for_hyperlink(|node| {
+            Ok(hyperlink_range(&node).map_or(-1, |range| {
+                index_to_i32(range.start().to_global_usv_index())
+            }))
+        })
+    }
+
+    pub fn link_end_index(&self) -> Result<i32> {
+        self.resolve_for_hyperlink(|node| {
+            Ok(hyperlink_range(&node)
+                .map_or(-1, |range| index_to_i32(range.end().to_global_usv_index())))
+        })
+    }
+
+    pub fn uri(&self) -> Result<String> {
+        self.resolve_for_hyperlink(|node| Ok(node.url().unwrap_or_default().into()))
+    }
+
+    pub fn set_text_contents(&self, text: &str) -> Result<bool> {
+        self.resolve_for_editable_text_with_context(|node, context| {
+            if node.supports_set_value() {
+                request_action(
+                    context,
+                    &node,
+                    ActionRequest {
+                        action: Action::SetValue,
+                        target: node.id(),
+                        data: Some(ActionData::Value(text.into())),
+                    },
+                );
+            } else {
+                replace_text_range(context, &node, node.document_range(), text);
+            }
+            Ok(true)
+        })
+    }
+
+    pub fn insert_text(&self, position: i32, text: &str, length: i32) -> Result<bool> {
+        self.resolve_for_editable_text_with_context(|node, context| {
+            let position =
+                text_position_from_offset(&node, position).ok_or(Error::IndexOutOfRange)?;
+            // The length is in bytes, and may cut off the end of the text.
+            let text = match usize::try_from(length) {
+                Ok(length) if length < text.len() => {
+                    let mut end = length;
+                    while !text.is_char_boundary(end) {
+                        end -= 1;
+                    }
+                    &text[..end]
+                }
+                _ => text,
+            };
+            replace_text_range(context, &node, position.to_degenerate_range(), text);
+            Ok(true)
+        })
+    }
+
+    pub fn delete_text(&self, start_position: i32, end_position: i32) -> Result<bool> {
+        self.resolve_for_editable_text_with_context(|node, context| {
+            let range = text_range_from_offsets(&node, start_position, end_position)
+                .ok_or(Error::IndexOutOfRange)?;
+            replace_text_range(context, &node, range, "");
             Ok(true)
         })
     }
@@ -1171,14 +1715,32 @@ impl PlatformNode {
 
     pub fn text_attribute_run(
         &self,
-        _offset: i32,
+        offset: i32,
         _include_defaults: bool,
     ) -> Result<(HashMap<String, String>, i32, i32)> {
-        // TODO: Implement rich text.
-        // For now, just report a range spanning the entire text with no attributes,
-        // this is required by Orca to announce selection content and caret movements.
-        let character_count = self.character_count()?;
-        Ok((HashMap::new(), 0, character_count))
+        self.resolve_for_text(|node| {
+            let pos = text_position_from_offset(&node, offset).ok_or(Error::IndexOutOfRange)?;
+            let start = if pos.is_format_start() {
+                pos
+            } else {
+                pos.backward_to_format_start()
+            };
+            let end = start.forward_to_format_end();
+            // The attributes that apply to the whole text are always
+            // included, because Orca needs them to announce selection
+            // content and caret movements.
+            let mut attributes = text_attributes(&node);
+            attributes.extend(format_attributes(end.inner_node()));
+            let start = start
+                .to_global_usv_index()
+                .try_into()
+                .map_err(|_| Error::TooManyCharacters)?;
+            let end = end
+                .to_global_usv_index()
+                .try_into()
+                .map_err(|_| Error::TooManyCharacters)?;
+            Ok((attributes, start, end))
+        })
     }
 
     pub fn scroll_substring_to(
@@ -1189,11 +1751,15 @@ impl PlatformNode {
     ) -> Result<bool> {
         self.resolve_for_text_with_context(|node, context| {
             if let Some(rect) = text_range_bounds_from_offsets(&node, start_offset, end_offset) {
-                context.do_action(ActionRequest {
-                    action: Action::ScrollIntoView,
-                    target: node.id(),
-                    data: Some(ActionData::ScrollTargetRect(rect)),
-                });
+                request_action(
+                    context,
+                    &node,
+                    ActionRequest {
+                        action: Action::ScrollIntoView,
+                        target: node.id(),
+                        data: Some(ActionData::ScrollTargetRect(rect)),
+                    },
+                );
                 Ok(true)
             } else {
                 Ok(false)
@@ -1219,11 +1785,15 @@ impl PlatformNode {
 
             if let Some(rect) = text_range_bounds_from_offsets(&node, start_offset, end_offset) {
                 let point = Point::new(target_point.x - rect.x0, target_point.y - rect.y0);
-                context.do_action(ActionRequest {
-                    action: Action::ScrollToPoint,
-                    target: node.id(),
-                    data: Some(ActionData::ScrollToPoint(point)),
-                });
+                request_action(
+                    context,
+                    &node,
+                    ActionRequest {
+                        action: Action::ScrollToPoint,
+                        target: node.id(),
+                        data: Some(ActionData::ScrollToPoint(point)),
+                    },
+                );
                 return Ok(true);
             }
             Ok(false)
@@ -1250,10 +1820,337 @@ impl PlatformNode {
     }
 
     pub fn set_current_value(&self, value: f64) -> Result<()> {
-        self.do_action_internal(|_, _| ActionRequest {
-            action: Action::SetValue,
-            target: self.id,
-            data: Some(ActionData::NumericValue(value)),
+        self.do_action_internal(|node| {
+            let (action, data) = match node {
+                Some(node) => node.set_numeric_value_action(value),
+                None => (Action::SetValue, Some(ActionData::NumericValue(value))),
+            };
+            ActionRequest {
+                action,
+                target: self.id,
+                data,
+            }
+        })
+    }
+
+    pub fn n_selected_children(&self) -> Result<i32> {
+        self.resolve_for_selection(|node| {
+            Ok(index_to_i32(
+                selectable_items(&node)
+                    .iter()
+                    .filter(|item| item.is_selected() == Some(true))
+                    .count(),
+            ))
+        })
+    }
+
+    pub fn selected_child(&self, selected_child_index: usize) -> Result<Option<NodeId>> {
+        self.resolve_for_selection(|node| {
+            Ok(selectable_items(&node)
+                .into_iter()
+                .filter(|item| item.is_selected() == Some(true))
+                .nth(selected_child_index)
+                .map(|item| item.id()))
+        })
+    }
+
+    /// Child indices passed to the Selection interface count
+    /// the selectable items of the container, wherever they are nested.
+    pub fn select_child(&self, child_index: usize) -> Result<bool> {
+        self.resolve_for_selection_with_context(|node, context| {
+            let Some(item) = selectable_items(&node).into_iter().nth(child_index) else {
+                return Ok(false);
+            };
+            Ok(request_selection_change(context, &item, true))
+        })
+    }
+
+    pub fn deselect_selected_child(&self, selected_child_index: usize) -> Result<bool> {
+        self.resolve_for_selection_with_context(|node, context| {
+            let Some(item) = selectable_items(&node)
+                .into_iter()
+                .filter(|item| item.is_selected() == Some(true))
+                .nth(selected_child_index)
+            else {
+                return Ok(false);
+            };
+            Ok(request_selection_change(context, &item, false))
+        })
+    }
+
+    pub fn is_child_selected(&self, child_index: usize) -> Result<bool> {
+        self.resolve_for_selection(|node| {
+            Ok(selectable_items(&node)
+                .into_iter()
+                .nth(child_index)
+                .is_some_and(|item| item.is_selected() == Some(true)))
+        })
+    }
+
+    pub fn select_all(&self) -> Result<bool> {
+        self.resolve_for_selection_with_context(|node, context| {
+            if !node.is_multiselectable() {
+                return Ok(false);
+            }
+            let mut result = true;
+            for item in selectable_items(&node)
+                .into_iter()
+                .filter(|item| item.is_selected() != Some(true))
+            {
+                result &= request_selection_change(context, &item, true);
+            }
+            Ok(result)
+        })
+    }
+
+    pub fn clear_selection(&self) -> Result<bool> {
+        self.resolve_for_selection_with_context(|node, context| {
+            let mut result = true;
+            for item in selectable_items(&node)
+                .into_iter()
+                .filter(|item| item.is_selected() == Some(true))
+            {
+                result &= request_selection_change(context, &item, false);
+            }
+            Ok(result)
+        })
+    }
+
+    pub fn deselect_child(&self, child_index: usize) -> Result<bool> {
+        self.resolve_for_selection_with_context(|node, context| {
+            let Some(item) = selectable_items(&node).into_iter().nth(child_index) else {
+                return Ok(false);
+            };
+            Ok(request_selection_change(context, &item, false))
+        })
+    }
+
+    pub fn n_rows(&self) -> Result<i32> {
+        self.resolve_for_table(|node| Ok(index_to_i32(node.table_row_count())))
+    }
+
+    pub fn n_columns(&self) -> Result<i32> {
+        self.resolve_for_table(|node| Ok(index_to_i32(node.table_column_count())))
+    }
+
+    pub fn caption(&self) -> Result<Option<NodeId>> {
+        self.resolve_for_table(|node| Ok(node.table_caption().map(|caption| caption.id())))
+    }
+
+    pub fn accessible_at(&self, row: i32, column: i32) -> Result<Option<NodeId>> {
+        self.resolve_for_table(|node| Ok(table_cell_at(&node, row, column).map(|cell| cell.id())))
+    }
+
+    pub fn index_at(&self, row: i32, column: i32) -> Result<i32> {
+        self.resolve_for_table(|node| {
+            let (Ok(row), Ok(column)) = (usize::try_from(row), usize::try_from(column)) else {
+                return Ok(-1);
+            };
+            let column_count = node.table_column_count();
+            if row >= node.table_row_count() || column >= column_count {
+                return Ok(-1);
+            }
+            Ok(index_to_i32(row * column_count + column))
+        })
+    }
+
+    pub fn row_at_index(&self, index: i32) -> Result<i32> {
+        self.resolve_for_table(|node| {
+            Ok(row_and_column_at_index(&node, index).map_or(-1, |(row, _)| index_to_i32(row)))
+        })
+    }
+
+    pub fn column_at_index(&self, index: i32) -> Result<i32> {
+        self.resolve_for_table(|node| {
+            Ok(
+                row_and_column_at_index(&node, index)
+                    .map_or(-1, |(_, column)| index_to_i32(column)),
+            )
+        })
+    }
+
+    pub fn row_description(&self, row: i32) -> Result<String> {
+        self.resolve_for_table(|node| {
+            let Ok(row) = usize::try_from(row) else {
+                return Ok(String::new());
+            };
+            Ok(node
+                .table_row_headers(row)
+                .first()
+                .and_then(|header| NodeWrapper(header).name())
+                .unwrap_or_default())
+        })
+    }
+
+    pub fn column_description(&self, column: i32) -> Result<String> {
+        self.resolve_for_table(|node| {
+            let Ok(column) = usize::try_from(column) else {
+                return Ok(String::new());
+            };
+            Ok(node
+                .table_column_headers(column)
+                .first()
+                .and_then(|header| NodeWrapper(header).name())
+                .unwrap_or_default())
+        })
+    }
+
+    pub fn row_extent_at(&self, row: i32, column: i32) -> Result<i32> {
+        self.resolve_for_table(|node| {
+            Ok(table_cell_at(&node, row, column)
+                .and_then(|cell| cell.table_cell_position())
+                .map_or(0, |position| index_to_i32(position.row_span)))
+        })
+    }
+
+    pub fn column_extent_at(&self, row: i32, column: i32) -> Result<i32> {
+        self.resolve_for_table(|node| {
+            Ok(table_cell_at(&node, row, column)
+                .and_then(|cell| cell.table_cell_position())
+                .map_or(0, |position| index_to_i32(position.column_span)))
+        })
+    }
+
+    pub fn row_header(&self, row: i32) -> Result<Option<NodeId>> {
+        self.resolve_for_table(|node| {
+            let Ok(row) = usize::try_from(row) else {
+                return Ok(None);
+            };
+            Ok(node
+                .table_row_headers(row)
+                .first()
+                .map(|header| header.id()))
+        })
+    }
+
+    pub fn column_header(&self, column: i32) -> Result<Option<NodeId>> {
+        self.resolve_for_table(|node| {
+            let Ok(column) = usize::try_from(column) else {
+                return Ok(None);
+            };
+            Ok(node
+                .table_column_headers(column)
+                .first()
+                .map(|header| header.id()))
+        })
+    }
+
+    pub fn selected_rows(&self) -> Result<Vec<i32>> {
+        self.resolve_for_table(|node| {
+            Ok(node
+                .table_selected_rows()
+                .into_iter()
+                .map(index_to_i32)
+                .collect())
+        })
+    }
+
+    pub fn is_row_selected(&self, row: i32) -> Result<bool> {
+        self.resolve_for_table(|node| {
+            Ok(usize::try_from(row).is_ok_and(|row| node.table_selected_rows().contains(&row)))
+        })
+    }
+
+    pub fn is_selected(&self, row: i32, column: i32) -> Result<bool> {
+        self.resolve_for_table(|node| {
+            if table_cell_at(&node, row, column)
+                .is_some_and(|cell| cell.is_selected() == Some(true))
+            {
+                return Ok(true);
+            }
+            Ok(usize::try_from(row).is_ok_and(|row| node.table_selected_rows().contains(&row)))
+        })
+    }
+
+    pub fn row_column_extents_at_index(
+        &self,
+        index: i32,
+    ) -> Result<(bool, i32, i32, i32, i32, bool)> {
+        self.resolve_for_table(|node| {
+            let position = row_and_column_at_index(&node, index).and_then(|(row, column)| {
+                node.table_cell_at(row, column)
+                    .and_then(|cell| Some((cell, cell.table_cell_position()?)))
+            });
+            Ok(match position {
+                Some((cell, position)) => (
+                    true,
+                    index_to_i32(position.row_index),
+                    index_to_i32(position.column_index),
+                    index_to_i32(position.row_span),
+                    index_to_i32(position.column_span),
+                    cell.is_selected() == Some(true),
+                ),
+                None => (false, -1, -1, 0, 0, false),
+            })
+        })
+    }
+
+    pub fn cell_position(&self) -> Result<(i32, i32)> {
+        self.resolve_for_table_cell(|node| {
+            Ok(node.table_cell_position().map_or((-1, -1), |position| {
+                (
+                    index_to_i32(position.row_index),
+                    index_to_i32(position.column_index),
+                )
+            }))
+        })
+    }
+
+    pub fn cell_row_span(&self) -> Result<i32> {
+        self.resolve_for_table_cell(|node| {
+            Ok(node
+                .table_cell_position()
+                .map_or(1, |position| index_to_i32(position.row_span)))
+        })
+    }
+
+    pub fn cell_column_span(&self) -> Result<i32> {
+        self.resolve_for_table_cell(|node| {
+            Ok(node
+                .table_cell_position()
+                .map_or(1, |position| index_to_i32(position.column_span)))
+        })
+    }
+
+    pub fn cell_table(&self) -> Result<Option<NodeId>> {
+        self.resolve_for_table_cell(|node| Ok(node.containing_table().map(|table| table.id())))
+    }
+
+    pub fn cell_row_header_cells(&self) -> Result<Vec<NodeId>> {
+        self.resolve_for_table_cell(|node| {
+            let (Some(table), Some(position)) =
+                (node.containing_table(), node.table_cell_position())
+            else {
+                return Ok(Vec::new());
+            };
+            let mut headers = Vec::new();
+            for row in position.row_index..position.row_index + position.row_span {
+                for header in table.table_row_headers(row) {
+                    if header.id() != node.id() && !headers.contains(&header.id()) {
+                        headers.push(header.id());
+                    }
+                }
+            }
+            Ok(headers)
+        })
+    }
+
+    pub fn cell_column_header_cells(&self) -> Result<Vec<NodeId>> {
+        self.resolve_for_table_cell(|node| {
+            let (Some(table), Some(position)) =
+                (node.containing_table(), node.table_cell_position())
+            else {
+                return Ok(Vec::new());
+            };
+            let mut headers = Vec::new();
+            for column in position.column_index..position.column_index + position.column_span {
+                for header in table.table_column_headers(column) {
+                    if header.id() != node.id() && !headers.contains(&header.id()) {
+                        headers.push(header.id());
+                    }
+                }
+            }
+            Ok(headers)
         })
     }
 }
@@ -1342,6 +2239,31 @@ impl PlatformRoot {
         })
     }
 
+    /// Calls the given function for every node that is exposed to
+    /// assistive technologies, in every adapter, in depth-first order.
+    pub fn map_descendants<T, I>(&self, f: impl FnMut(PlatformNode) -> I) -> Result<T>
+    where
+        T: FromIterator<I>,
+    {
+        // Collect the nodes first, so the tree locks are released
+        // before `f` resolves any of them.
+        let descendants = self.resolve_app_context(|context| {
+            let mut descendants = Vec::new();
+            for (adapter_id, context) in &context.adapters {
+                let tree = context.read_tree();
+                let mut stack = vec![tree.state().root()];
+                while let Some(node) = stack.pop() {
+                    descendants.push(PlatformNode::new(context, *adapter_id, node.id()));
+                    let start = stack.len();
+                    stack.extend(node.filtered_children(&filter));
+                    stack[start..].reverse();
+                }
+            }
+            Ok(descendants)
+        })?;
+        Ok(descendants.into_iter().map(f).collect())
+    }
+
     pub fn map_child_ids<T, I>(&self, f: impl Fn((usize, NodeId)) -> I) -> Result<T>
     where
         T: FromIterator<I>,
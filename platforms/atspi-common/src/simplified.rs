@@ -51,6 +51,13 @@ impl Accessible {
         }
     }
 
+    pub fn help_text(&self) -> Result<String> {
+        match self {
+            Self::Node(node) => node.help_text(),
+            Self::Root(_) => Ok("".into()),
+        }
+    }
+
     pub fn state(&self) -> StateSet {
         match self {
             Self::Node(node) => node.state(),
@@ -215,6 +222,13 @@ impl Accessible {
         }
     }
 
+    pub fn scroll_to(&self, scroll_type: ScrollType) -> Result<bool> {
+        match self {
+            Self::Node(node) => node.scroll_to(scroll_type),
+            Self::Root(_) => Err(Error::UnsupportedInterface),
+        }
+    }
+
     pub fn scroll_to_point(&self, coord_type: CoordType, x: i32, y: i32) -> Result<bool> {
         match self {
             Self::Node(node) => node.scroll_to_point(coord_type, x, y),
@@ -556,8 +570,13 @@ impl Event {
                         start_index,
                         length,
                         content,
+                        is_programmatic,
                     } => Self {
-                        kind: "object:text-changed:insert".into(),
+                        kind: if is_programmatic {
+                            "object:text-changed:insert:system".into()
+                        } else {
+                            "object:text-changed:insert".into()
+                        },
                         source,
                         detail1: start_index,
                         detail2: length,
@@ -567,13 +586,25 @@ impl Event {
                         start_index,
                         length,
                         content,
+                        is_programmatic,
                     } => Self {
-                        kind: "object:text-changed:delete".into(),
+                        kind: if is_programmatic {
+                            "object:text-changed:delete:system".into()
+                        } else {
+                            "object:text-changed:delete".into()
+                        },
                         source,
                         detail1: start_index,
                         detail2: length,
                         data: Some(EventData::String(content)),
                     },
+                    ObjectEvent::SelectionChanged => Self {
+                        kind: "object:selection-changed".into(),
+                        source,
+                        detail1: 0,
+                        detail2: 0,
+                        data: None,
+                    },
                     ObjectEvent::TextSelectionChanged => Self {
                         kind: "object:text-selection-changed".into(),
                         source,
@@ -0,0 +1,96 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Action, ActionRequestError, Node, NodeId, Role, Tree, TreeUpdate};
+use atspi_common::Interface;
+
+use super::{adapter, refreshable_adapter};
+use crate::Error;
+
+const ROOT_ID: NodeId = NodeId(0);
+const BUTTON_ID: NodeId = NodeId(1);
+
+fn update(children: Vec<NodeId>) -> TreeUpdate {
+    let mut nodes = vec![(ROOT_ID, {
+        let mut node = Node::new(Role::Window);
+        node.set_children(children.clone());
+        node
+    })];
+    if !children.is_empty() {
+        nodes.push((BUTTON_ID, {
+            let mut node = Node::new(Role::Button);
+            node.add_action(Action::Focus);
+            node
+        }));
+    }
+    TreeUpdate {
+        nodes,
+        tree: Some(Tree::new(ROOT_ID)),
+        focus: ROOT_ID,
+    }
+}
+
+#[test]
+fn supported_action() {
+    let (adapter, recorder) = adapter(update(vec![BUTTON_ID]));
+    let node = adapter.platform_node(BUTTON_ID);
+    assert!(node.grab_focus().unwrap());
+    let requests = recorder.take_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].action, Action::Focus);
+    assert_eq!(requests[0].target, BUTTON_ID);
+    assert!(recorder.take_dropped().is_empty());
+}
+
+#[test]
+fn unsupported_action() {
+    let (adapter, recorder) = adapter(update(vec![BUTTON_ID]));
+    let node = adapter.platform_node(BUTTON_ID);
+    assert!(node.scroll_to(atspi_common::ScrollType::Anywhere).unwrap());
+    assert!(recorder.take_requests().is_empty());
+    let dropped = recorder.take_dropped();
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].0.action, Action::ScrollIntoView);
+    assert_eq!(dropped[0].1, ActionRequestError::UnsupportedAction);
+}
+
+#[test]
+fn removed_target() {
+    let (mut adapter, recorder) = adapter(update(vec![BUTTON_ID]));
+    let node = adapter.platform_node(BUTTON_ID);
+    adapter.update(update(vec![]));
+    assert!(matches!(node.grab_focus(), Err(Error::Defunct)));
+    assert!(recorder.take_requests().is_empty());
+    let dropped = recorder.take_dropped();
+    assert_eq!(dropped.len(), 1);
+    assert_eq!(dropped[0].0.target, BUTTON_ID);
+    assert_eq!(dropped[0].1, ActionRequestError::UnknownTarget);
+}
+
+#[test]
+fn no_refresh_action_without_handler() {
+    let (adapter, recorder) = adapter(update(vec![BUTTON_ID]));
+    let root = adapter.platform_node(ROOT_ID);
+    assert_eq!(root.n_actions().unwrap(), 0);
+    assert!(root.actions().unwrap().is_empty());
+    assert!(!root.interfaces().unwrap().contains(Interface::Action));
+    assert!(!root.do_action(0).unwrap());
+    assert!(recorder.take_refreshes().is_empty());
+}
+
+#[test]
+fn refresh_action_with_handler() {
+    let (adapter, recorder) = refreshable_adapter(update(vec![BUTTON_ID]));
+    let root = adapter.platform_node(ROOT_ID);
+    assert_eq!(root.n_actions().unwrap(), 1);
+    assert_eq!(root.action_name(0).unwrap(), "refresh");
+    assert!(root.interfaces().unwrap().contains(Interface::Action));
+    assert!(root.do_action(0).unwrap());
+    assert_eq!(recorder.take_refreshes(), vec![adapter.id()]);
+    assert!(recorder.take_requests().is_empty());
+
+    let button = adapter.platform_node(BUTTON_ID);
+    assert_eq!(button.n_actions().unwrap(), 0);
+}
@@ -0,0 +1,69 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Node, NodeId, Role, Tree, TreeUpdate};
+use std::sync::{Arc, RwLock};
+
+use super::Recorder;
+use crate::{Adapter, AppContext, NodeIdOrRoot, ObjectEvent, WindowBounds};
+
+fn window_update(id: NodeId) -> TreeUpdate {
+    TreeUpdate {
+        nodes: vec![(id, Node::new(Role::Window))],
+        tree: Some(Tree::new(id)),
+        focus: id,
+    }
+}
+
+/// Activates an adapter with the given ID, returning it along with
+/// the windows it reported as added to the application root.
+fn activate(
+    app_context: &Arc<RwLock<AppContext>>,
+    id: usize,
+    window: NodeId,
+) -> (Adapter, Vec<(usize, NodeId)>) {
+    let recorder = Recorder::default();
+    let adapter = Adapter::with_id(
+        id,
+        app_context,
+        recorder.clone(),
+        window_update(window),
+        false,
+        WindowBounds::default(),
+        recorder.clone(),
+    );
+    let added = recorder
+        .take_object_events()
+        .into_iter()
+        .filter(|(target, _)| *target == NodeIdOrRoot::Root)
+        .filter_map(|(_, event)| match event {
+            ObjectEvent::ChildAdded(index, child) => Some((index, child)),
+            _ => None,
+        })
+        .collect();
+    (adapter, added)
+}
+
+#[test]
+fn deferred_activation_keeps_creation_order() {
+    let app_context = AppContext::new(None);
+    // The adapter created second is activated first.
+    let (_second, added) = activate(&app_context, 2, NodeId(20));
+    assert_eq!(added, [(0, NodeId(20))]);
+    let (_first, added) = activate(&app_context, 1, NodeId(10));
+    assert_eq!(added, [(0, NodeId(10))]);
+    let (_third, added) = activate(&app_context, 3, NodeId(30));
+    assert_eq!(added, [(2, NodeId(30))]);
+    let app_context = app_context.read().unwrap();
+    let ids = app_context
+        .adapters
+        .iter()
+        .map(|(id, _)| *id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, [1, 2, 3]);
+    assert_eq!(app_context.adapter_index(1), Ok(0));
+    assert_eq!(app_context.adapter_index(2), Ok(1));
+    assert_eq!(app_context.adapter_index(3), Ok(2));
+}
@@ -0,0 +1,72 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Node, NodeId, Role, Tree, TreeUpdate};
+
+use super::adapter;
+use crate::{NodeIdOrRoot, ObjectEvent};
+
+const LIST_ID: NodeId = NodeId(0);
+
+fn list_update(children: &[u64]) -> TreeUpdate {
+    let mut nodes = vec![(LIST_ID, {
+        let mut node = Node::new(Role::List);
+        node.set_children(children.iter().copied().map(NodeId).collect::<Vec<_>>());
+        node
+    })];
+    for id in children {
+        nodes.push((NodeId(*id), Node::new(Role::ListItem)));
+    }
+    TreeUpdate {
+        nodes,
+        tree: Some(Tree::new(LIST_ID)),
+        focus: LIST_ID,
+    }
+}
+
+/// Returns the added and removed children reported for the list.
+fn child_events(events: Vec<(NodeIdOrRoot, ObjectEvent)>) -> Vec<(Option<usize>, NodeId)> {
+    events
+        .into_iter()
+        .filter(|(target, _)| *target == NodeIdOrRoot::Node(LIST_ID))
+        .filter_map(|(_, event)| match event {
+            ObjectEvent::ChildAdded(index, child) => Some((Some(index), child)),
+            ObjectEvent::ChildRemoved(child) => Some((None, child)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn move_to_front() {
+    let (mut adapter, recorder) = adapter(list_update(&[1, 2, 3, 4, 5]));
+    adapter.update(list_update(&[5, 1, 2, 3, 4]));
+    assert_eq!(
+        child_events(recorder.take_object_events()),
+        vec![(None, NodeId(5)), (Some(0), NodeId(5))]
+    );
+}
+
+#[test]
+fn swap() {
+    let (mut adapter, recorder) = adapter(list_update(&[1, 2, 3, 4]));
+    adapter.update(list_update(&[1, 3, 2, 4]));
+    let events = child_events(recorder.take_object_events());
+    assert_eq!(events.len(), 2);
+    assert!(
+        events == vec![(None, NodeId(3)), (Some(1), NodeId(3))]
+            || events == vec![(None, NodeId(2)), (Some(2), NodeId(2))]
+    );
+}
+
+#[test]
+fn added_and_removed() {
+    let (mut adapter, recorder) = adapter(list_update(&[1, 2, 3]));
+    adapter.update(list_update(&[1, 3, 4]));
+    assert_eq!(
+        child_events(recorder.take_object_events()),
+        vec![(Some(2), NodeId(4)), (None, NodeId(2))]
+    );
+}
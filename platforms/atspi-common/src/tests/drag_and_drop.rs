@@ -0,0 +1,78 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Action, Node, NodeId, Role, Tree, TreeUpdate};
+
+use super::adapter;
+
+const ROOT_ID: NodeId = NodeId(0);
+const ITEM_ID: NodeId = NodeId(1);
+const TARGET_ID: NodeId = NodeId(2);
+
+fn update(is_grabbed: bool) -> TreeUpdate {
+    let root = {
+        let mut node = Node::new(Role::Window);
+        node.set_children(vec![ITEM_ID, TARGET_ID]);
+        node
+    };
+    let item = {
+        let mut node = Node::new(Role::ListItem);
+        node.add_action(Action::Drag);
+        if is_grabbed {
+            node.set_grabbed();
+        }
+        node
+    };
+    let target = {
+        let mut node = Node::new(Role::List);
+        node.add_action(Action::Drop);
+        node.set_drop_target();
+        node
+    };
+    TreeUpdate {
+        nodes: vec![(ROOT_ID, root), (ITEM_ID, item), (TARGET_ID, target)],
+        tree: Some(Tree::new(ROOT_ID)),
+        focus: ROOT_ID,
+    }
+}
+
+#[test]
+fn actions() {
+    let (adapter, recorder) = adapter(update(false));
+    let item = adapter.platform_node(ITEM_ID);
+    assert_eq!(item.n_actions().unwrap(), 1);
+    assert_eq!(item.action_name(0).unwrap(), "drag");
+    assert!(item.do_action(0).unwrap());
+    let target = adapter.platform_node(TARGET_ID);
+    assert_eq!(target.n_actions().unwrap(), 1);
+    assert_eq!(target.action_name(0).unwrap(), "drop");
+    assert!(target.do_action(0).unwrap());
+    let requests = recorder.take_requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].action, Action::Drag);
+    assert_eq!(requests[0].target, ITEM_ID);
+    assert_eq!(requests[1].action, Action::Drop);
+    assert_eq!(requests[1].target, TARGET_ID);
+}
+
+#[test]
+fn attributes() {
+    let (mut adapter, _) = adapter(update(false));
+    let item = adapter.platform_node(ITEM_ID);
+    let attributes = item.attributes().unwrap();
+    assert_eq!(attributes.get("grabbed").map(String::as_str), Some("false"));
+    assert!(!attributes.contains_key("droptarget"));
+    let target = adapter.platform_node(TARGET_ID);
+    let attributes = target.attributes().unwrap();
+    assert_eq!(
+        attributes.get("droptarget").map(String::as_str),
+        Some("true")
+    );
+    assert!(!attributes.contains_key("grabbed"));
+
+    adapter.update(update(true));
+    let attributes = item.attributes().unwrap();
+    assert_eq!(attributes.get("grabbed").map(String::as_str), Some("true"));
+}
@@ -0,0 +1,76 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{
+    Action, ActionData, Node, NodeId, Role, TextPosition, TextSelection, Tree, TreeUpdate,
+};
+
+use super::adapter;
+
+const ROOT_ID: NodeId = NodeId(0);
+const TEXT_INPUT_ID: NodeId = NodeId(1);
+const TEXT_RUN_ID: NodeId = NodeId(2);
+
+fn text_input_update(actions: &[Action]) -> TreeUpdate {
+    TreeUpdate {
+        nodes: vec![
+            (ROOT_ID, {
+                let mut node = Node::new(Role::Window);
+                node.set_children(vec![TEXT_INPUT_ID]);
+                node
+            }),
+            (TEXT_INPUT_ID, {
+                let mut node = Node::new(Role::TextInput);
+                node.set_children(vec![TEXT_RUN_ID]);
+                for action in actions {
+                    node.add_action(*action);
+                }
+                node
+            }),
+            (TEXT_RUN_ID, {
+                let mut node = Node::new(Role::TextRun);
+                node.set_value("hello");
+                node.set_character_lengths([1, 1, 1, 1, 1]);
+                node
+            }),
+        ],
+        tree: Some(Tree::new(ROOT_ID)),
+        focus: TEXT_INPUT_ID,
+    }
+}
+
+#[test]
+fn requires_set_text_selection() {
+    let (adapter, _) = adapter(text_input_update(&[Action::ReplaceSelectedText]));
+    let node = adapter.platform_node(TEXT_INPUT_ID);
+    assert!(!node.supports_editable_text().unwrap());
+}
+
+#[test]
+fn insert_text() {
+    let (adapter, recorder) = adapter(text_input_update(&[
+        Action::SetTextSelection,
+        Action::ReplaceSelectedText,
+    ]));
+    let node = adapter.platform_node(TEXT_INPUT_ID);
+    assert!(node.supports_editable_text().unwrap());
+    assert!(node.insert_text(5, " world", -1).unwrap());
+    let requests = recorder.take_requests();
+    assert_eq!(requests.len(), 2);
+    let end = TextPosition {
+        node: TEXT_RUN_ID,
+        character_index: 5,
+    };
+    assert_eq!(requests[0].action, Action::SetTextSelection);
+    assert_eq!(
+        requests[0].data,
+        Some(ActionData::SetTextSelection(TextSelection {
+            anchor: end,
+            focus: end,
+        }))
+    );
+    assert_eq!(requests[1].action, Action::ReplaceSelectedText);
+    assert_eq!(requests[1].data, Some(ActionData::Value(" world".into())));
+}
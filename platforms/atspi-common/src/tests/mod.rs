@@ -0,0 +1,115 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{ActionHandler, ActionRequest, ActionRequestError, NodeId, TreeUpdate};
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    Adapter, AdapterCallback, AppContext, Event, InterfaceSet, NodeIdOrRoot, ObjectEvent,
+    WindowBounds,
+};
+
+mod actions;
+mod app_context;
+mod children;
+mod drag_and_drop;
+mod editable_text;
+mod name;
+mod selection;
+
+/// Records the events emitted by an adapter, the refreshes it requests
+/// and the action requests passed to or dropped by its action handler.
+#[derive(Clone, Default)]
+pub(crate) struct Recorder {
+    can_refresh: bool,
+    events: Arc<Mutex<Vec<Event>>>,
+    refreshes: Arc<Mutex<Vec<usize>>>,
+    requests: Arc<Mutex<Vec<ActionRequest>>>,
+    dropped: Arc<Mutex<Vec<(ActionRequest, ActionRequestError)>>>,
+}
+
+impl Recorder {
+    /// Returns the object events emitted since the last call,
+    /// along with their targets.
+    pub(crate) fn take_object_events(&self) -> Vec<(NodeIdOrRoot, ObjectEvent)> {
+        self.events
+            .lock()
+            .unwrap()
+            .drain(..)
+            .filter_map(|event| match event {
+                Event::Object { target, event } => Some((target, event)),
+                Event::Window { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Returns the IDs of the adapters whose refresh was requested
+    /// since the last call.
+    pub(crate) fn take_refreshes(&self) -> Vec<usize> {
+        self.refreshes.lock().unwrap().drain(..).collect()
+    }
+
+    pub(crate) fn take_requests(&self) -> Vec<ActionRequest> {
+        self.requests.lock().unwrap().drain(..).collect()
+    }
+
+    pub(crate) fn take_dropped(&self) -> Vec<(ActionRequest, ActionRequestError)> {
+        self.dropped.lock().unwrap().drain(..).collect()
+    }
+}
+
+impl AdapterCallback for Recorder {
+    fn register_interfaces(&self, _adapter: &Adapter, _id: NodeId, _interfaces: InterfaceSet) {}
+
+    fn unregister_interfaces(&self, _adapter: &Adapter, _id: NodeId, _interfaces: InterfaceSet) {}
+
+    fn emit_event(&self, _adapter: &Adapter, event: Event) {
+        self.events.lock().unwrap().push(event);
+    }
+
+    fn supports_refresh(&self) -> bool {
+        self.can_refresh
+    }
+
+    fn request_refresh(&self, adapter_id: usize) {
+        self.refreshes.lock().unwrap().push(adapter_id);
+    }
+}
+
+impl ActionHandler for Recorder {
+    fn do_action(&mut self, request: ActionRequest) {
+        self.requests.lock().unwrap().push(request);
+    }
+
+    fn action_request_dropped(&mut self, request: &ActionRequest, error: ActionRequestError) {
+        self.dropped.lock().unwrap().push((request.clone(), error));
+    }
+}
+
+pub(crate) fn adapter(initial_state: TreeUpdate) -> (Adapter, Recorder) {
+    adapter_with_recorder(initial_state, Recorder::default())
+}
+
+/// Creates an adapter whose callback handles refresh requests.
+pub(crate) fn refreshable_adapter(initial_state: TreeUpdate) -> (Adapter, Recorder) {
+    let recorder = Recorder {
+        can_refresh: true,
+        ..Default::default()
+    };
+    adapter_with_recorder(initial_state, recorder)
+}
+
+fn adapter_with_recorder(initial_state: TreeUpdate, recorder: Recorder) -> (Adapter, Recorder) {
+    let adapter = Adapter::new(
+        &AppContext::new(None),
+        recorder.clone(),
+        initial_state,
+        true,
+        WindowBounds::default(),
+        recorder.clone(),
+    );
+    recorder.take_object_events();
+    (adapter, recorder)
+}
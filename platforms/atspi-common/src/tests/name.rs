@@ -0,0 +1,52 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Node, NodeId, Role, Tree, TreeUpdate};
+
+use super::adapter;
+
+const ROOT_ID: NodeId = NodeId(0);
+const TEXT_INPUT_ID: NodeId = NodeId(1);
+
+fn text_input_update(label: Option<&str>) -> TreeUpdate {
+    TreeUpdate {
+        nodes: vec![
+            (ROOT_ID, {
+                let mut node = Node::new(Role::Window);
+                node.set_children(vec![TEXT_INPUT_ID]);
+                node
+            }),
+            (TEXT_INPUT_ID, {
+                let mut node = Node::new(Role::TextInput);
+                node.set_placeholder("Search");
+                if let Some(label) = label {
+                    node.set_label(label);
+                }
+                node
+            }),
+        ],
+        tree: Some(Tree::new(ROOT_ID)),
+        focus: TEXT_INPUT_ID,
+    }
+}
+
+#[test]
+fn placeholder_as_name() {
+    let (adapter, _) = adapter(text_input_update(None));
+    let node = adapter.platform_node(TEXT_INPUT_ID);
+    assert_eq!(node.name().unwrap(), "Search");
+    assert!(!node.attributes().unwrap().contains_key("placeholder-text"));
+}
+
+#[test]
+fn placeholder_with_label() {
+    let (adapter, _) = adapter(text_input_update(Some("Query")));
+    let node = adapter.platform_node(TEXT_INPUT_ID);
+    assert_eq!(node.name().unwrap(), "Query");
+    assert_eq!(
+        node.attributes().unwrap().get("placeholder-text"),
+        Some(&"Search".to_string())
+    );
+}
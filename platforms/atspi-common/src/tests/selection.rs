@@ -0,0 +1,88 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Action, Node, NodeId, Role, Tree, TreeUpdate};
+
+use super::adapter;
+use crate::{NodeIdOrRoot, ObjectEvent};
+
+const ROOT_ID: NodeId = NodeId(0);
+const TREE_ID: NodeId = NodeId(1);
+const PARENT_ITEM_ID: NodeId = NodeId(2);
+const GROUP_ID: NodeId = NodeId(3);
+const NESTED_ITEM_1_ID: NodeId = NodeId(4);
+const NESTED_ITEM_2_ID: NodeId = NodeId(5);
+
+fn tree_item(selected: bool) -> Node {
+    let mut node = Node::new(Role::TreeItem);
+    node.set_selected(selected);
+    node.add_action(Action::Select);
+    node
+}
+
+/// A tree whose second and third items are nested in a group
+/// under the first one.
+fn update(nested_item_2_selected: bool) -> TreeUpdate {
+    TreeUpdate {
+        nodes: vec![
+            (ROOT_ID, {
+                let mut node = Node::new(Role::Window);
+                node.set_children(vec![TREE_ID]);
+                node
+            }),
+            (TREE_ID, {
+                let mut node = Node::new(Role::Tree);
+                node.set_children(vec![PARENT_ITEM_ID]);
+                node
+            }),
+            (PARENT_ITEM_ID, {
+                let mut node = tree_item(false);
+                node.set_expanded(true);
+                node.set_children(vec![GROUP_ID]);
+                node
+            }),
+            (GROUP_ID, {
+                let mut node = Node::new(Role::Group);
+                node.set_children(vec![NESTED_ITEM_1_ID, NESTED_ITEM_2_ID]);
+                node
+            }),
+            (NESTED_ITEM_1_ID, tree_item(false)),
+            (NESTED_ITEM_2_ID, tree_item(nested_item_2_selected)),
+        ],
+        tree: Some(Tree::new(ROOT_ID)),
+        focus: ROOT_ID,
+    }
+}
+
+#[test]
+fn nested_items() {
+    let (adapter, recorder) = adapter(update(true));
+    let tree = adapter.platform_node(TREE_ID);
+    assert_eq!(tree.n_selected_children().unwrap(), 1);
+    assert_eq!(tree.selected_child(0).unwrap(), Some(NESTED_ITEM_2_ID));
+    assert!(tree.is_child_selected(2).unwrap());
+    assert!(!tree.is_child_selected(1).unwrap());
+
+    assert!(tree.select_child(1).unwrap());
+    let requests = recorder.take_requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].action, Action::Select);
+    assert_eq!(requests[0].target, NESTED_ITEM_1_ID);
+}
+
+#[test]
+fn selection_changed_on_container() {
+    let (mut adapter, recorder) = adapter(update(false));
+    adapter.update(update(true));
+    let events = recorder.take_object_events();
+    assert!(events
+        .iter()
+        .any(|(target, event)| *target == NodeIdOrRoot::Node(TREE_ID)
+            && matches!(event, ObjectEvent::SelectionChanged)));
+    assert!(!events
+        .iter()
+        .any(|(target, event)| *target == NodeIdOrRoot::Node(GROUP_ID)
+            && matches!(event, ObjectEvent::SelectionChanged)));
+}
@@ -3,9 +3,10 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-use accesskit::{Point, Rect};
-use accesskit_consumer::{Node, TextPosition, TextRange};
+use accesskit::{Point, Rect, TextDecoration, VerticalOffset};
+use accesskit_consumer::{Node, TextAttributeValue, TextPosition, TextRange};
 use atspi_common::{CoordType, Granularity};
+use std::collections::HashMap;
 
 use crate::Error;
 
@@ -56,6 +57,43 @@ impl WindowBounds {
     }
 }
 
+/// Converts a count or index to the `i32` used by AT-SPI,
+/// saturating if it's too large.
+pub(crate) fn index_to_i32(index: usize) -> i32 {
+    i32::try_from(index).unwrap_or(i32::MAX)
+}
+
+pub(crate) fn table_cell_at<'a>(table: &Node<'a>, row: i32, column: i32) -> Option<Node<'a>> {
+    let row = usize::try_from(row).ok()?;
+    let column = usize::try_from(column).ok()?;
+    table.table_cell_at(row, column)
+}
+
+/// Maps an AT-SPI child index, which counts cells row by row,
+/// to a row and column.
+pub(crate) fn row_and_column_at_index(table: &Node, index: i32) -> Option<(usize, usize)> {
+    let index = usize::try_from(index).ok()?;
+    let column_count = table.table_column_count();
+    if column_count == 0 {
+        return None;
+    }
+    let (row, column) = (index / column_count, index % column_count);
+    (row < table.table_row_count()).then_some((row, column))
+}
+
+/// Returns the range of text that a link covers within the nearest
+/// ancestor that exposes the Hypertext interface.
+pub(crate) fn hyperlink_range<'a>(link: &Node<'a>) -> Option<TextRange<'a>> {
+    let mut current = link.parent();
+    while let Some(node) = current {
+        if node.supports_text_ranges() {
+            return node.text_range_of_descendant(link);
+        }
+        current = node.parent();
+    }
+    None
+}
+
 pub(crate) fn text_position_from_offset<'a>(
     node: &'a Node,
     offset: i32,
@@ -120,3 +158,88 @@ pub(crate) fn text_range_bounds_from_offsets(
         .into_iter()
         .reduce(|rect1, rect2| rect1.union(rect2))
 }
+
+type NumericAttribute = (&'static str, fn(&Node) -> Option<f64>);
+
+/// Returns the AT-SPI text attributes that apply to the whole text of
+/// `node`. Formatting that varies within the text is reported per format
+/// run by [`format_attributes`].
+pub(crate) fn text_attributes(node: &Node) -> HashMap<String, String> {
+    // AT-SPI only has physical margins, so leading and trailing margins
+    // are assumed to be on the left and right respectively.
+    const ATTRIBUTES: &[NumericAttribute] = &[
+        ("letter-spacing", |node| node.letter_spacing()),
+        ("line-height", |node| node.line_height()),
+        ("indent", |node| node.text_indent()),
+        ("pixels-above-lines", |node| node.margin_top()),
+        ("pixels-below-lines", |node| node.margin_bottom()),
+        ("left-margin", |node| node.margin_leading()),
+        ("right-margin", |node| node.margin_trailing()),
+    ];
+    let range = node.document_range();
+    let mut result = HashMap::new();
+    for (name, f) in ATTRIBUTES {
+        if let TextAttributeValue::Single(Some(value)) = range.attribute(f) {
+            result.insert(name.to_string(), format!("{}", value.round()));
+        }
+    }
+    result
+}
+
+fn color_attribute(color: u32) -> String {
+    format!(
+        "{},{},{}",
+        color >> 24,
+        (color >> 16) & 0xff,
+        (color >> 8) & 0xff
+    )
+}
+
+/// Returns the AT-SPI text attributes of a format run, whose text runs all
+/// have the same formatting.
+pub(crate) fn format_attributes(run: &Node) -> HashMap<String, String> {
+    let mut result = HashMap::new();
+    if let Some(family) = run.font_family() {
+        result.insert("family-name".into(), family.into());
+    }
+    if let Some(language) = run.language() {
+        result.insert("language".into(), language.into());
+    }
+    if let Some(size) = run.font_size() {
+        // AT-SPI expects points, assuming 96 pixels per inch.
+        result.insert("size".into(), format!("{}", (size * 0.75).round()));
+    }
+    if let Some(weight) = run.font_weight() {
+        result.insert("weight".into(), format!("{}", weight.round()));
+    } else if run.is_bold() {
+        result.insert("weight".into(), "700".into());
+    }
+    if run.is_italic() {
+        result.insert("style".into(), "italic".into());
+    }
+    if let Some(color) = run.foreground_color() {
+        result.insert("fg-color".into(), color_attribute(color));
+    }
+    if let Some(color) = run.background_color() {
+        result.insert("bg-color".into(), color_attribute(color));
+    }
+    if let Some(decoration) = run.underline() {
+        let value = match decoration {
+            TextDecoration::Double => "double",
+            TextDecoration::Wavy => "error",
+            _ => "single",
+        };
+        result.insert("underline".into(), value.into());
+    }
+    if run.strikethrough().is_some() {
+        result.insert("strikethrough".into(), "true".into());
+    }
+    if let Some(offset) = run.vertical_offset() {
+        let value = match offset {
+            VerticalOffset::Subscript => "sub",
+            VerticalOffset::Superscript => "super",
+        };
+        result.insert("text-position".into(), value.into());
+    }
+    result
+}
@@ -0,0 +1,282 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{
+    ActionHandler, ActionRequest, ActivationHandler, AnnouncementKind, Node as NodeProvider,
+    NodeId, Role, Tree as TreeData, TreeUpdate,
+};
+use accesskit_consumer::{FilterResult, Tree};
+use objc2::rc::{Id, WeakId};
+use objc2_foundation::{MainThreadMarker, NSArray, NSObject};
+use objc2_ui_kit::UIView;
+use std::{ffi::c_void, rc::Rc};
+
+use crate::{
+    context::{ActionHandlerNoMut, ActionHandlerWrapper, Context},
+    event::{focus_event, EventGenerator, QueuedEvent, QueuedEvents},
+    filters::filter,
+};
+
+const PLACEHOLDER_ROOT_ID: NodeId = NodeId(0);
+
+enum State {
+    Inactive {
+        view: WeakId<UIView>,
+        is_view_focused: bool,
+        action_handler: Rc<dyn ActionHandlerNoMut>,
+        mtm: MainThreadMarker,
+    },
+    Placeholder {
+        placeholder_context: Rc<Context>,
+        is_view_focused: bool,
+        action_handler: Rc<dyn ActionHandlerNoMut>,
+    },
+    Active(Rc<Context>),
+}
+
+struct PlaceholderActionHandler;
+
+impl ActionHandler for PlaceholderActionHandler {
+    fn do_action(&mut self, _request: ActionRequest) {}
+}
+
+/// An adapter that exposes the tree through the `UIAccessibility`
+/// informal protocol, as a hierarchy of `UIAccessibilityElement`
+/// objects contained in a `UIView`.
+///
+/// The view must return `NO` from `isAccessibilityElement` and
+/// the result of [`Adapter::accessibility_elements`] from
+/// `accessibilityElements`.
+///
+/// # Example
+///
+/// Everything happens on the main thread. The activation handler is called
+/// from `accessibilityElements` while the adapter is in use, so it must not
+/// call back into the adapter. Likewise, queued events must be raised after
+/// releasing any borrow of the adapter.
+///
+/// ```no_run
+/// use accesskit::{ActivationHandler, TreeUpdate};
+/// use accesskit_ios::{Adapter, NSArray, NSObject};
+/// use std::cell::RefCell;
+///
+/// /// Called from the view's `accessibilityElements` implementation.
+/// fn accessibility_elements(
+///     adapter: &RefCell<Adapter>,
+///     activation_handler: &mut impl ActivationHandler,
+/// ) -> *mut NSArray<NSObject> {
+///     adapter
+///         .borrow_mut()
+///         .accessibility_elements(activation_handler)
+/// }
+///
+/// /// Called on the main thread whenever the UI changes.
+/// fn update(adapter: &RefCell<Adapter>, build_update: impl FnOnce() -> TreeUpdate) {
+///     let events = adapter.borrow_mut().update_if_active(build_update);
+///     if let Some(events) = events {
+///         events.raise();
+///     }
+/// }
+/// ```
+pub struct Adapter {
+    state: State,
+}
+
+impl Adapter {
+    /// Create a new iOS adapter. This function must be called on
+    /// the main thread.
+    ///
+    /// The action handler will always be called on the main thread.
+    ///
+    /// # Safety
+    ///
+    /// `view` must be a valid, unreleased pointer to a `UIView`.
+    pub unsafe fn new(
+        view: *mut c_void,
+        is_view_focused: bool,
+        action_handler: impl 'static + ActionHandler,
+    ) -> Self {
+        let mtm = MainThreadMarker::new().unwrap();
+        let view = unsafe { Id::retain(view as *mut UIView) }.unwrap();
+        let view = WeakId::from_id(&view);
+        let state = State::Inactive {
+            view,
+            is_view_focused,
+            action_handler: Rc::new(ActionHandlerWrapper::new(action_handler)),
+            mtm,
+        };
+        Self { state }
+    }
+
+    /// If and only if the tree has been initialized, call the provided function
+    /// and apply the resulting update. Note: If the caller's implementation of
+    /// [`ActivationHandler::request_initial_tree`] initially returned `None`,
+    /// the [`TreeUpdate`] returned by the provided function must contain
+    /// a full tree.
+    ///
+    /// If a [`QueuedEvents`] instance is returned, the caller must call
+    /// [`QueuedEvents::raise`] on it.
+    pub fn update_if_active(
+        &mut self,
+        update_factory: impl FnOnce() -> TreeUpdate,
+    ) -> Option<QueuedEvents> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("accesskit_update_if_active").entered();
+        match &self.state {
+            State::Inactive { .. } => None,
+            State::Placeholder {
+                placeholder_context,
+                is_view_focused,
+                action_handler,
+            } => {
+                let tree = Tree::new(update_factory(), *is_view_focused);
+                let context = Context::new(
+                    placeholder_context.view.clone(),
+                    tree,
+                    Rc::clone(action_handler),
+                    placeholder_context.mtm,
+                );
+                let result = context
+                    .tree
+                    .borrow()
+                    .state()
+                    .focus_id()
+                    .map(|id| QueuedEvents::new(Rc::clone(&context), vec![focus_event(id)]));
+                self.state = State::Active(context);
+                result
+            }
+            State::Active(context) => {
+                let mut event_generator = EventGenerator::new();
+                let mut tree = context.tree.borrow_mut();
+                tree.update_and_process_changes(update_factory(), &mut event_generator);
+                Some(event_generator.into_result(Rc::clone(context)))
+            }
+        }
+    }
+
+    /// Update the tree state based on whether the view is focused.
+    ///
+    /// If a [`QueuedEvents`] instance is returned, the caller must call
+    /// [`QueuedEvents::raise`] on it.
+    pub fn update_view_focus_state(&mut self, is_focused: bool) -> Option<QueuedEvents> {
+        match &mut self.state {
+            State::Inactive {
+                is_view_focused, ..
+            } => {
+                *is_view_focused = is_focused;
+                None
+            }
+            State::Placeholder {
+                is_view_focused, ..
+            } => {
+                *is_view_focused = is_focused;
+                None
+            }
+            State::Active(context) => {
+                let mut event_generator = EventGenerator::new();
+                let mut tree = context.tree.borrow_mut();
+                tree.update_host_focus_state_and_process_changes(is_focused, &mut event_generator);
+                Some(event_generator.into_result(Rc::clone(context)))
+            }
+        }
+    }
+
+    /// Ask VoiceOver to speak the given text, without requiring
+    /// a live region in the tree. This posts
+    /// `UIAccessibilityAnnouncementNotification`.
+    ///
+    /// VoiceOver speaks announcements immediately, and queuing them
+    /// isn't yet supported, so `kind` is currently ignored on this platform.
+    ///
+    /// If the tree isn't active, there is no assistive technology to notify,
+    /// so this returns `None`.
+    ///
+    /// If a [`QueuedEvents`] instance is returned, the caller must call
+    /// [`QueuedEvents::raise`] on it.
+    pub fn announce(&mut self, text: &str, _kind: AnnouncementKind) -> Option<QueuedEvents> {
+        match &self.state {
+            State::Inactive { .. } | State::Placeholder { .. } => None,
+            State::Active(context) => Some(QueuedEvents::new(
+                context.clone(),
+                vec![QueuedEvent::Announcement(text.into())],
+            )),
+        }
+    }
+
+    fn get_or_init_context<H: ActivationHandler + ?Sized>(
+        &mut self,
+        activation_handler: &mut H,
+    ) -> Rc<Context> {
+        match &self.state {
+            State::Inactive {
+                view,
+                is_view_focused,
+                action_handler,
+                mtm,
+            } => match activation_handler.request_initial_tree() {
+                Some(initial_state) => {
+                    let tree = Tree::new(initial_state, *is_view_focused);
+                    let context = Context::new(view.clone(), tree, Rc::clone(action_handler), *mtm);
+                    let result = Rc::clone(&context);
+                    self.state = State::Active(context);
+                    result
+                }
+                None => {
+                    let placeholder_update = TreeUpdate {
+                        nodes: vec![(PLACEHOLDER_ROOT_ID, NodeProvider::new(Role::Window))],
+                        tree: Some(TreeData::new(PLACEHOLDER_ROOT_ID)),
+                        focus: PLACEHOLDER_ROOT_ID,
+                    };
+                    let placeholder_tree = Tree::new(placeholder_update, false);
+                    let placeholder_context = Context::new(
+                        view.clone(),
+                        placeholder_tree,
+                        Rc::new(ActionHandlerWrapper::new(PlaceholderActionHandler {})),
+                        *mtm,
+                    );
+                    let result = Rc::clone(&placeholder_context);
+                    self.state = State::Placeholder {
+                        placeholder_context,
+                        is_view_focused: *is_view_focused,
+                        action_handler: Rc::clone(action_handler),
+                    };
+                    result
+                }
+            },
+            State::Placeholder {
+                placeholder_context,
+                ..
+            } => Rc::clone(placeholder_context),
+            State::Active(context) => Rc::clone(context),
+        }
+    }
+
+    /// Returns the elements that the view should return from
+    /// `accessibilityElements`, initializing the tree if necessary.
+    pub fn accessibility_elements<H: ActivationHandler + ?Sized>(
+        &mut self,
+        activation_handler: &mut H,
+    ) -> *mut NSArray<NSObject> {
+        let context = self.get_or_init_context(activation_handler);
+        let tree = context.tree.borrow();
+        let state = tree.state();
+        let node = state.root();
+        let platform_nodes = if filter(&node) == FilterResult::Include {
+            vec![Id::into_super(Id::into_super(Id::into_super(
+                context.get_or_create_platform_node(node.id()),
+            )))]
+        } else {
+            node.filtered_children(filter)
+                .map(|node| {
+                    Id::into_super(Id::into_super(Id::into_super(
+                        context.get_or_create_platform_node(node.id()),
+                    )))
+                })
+                .collect::<Vec<Id<NSObject>>>()
+        };
+        let array = NSArray::from_vec(platform_nodes);
+        Id::autorelease_return(array)
+    }
+}
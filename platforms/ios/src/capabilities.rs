@@ -0,0 +1,251 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Action, AdapterCapabilities, Role};
+
+/// The parts of the AccessKit schema that are exposed through
+/// the UIAccessibility informal protocol.
+///
+/// UIKit describes elements with a small set of traits rather than roles,
+/// so most roles are exposed as plain elements.
+///
+/// This must be kept in sync with the mappings in the `node` module.
+/// A unit test checks that each listed property and action is handled.
+pub const CAPABILITIES: AdapterCapabilities = AdapterCapabilities {
+    api: "UIAccessibility",
+    unmapped_roles: &[
+        Role::Unknown,
+        Role::TextRun,
+        Role::Cell,
+        Role::Row,
+        Role::ListItem,
+        Role::ListMarker,
+        Role::TreeItem,
+        Role::ListBoxOption,
+        Role::MenuListOption,
+        Role::Paragraph,
+        Role::GenericContainer,
+        Role::TextInput,
+        Role::Pane,
+        Role::RowHeader,
+        Role::ColumnHeader,
+        Role::RowGroup,
+        Role::List,
+        Role::Table,
+        Role::LayoutTableCell,
+        Role::LayoutTableRow,
+        Role::LayoutTable,
+        Role::Menu,
+        Role::MultilineTextInput,
+        Role::DateInput,
+        Role::DateTimeInput,
+        Role::WeekInput,
+        Role::MonthInput,
+        Role::TimeInput,
+        Role::EmailInput,
+        Role::NumberInput,
+        Role::PasswordInput,
+        Role::PhoneNumberInput,
+        Role::UrlInput,
+        Role::Abbr,
+        Role::Alert,
+        Role::AlertDialog,
+        Role::Application,
+        Role::Article,
+        Role::Audio,
+        Role::Banner,
+        Role::Blockquote,
+        Role::Caption,
+        Role::Caret,
+        Role::Code,
+        Role::ColorWell,
+        Role::ComboBox,
+        Role::EditableComboBox,
+        Role::Complementary,
+        Role::Comment,
+        Role::ContentDeletion,
+        Role::ContentInsertion,
+        Role::ContentInfo,
+        Role::Definition,
+        Role::DescriptionList,
+        Role::DescriptionListDetail,
+        Role::DescriptionListTerm,
+        Role::Details,
+        Role::Dialog,
+        Role::Directory,
+        Role::Document,
+        Role::EmbeddedObject,
+        Role::Emphasis,
+        Role::Feed,
+        Role::FigureCaption,
+        Role::Figure,
+        Role::Footer,
+        Role::FooterAsNonLandmark,
+        Role::Form,
+        Role::Grid,
+        Role::Group,
+        Role::Header,
+        Role::HeaderAsNonLandmark,
+        Role::Iframe,
+        Role::IframePresentational,
+        Role::ImeCandidate,
+        Role::Keyboard,
+        Role::Legend,
+        Role::LineBreak,
+        Role::ListBox,
+        Role::Log,
+        Role::Main,
+        Role::Mark,
+        Role::Marquee,
+        Role::Math,
+        Role::MenuBar,
+        Role::MenuListPopup,
+        Role::Meter,
+        Role::Navigation,
+        Role::Note,
+        Role::PluginObject,
+        Role::Portal,
+        Role::Pre,
+        Role::RadioGroup,
+        Role::Region,
+        Role::RootWebArea,
+        Role::Ruby,
+        Role::RubyAnnotation,
+        Role::ScrollBar,
+        Role::ScrollView,
+        Role::Search,
+        Role::Section,
+        Role::Splitter,
+        Role::Status,
+        Role::Strong,
+        Role::Suggestion,
+        Role::SvgRoot,
+        Role::TabPanel,
+        Role::Term,
+        Role::Time,
+        Role::TitleBar,
+        Role::Toolbar,
+        Role::Tooltip,
+        Role::Tree,
+        Role::TreeGrid,
+        Role::Video,
+        Role::WebView,
+        Role::Window,
+        Role::PdfActionableHighlight,
+        Role::PdfRoot,
+        Role::GraphicsDocument,
+        Role::GraphicsObject,
+        Role::GraphicsSymbol,
+        Role::DocAbstract,
+        Role::DocAcknowledgements,
+        Role::DocAfterword,
+        Role::DocAppendix,
+        Role::DocBackLink,
+        Role::DocBiblioEntry,
+        Role::DocBibliography,
+        Role::DocBiblioRef,
+        Role::DocChapter,
+        Role::DocColophon,
+        Role::DocConclusion,
+        Role::DocCover,
+        Role::DocCredit,
+        Role::DocCredits,
+        Role::DocDedication,
+        Role::DocEndnote,
+        Role::DocEndnotes,
+        Role::DocEpigraph,
+        Role::DocEpilogue,
+        Role::DocErrata,
+        Role::DocExample,
+        Role::DocFootnote,
+        Role::DocForeword,
+        Role::DocGlossary,
+        Role::DocGlossRef,
+        Role::DocIndex,
+        Role::DocIntroduction,
+        Role::DocNoteRef,
+        Role::DocNotice,
+        Role::DocPageBreak,
+        Role::DocPageFooter,
+        Role::DocPageHeader,
+        Role::DocPageList,
+        Role::DocPart,
+        Role::DocPreface,
+        Role::DocPrologue,
+        Role::DocPullquote,
+        Role::DocQna,
+        Role::DocSubtitle,
+        Role::DocTip,
+        Role::DocToc,
+        Role::ListGrid,
+        Role::Terminal,
+    ],
+    properties: &[
+        "children",
+        "bounds",
+        "transform",
+        "label",
+        "description",
+        "active_descendant",
+        "described_by",
+        "value",
+        "author_id",
+        "help_text",
+        "numeric_value",
+        "toggled",
+        "selected",
+        "live",
+        "disabled",
+        "scroll_x_min",
+        "scroll_x_max",
+        "scroll_y_min",
+        "scroll_y_max",
+    ],
+    actions: &[
+        Action::Click,
+        Action::Increment,
+        Action::Decrement,
+        Action::ScrollBackward,
+        Action::ScrollDown,
+        Action::ScrollForward,
+        Action::ScrollLeft,
+        Action::ScrollRight,
+        Action::ScrollUp,
+    ],
+    events: &[
+        "UIAccessibilityAnnouncementNotification",
+        "UIAccessibilityLayoutChangedNotification",
+        "UIAccessibilityScreenChangedNotification",
+    ],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::CAPABILITIES;
+
+    #[test]
+    fn capabilities_match_mappings() {
+        accesskit_testing::check_capabilities(
+            &CAPABILITIES,
+            &[
+                include_str!("adapter.rs"),
+                include_str!("event.rs"),
+                include_str!("node.rs"),
+                include_str!("util.rs"),
+            ],
+            &[
+                ("children", &["filtered_children"]),
+                ("bounds", &["bounding_box"]),
+                ("transform", &["bounding_box"]),
+                ("active_descendant", &["focus_id"]),
+                ("described_by", &["description"]),
+                ("scroll_x_min", &["is_horizontally_scrollable"]),
+                ("scroll_x_max", &["is_horizontally_scrollable"]),
+                ("scroll_y_min", &["is_vertically_scrollable"]),
+                ("scroll_y_max", &["is_vertically_scrollable"]),
+            ],
+        );
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{ActionHandler, ActionRequest, ActionRequestError, NodeId};
+use accesskit_consumer::Tree;
+use hashbrown::HashMap;
+use objc2::rc::{Id, WeakId};
+use objc2_foundation::MainThreadMarker;
+use objc2_ui_kit::UIView;
+use std::{cell::RefCell, rc::Rc};
+
+use crate::node::PlatformNode;
+
+pub(crate) trait ActionHandlerNoMut {
+    fn do_action(&self, request: ActionRequest);
+    fn action_request_dropped(&self, request: &ActionRequest, error: ActionRequestError);
+}
+
+pub(crate) struct ActionHandlerWrapper<H: ActionHandler>(RefCell<H>);
+
+impl<H: 'static + ActionHandler> ActionHandlerWrapper<H> {
+    pub(crate) fn new(inner: H) -> Self {
+        Self(RefCell::new(inner))
+    }
+}
+
+impl<H: ActionHandler> ActionHandlerNoMut for ActionHandlerWrapper<H> {
+    fn do_action(&self, request: ActionRequest) {
+        self.0.borrow_mut().do_action(request)
+    }
+
+    fn action_request_dropped(&self, request: &ActionRequest, error: ActionRequestError) {
+        self.0.borrow_mut().action_request_dropped(request, error)
+    }
+}
+
+pub(crate) struct Context {
+    pub(crate) view: WeakId<UIView>,
+    pub(crate) tree: RefCell<Tree>,
+    pub(crate) action_handler: Rc<dyn ActionHandlerNoMut>,
+    platform_nodes: RefCell<HashMap<NodeId, Id<PlatformNode>>>,
+    pub(crate) mtm: MainThreadMarker,
+}
+
+impl Context {
+    pub(crate) fn new(
+        view: WeakId<UIView>,
+        tree: Tree,
+        action_handler: Rc<dyn ActionHandlerNoMut>,
+        mtm: MainThreadMarker,
+    ) -> Rc<Self> {
+        Rc::new(Self {
+            view,
+            tree: RefCell::new(tree),
+            action_handler,
+            platform_nodes: RefCell::new(HashMap::new()),
+            mtm,
+        })
+    }
+
+    pub(crate) fn get_or_create_platform_node(self: &Rc<Self>, id: NodeId) -> Id<PlatformNode> {
+        let mut platform_nodes = self.platform_nodes.borrow_mut();
+        if let Some(result) = platform_nodes.get(&id) {
+            return result.clone();
+        }
+
+        let result = PlatformNode::new(Rc::downgrade(self), id, self.mtm);
+        platform_nodes.insert(id, result.clone());
+        result
+    }
+
+    pub(crate) fn remove_platform_node(&self, id: NodeId) -> Option<Id<PlatformNode>> {
+        let mut platform_nodes = self.platform_nodes.borrow_mut();
+        platform_nodes.remove(&id)
+    }
+
+    pub(crate) fn do_action(&self, request: ActionRequest) {
+        let tree = self.tree.borrow();
+        let result = tree.state().check_action_request(&request);
+        drop(tree);
+        match result {
+            Ok(()) => self.action_handler.do_action(request),
+            Err(error) => self.action_handler.action_request_dropped(&request, error),
+        }
+    }
+}
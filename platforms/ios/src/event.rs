@@ -0,0 +1,338 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Live, NodeId, Role};
+use accesskit_consumer::{FilterResult, LiveRegionChange, Node, TreeChangeHandler};
+use objc2::rc::Id;
+use objc2_foundation::NSString;
+use objc2_ui_kit::{
+    UIAccessibilityAnnouncementNotification, UIAccessibilityLayoutChangedNotification,
+    UIAccessibilityNotifications, UIAccessibilityPostNotification,
+    UIAccessibilityScreenChangedNotification,
+};
+use std::rc::Rc;
+
+use crate::{context::Context, filters::filter};
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum QueuedEvent {
+    LayoutChanged(Option<NodeId>),
+    ScreenChanged(Option<NodeId>),
+    NodeDestroyed(NodeId),
+    Announcement(String),
+}
+
+fn post_element_notification(
+    context: &Rc<Context>,
+    notification: UIAccessibilityNotifications,
+    node_id: Option<NodeId>,
+) {
+    let platform_node = node_id.map(|node_id| context.get_or_create_platform_node(node_id));
+    let argument = platform_node.map(|platform_node| {
+        Id::into_super(Id::into_super(Id::into_super(Id::into_super(
+            platform_node,
+        ))))
+    });
+    unsafe { UIAccessibilityPostNotification(notification, argument.as_deref()) };
+}
+
+impl QueuedEvent {
+    fn raise(self, context: &Rc<Context>) {
+        match self {
+            Self::LayoutChanged(node_id) => {
+                let notification = unsafe { UIAccessibilityLayoutChangedNotification };
+                post_element_notification(context, notification, node_id);
+            }
+            Self::ScreenChanged(node_id) => {
+                let notification = unsafe { UIAccessibilityScreenChangedNotification };
+                post_element_notification(context, notification, node_id);
+            }
+            Self::NodeDestroyed(node_id) => {
+                context.remove_platform_node(node_id);
+            }
+            Self::Announcement(text) => {
+                let text = Id::into_super(Id::into_super(NSString::from_str(&text)));
+                unsafe {
+                    UIAccessibilityPostNotification(
+                        UIAccessibilityAnnouncementNotification,
+                        Some(&*text),
+                    )
+                };
+            }
+        }
+    }
+}
+
+/// Events generated by a tree update.
+#[must_use = "events must be explicitly raised"]
+pub struct QueuedEvents {
+    context: Rc<Context>,
+    events: Vec<QueuedEvent>,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl QueuedEvents {
+    pub(crate) fn new(context: Rc<Context>, events: Vec<QueuedEvent>) -> Self {
+        Self {
+            context,
+            events,
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::current(),
+        }
+    }
+
+    /// Raise all queued events synchronously.
+    ///
+    /// It is unknown whether accessibility methods on the view may be
+    /// called while events are being raised. This means that any locks
+    /// or runtime borrows required to access the adapter must not
+    /// be held while this method is called.
+    pub fn raise(self) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            parent: &self.span,
+            "accesskit_raise_events",
+            event_count = self.events.len()
+        )
+        .entered();
+        for event in self.events {
+            event.raise(&self.context);
+        }
+    }
+}
+
+pub(crate) fn focus_event(node_id: NodeId) -> QueuedEvent {
+    QueuedEvent::LayoutChanged(Some(node_id))
+}
+
+/// Returns whether adding or removing the node changes what VoiceOver
+/// should treat as the current screen, as when a dialog opens or closes.
+fn is_screen(node: &Node) -> bool {
+    matches!(node.role(), Role::Dialog | Role::AlertDialog) || node.data().is_modal()
+}
+
+pub(crate) struct EventGenerator {
+    events: Vec<QueuedEvent>,
+    focus: Option<NodeId>,
+    layout_changed: bool,
+    screen_changed: bool,
+}
+
+impl EventGenerator {
+    pub(crate) fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            focus: None,
+            layout_changed: false,
+            screen_changed: false,
+        }
+    }
+
+    fn into_events(mut self) -> Vec<QueuedEvent> {
+        // UIKit has no dedicated focus event. Instead, a layout or screen
+        // change notification tells VoiceOver which element to move to.
+        if self.screen_changed {
+            self.events.push(QueuedEvent::ScreenChanged(self.focus));
+        } else if let Some(node_id) = self.focus {
+            self.events.push(focus_event(node_id));
+        } else if self.layout_changed {
+            self.events.push(QueuedEvent::LayoutChanged(None));
+        }
+        self.events
+    }
+
+    pub(crate) fn into_result(self, context: Rc<Context>) -> QueuedEvents {
+        QueuedEvents::new(context, self.into_events())
+    }
+}
+
+impl TreeChangeHandler for EventGenerator {
+    fn node_added(&mut self, node: &Node) {
+        if filter(node) != FilterResult::Include {
+            return;
+        }
+        self.layout_changed = true;
+        if is_screen(node) {
+            self.screen_changed = true;
+        }
+        let text = node
+            .value()
+            .or_else(|| node.is_notification().then(|| node.label()).flatten());
+        if let Some(text) = text {
+            if node.live() != Live::Off {
+                self.events.push(QueuedEvent::Announcement(text));
+            }
+        }
+    }
+
+    fn node_updated(&mut self, old_node: &Node, new_node: &Node) {
+        if filter(new_node) != filter(old_node) {
+            self.layout_changed = true;
+            if is_screen(new_node) {
+                self.screen_changed = true;
+            }
+        }
+        if filter(new_node) != FilterResult::Include {
+            return;
+        }
+        if let Some(new_value) = new_node.value() {
+            if new_node.live() != Live::Off {
+                let text = if new_node.live() != old_node.live()
+                    || filter(old_node) != FilterResult::Include
+                {
+                    Some(new_value)
+                } else {
+                    let old_value = old_node.value().unwrap_or_default();
+                    LiveRegionChange::new(&old_value, &new_value)
+                        .map(|change| change.text().to_string())
+                };
+                if let Some(text) = text {
+                    self.events.push(QueuedEvent::Announcement(text));
+                }
+            }
+        }
+    }
+
+    fn focus_moved(&mut self, _old_node: Option<&Node>, new_node: Option<&Node>) {
+        self.focus = new_node
+            .filter(|node| filter(node) == FilterResult::Include && !node.is_root())
+            .map(|node| node.id());
+    }
+
+    fn node_removed(&mut self, node: &Node) {
+        if filter(node) == FilterResult::Include {
+            self.layout_changed = true;
+            if is_screen(node) {
+                self.screen_changed = true;
+            }
+        }
+        self.events.push(QueuedEvent::NodeDestroyed(node.id()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{Action, Live, Node, NodeId, Role, Tree, TreeUpdate};
+
+    use super::{EventGenerator, QueuedEvent};
+
+    const ROOT_ID: NodeId = NodeId(0);
+    const BUTTON_ID: NodeId = NodeId(1);
+    const STATUS_ID: NodeId = NodeId(2);
+    const DIALOG_ID: NodeId = NodeId(3);
+    const DIALOG_BUTTON_ID: NodeId = NodeId(4);
+
+    fn button(label: &str) -> Node {
+        let mut node = Node::new(Role::Button);
+        node.set_label(label);
+        node.add_action(Action::Focus);
+        node.add_action(Action::Click);
+        node
+    }
+
+    fn root(children: Vec<NodeId>) -> Node {
+        let mut node = Node::new(Role::Window);
+        node.set_children(children);
+        node
+    }
+
+    fn initial_tree() -> accesskit_consumer::Tree {
+        let update = TreeUpdate {
+            nodes: vec![
+                (ROOT_ID, root(vec![BUTTON_ID])),
+                (BUTTON_ID, button("Save")),
+            ],
+            tree: Some(Tree::new(ROOT_ID)),
+            focus: BUTTON_ID,
+        };
+        accesskit_consumer::Tree::new(update, true)
+    }
+
+    fn events(tree: &mut accesskit_consumer::Tree, update: TreeUpdate) -> Vec<QueuedEvent> {
+        let mut generator = EventGenerator::new();
+        tree.update_and_process_changes(update, &mut generator);
+        generator.into_events()
+    }
+
+    #[test]
+    fn live_region_added() {
+        let mut tree = initial_tree();
+        let update = TreeUpdate {
+            nodes: vec![
+                (ROOT_ID, root(vec![BUTTON_ID, STATUS_ID])),
+                (STATUS_ID, {
+                    let mut node = Node::new(Role::Label);
+                    node.set_value("Saved");
+                    node.set_live(Live::Polite);
+                    node
+                }),
+            ],
+            tree: None,
+            focus: BUTTON_ID,
+        };
+        assert_eq!(
+            events(&mut tree, update),
+            [
+                QueuedEvent::Announcement("Saved".into()),
+                QueuedEvent::LayoutChanged(None)
+            ]
+        );
+    }
+
+    #[test]
+    fn dialog_opened_and_closed() {
+        let mut tree = initial_tree();
+        let update = TreeUpdate {
+            nodes: vec![
+                (ROOT_ID, root(vec![BUTTON_ID, DIALOG_ID])),
+                (DIALOG_ID, {
+                    let mut node = Node::new(Role::Dialog);
+                    node.set_label("Confirm");
+                    node.set_children(vec![DIALOG_BUTTON_ID]);
+                    node
+                }),
+                (DIALOG_BUTTON_ID, button("OK")),
+            ],
+            tree: None,
+            focus: DIALOG_BUTTON_ID,
+        };
+        assert_eq!(
+            events(&mut tree, update),
+            [QueuedEvent::ScreenChanged(Some(DIALOG_BUTTON_ID))]
+        );
+
+        let update = TreeUpdate {
+            nodes: vec![(ROOT_ID, root(vec![BUTTON_ID]))],
+            tree: None,
+            focus: BUTTON_ID,
+        };
+        assert_eq!(
+            events(&mut tree, update),
+            [
+                QueuedEvent::NodeDestroyed(DIALOG_ID),
+                QueuedEvent::NodeDestroyed(DIALOG_BUTTON_ID),
+                QueuedEvent::ScreenChanged(Some(BUTTON_ID))
+            ]
+        );
+    }
+
+    #[test]
+    fn focus_moved() {
+        let mut tree = initial_tree();
+        let update = TreeUpdate {
+            nodes: vec![
+                (ROOT_ID, root(vec![BUTTON_ID, DIALOG_BUTTON_ID])),
+                (DIALOG_BUTTON_ID, button("Cancel")),
+            ],
+            tree: None,
+            focus: DIALOG_BUTTON_ID,
+        };
+        assert_eq!(
+            events(&mut tree, update),
+            [QueuedEvent::LayoutChanged(Some(DIALOG_BUTTON_ID))]
+        );
+    }
+}
@@ -0,0 +1,301 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Action, ActionRequest, NodeId, Role, Toggled};
+use accesskit_consumer::{FilterResult, Node};
+use objc2::{
+    declare_class, msg_send_id, mutability::MainThreadOnly, rc::Id, runtime::AnyObject, ClassType,
+    DeclaredClass,
+};
+use objc2_foundation::{CGRect, MainThreadMarker, NSArray, NSObject, NSString};
+use objc2_ui_kit::*;
+use std::rc::{Rc, Weak};
+
+use crate::{context::Context, filters::filter, util::*};
+
+fn label(node: &Node) -> Option<String> {
+    // The text of these nodes is exposed as the value.
+    if node.label_comes_from_value() {
+        node.label()
+    } else {
+        node.name()
+    }
+}
+
+fn traits(node: &Node) -> UIAccessibilityTraits {
+    let mut traits = 0;
+    unsafe {
+        match node.role() {
+            Role::Button
+            | Role::DefaultButton
+            | Role::CheckBox
+            | Role::RadioButton
+            | Role::Switch
+            | Role::MenuItem
+            | Role::MenuItemCheckBox
+            | Role::MenuItemRadio
+            | Role::Tab
+            | Role::DisclosureTriangle => traits |= UIAccessibilityTraitButton,
+            Role::Link => traits |= UIAccessibilityTraitLink,
+            Role::Heading => traits |= UIAccessibilityTraitHeader,
+            Role::SearchInput => traits |= UIAccessibilityTraitSearchField,
+            Role::Image | Role::Canvas => traits |= UIAccessibilityTraitImage,
+            Role::Label => traits |= UIAccessibilityTraitStaticText,
+            Role::TabList => traits |= UIAccessibilityTraitTabBar,
+            Role::Timer | Role::ProgressIndicator => {
+                traits |= UIAccessibilityTraitUpdatesFrequently
+            }
+            _ => (),
+        }
+        if node.supports_increment() || node.supports_decrement() {
+            traits |= UIAccessibilityTraitAdjustable;
+        }
+        if node.is_disabled() {
+            traits |= UIAccessibilityTraitNotEnabled;
+        }
+        if node.is_selected() == Some(true)
+            || node
+                .toggled()
+                .is_some_and(|toggled| toggled != Toggled::False)
+        {
+            traits |= UIAccessibilityTraitSelected;
+        }
+    }
+    traits
+}
+
+/// VoiceOver doesn't visit the children of an accessibility element,
+/// so only leaves and controls are exposed as elements; other nodes are
+/// exposed as containers of their children.
+fn is_element(node: &Node) -> bool {
+    if filter(node) != FilterResult::Include || node.is_root() {
+        return false;
+    }
+    node.filtered_children(filter).next().is_none()
+        || node.is_clickable()
+        || node.supports_increment()
+        || node.supports_decrement()
+}
+
+fn value(node: &Node) -> Option<String> {
+    if node.role() == Role::Switch {
+        return node
+            .toggled()
+            .map(|toggled| if toggled == Toggled::True { "1" } else { "0" }.into());
+    }
+    node.value()
+        .or_else(|| node.numeric_value().map(|value| value.to_string()))
+}
+
+pub(crate) struct PlatformNodeIvars {
+    context: Weak<Context>,
+    node_id: NodeId,
+}
+
+declare_class!(
+    pub(crate) struct PlatformNode;
+
+    unsafe impl ClassType for PlatformNode {
+        #[inherits(UIResponder, NSObject)]
+        type Super = UIAccessibilityElement;
+        type Mutability = MainThreadOnly;
+        const NAME: &'static str = "AccessKitNode";
+    }
+
+    impl DeclaredClass for PlatformNode {
+        type Ivars = PlatformNodeIvars;
+    }
+
+    unsafe impl PlatformNode {
+        #[method(isAccessibilityElement)]
+        fn is_accessibility_element(&self) -> bool {
+            self.resolve(is_element).unwrap_or(false)
+        }
+
+        #[method_id(accessibilityContainer)]
+        fn container(&self) -> Option<Id<AnyObject>> {
+            self.resolve_with_context(|node, context| {
+                if let Some(parent) = node.filtered_parent(&filter) {
+                    Some(Id::into_super(Id::into_super(Id::into_super(
+                        Id::into_super(context.get_or_create_platform_node(parent.id())),
+                    ))))
+                } else {
+                    context
+                        .view
+                        .load()
+                        .map(|view| Id::into_super(Id::into_super(Id::into_super(view))))
+                }
+            })
+            .flatten()
+        }
+
+        #[method_id(accessibilityElements)]
+        fn elements(&self) -> Option<Id<NSArray<PlatformNode>>> {
+            self.resolve_with_context(|node, context| {
+                let platform_nodes = node
+                    .filtered_children(filter)
+                    .map(|child| context.get_or_create_platform_node(child.id()))
+                    .collect::<Vec<Id<PlatformNode>>>();
+                NSArray::from_vec(platform_nodes)
+            })
+        }
+
+        #[method_id(accessibilityLabel)]
+        fn label(&self) -> Option<Id<NSString>> {
+            self.resolve(|node| label(node).map(|label| NSString::from_str(&label)))
+                .flatten()
+        }
+
+        #[method_id(accessibilityValue)]
+        fn value(&self) -> Option<Id<NSString>> {
+            self.resolve(|node| value(node).map(|value| NSString::from_str(&value)))
+                .flatten()
+        }
+
+        #[method_id(accessibilityHint)]
+        fn hint(&self) -> Option<Id<NSString>> {
+            self.resolve(|node| {
+                node.help_text()
+                    .map(str::to_string)
+                    .or_else(|| node.description())
+                    .map(|hint| NSString::from_str(&hint))
+            })
+            .flatten()
+        }
+
+        #[method_id(accessibilityIdentifier)]
+        fn identifier(&self) -> Option<Id<NSString>> {
+            self.resolve(|node| node.author_id().map(NSString::from_str))
+                .flatten()
+        }
+
+        #[method(accessibilityTraits)]
+        fn traits(&self) -> UIAccessibilityTraits {
+            self.resolve(traits).unwrap_or(0)
+        }
+
+        #[method(accessibilityFrame)]
+        fn frame(&self) -> CGRect {
+            self.resolve_with_context(|node, context| {
+                let view = context.view.load()?;
+                let rect = node.bounding_box()?;
+                Some(to_screen_rect(&view, rect))
+            })
+            .flatten()
+            .unwrap_or(CGRect::ZERO)
+        }
+
+        #[method(accessibilityActivate)]
+        fn activate(&self) -> bool {
+            self.resolve_with_context(|node, context| {
+                let clickable = node.is_clickable();
+                if clickable {
+                    context.do_action(ActionRequest {
+                        action: Action::Click,
+                        target: node.id(),
+                        data: None,
+                    });
+                }
+                clickable
+            })
+            .unwrap_or(false)
+        }
+
+        #[method(accessibilityIncrement)]
+        fn increment(&self) {
+            self.resolve_with_context(|node, context| {
+                if node.supports_increment() {
+                    context.do_action(ActionRequest {
+                        action: Action::Increment,
+                        target: node.id(),
+                        data: None,
+                    });
+                }
+            });
+        }
+
+        #[method(accessibilityDecrement)]
+        fn decrement(&self) {
+            self.resolve_with_context(|node, context| {
+                if node.supports_decrement() {
+                    context.do_action(ActionRequest {
+                        action: Action::Decrement,
+                        target: node.id(),
+                        data: None,
+                    });
+                }
+            });
+        }
+
+        #[method(accessibilityScroll:)]
+        fn scroll(&self, direction: UIAccessibilityScrollDirection) -> bool {
+            self.resolve_with_context(|node, context| {
+                let horizontal = node.is_horizontally_scrollable();
+                let vertical = node.is_vertically_scrollable();
+                let action = match direction {
+                    UIAccessibilityScrollDirection::Right if horizontal => Action::ScrollRight,
+                    UIAccessibilityScrollDirection::Left if horizontal => Action::ScrollLeft,
+                    UIAccessibilityScrollDirection::Up if vertical => Action::ScrollUp,
+                    UIAccessibilityScrollDirection::Down if vertical => Action::ScrollDown,
+                    UIAccessibilityScrollDirection::Next if horizontal || vertical => {
+                        Action::ScrollForward
+                    }
+                    UIAccessibilityScrollDirection::Previous if horizontal || vertical => {
+                        Action::ScrollBackward
+                    }
+                    _ => {
+                        return false;
+                    }
+                };
+                context.do_action(ActionRequest {
+                    action,
+                    target: node.id(),
+                    data: None,
+                });
+                true
+            })
+            .unwrap_or(false)
+        }
+    }
+);
+
+impl PlatformNode {
+    pub(crate) fn new(context: Weak<Context>, node_id: NodeId, mtm: MainThreadMarker) -> Id<Self> {
+        // The container is computed on demand by `accessibilityContainer`,
+        // but UIKit requires one at initialization.
+        let container = context
+            .upgrade()
+            .and_then(|context| context.view.load())
+            .map(|view| Id::into_super(Id::into_super(Id::into_super(view))));
+        let this = mtm
+            .alloc::<Self>()
+            .set_ivars(PlatformNodeIvars { context, node_id });
+
+        unsafe {
+            msg_send_id![
+                super(this),
+                initWithAccessibilityContainer: container.as_deref()
+            ]
+        }
+    }
+
+    fn resolve_with_context<F, T>(&self, f: F) -> Option<T>
+    where
+        F: FnOnce(&Node, &Rc<Context>) -> T,
+    {
+        let context = self.ivars().context.upgrade()?;
+        let tree = context.tree.borrow();
+        let state = tree.state();
+        let node = state.node_by_id(self.ivars().node_id)?;
+        Some(f(&node, &context))
+    }
+
+    fn resolve<F, T>(&self, f: F) -> Option<T>
+    where
+        F: FnOnce(&Node) -> T,
+    {
+        self.resolve_with_context(|node, _| f(node))
+    }
+}
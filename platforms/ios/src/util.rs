@@ -0,0 +1,25 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::Rect;
+use objc2_foundation::{CGPoint, CGRect, CGSize};
+use objc2_ui_kit::{UIAccessibilityConvertFrameToScreenCoordinates, UIView};
+
+pub(crate) fn to_screen_rect(view: &UIView, rect: Rect) -> CGRect {
+    // AccessKit coordinates are in physical pixels, but UIKit expects
+    // points here.
+    let factor = view.contentScaleFactor();
+    let rect = CGRect {
+        origin: CGPoint {
+            x: rect.x0 / factor,
+            y: rect.y0 / factor,
+        },
+        size: CGSize {
+            width: rect.width() / factor,
+            height: rect.height() / factor,
+        },
+    };
+    unsafe { UIAccessibilityConvertFrameToScreenCoordinates(rect, view) }
+}
@@ -4,8 +4,8 @@
 // the LICENSE-MIT file), at your option.
 
 use accesskit::{
-    ActionHandler, ActionRequest, ActivationHandler, Node as NodeProvider, NodeId, Role,
-    Tree as TreeData, TreeUpdate,
+    ActionHandler, ActionRequest, ActivationHandler, AnnouncementKind, GeometryUpdate,
+    Node as NodeProvider, NodeId, Role, Tree as TreeData, TreeUpdate, Vec2,
 };
 use accesskit_consumer::{FilterResult, Tree};
 use objc2::rc::{Id, WeakId};
@@ -15,7 +15,7 @@ use std::{ffi::c_void, ptr::null_mut, rc::Rc};
 
 use crate::{
     context::{ActionHandlerNoMut, ActionHandlerWrapper, Context},
-    event::{focus_event, EventGenerator, QueuedEvents},
+    event::{announcement_event, focus_event, EventGenerator, QueuedEvents},
     filters::filter,
     node::can_be_focused,
     util::*,
@@ -28,6 +28,8 @@ enum State {
         view: WeakId<NSView>,
         is_view_focused: bool,
         action_handler: Rc<dyn ActionHandlerNoMut>,
+        content_origin: Vec2,
+        host_parent: Option<WeakId<NSObject>>,
         mtm: MainThreadMarker,
     },
     Placeholder {
@@ -69,11 +71,73 @@ impl Adapter {
             view,
             is_view_focused,
             action_handler: Rc::new(ActionHandlerWrapper::new(action_handler)),
+            content_origin: Vec2::ZERO,
+            host_parent: None,
             mtm,
         };
         Self { state }
     }
 
+    /// Set the offset of the origin of the node coordinate space
+    /// from the top-left corner of the view, in physical pixels.
+    /// This is useful when the toolkit's coordinates are relative
+    /// to something other than the view, for example a surface that
+    /// extends under a custom title bar. The default is zero.
+    pub fn set_content_origin(&mut self, origin: Vec2) {
+        match &mut self.state {
+            State::Inactive { content_origin, .. } => {
+                *content_origin = origin;
+            }
+            State::Placeholder {
+                placeholder_context,
+                ..
+            } => {
+                placeholder_context.content_origin.set(origin);
+            }
+            State::Active(context) => {
+                context.content_origin.set(origin);
+            }
+        }
+    }
+
+    /// Set the accessibility element that the tree's top-level nodes report
+    /// as their parent. By default, this is the parent of the view, since
+    /// the view itself is normally replaced by the tree. An application
+    /// that makes only part of its existing accessibility hierarchy
+    /// accessible through AccessKit, for example a custom-drawn canvas
+    /// among native controls, can instead host the tree beneath one of its
+    /// own accessibility elements. That element must then return the result
+    /// of [`Adapter::view_children`] from `accessibilityChildren`, and
+    /// forward `accessibilityHitTest:` to [`Adapter::hit_test`] and
+    /// `accessibilityFocusedUIElement` to [`Adapter::focus`] while the point
+    /// or the focus is within the hosted content.
+    ///
+    /// The adapter only keeps a weak reference to the element. Pass
+    /// a null pointer to restore the default.
+    ///
+    /// # Safety
+    ///
+    /// `parent` must be null or a valid, unreleased pointer to an object
+    /// that implements the `NSAccessibility` protocol.
+    pub unsafe fn set_host_parent(&mut self, parent: *mut c_void) {
+        let parent = unsafe { Id::retain(parent as *mut NSObject) };
+        let parent = parent.map(|parent| WeakId::from_id(&parent));
+        match &mut self.state {
+            State::Inactive { host_parent, .. } => {
+                *host_parent = parent;
+            }
+            State::Placeholder {
+                placeholder_context,
+                ..
+            } => {
+                *placeholder_context.host_parent.borrow_mut() = parent;
+            }
+            State::Active(context) => {
+                *context.host_parent.borrow_mut() = parent;
+            }
+        }
+    }
+
     /// If and only if the tree has been initialized, call the provided function
     /// and apply the resulting update. Note: If the caller's implementation of
     /// [`ActivationHandler::request_initial_tree`] initially returned `None`,
@@ -86,6 +150,8 @@ impl Adapter {
         &mut self,
         update_factory: impl FnOnce() -> TreeUpdate,
     ) -> Option<QueuedEvents> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("accesskit_update_if_active").entered();
         match &self.state {
             State::Inactive { .. } => None,
             State::Placeholder {
@@ -98,6 +164,8 @@ impl Adapter {
                     placeholder_context.view.clone(),
                     tree,
                     Rc::clone(action_handler),
+                    placeholder_context.content_origin.get(),
+                    placeholder_context.host_parent.borrow().clone(),
                     placeholder_context.mtm,
                 );
                 let result = context
@@ -118,6 +186,26 @@ impl Adapter {
         }
     }
 
+    /// If and only if the tree has been initialized, call the provided function
+    /// and apply the resulting [`GeometryUpdate`]. Since such an update only
+    /// applies to an existing tree, the function isn't called while the
+    /// adapter is waiting for the initial tree.
+    ///
+    /// If a [`QueuedEvents`] instance is returned, the caller must call
+    /// [`QueuedEvents::raise`] on it.
+    pub fn update_geometry_if_active(
+        &mut self,
+        update_factory: impl FnOnce() -> GeometryUpdate,
+    ) -> Option<QueuedEvents> {
+        let State::Active(context) = &self.state else {
+            return None;
+        };
+        let mut event_generator = EventGenerator::new(context.clone());
+        let mut tree = context.tree.borrow_mut();
+        tree.update_geometry_and_process_changes(update_factory(), &mut event_generator);
+        Some(event_generator.into_result())
+    }
+
     /// Update the tree state based on whether the window is focused.
     ///
     /// If a [`QueuedEvents`] instance is returned, the caller must call
@@ -145,6 +233,26 @@ impl Adapter {
         }
     }
 
+    /// Ask assistive technologies to speak the given text, without
+    /// requiring a live region in the tree. This posts
+    /// `NSAccessibilityAnnouncementRequestedNotification` on the window
+    /// containing the view.
+    ///
+    /// If the tree isn't active, there is no assistive technology to notify,
+    /// so this returns `None`.
+    ///
+    /// If a [`QueuedEvents`] instance is returned, the caller must call
+    /// [`QueuedEvents::raise`] on it.
+    pub fn announce(&mut self, text: &str, kind: AnnouncementKind) -> Option<QueuedEvents> {
+        match &self.state {
+            State::Inactive { .. } | State::Placeholder { .. } => None,
+            State::Active(context) => Some(QueuedEvents::new(
+                context.clone(),
+                vec![announcement_event(text.into(), kind)],
+            )),
+        }
+    }
+
     fn get_or_init_context<H: ActivationHandler + ?Sized>(
         &mut self,
         activation_handler: &mut H,
@@ -154,11 +262,20 @@ impl Adapter {
                 view,
                 is_view_focused,
                 action_handler,
+                content_origin,
+                host_parent,
                 mtm,
             } => match activation_handler.request_initial_tree() {
                 Some(initial_state) => {
                     let tree = Tree::new(initial_state, *is_view_focused);
-                    let context = Context::new(view.clone(), tree, Rc::clone(action_handler), *mtm);
+                    let context = Context::new(
+                        view.clone(),
+                        tree,
+                        Rc::clone(action_handler),
+                        *content_origin,
+                        host_parent.clone(),
+                        *mtm,
+                    );
                     let result = Rc::clone(&context);
                     self.state = State::Active(context);
                     result
@@ -174,6 +291,8 @@ impl Adapter {
                         view.clone(),
                         placeholder_tree,
                         Rc::new(ActionHandlerWrapper::new(PlaceholderActionHandler {})),
+                        *content_origin,
+                        host_parent.clone(),
                         *mtm,
                     );
                     let result = Rc::clone(&placeholder_context);
@@ -261,7 +380,7 @@ impl Adapter {
         let tree = context.tree.borrow();
         let state = tree.state();
         let root = state.root();
-        let point = from_ns_point(&view, &root, point);
+        let point = from_ns_point(&view, context.content_origin.get(), &root, point);
         let node = root.node_at_point(point, &filter).unwrap_or(root);
         Id::autorelease_return(context.get_or_create_platform_node(node.id())) as *mut _
     }
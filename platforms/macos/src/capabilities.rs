@@ -0,0 +1,113 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Action, AdapterCapabilities, Role};
+
+/// The parts of the AccessKit schema that are exposed through
+/// the NSAccessibility protocol.
+///
+/// This must be kept in sync with the mappings in the `node` module.
+/// A unit test checks that each listed property and action is handled.
+pub const CAPABILITIES: AdapterCapabilities = AdapterCapabilities {
+    api: "NSAccessibility",
+    unmapped_roles: &[
+        Role::Unknown,
+        Role::TextRun,
+        Role::GenericContainer,
+        Role::Pane,
+        Role::Caret,
+        Role::Feed,
+        Role::ImeCandidate,
+        Role::Keyboard,
+        Role::RubyAnnotation,
+        Role::ScrollView,
+        Role::WebView,
+        Role::ListGrid,
+    ],
+    properties: &[
+        "children",
+        "bounds",
+        "transform",
+        "label",
+        "description",
+        "active_descendant",
+        "described_by",
+        "value",
+        "author_id",
+        "placeholder",
+        "role_description",
+        "help_text",
+        "pronunciation",
+        "numeric_value",
+        "min_numeric_value",
+        "max_numeric_value",
+        "character_lengths",
+        "text_selection",
+        "text_selections",
+        "toggled",
+        "live",
+        "orientation",
+        "disabled",
+        "read_only",
+        "required",
+        "grabbed",
+        "row_count",
+        "column_count",
+        "row_index",
+        "column_index",
+        "row_span",
+        "column_span",
+        "selected",
+    ],
+    actions: &[
+        Action::Click,
+        Action::Focus,
+        Action::Increment,
+        Action::Decrement,
+        Action::SetTextSelection,
+        Action::Drag,
+        Action::Drop,
+    ],
+    events: &[
+        "AXAnnouncementRequested",
+        "AXFocusedUIElementChanged",
+        "AXSelectedTextChanged",
+        "AXTitleChanged",
+        "AXUIElementDestroyed",
+        "AXValueChanged",
+    ],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::CAPABILITIES;
+
+    #[test]
+    fn capabilities_match_mappings() {
+        accesskit_testing::check_capabilities(
+            &CAPABILITIES,
+            &[
+                include_str!("adapter.rs"),
+                include_str!("column.rs"),
+                include_str!("event.rs"),
+                include_str!("node.rs"),
+                include_str!("text_marker.rs"),
+                include_str!("util.rs"),
+            ],
+            &[
+                ("children", &["filtered_children"]),
+                ("active_descendant", &["focus_id"]),
+                ("described_by", &["description"]),
+                ("character_lengths", &["document_range"]),
+                ("row_count", &["table_row_count"]),
+                ("column_count", &["table_column_count"]),
+                ("row_index", &["table_cell_position"]),
+                ("column_index", &["table_cell_position"]),
+                ("row_span", &["table_cell_position"]),
+                ("column_span", &["table_cell_position"]),
+            ],
+        );
+    }
+}
@@ -0,0 +1,180 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::NodeId;
+use accesskit_consumer::Node;
+use objc2::{
+    declare_class, msg_send_id,
+    mutability::InteriorMutable,
+    rc::Id,
+    runtime::{AnyObject, Sel},
+    sel, ClassType, DeclaredClass,
+};
+use objc2_app_kit::*;
+use objc2_foundation::{NSArray, NSCopying, NSInteger, NSObject, NSRect};
+use std::rc::{Rc, Weak};
+
+use crate::{context::Context, node::PlatformNode, util::*};
+
+pub(crate) struct PlatformColumnIvars {
+    context: Weak<Context>,
+    table_id: NodeId,
+    index: usize,
+}
+
+declare_class!(
+    /// A column of a table. AccessKit trees don't have column nodes,
+    /// but VoiceOver expects tables to expose their columns as elements
+    /// with the `AXColumn` role, as `NSTableView` does.
+    pub(crate) struct PlatformColumn;
+
+    unsafe impl ClassType for PlatformColumn {
+        #[inherits(NSObject)]
+        type Super = NSAccessibilityElement;
+        type Mutability = InteriorMutable;
+        const NAME: &'static str = "AccessKitColumn";
+    }
+
+    impl DeclaredClass for PlatformColumn {
+        type Ivars = PlatformColumnIvars;
+    }
+
+    unsafe impl PlatformColumn {
+        #[method_id(accessibilityParent)]
+        fn parent(&self) -> Option<Id<AnyObject>> {
+            self.resolve_with_context(|table, context| {
+                Id::into_super(Id::into_super(Id::into_super(
+                    context.get_or_create_platform_node(table.id()),
+                )))
+            })
+        }
+
+        #[method_id(accessibilityWindow)]
+        fn window(&self) -> Option<Id<AnyObject>> {
+            self.resolve_with_context(|_, context| {
+                context
+                    .view
+                    .load()
+                    .and_then(|view| unsafe { NSAccessibility::accessibilityParent(&*view) })
+            })
+            .flatten()
+        }
+
+        #[method_id(accessibilityTopLevelUIElement)]
+        fn top_level(&self) -> Option<Id<AnyObject>> {
+            self.resolve_with_context(|_, context| {
+                context
+                    .view
+                    .load()
+                    .and_then(|view| unsafe { NSAccessibility::accessibilityParent(&*view) })
+            })
+            .flatten()
+        }
+
+        #[method_id(accessibilityChildren)]
+        fn children(&self) -> Option<Id<NSArray<PlatformNode>>> {
+            self.resolve_with_context(|table, context| {
+                let cells = table
+                    .table_column_cells(self.ivars().index)
+                    .into_iter()
+                    .map(|cell| context.get_or_create_platform_node(cell.id()))
+                    .collect::<Vec<Id<PlatformNode>>>();
+                NSArray::from_vec(cells)
+            })
+        }
+
+        #[method_id(accessibilityHeader)]
+        fn header(&self) -> Option<Id<AnyObject>> {
+            self.resolve_with_context(|table, context| {
+                let header = table
+                    .table_column_headers(self.ivars().index)
+                    .into_iter()
+                    .next()?;
+                Some(Id::into_super(Id::into_super(Id::into_super(
+                    context.get_or_create_platform_node(header.id()),
+                ))))
+            })
+            .flatten()
+        }
+
+        #[method(accessibilityFrame)]
+        fn frame(&self) -> NSRect {
+            self.resolve_with_context(|table, context| {
+                let view = context.view.load()?;
+                let rect = table
+                    .table_column_cells(self.ivars().index)
+                    .iter()
+                    .filter_map(Node::bounding_box)
+                    .reduce(|a, b| a.union(b))?;
+                Some(to_ns_rect(&view, context.content_origin.get(), rect))
+            })
+            .flatten()
+            .unwrap_or(NSRect::ZERO)
+        }
+
+        #[method_id(accessibilityRole)]
+        fn role(&self) -> Id<NSAccessibilityRole> {
+            unsafe { NSAccessibilityColumnRole }.copy()
+        }
+
+        #[method(accessibilityIndex)]
+        fn index(&self) -> NSInteger {
+            self.ivars().index as _
+        }
+
+        #[method(isAccessibilityElement)]
+        fn is_accessibility_element(&self) -> bool {
+            self.resolve_with_context(|_, _| ()).is_some()
+        }
+
+        #[method(accessibilityNotifiesWhenDestroyed)]
+        fn notifies_when_destroyed(&self) -> bool {
+            true
+        }
+
+        #[method(isAccessibilitySelectorAllowed:)]
+        fn is_selector_allowed(&self, selector: Sel) -> bool {
+            selector == sel!(accessibilityParent)
+                || selector == sel!(accessibilityWindow)
+                || selector == sel!(accessibilityTopLevelUIElement)
+                || selector == sel!(accessibilityChildren)
+                || selector == sel!(accessibilityHeader)
+                || selector == sel!(accessibilityFrame)
+                || selector == sel!(accessibilityRole)
+                || selector == sel!(accessibilityIndex)
+                || selector == sel!(isAccessibilityElement)
+                || selector == sel!(accessibilityNotifiesWhenDestroyed)
+                || selector == sel!(isAccessibilitySelectorAllowed:)
+        }
+    }
+);
+
+impl PlatformColumn {
+    pub(crate) fn new(context: Weak<Context>, table_id: NodeId, index: usize) -> Id<Self> {
+        let this = Self::alloc().set_ivars(PlatformColumnIvars {
+            context,
+            table_id,
+            index,
+        });
+
+        unsafe { msg_send_id![super(this), init] }
+    }
+
+    /// Calls the given function with the table, unless the table has been
+    /// removed or no longer has this column.
+    fn resolve_with_context<F, T>(&self, f: F) -> Option<T>
+    where
+        F: FnOnce(&Node, &Rc<Context>) -> T,
+    {
+        let context = self.ivars().context.upgrade()?;
+        let tree = context.tree.borrow();
+        let state = tree.state();
+        let table = state.node_by_id(self.ivars().table_id)?;
+        if !table.is_table() || self.ivars().index >= table.table_column_count() {
+            return None;
+        }
+        Some(f(&table, &context))
+    }
+}
@@ -3,18 +3,23 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-use accesskit::{ActionHandler, ActionRequest, NodeId};
-use accesskit_consumer::Tree;
+use accesskit::{ActionHandler, ActionRequest, ActionRequestError, NodeId, Vec2};
+use accesskit_consumer::{ProgressThrottle, Tree};
 use hashbrown::HashMap;
 use objc2::rc::{Id, WeakId};
 use objc2_app_kit::*;
-use objc2_foundation::MainThreadMarker;
-use std::{cell::RefCell, rc::Rc};
+use objc2_foundation::{MainThreadMarker, NSObject};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Instant,
+};
 
-use crate::node::PlatformNode;
+use crate::{column::PlatformColumn, node::PlatformNode};
 
 pub(crate) trait ActionHandlerNoMut {
     fn do_action(&self, request: ActionRequest);
+    fn action_request_dropped(&self, request: &ActionRequest, error: ActionRequestError);
 }
 
 pub(crate) struct ActionHandlerWrapper<H: ActionHandler>(RefCell<H>);
@@ -29,6 +34,10 @@ impl<H: ActionHandler> ActionHandlerNoMut for ActionHandlerWrapper<H> {
     fn do_action(&self, request: ActionRequest) {
         self.0.borrow_mut().do_action(request)
     }
+
+    fn action_request_dropped(&self, request: &ActionRequest, error: ActionRequestError) {
+        self.0.borrow_mut().action_request_dropped(request, error)
+    }
 }
 
 pub(crate) struct Context {
@@ -36,6 +45,13 @@ pub(crate) struct Context {
     pub(crate) tree: RefCell<Tree>,
     pub(crate) action_handler: Rc<dyn ActionHandlerNoMut>,
     platform_nodes: RefCell<HashMap<NodeId, Id<PlatformNode>>>,
+    platform_columns: RefCell<HashMap<(NodeId, usize), Id<PlatformColumn>>>,
+    pub(crate) content_origin: Cell<Vec2>,
+    pub(crate) host_parent: RefCell<Option<WeakId<NSObject>>>,
+    // VoiceOver doesn't report the progress of long operations by itself,
+    // so we announce it periodically.
+    pub(crate) progress: RefCell<ProgressThrottle>,
+    pub(crate) creation_time: Instant,
     pub(crate) mtm: MainThreadMarker,
 }
 
@@ -44,6 +60,8 @@ impl Context {
         view: WeakId<NSView>,
         tree: Tree,
         action_handler: Rc<dyn ActionHandlerNoMut>,
+        content_origin: Vec2,
+        host_parent: Option<WeakId<NSObject>>,
         mtm: MainThreadMarker,
     ) -> Rc<Self> {
         Rc::new(Self {
@@ -51,6 +69,11 @@ impl Context {
             tree: RefCell::new(tree),
             action_handler,
             platform_nodes: RefCell::new(HashMap::new()),
+            platform_columns: RefCell::new(HashMap::new()),
+            content_origin: Cell::new(content_origin),
+            host_parent: RefCell::new(host_parent),
+            progress: RefCell::new(ProgressThrottle::default()),
+            creation_time: Instant::now(),
             mtm,
         })
     }
@@ -71,8 +94,41 @@ impl Context {
         platform_nodes.remove(&id)
     }
 
+    pub(crate) fn get_or_create_platform_column(
+        self: &Rc<Self>,
+        table_id: NodeId,
+        index: usize,
+    ) -> Id<PlatformColumn> {
+        let mut platform_columns = self.platform_columns.borrow_mut();
+        if let Some(result) = platform_columns.get(&(table_id, index)) {
+            return result.clone();
+        }
+
+        let result = PlatformColumn::new(Rc::downgrade(self), table_id, index);
+        platform_columns.insert((table_id, index), result.clone());
+        result
+    }
+
+    pub(crate) fn remove_platform_columns(&self, table_id: NodeId) -> Vec<Id<PlatformColumn>> {
+        let mut platform_columns = self.platform_columns.borrow_mut();
+        let keys = platform_columns
+            .keys()
+            .filter(|(id, _)| *id == table_id)
+            .copied()
+            .collect::<Vec<_>>();
+        keys.into_iter()
+            .filter_map(|key| platform_columns.remove(&key))
+            .collect()
+    }
+
     pub(crate) fn do_action(&self, request: ActionRequest) {
-        self.action_handler.do_action(request);
+        let tree = self.tree.borrow();
+        let result = tree.state().check_action_request(&request);
+        drop(tree);
+        match result {
+            Ok(()) => self.action_handler.do_action(request),
+            Err(error) => self.action_handler.action_request_dropped(&request, error),
+        }
     }
 }
 
@@ -87,5 +143,14 @@ impl Drop for Context {
                 )
             };
         }
+        let platform_columns = self.platform_columns.borrow();
+        for platform_column in platform_columns.values() {
+            unsafe {
+                NSAccessibilityPostNotification(
+                    platform_column,
+                    NSAccessibilityUIElementDestroyedNotification,
+                )
+            };
+        }
     }
 }
@@ -3,8 +3,8 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-use accesskit::{Live, NodeId, Role};
-use accesskit_consumer::{FilterResult, Node, TreeChangeHandler};
+use accesskit::{AnnouncementKind, Live, NodeId, Role};
+use accesskit_consumer::{FilterResult, LiveRegionChange, Node, TreeChangeHandler};
 use hashbrown::HashSet;
 use objc2::runtime::{AnyObject, ProtocolObject};
 use objc2_app_kit::*;
@@ -28,9 +28,9 @@ pub(crate) enum QueuedEvent {
 }
 
 impl QueuedEvent {
-    fn live_region_announcement(node: &Node) -> Self {
+    fn live_region_announcement(node: &Node, text: String) -> Self {
         Self::Announcement {
-            text: node.value().unwrap(),
+            text,
             priority: if node.live() == Live::Assertive {
                 NSAccessibilityPriorityLevel::NSAccessibilityPriorityHigh
             } else {
@@ -57,6 +57,14 @@ impl QueuedEvent {
                         )
                     };
                 }
+                for platform_column in context.remove_platform_columns(node_id) {
+                    unsafe {
+                        NSAccessibilityPostNotification(
+                            &platform_column,
+                            NSAccessibilityUIElementDestroyedNotification,
+                        )
+                    };
+                }
             }
             Self::Announcement { text, priority } => {
                 let view = match context.view.load() {
@@ -106,11 +114,18 @@ impl QueuedEvent {
 pub struct QueuedEvents {
     context: Rc<Context>,
     events: Vec<QueuedEvent>,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
 }
 
 impl QueuedEvents {
     pub(crate) fn new(context: Rc<Context>, events: Vec<QueuedEvent>) -> Self {
-        Self { context, events }
+        Self {
+            context,
+            events,
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::current(),
+        }
     }
 
     /// Raise all queued events synchronously.
@@ -120,6 +135,13 @@ impl QueuedEvents {
     /// or runtime borrows required to access the adapter must not
     /// be held while this method is called.
     pub fn raise(self) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            parent: &self.span,
+            "accesskit_raise_events",
+            event_count = self.events.len()
+        )
+        .entered();
         for event in self.events {
             event.raise(&self.context);
         }
@@ -133,6 +155,18 @@ pub(crate) fn focus_event(node_id: NodeId) -> QueuedEvent {
     }
 }
 
+pub(crate) fn announcement_event(text: String, kind: AnnouncementKind) -> QueuedEvent {
+    QueuedEvent::Announcement {
+        text,
+        priority: match kind {
+            AnnouncementKind::Polite => NSAccessibilityPriorityLevel::NSAccessibilityPriorityMedium,
+            AnnouncementKind::Assertive => {
+                NSAccessibilityPriorityLevel::NSAccessibilityPriorityHigh
+            }
+        },
+    }
+}
+
 pub(crate) struct EventGenerator {
     context: Rc<Context>,
     events: Vec<QueuedEvent>,
@@ -189,9 +223,14 @@ impl TreeChangeHandler for EventGenerator {
         if filter(node) != FilterResult::Include {
             return;
         }
-        if node.value().is_some() && node.live() != Live::Off {
-            self.events
-                .push(QueuedEvent::live_region_announcement(node));
+        let text = node
+            .value()
+            .or_else(|| node.is_notification().then(|| node.label()).flatten());
+        if let Some(text) = text {
+            if node.live() != Live::Off {
+                self.events
+                    .push(QueuedEvent::live_region_announcement(node, text));
+            }
         }
     }
 
@@ -219,24 +258,51 @@ impl TreeChangeHandler for EventGenerator {
         }
         if old_wrapper.supports_text_ranges()
             && new_wrapper.supports_text_ranges()
-            && old_wrapper.raw_text_selection() != new_wrapper.raw_text_selection()
+            && (old_wrapper.raw_text_selection() != new_wrapper.raw_text_selection()
+                || old_wrapper.raw_text_selections() != new_wrapper.raw_text_selections())
         {
             self.events.push(QueuedEvent::Generic {
                 node_id,
                 notification: unsafe { NSAccessibilitySelectedTextChangedNotification },
             });
         }
-        if new_node.value().is_some()
-            && new_node.live() != Live::Off
-            && (new_node.value() != old_node.value()
-                || new_node.live() != old_node.live()
-                || filter(old_node) != FilterResult::Include)
-        {
-            self.events
-                .push(QueuedEvent::live_region_announcement(new_node));
+        if let Some(new_value) = new_node.value() {
+            if new_node.live() != Live::Off {
+                let text = if new_node.live() != old_node.live()
+                    || filter(old_node) != FilterResult::Include
+                {
+                    Some(new_value)
+                } else {
+                    let old_value = old_node.value().unwrap_or_default();
+                    LiveRegionChange::new(&old_value, &new_value)
+                        .map(|change| change.text().to_string())
+                };
+                if let Some(text) = text {
+                    self.events
+                        .push(QueuedEvent::live_region_announcement(new_node, text));
+                }
+            }
+        }
+        let now = self.context.creation_time.elapsed();
+        let progress = self
+            .context
+            .progress
+            .borrow_mut()
+            .node_updated(old_node, new_node, now);
+        if let Some(text) = progress {
+            self.events.push(QueuedEvent::Announcement {
+                text,
+                priority: NSAccessibilityPriorityLevel::NSAccessibilityPriorityLow,
+            });
         }
     }
 
+    fn bounds_changed(&mut self, _old_node: &Node, _new_node: &Node) {
+        // VoiceOver reads the frame of an element when it needs it,
+        // and there's no notification for an element other than
+        // a window moving or resizing.
+    }
+
     fn focus_moved(&mut self, _old_node: Option<&Node>, new_node: Option<&Node>) {
         if let Some(new_node) = new_node {
             if filter(new_node) != FilterResult::Include {
@@ -248,6 +314,7 @@ impl TreeChangeHandler for EventGenerator {
 
     fn node_removed(&mut self, node: &Node) {
         self.insert_text_change_if_needed(node);
+        self.context.progress.borrow_mut().node_removed(node);
         self.events.push(QueuedEvent::NodeDestroyed(node.id()));
     }
 }
@@ -5,9 +5,12 @@
 
 #![deny(unsafe_op_in_unsafe_fn)]
 
+mod capabilities;
+mod column;
 mod context;
 mod filters;
 mod node;
+mod text_marker;
 mod util;
 
 mod adapter;
@@ -22,4 +25,6 @@ pub use patch::add_focus_forwarder_to_window_class;
 mod subclass;
 pub use subclass::SubclassingAdapter;
 
+pub use capabilities::CAPABILITIES;
+
 pub use objc2_foundation::{NSArray, NSObject, NSPoint};
@@ -15,7 +15,7 @@ use accesskit::{
 };
 use accesskit_consumer::{FilterResult, Node};
 use objc2::{
-    declare_class, msg_send_id,
+    declare_class, msg_send, msg_send_id,
     mutability::InteriorMutable,
     rc::Id,
     runtime::{AnyObject, Sel},
@@ -24,11 +24,11 @@ use objc2::{
 use objc2_app_kit::*;
 use objc2_foundation::{
     ns_string, NSArray, NSCopying, NSInteger, NSNumber, NSObject, NSPoint, NSRange, NSRect,
-    NSString,
+    NSString, NSValue,
 };
 use std::rc::{Rc, Weak};
 
-use crate::{context::Context, filters::filter, util::*};
+use crate::{column::PlatformColumn, context::Context, filters::filter, text_marker, util::*};
 
 fn ns_role(node: &Node) -> &'static NSAccessibilityRole {
     let role = node.role();
@@ -309,7 +309,11 @@ impl NodeWrapper<'_> {
             // includes a title, VoiceOver behavior is broken.
             return None;
         }
-        self.0.label()
+        if self.0.label_comes_from_value() {
+            // The text of these nodes is exposed as the value.
+            return self.0.label();
+        }
+        self.0.name()
     }
 
     pub(crate) fn description(&self) -> Option<String> {
@@ -317,7 +321,14 @@ impl NodeWrapper<'_> {
     }
 
     pub(crate) fn placeholder(&self) -> Option<&str> {
-        self.0.placeholder()
+        self.0.placeholder_unless_name()
+    }
+
+    pub(crate) fn help(&self) -> Option<String> {
+        self.0
+            .help_text()
+            .map(str::to_string)
+            .or_else(|| self.description())
     }
 
     pub(crate) fn value(&self) -> Option<Value> {
@@ -340,6 +351,68 @@ impl NodeWrapper<'_> {
     pub(crate) fn raw_text_selection(&self) -> Option<&TextSelection> {
         self.0.raw_text_selection()
     }
+
+    pub(crate) fn raw_text_selections(&self) -> &[TextSelection] {
+        self.0.raw_text_selections()
+    }
+}
+
+const GRABBED_ATTRIBUTE: &str = "AXGrabbed";
+// NSAccessibility has no standard pronunciation attribute.
+const PRONUNCIATION_ATTRIBUTE: &str = "AXAccessKitPronunciation";
+
+fn is_draggable(node: &Node) -> bool {
+    node.supports_drag() || node.is_grabbed()
+}
+
+/// AppKit has no drag-and-drop actions, so they are exposed under
+/// their own names, along with a description for VoiceOver to present.
+const DRAG_ACTION: &str = "AXDrag";
+const DROP_ACTION: &str = "AXDrop";
+
+fn drag_and_drop_actions(node: &Node) -> Vec<&'static str> {
+    let mut names = Vec::new();
+    if node.supports_drag() {
+        names.push(DRAG_ACTION);
+    }
+    if node.supports_drop() {
+        names.push(DROP_ACTION);
+    }
+    names
+}
+
+fn drag_and_drop_action(node: &Node, name: &str) -> Option<Action> {
+    match name {
+        DRAG_ACTION if node.supports_drag() => Some(Action::Drag),
+        DROP_ACTION if node.supports_drop() => Some(Action::Drop),
+        _ => None,
+    }
+}
+
+fn append_names(names: &NSArray<NSString>, extra: &[&str]) -> Id<NSArray<NSString>> {
+    let mut names = names.to_vec_retained();
+    names.extend(extra.iter().map(|name| NSString::from_str(name)));
+    NSArray::from_vec(names)
+}
+
+fn platform_node_array(context: &Rc<Context>, nodes: Vec<Node>) -> Id<NSArray<PlatformNode>> {
+    let platform_nodes = nodes
+        .into_iter()
+        .map(|node| context.get_or_create_platform_node(node.id()))
+        .collect::<Vec<Id<PlatformNode>>>();
+    NSArray::from_vec(platform_nodes)
+}
+
+/// Returns the index of the given row within its table.
+fn row_index(node: &Node) -> Option<usize> {
+    if node.role() != Role::Row {
+        return None;
+    }
+    let table = node.containing_table()?;
+    table
+        .table_rows()
+        .iter()
+        .position(|row| row.id() == node.id())
 }
 
 pub(crate) struct PlatformNodeIvars {
@@ -367,6 +440,8 @@ declare_class!(
             self.resolve_with_context(|node, context| {
                 if let Some(parent) = node.filtered_parent(&filter) {
                     Some(Id::into_super(Id::into_super(Id::into_super(context.get_or_create_platform_node(parent.id())))))
+                } else if let Some(host_parent) = context.host_parent.borrow().as_ref() {
+                    host_parent.load().map(Id::into_super)
                 } else {
                     context
                         .view
@@ -428,7 +503,7 @@ declare_class!(
                             NSRect::ZERO
                         }
                     },
-                    |rect| to_ns_rect(&view, rect),
+                    |rect| to_ns_rect(&view, context.content_origin.get(), rect),
                 )
             })
             .unwrap_or(NSRect::ZERO)
@@ -478,10 +553,10 @@ declare_class!(
         }
 
         #[method_id(accessibilityHelp)]
-        fn description(&self) -> Option<Id<NSString>> {
+        fn help(&self) -> Option<Id<NSString>> {
             self.resolve(|node| {
                 let wrapper = NodeWrapper(node);
-                wrapper.description().map(|description| NSString::from_str(&description))
+                wrapper.help().map(|help| NSString::from_str(&help))
             })
             .flatten()
         }
@@ -681,6 +756,23 @@ declare_class!(
             .unwrap_or_else(|| NSRange::new(0, 0))
         }
 
+        #[method_id(accessibilitySelectedTextRanges)]
+        fn selected_text_ranges(&self) -> Option<Id<NSArray<NSValue>>> {
+            self.resolve(|node| {
+                if node.supports_text_ranges() {
+                    let values = node
+                        .text_selections()
+                        .map(|range| NSValue::new(to_ns_range(&range)))
+                        .collect::<Vec<_>>();
+                    if !values.is_empty() {
+                        return Some(NSArray::from_vec(values));
+                    }
+                }
+                None
+            })
+            .flatten()
+        }
+
         #[method(accessibilityInsertionPointLineNumber)]
         fn insertion_point_line_number(&self) -> NSInteger {
             self.resolve(|node| {
@@ -718,7 +810,7 @@ declare_class!(
                 };
 
                 if node.supports_text_ranges() {
-                    let point = from_ns_point(&view, node, point);
+                    let point = from_ns_point(&view, context.content_origin.get(), node, point);
                     let pos = node.text_position_at_point(point);
                     return to_ns_range_for_character(&pos);
                 }
@@ -757,7 +849,7 @@ declare_class!(
                         if let Some(rect) =
                             rects.into_iter().reduce(|rect1, rect2| rect1.union(rect2))
                         {
-                            return to_ns_rect(&view, rect);
+                            return to_ns_rect(&view, context.content_origin.get(), rect);
                         }
                     }
                 }
@@ -807,6 +899,221 @@ declare_class!(
             });
         }
 
+        #[method_id(accessibilityAttributeNames)]
+        fn attribute_names(&self) -> Id<NSArray<NSString>> {
+            let names: Id<NSArray<NSString>> =
+                unsafe { msg_send_id![super(self), accessibilityAttributeNames] };
+            let extra = self
+                .resolve(|node| {
+                    let mut extra = Vec::new();
+                    if node.supports_text_ranges() {
+                        extra.extend_from_slice(text_marker::ATTRIBUTES);
+                    }
+                    if is_draggable(node) {
+                        extra.push(GRABBED_ATTRIBUTE);
+                    }
+                    if node.pronunciation().is_some() {
+                        extra.push(PRONUNCIATION_ATTRIBUTE);
+                    }
+                    extra
+                })
+                .unwrap_or_default();
+            append_names(&names, &extra)
+        }
+
+        #[method_id(accessibilityAttributeValue:)]
+        fn attribute_value(&self, attribute: &NSString) -> Option<Id<AnyObject>> {
+            let value = self
+                .resolve(|node| {
+                    let attribute = attribute.to_string();
+                    if attribute == GRABBED_ATTRIBUTE && is_draggable(node) {
+                        let grabbed = NSNumber::new_bool(node.is_grabbed());
+                        return Some(Id::into_super(Id::into_super(Id::into_super(grabbed))));
+                    }
+                    if attribute == PRONUNCIATION_ATTRIBUTE {
+                        if let Some(pronunciation) = node.pronunciation() {
+                            let pronunciation = NSString::from_str(pronunciation);
+                            return Some(Id::into_super(Id::into_super(pronunciation)));
+                        }
+                    }
+                    if !node.supports_text_ranges() {
+                        return None;
+                    }
+                    text_marker::attribute_value(node, &attribute)
+                })
+                .flatten();
+            value.or_else(|| unsafe { msg_send_id![super(self), accessibilityAttributeValue: attribute] })
+        }
+
+        #[method_id(accessibilityActionNames)]
+        fn action_names(&self) -> Id<NSArray<NSString>> {
+            let names: Id<NSArray<NSString>> =
+                unsafe { msg_send_id![super(self), accessibilityActionNames] };
+            let extra = self.resolve(drag_and_drop_actions).unwrap_or_default();
+            append_names(&names, &extra)
+        }
+
+        #[method_id(accessibilityActionDescription:)]
+        fn action_description(&self, action: &NSString) -> Option<Id<NSString>> {
+            let description = self
+                .resolve(|node| {
+                    drag_and_drop_action(node, &action.to_string()).map(|action| match action {
+                        Action::Drag => ns_string!("drag"),
+                        _ => ns_string!("drop"),
+                    })
+                })
+                .flatten();
+            match description {
+                Some(description) => Some(description.copy()),
+                None => unsafe { msg_send_id![super(self), accessibilityActionDescription: action] },
+            }
+        }
+
+        #[method(accessibilityPerformAction:)]
+        fn perform_action(&self, action: &NSString) {
+            let is_handled = self
+                .resolve_with_context(|node, context| {
+                    let request = ActionRequest {
+                        action: drag_and_drop_action(node, &action.to_string())?,
+                        target: node.id(),
+                        data: None,
+                    };
+                    context.do_action(request);
+                    Some(())
+                })
+                .flatten()
+                .is_some();
+            if !is_handled {
+                unsafe { msg_send![super(self), accessibilityPerformAction: action] }
+            }
+        }
+
+        #[method_id(accessibilityParameterizedAttributeNames)]
+        fn parameterized_attribute_names(&self) -> Id<NSArray<NSString>> {
+            let names: Id<NSArray<NSString>> =
+                unsafe { msg_send_id![super(self), accessibilityParameterizedAttributeNames] };
+            if self.resolve(|node| node.supports_text_ranges()).unwrap_or(false) {
+                return append_names(&names, text_marker::PARAMETERIZED_ATTRIBUTES);
+            }
+            names
+        }
+
+        #[method_id(accessibilityAttributeValue:forParameter:)]
+        fn parameterized_attribute_value(
+            &self,
+            attribute: &NSString,
+            parameter: Option<&AnyObject>,
+        ) -> Option<Id<AnyObject>> {
+            let value = self
+                .resolve_with_context(|node, context| {
+                    if !node.supports_text_ranges() {
+                        return None;
+                    }
+                    text_marker::parameterized_attribute_value(
+                        node,
+                        context,
+                        &attribute.to_string(),
+                        parameter?,
+                    )
+                })
+                .flatten();
+            value.or_else(|| unsafe {
+                msg_send_id![super(self), accessibilityAttributeValue: attribute, forParameter: parameter]
+            })
+        }
+
+        #[method(accessibilityRowCount)]
+        fn row_count(&self) -> NSInteger {
+            self.resolve(|node| node.table_row_count() as _)
+                .unwrap_or(0)
+        }
+
+        #[method(accessibilityColumnCount)]
+        fn column_count(&self) -> NSInteger {
+            self.resolve(|node| node.table_column_count() as _)
+                .unwrap_or(0)
+        }
+
+        #[method_id(accessibilityRows)]
+        fn rows(&self) -> Option<Id<NSArray<PlatformNode>>> {
+            self.resolve_with_context(|node, context| {
+                platform_node_array(context, node.table_rows())
+            })
+        }
+
+        #[method_id(accessibilityColumns)]
+        fn columns(&self) -> Option<Id<NSArray<PlatformColumn>>> {
+            self.resolve_with_context(|node, context| {
+                let columns = (0..node.table_column_count())
+                    .map(|index| context.get_or_create_platform_column(node.id(), index))
+                    .collect::<Vec<Id<PlatformColumn>>>();
+                NSArray::from_vec(columns)
+            })
+        }
+
+        #[method_id(accessibilitySelectedRows)]
+        fn selected_rows(&self) -> Option<Id<NSArray<PlatformNode>>> {
+            self.resolve_with_context(|node, context| {
+                let rows = node
+                    .table_rows()
+                    .into_iter()
+                    .filter(|row| row.is_selected() == Some(true))
+                    .collect();
+                platform_node_array(context, rows)
+            })
+        }
+
+        #[method_id(accessibilityRowHeaderUIElements)]
+        fn row_header_elements(&self) -> Option<Id<NSArray<PlatformNode>>> {
+            self.resolve_with_context(|node, context| {
+                platform_node_array(context, node.table_headers(Role::RowHeader))
+            })
+        }
+
+        #[method_id(accessibilityColumnHeaderUIElements)]
+        fn column_header_elements(&self) -> Option<Id<NSArray<PlatformNode>>> {
+            self.resolve_with_context(|node, context| {
+                platform_node_array(context, node.table_headers(Role::ColumnHeader))
+            })
+        }
+
+        #[method_id(accessibilityCellForColumn:row:)]
+        fn cell_for_column_row(&self, column: NSInteger, row: NSInteger) -> Option<Id<PlatformNode>> {
+            let column = usize::try_from(column).ok()?;
+            let row = usize::try_from(row).ok()?;
+            self.resolve_with_context(|node, context| {
+                node.table_cell_at(row, column)
+                    .map(|cell| context.get_or_create_platform_node(cell.id()))
+            })
+            .flatten()
+        }
+
+        #[method(accessibilityRowIndexRange)]
+        fn row_index_range(&self) -> NSRange {
+            self.resolve(|node| {
+                node.table_cell_position()
+                    .map(|position| NSRange::new(position.row_index, position.row_span))
+            })
+            .flatten()
+            .unwrap_or_else(|| NSRange::new(0, 0))
+        }
+
+        #[method(accessibilityColumnIndexRange)]
+        fn column_index_range(&self) -> NSRange {
+            self.resolve(|node| {
+                node.table_cell_position()
+                    .map(|position| NSRange::new(position.column_index, position.column_span))
+            })
+            .flatten()
+            .unwrap_or_else(|| NSRange::new(0, 0))
+        }
+
+        #[method(accessibilityIndex)]
+        fn index(&self) -> NSInteger {
+            self.resolve(|node| row_index(node).map_or(0, |index| index as _))
+                .unwrap_or(0)
+        }
+
         #[method(isAccessibilityRequired)]
         fn is_required(&self) -> bool {
             self.resolve(|node| node.is_required())
@@ -831,6 +1138,7 @@ declare_class!(
                 if selector == sel!(accessibilityNumberOfCharacters)
                     || selector == sel!(accessibilitySelectedText)
                     || selector == sel!(accessibilitySelectedTextRange)
+                    || selector == sel!(accessibilitySelectedTextRanges)
                     || selector == sel!(accessibilityInsertionPointLineNumber)
                     || selector == sel!(accessibilityRangeForLine:)
                     || selector == sel!(accessibilityRangeForPosition:)
@@ -842,6 +1150,28 @@ declare_class!(
                 {
                     return node.supports_text_ranges();
                 }
+                if selector == sel!(accessibilityRowCount)
+                    || selector == sel!(accessibilityColumnCount)
+                    || selector == sel!(accessibilityRows)
+                    || selector == sel!(accessibilityColumns)
+                    || selector == sel!(accessibilitySelectedRows)
+                    || selector == sel!(accessibilityCellForColumn:row:)
+                {
+                    return node.is_table();
+                }
+                if selector == sel!(accessibilityRowIndexRange)
+                    || selector == sel!(accessibilityColumnIndexRange)
+                {
+                    return node.table_cell_position().is_some();
+                }
+                if selector == sel!(accessibilityRowHeaderUIElements)
+                    || selector == sel!(accessibilityColumnHeaderUIElements)
+                {
+                    return node.is_table() || node.table_cell_position().is_some();
+                }
+                if selector == sel!(accessibilityIndex) {
+                    return row_index(node).is_some();
+                }
                 if selector == sel!(setAccessibilityValue:) {
                     // Our implementation of this currently does nothing,
                     // and it's not clear if VoiceOver ever actually uses it,
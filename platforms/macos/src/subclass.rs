@@ -3,7 +3,9 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-use accesskit::{ActionHandler, ActivationHandler, TreeUpdate};
+use accesskit::{
+    ActionHandler, ActivationHandler, AnnouncementKind, GeometryUpdate, TreeUpdate, Vec2,
+};
 use objc2::{
     declare::ClassBuilder,
     declare_class,
@@ -115,6 +117,55 @@ unsafe extern "C" fn hit_test(this: &NSView, _cmd: Sel, point: NSPoint) -> *mut
 
 /// Uses dynamic Objective-C subclassing to implement the `NSView`
 /// accessibility methods when normal subclassing isn't an option.
+///
+/// # Example
+///
+/// Everything happens on the main thread. The activation handler is called
+/// from the view's accessibility methods while the adapter is in use, so it
+/// must not call back into the adapter. Likewise, queued events must be
+/// raised after releasing any borrow of the adapter, since VoiceOver may
+/// call the view's accessibility methods while they're raised.
+///
+/// This only shows the parts that are specific to this adapter; see the
+/// [`accesskit_unix` documentation](https://docs.rs/accesskit_unix) for a
+/// complete example of building a tree and implementing the handlers.
+///
+/// ```no_run
+/// # use accesskit::{ActionHandler, ActionRequest, ActivationHandler, TreeUpdate};
+/// use accesskit_macos::SubclassingAdapter;
+/// use std::{cell::RefCell, ffi::c_void};
+/// #
+/// # fn build_tree() -> TreeUpdate {
+/// #     unimplemented!()
+/// # }
+/// #
+/// # struct Activation;
+/// #
+/// # impl ActivationHandler for Activation {
+/// #     fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+/// #         Some(build_tree())
+/// #     }
+/// # }
+/// #
+/// # struct Actions;
+/// #
+/// # impl ActionHandler for Actions {
+/// #     fn do_action(&mut self, _request: ActionRequest) {}
+/// # }
+///
+/// /// `view` must be a valid `NSView` pointer that hasn't been shown yet.
+/// unsafe fn attach(view: *mut c_void) -> RefCell<SubclassingAdapter> {
+///     RefCell::new(unsafe { SubclassingAdapter::new(view, Activation, Actions) })
+/// }
+///
+/// /// Called on the main thread whenever the UI changes.
+/// fn update(adapter: &RefCell<SubclassingAdapter>) {
+///     let events = adapter.borrow_mut().update_if_active(build_tree);
+///     if let Some(events) = events {
+///         events.raise();
+///     }
+/// }
+/// ```
 pub struct SubclassingAdapter {
     view: Id<NSView>,
     associated: Id<AssociatedObject>,
@@ -238,6 +289,20 @@ impl SubclassingAdapter {
         state.adapter.update_if_active(update_factory)
     }
 
+    /// If and only if the tree has been initialized, call the provided function
+    /// and apply the resulting [`GeometryUpdate`].
+    /// See [`Adapter::update_geometry_if_active`] for details.
+    ///
+    /// If a [`QueuedEvents`] instance is returned, the caller must call
+    /// [`QueuedEvents::raise`] on it.
+    pub fn update_geometry_if_active(
+        &mut self,
+        update_factory: impl FnOnce() -> GeometryUpdate,
+    ) -> Option<QueuedEvents> {
+        let mut state = self.associated.ivars().state.borrow_mut();
+        state.adapter.update_geometry_if_active(update_factory)
+    }
+
     /// Update the tree state based on whether the window is focused.
     ///
     /// If a [`QueuedEvents`] instance is returned, the caller must call
@@ -246,6 +311,24 @@ impl SubclassingAdapter {
         let mut state = self.associated.ivars().state.borrow_mut();
         state.adapter.update_view_focus_state(is_focused)
     }
+
+    /// Ask assistive technologies to speak the given text.
+    /// See [`Adapter::announce`] for details.
+    ///
+    /// If a [`QueuedEvents`] instance is returned, the caller must call
+    /// [`QueuedEvents::raise`] on it.
+    pub fn announce(&mut self, text: &str, kind: AnnouncementKind) -> Option<QueuedEvents> {
+        let mut state = self.associated.ivars().state.borrow_mut();
+        state.adapter.announce(text, kind)
+    }
+
+    /// Set the offset of the origin of the node coordinate space
+    /// from the top-left corner of the view.
+    /// See [`Adapter::set_content_origin`] for details.
+    pub fn set_content_origin(&mut self, origin: Vec2) {
+        let mut state = self.associated.ivars().state.borrow_mut();
+        state.adapter.set_content_origin(origin);
+    }
 }
 
 impl Drop for SubclassingAdapter {
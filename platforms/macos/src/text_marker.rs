@@ -0,0 +1,506 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+// Derived from Chromium's accessibility abstraction.
+// Copyright 2018 The Chromium Authors. All rights reserved.
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE.chromium file.
+
+//! The text marker attributes that VoiceOver uses to navigate text.
+//!
+//! A text marker is an opaque blob of bytes that only we interpret.
+//! Ours hold the ID of the node that supports text ranges and a UTF-16
+//! offset into that node's text, so they remain meaningful across tree
+//! updates for as long as the offset is within the text.
+//!
+//! VoiceOver may pass any element a marker that it got from another one,
+//! so markers are resolved against the whole tree. When a marker is moved
+//! past the start or end of its node's text, it continues into the previous
+//! or next node that supports text ranges, which lets continuous reading
+//! cross elements. A marker range must still start and end in the same node.
+
+use accesskit::NodeId;
+use accesskit_consumer::{FilterResult, Node, TextPosition, TextRange, TreeState};
+use objc2::{msg_send, rc::Id, runtime::AnyObject, ClassType};
+use objc2_foundation::{NSArray, NSAttributedString, NSNumber, NSString, NSValue};
+use std::{ffi::c_void, rc::Rc};
+
+use crate::{context::Context, filters::filter, util::*};
+
+type CFTypeRef = *const c_void;
+type CFTypeID = usize;
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    fn CFGetTypeID(cf: CFTypeRef) -> CFTypeID;
+    fn CFRelease(cf: CFTypeRef);
+}
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    fn AXTextMarkerGetTypeID() -> CFTypeID;
+    fn AXTextMarkerCreate(allocator: CFTypeRef, bytes: *const u8, length: isize) -> CFTypeRef;
+    fn AXTextMarkerGetLength(marker: CFTypeRef) -> isize;
+    fn AXTextMarkerGetBytePtr(marker: CFTypeRef) -> *const u8;
+    fn AXTextMarkerRangeGetTypeID() -> CFTypeID;
+    fn AXTextMarkerRangeCreate(
+        allocator: CFTypeRef,
+        start_marker: CFTypeRef,
+        end_marker: CFTypeRef,
+    ) -> CFTypeRef;
+    fn AXTextMarkerRangeCopyStartMarker(range: CFTypeRef) -> CFTypeRef;
+    fn AXTextMarkerRangeCopyEndMarker(range: CFTypeRef) -> CFTypeRef;
+}
+
+const MARKER_LENGTH: usize = 16;
+
+pub(crate) const ATTRIBUTES: &[&str] = &[
+    "AXStartTextMarker",
+    "AXEndTextMarker",
+    "AXSelectedTextMarkerRange",
+];
+
+pub(crate) const PARAMETERIZED_ATTRIBUTES: &[&str] = &[
+    "AXStringForTextMarkerRange",
+    "AXAttributedStringForTextMarkerRange",
+    "AXLengthForTextMarkerRange",
+    "AXBoundsForTextMarkerRange",
+    "AXTextMarkerRangeForUnorderedTextMarkers",
+    "AXNextTextMarkerForTextMarker",
+    "AXPreviousTextMarkerForTextMarker",
+    "AXLeftWordTextMarkerRangeForTextMarker",
+    "AXRightWordTextMarkerRangeForTextMarker",
+    "AXNextWordEndTextMarkerForTextMarker",
+    "AXPreviousWordStartTextMarkerForTextMarker",
+    "AXLineTextMarkerRangeForTextMarker",
+    "AXNextLineEndTextMarkerForTextMarker",
+    "AXPreviousLineStartTextMarkerForTextMarker",
+    "AXParagraphTextMarkerRangeForTextMarker",
+    "AXNextParagraphEndTextMarkerForTextMarker",
+    "AXPreviousParagraphStartTextMarkerForTextMarker",
+    "AXTextMarkerForPosition",
+    "AXIndexForTextMarker",
+    "AXTextMarkerForIndex",
+];
+
+fn create_marker(node: &Node, index: usize) -> Id<AnyObject> {
+    let mut bytes = [0u8; MARKER_LENGTH];
+    bytes[..8].copy_from_slice(&node.id().0.to_ne_bytes());
+    bytes[8..].copy_from_slice(&(index as u64).to_ne_bytes());
+    let marker =
+        unsafe { AXTextMarkerCreate(std::ptr::null(), bytes.as_ptr(), MARKER_LENGTH as isize) };
+    // SAFETY: The marker was just created, so we own it, and CF objects
+    // are toll-free bridged to Objective-C objects.
+    unsafe { Id::from_raw(marker as *mut AnyObject) }.unwrap()
+}
+
+fn to_text_marker(node: &Node, pos: &TextPosition) -> Id<AnyObject> {
+    create_marker(node, pos.to_global_utf16_index())
+}
+
+fn to_text_marker_range(range: &TextRange) -> Id<AnyObject> {
+    let start = to_text_marker(range.node(), &range.start());
+    let end = to_text_marker(range.node(), &range.end());
+    let range = unsafe {
+        AXTextMarkerRangeCreate(
+            std::ptr::null(),
+            Id::as_ptr(&start) as CFTypeRef,
+            Id::as_ptr(&end) as CFTypeRef,
+        )
+    };
+    // SAFETY: Same as in `create_marker`.
+    unsafe { Id::from_raw(range as *mut AnyObject) }.unwrap()
+}
+
+fn downcast<T: ClassType>(object: &AnyObject) -> Option<&T> {
+    let is_kind: bool = unsafe { msg_send![object, isKindOfClass: T::class()] };
+    // SAFETY: We just checked the class of the object.
+    is_kind.then(|| unsafe { &*(object as *const AnyObject).cast::<T>() })
+}
+
+fn is_of_type(object: &AnyObject, type_id: CFTypeID) -> bool {
+    unsafe { CFGetTypeID(object as *const AnyObject as CFTypeRef) == type_id }
+}
+
+/// Returns the node and UTF-16 offset that the given marker refers to,
+/// if the marker is ours and the node still supports text ranges.
+fn resolve_marker(state: &TreeState, marker: CFTypeRef) -> Option<(Node<'_>, usize)> {
+    if marker.is_null() || unsafe { AXTextMarkerGetLength(marker) } != MARKER_LENGTH as isize {
+        return None;
+    }
+    let bytes =
+        unsafe { std::slice::from_raw_parts(AXTextMarkerGetBytePtr(marker), MARKER_LENGTH) };
+    let node_id = u64::from_ne_bytes(bytes[..8].try_into().unwrap());
+    let node = state.node_by_id(NodeId(node_id))?;
+    if !node.supports_text_ranges() {
+        return None;
+    }
+    let index = u64::from_ne_bytes(bytes[8..].try_into().unwrap());
+    Some((node, index.try_into().ok()?))
+}
+
+fn resolve_marker_object<'a>(
+    state: &'a TreeState,
+    marker: &AnyObject,
+) -> Option<(Node<'a>, usize)> {
+    if !is_of_type(marker, unsafe { AXTextMarkerGetTypeID() }) {
+        return None;
+    }
+    resolve_marker(state, marker as *const AnyObject as CFTypeRef)
+}
+
+/// Calls the given function with the node and position that
+/// the given marker refers to.
+fn with_marker<T>(
+    state: &TreeState,
+    marker: &AnyObject,
+    f: impl FnOnce(&Node, TextPosition) -> Option<T>,
+) -> Option<T> {
+    let (node, index) = resolve_marker_object(state, marker)?;
+    let pos = node.text_position_from_global_utf16_index(index)?;
+    f(&node, pos)
+}
+
+/// Calls the given function with the range that the given marker range
+/// refers to, if both of its markers are in the same node.
+fn with_marker_range<T>(
+    state: &TreeState,
+    range: &AnyObject,
+    f: impl FnOnce(TextRange) -> Option<T>,
+) -> Option<T> {
+    if !is_of_type(range, unsafe { AXTextMarkerRangeGetTypeID() }) {
+        return None;
+    }
+    let range = range as *const AnyObject as CFTypeRef;
+    let (start, end) = unsafe {
+        let start_marker = AXTextMarkerRangeCopyStartMarker(range);
+        let end_marker = AXTextMarkerRangeCopyEndMarker(range);
+        let start = resolve_marker(state, start_marker);
+        let end = resolve_marker(state, end_marker);
+        for marker in [start_marker, end_marker] {
+            if !marker.is_null() {
+                CFRelease(marker);
+            }
+        }
+        (start?, end?)
+    };
+    let ((node, start), (end_node, end)) = (start, end);
+    if end_node.id() != node.id() {
+        return None;
+    }
+    let start = node.text_position_from_global_utf16_index(start)?;
+    let end = node.text_position_from_global_utf16_index(end)?;
+    f(ordered_range(start, end))
+}
+
+fn is_text_node(node: &Node) -> bool {
+    node.supports_text_ranges() && filter(node) == FilterResult::Include
+}
+
+/// Returns the next node in tree order that supports text ranges,
+/// skipping the descendants of the given node, which are its text runs.
+fn next_text_node<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    let mut current = *node;
+    let mut skip_children = true;
+    loop {
+        let child = (!skip_children)
+            .then(|| current.children().next())
+            .flatten();
+        current = match child {
+            Some(child) => child,
+            None => loop {
+                if let Some(sibling) = current.following_siblings().next() {
+                    break sibling;
+                }
+                current = current.parent()?;
+            },
+        };
+        if is_text_node(&current) {
+            return Some(current);
+        }
+        skip_children = false;
+    }
+}
+
+/// Returns the previous node in tree order that supports text ranges.
+fn previous_text_node<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    let mut current = *node;
+    loop {
+        current = match current.preceding_siblings().next() {
+            Some(mut sibling) => {
+                while !is_text_node(&sibling) {
+                    match sibling.children().next_back() {
+                        Some(child) => sibling = child,
+                        None => break,
+                    }
+                }
+                sibling
+            }
+            None => current.parent()?,
+        };
+        if is_text_node(&current) {
+            return Some(current);
+        }
+    }
+}
+
+/// A unit of text that markers can be moved by.
+#[derive(Clone, Copy)]
+enum Unit {
+    Character,
+    Word,
+    Line,
+    Paragraph,
+}
+
+impl Unit {
+    fn forward<'a>(self, pos: &TextPosition<'a>) -> TextPosition<'a> {
+        match self {
+            Self::Character => pos.forward_to_character_end(),
+            Self::Word => pos.forward_to_word_end(),
+            Self::Line => pos.forward_to_line_end(),
+            Self::Paragraph => pos.forward_to_paragraph_end(),
+        }
+    }
+
+    fn backward<'a>(self, pos: &TextPosition<'a>) -> TextPosition<'a> {
+        match self {
+            Self::Character => pos.backward_to_character_start(),
+            Self::Word => pos.backward_to_word_start(),
+            Self::Line => pos.backward_to_line_start(),
+            Self::Paragraph => pos.backward_to_paragraph_start(),
+        }
+    }
+}
+
+/// Returns a marker for the end of the unit that follows the given
+/// position. At the end of the node's text, this continues from the start
+/// of the next node that supports text ranges.
+fn next_marker(node: &Node, pos: TextPosition, unit: Unit) -> Option<Id<AnyObject>> {
+    if !pos.is_document_end() {
+        return Some(to_text_marker(node, &unit.forward(&pos)));
+    }
+    let next = next_text_node(node)?;
+    let start = next.document_range().start();
+    if start.is_document_end() {
+        return Some(to_text_marker(&next, &start));
+    }
+    Some(to_text_marker(&next, &unit.forward(&start)))
+}
+
+/// Like [`next_marker`], but moving backward to the start of a unit,
+/// and continuing from the end of the previous node that supports
+/// text ranges.
+fn previous_marker(node: &Node, pos: TextPosition, unit: Unit) -> Option<Id<AnyObject>> {
+    if !pos.is_document_start() {
+        return Some(to_text_marker(node, &unit.backward(&pos)));
+    }
+    let previous = previous_text_node(node)?;
+    let end = previous.document_range().end();
+    if end.is_document_start() {
+        return Some(to_text_marker(&previous, &end));
+    }
+    Some(to_text_marker(&previous, &unit.backward(&end)))
+}
+
+fn ordered_range<'a>(start: TextPosition<'a>, end: TextPosition<'a>) -> TextRange<'a> {
+    let (start, end) = if end < start {
+        (end, start)
+    } else {
+        (start, end)
+    };
+    let mut range = start.to_degenerate_range();
+    range.set_end(end);
+    range
+}
+
+fn to_string_object(text: &str) -> Id<AnyObject> {
+    Id::into_super(Id::into_super(NSString::from_str(text)))
+}
+
+fn to_number_object(value: usize) -> Id<AnyObject> {
+    Id::into_super(Id::into_super(Id::into_super(
+        NSNumber::numberWithUnsignedInteger(value),
+    )))
+}
+
+/// Returns the range of the text unit that contains the given position,
+/// given the functions that find the boundaries of that unit.
+fn unit_range<'a>(
+    pos: TextPosition<'a>,
+    is_start: fn(&TextPosition<'a>) -> bool,
+    backward_to_start: fn(&TextPosition<'a>) -> TextPosition<'a>,
+    forward_to_end: fn(&TextPosition<'a>) -> TextPosition<'a>,
+) -> TextRange<'a> {
+    let start = if is_start(&pos) || pos.is_document_start() {
+        pos
+    } else {
+        backward_to_start(&pos)
+    };
+    let end = if start.is_document_end() {
+        start
+    } else {
+        forward_to_end(&start)
+    };
+    ordered_range(start, end)
+}
+
+fn word_range(pos: TextPosition) -> TextRange {
+    unit_range(
+        pos,
+        TextPosition::is_word_start,
+        TextPosition::backward_to_word_start,
+        TextPosition::forward_to_word_end,
+    )
+}
+
+fn line_range(pos: TextPosition) -> TextRange {
+    unit_range(
+        pos,
+        TextPosition::is_line_start,
+        TextPosition::backward_to_line_start,
+        TextPosition::forward_to_line_end,
+    )
+}
+
+fn paragraph_range(pos: TextPosition) -> TextRange {
+    unit_range(
+        pos,
+        TextPosition::is_paragraph_start,
+        TextPosition::backward_to_paragraph_start,
+        TextPosition::forward_to_paragraph_end,
+    )
+}
+
+pub(crate) fn attribute_value(node: &Node, attribute: &str) -> Option<Id<AnyObject>> {
+    match attribute {
+        "AXStartTextMarker" => {
+            let range = node.document_range();
+            Some(to_text_marker(node, &range.start()))
+        }
+        "AXEndTextMarker" => {
+            let range = node.document_range();
+            Some(to_text_marker(node, &range.end()))
+        }
+        "AXSelectedTextMarkerRange" => node
+            .text_selection()
+            .map(|range| to_text_marker_range(&range)),
+        _ => None,
+    }
+}
+
+pub(crate) fn parameterized_attribute_value(
+    node: &Node,
+    context: &Rc<Context>,
+    attribute: &str,
+    parameter: &AnyObject,
+) -> Option<Id<AnyObject>> {
+    let tree = context.tree.borrow();
+    let state = tree.state();
+    match attribute {
+        "AXStringForTextMarkerRange" => with_marker_range(state, parameter, |range| {
+            Some(to_string_object(&range.text()))
+        }),
+        "AXAttributedStringForTextMarkerRange" => with_marker_range(state, parameter, |range| {
+            let string = NSAttributedString::from_nsstring(&NSString::from_str(&range.text()));
+            Some(Id::into_super(Id::into_super(string)))
+        }),
+        "AXLengthForTextMarkerRange" => with_marker_range(state, parameter, |range| {
+            let length =
+                range.end().to_global_utf16_index() - range.start().to_global_utf16_index();
+            Some(to_number_object(length))
+        }),
+        "AXBoundsForTextMarkerRange" => with_marker_range(state, parameter, |range| {
+            let view = context.view.load()?;
+            let rect = range
+                .bounding_boxes()
+                .into_iter()
+                .reduce(|rect1, rect2| rect1.union(rect2))?;
+            let rect = to_ns_rect(&view, context.content_origin.get(), rect);
+            Some(Id::into_super(Id::into_super(NSValue::new(rect))))
+        }),
+        "AXTextMarkerRangeForUnorderedTextMarkers" => {
+            let markers = downcast::<NSArray>(parameter)?;
+            if markers.count() != 2 {
+                return None;
+            }
+            let (start_node, start) = resolve_marker_object(state, &markers.objectAtIndex(0))?;
+            let (end_node, end) = resolve_marker_object(state, &markers.objectAtIndex(1))?;
+            if start_node.id() != end_node.id() {
+                return None;
+            }
+            let start = start_node.text_position_from_global_utf16_index(start)?;
+            let end = start_node.text_position_from_global_utf16_index(end)?;
+            Some(to_text_marker_range(&ordered_range(start, end)))
+        }
+        "AXNextTextMarkerForTextMarker" => with_marker(state, parameter, |node, pos| {
+            next_marker(node, pos, Unit::Character)
+        }),
+        "AXPreviousTextMarkerForTextMarker" => with_marker(state, parameter, |node, pos| {
+            previous_marker(node, pos, Unit::Character)
+        }),
+        "AXLeftWordTextMarkerRangeForTextMarker" => with_marker(state, parameter, |_, pos| {
+            let pos = if pos.is_document_start() {
+                pos
+            } else {
+                pos.backward_to_character_start()
+            };
+            Some(to_text_marker_range(&word_range(pos)))
+        }),
+        "AXRightWordTextMarkerRangeForTextMarker" => with_marker(state, parameter, |_, pos| {
+            Some(to_text_marker_range(&word_range(pos)))
+        }),
+        "AXNextWordEndTextMarkerForTextMarker" => with_marker(state, parameter, |node, pos| {
+            next_marker(node, pos, Unit::Word)
+        }),
+        "AXPreviousWordStartTextMarkerForTextMarker" => {
+            with_marker(state, parameter, |node, pos| {
+                previous_marker(node, pos, Unit::Word)
+            })
+        }
+        "AXLineTextMarkerRangeForTextMarker" => with_marker(state, parameter, |_, pos| {
+            Some(to_text_marker_range(&line_range(pos)))
+        }),
+        "AXNextLineEndTextMarkerForTextMarker" => with_marker(state, parameter, |node, pos| {
+            next_marker(node, pos, Unit::Line)
+        }),
+        "AXPreviousLineStartTextMarkerForTextMarker" => {
+            with_marker(state, parameter, |node, pos| {
+                previous_marker(node, pos, Unit::Line)
+            })
+        }
+        "AXParagraphTextMarkerRangeForTextMarker" => with_marker(state, parameter, |_, pos| {
+            Some(to_text_marker_range(&paragraph_range(pos)))
+        }),
+        "AXNextParagraphEndTextMarkerForTextMarker" => {
+            with_marker(state, parameter, |node, pos| {
+                next_marker(node, pos, Unit::Paragraph)
+            })
+        }
+        "AXPreviousParagraphStartTextMarkerForTextMarker" => {
+            with_marker(state, parameter, |node, pos| {
+                previous_marker(node, pos, Unit::Paragraph)
+            })
+        }
+        "AXTextMarkerForPosition" => {
+            let point = downcast::<NSValue>(parameter)?.get_point()?;
+            let view = context.view.load()?;
+            let point = from_ns_point(&view, context.content_origin.get(), node, point);
+            let pos = node.text_position_at_point(point);
+            Some(to_text_marker(node, &pos))
+        }
+        "AXIndexForTextMarker" => with_marker(state, parameter, |marker_node, pos| {
+            // The index is an offset into this node's text.
+            (marker_node.id() == node.id()).then(|| to_number_object(pos.to_global_utf16_index()))
+        }),
+        "AXTextMarkerForIndex" => {
+            let number = downcast::<NSNumber>(parameter)?;
+            let index = usize::try_from(number.integerValue()).ok()?;
+            let pos = node.text_position_from_global_utf16_index(index)?;
+            Some(to_text_marker(node, &pos))
+        }
+        _ => None,
+    }
+}
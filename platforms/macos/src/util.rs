@@ -3,7 +3,7 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-use accesskit::{Point, Rect};
+use accesskit::{Point, Rect, Vec2};
 use accesskit_consumer::{Node, TextPosition, TextRange};
 use objc2_app_kit::*;
 use objc2_foundation::{NSPoint, NSRange, NSRect, NSSize};
@@ -33,7 +33,12 @@ pub(crate) fn to_ns_range_for_character(pos: &TextPosition) -> NSRange {
     to_ns_range(&range)
 }
 
-pub(crate) fn from_ns_point(view: &NSView, node: &Node, point: NSPoint) -> Point {
+pub(crate) fn from_ns_point(
+    view: &NSView,
+    content_origin: Vec2,
+    node: &Node,
+    point: NSPoint,
+) -> Point {
     let window = view.window().unwrap();
     let point = window.convertPointFromScreen(point);
     let point = view.convertPoint_fromView(point, None);
@@ -48,12 +53,13 @@ pub(crate) fn from_ns_point(view: &NSView, node: &Node, point: NSPoint) -> Point
             let view_bounds = view.bounds();
             (view_bounds.size.height - point.y) * factor
         },
-    );
+    ) - content_origin;
     node.transform().inverse() * point
 }
 
-pub(crate) fn to_ns_rect(view: &NSView, rect: Rect) -> NSRect {
+pub(crate) fn to_ns_rect(view: &NSView, content_origin: Vec2, rect: Rect) -> NSRect {
     let window = view.window().unwrap();
+    let rect = rect + content_origin;
     // AccessKit coordinates are in physical (DPI-dependent)
     // pixels, but macOS expects logical (DPI-independent)
     // coordinates here.
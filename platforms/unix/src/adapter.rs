@@ -3,7 +3,11 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-use accesskit::{ActionHandler, ActivationHandler, DeactivationHandler, NodeId, Rect, TreeUpdate};
+use accesskit::{
+    ActionHandler, ActionRequest, ActivationHandler, AnnouncementKind, AsyncActionHandler,
+    AsyncActivationHandler, BoxFuture, DeactivationHandler, GeometryUpdate, NodeId, Rect,
+    TreeUpdate,
+};
 use accesskit_atspi_common::{
     next_adapter_id, ActionHandlerNoMut, ActionHandlerWrapper, Adapter as AdapterImpl,
     AdapterCallback, Event, PlatformNode, WindowBounds,
@@ -15,7 +19,10 @@ use std::sync::{Arc, Mutex};
 #[cfg(feature = "tokio")]
 use tokio::sync::mpsc::UnboundedSender as Sender;
 
-use crate::context::{get_or_init_app_context, get_or_init_messages};
+use crate::{
+    context::{get_or_init_app_context, get_or_init_messages},
+    Backend,
+};
 
 pub(crate) struct Callback {
     messages: Sender<Message>,
@@ -53,8 +60,34 @@ impl AdapterCallback for Callback {
         self.send_message(Message::EmitEvent {
             adapter_id: adapter.id(),
             event,
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::current(),
         });
     }
+
+    fn supports_refresh(&self) -> bool {
+        true
+    }
+
+    fn request_refresh(&self, adapter_id: usize) {
+        self.send_message(Message::RefreshAdapter { id: adapter_id });
+    }
+}
+
+/// Forwards the futures returned by an [`AsyncActionHandler`] to the
+/// worker, which runs them on its executor.
+struct AsyncActionHandlerAdapter<H: AsyncActionHandler + Send>(H);
+
+impl<H: AsyncActionHandler + Send> ActionHandler for AsyncActionHandlerAdapter<H> {
+    fn do_action(&mut self, request: ActionRequest) {
+        let future = self.0.do_action(request);
+        Callback::new().send_message(Message::RunAction { future });
+    }
+}
+
+pub(crate) enum AnyActivationHandler {
+    Sync(Box<dyn ActivationHandler + Send>),
+    Async(Box<dyn AsyncActivationHandler + Send>),
 }
 
 pub(crate) enum AdapterState {
@@ -71,27 +104,133 @@ pub(crate) enum AdapterState {
     Active(AdapterImpl),
 }
 
+impl AdapterState {
+    /// Activates a pending adapter with the full tree that the application
+    /// sent after declining to provide it from its activation handler.
+    pub(crate) fn activate_pending(&mut self, id: usize, initial_state: TreeUpdate) {
+        if let Self::Pending {
+            is_window_focused,
+            root_window_bounds,
+            action_handler,
+        } = self
+        {
+            let r#impl = AdapterImpl::with_wrapped_action_handler(
+                id,
+                get_or_init_app_context(),
+                Callback::new(),
+                initial_state,
+                *is_window_focused,
+                *root_window_bounds,
+                Arc::clone(action_handler),
+            );
+            *self = Self::Active(r#impl);
+        }
+    }
+}
+
+/// Exposes one window's accessibility tree through AT-SPI.
+///
+/// An application with several windows should create one adapter for each
+/// window. All of the adapters in a process share the same D-Bus connection
+/// and application object, and each adapter's root node appears as a
+/// separate child of that application object, in the order in which the
+/// adapters were created.
 pub struct Adapter {
-    messages: Sender<Message>,
+    messages: Option<Sender<Message>>,
     id: usize,
     state: Arc<Mutex<AdapterState>>,
 }
 
 impl Adapter {
-    /// Create a new Unix adapter.
+    /// Create a new Unix adapter, using the backend chosen by
+    /// [`Backend::detect`].
     ///
     /// All of the handlers will always be called from another thread.
     pub fn new(
         activation_handler: impl 'static + ActivationHandler + Send,
         action_handler: impl 'static + ActionHandler + Send,
         deactivation_handler: impl 'static + DeactivationHandler + Send,
+    ) -> Self {
+        Self::with_backend(
+            Backend::detect(),
+            activation_handler,
+            action_handler,
+            deactivation_handler,
+        )
+    }
+
+    /// Create a new Unix adapter that uses the specified backend.
+    ///
+    /// All of the handlers will always be called from another thread.
+    pub fn with_backend(
+        backend: Backend,
+        activation_handler: impl 'static + ActivationHandler + Send,
+        action_handler: impl 'static + ActionHandler + Send,
+        deactivation_handler: impl 'static + DeactivationHandler + Send,
+    ) -> Self {
+        Self::with_boxed_handlers(
+            backend,
+            AnyActivationHandler::Sync(Box::new(activation_handler)),
+            Arc::new(ActionHandlerWrapper::new(action_handler)),
+            Box::new(deactivation_handler),
+        )
+    }
+
+    /// Create a new Unix adapter with asynchronous activation and action
+    /// handlers, using the backend chosen by [`Backend::detect`].
+    ///
+    /// The futures returned by the handlers are run on the adapter's
+    /// executor, on the same thread as the handlers themselves. Until the
+    /// future returned by the activation handler completes, assistive
+    /// technologies see no tree, as when
+    /// [`ActivationHandler::request_initial_tree`] returns `None`.
+    pub fn with_async_handlers(
+        activation_handler: impl 'static + AsyncActivationHandler + Send,
+        action_handler: impl 'static + AsyncActionHandler + Send,
+        deactivation_handler: impl 'static + DeactivationHandler + Send,
+    ) -> Self {
+        Self::with_backend_and_async_handlers(
+            Backend::detect(),
+            activation_handler,
+            action_handler,
+            deactivation_handler,
+        )
+    }
+
+    /// Create a new Unix adapter with asynchronous activation and action
+    /// handlers, using the specified backend.
+    /// See [`Adapter::with_async_handlers`] for details.
+    pub fn with_backend_and_async_handlers(
+        backend: Backend,
+        activation_handler: impl 'static + AsyncActivationHandler + Send,
+        action_handler: impl 'static + AsyncActionHandler + Send,
+        deactivation_handler: impl 'static + DeactivationHandler + Send,
+    ) -> Self {
+        Self::with_boxed_handlers(
+            backend,
+            AnyActivationHandler::Async(Box::new(activation_handler)),
+            Arc::new(ActionHandlerWrapper::new(AsyncActionHandlerAdapter(
+                action_handler,
+            ))),
+            Box::new(deactivation_handler),
+        )
+    }
+
+    fn with_boxed_handlers(
+        backend: Backend,
+        activation_handler: AnyActivationHandler,
+        action_handler: Arc<dyn ActionHandlerNoMut + Send + Sync>,
+        deactivation_handler: Box<dyn DeactivationHandler + Send>,
     ) -> Self {
         let id = next_adapter_id();
-        let messages = get_or_init_messages();
+        let messages = match backend {
+            Backend::AtSpi => Some(get_or_init_messages()),
+            Backend::None => None,
+        };
         let state = Arc::new(Mutex::new(AdapterState::Inactive {
             is_window_focused: false,
             root_window_bounds: Default::default(),
-            action_handler: Arc::new(ActionHandlerWrapper::new(action_handler)),
+            action_handler,
         }));
         let adapter = Self {
             id,
@@ -100,18 +239,30 @@ impl Adapter {
         };
         adapter.send_message(Message::AddAdapter {
             id,
-            activation_handler: Box::new(activation_handler),
-            deactivation_handler: Box::new(deactivation_handler),
+            activation_handler,
+            deactivation_handler,
             state,
         });
         adapter
     }
 
+    /// Returns the backend that this adapter uses.
+    pub fn backend(&self) -> Backend {
+        if self.messages.is_some() {
+            Backend::AtSpi
+        } else {
+            Backend::None
+        }
+    }
+
     pub(crate) fn send_message(&self, message: Message) {
+        let Some(messages) = &self.messages else {
+            return;
+        };
         #[cfg(not(feature = "tokio"))]
-        let _ = self.messages.try_send(message);
+        let _ = messages.try_send(message);
         #[cfg(feature = "tokio")]
-        let _ = self.messages.send(message);
+        let _ = messages.send(message);
     }
 
     pub fn set_root_window_bounds(&mut self, outer: Rect, inner: Rect) {
@@ -138,30 +289,28 @@ impl Adapter {
     /// the [`TreeUpdate`] returned by the provided function must contain
     /// a full tree.
     pub fn update_if_active(&mut self, update_factory: impl FnOnce() -> TreeUpdate) {
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::debug_span!("accesskit_update_if_active", adapter_id = self.id).entered();
         let mut state = self.state.lock().unwrap();
         match &mut *state {
             AdapterState::Inactive { .. } => (),
-            AdapterState::Pending {
-                is_window_focused,
-                root_window_bounds,
-                action_handler,
-            } => {
-                let initial_state = update_factory();
-                let r#impl = AdapterImpl::with_wrapped_action_handler(
-                    self.id,
-                    get_or_init_app_context(),
-                    Callback::new(),
-                    initial_state,
-                    *is_window_focused,
-                    *root_window_bounds,
-                    Arc::clone(action_handler),
-                );
-                *state = AdapterState::Active(r#impl);
-            }
+            AdapterState::Pending { .. } => state.activate_pending(self.id, update_factory()),
             AdapterState::Active(r#impl) => r#impl.update(update_factory()),
         }
     }
 
+    /// If and only if the tree has been initialized, call the provided function
+    /// and apply the resulting [`GeometryUpdate`]. Since such an update only
+    /// applies to an existing tree, the function isn't called while the
+    /// adapter is waiting for the initial tree.
+    pub fn update_geometry_if_active(&mut self, update_factory: impl FnOnce() -> GeometryUpdate) {
+        let mut state = self.state.lock().unwrap();
+        if let AdapterState::Active(r#impl) = &mut *state {
+            r#impl.update_geometry(update_factory());
+        }
+    }
+
     /// Update the tree state based on whether the window is focused.
     pub fn update_window_focus_state(&mut self, is_focused: bool) {
         let mut state = self.state.lock().unwrap();
@@ -179,6 +328,19 @@ impl Adapter {
             AdapterState::Active(r#impl) => r#impl.update_window_focus_state(is_focused),
         }
     }
+
+    /// Ask assistive technologies to speak the given text, without
+    /// requiring a live region in the tree. The announcement is emitted
+    /// as an AT-SPI `Announcement` event on the root of the tree.
+    ///
+    /// If the tree isn't active yet, no assistive technology is listening,
+    /// so the announcement is dropped.
+    pub fn announce(&mut self, text: &str, kind: AnnouncementKind) {
+        let state = self.state.lock().unwrap();
+        if let AdapterState::Active(r#impl) = &*state {
+            r#impl.announce(text, kind);
+        }
+    }
 }
 
 impl Drop for Adapter {
@@ -190,13 +352,17 @@ impl Drop for Adapter {
 pub(crate) enum Message {
     AddAdapter {
         id: usize,
-        activation_handler: Box<dyn ActivationHandler + Send>,
+        activation_handler: AnyActivationHandler,
         deactivation_handler: Box<dyn DeactivationHandler + Send>,
         state: Arc<Mutex<AdapterState>>,
     },
     RemoveAdapter {
         id: usize,
     },
+    /// An assistive technology asked for the adapter's tree to be rebuilt.
+    RefreshAdapter {
+        id: usize,
+    },
     RegisterInterfaces {
         node: PlatformNode,
         interfaces: InterfaceSet,
@@ -209,5 +375,107 @@ pub(crate) enum Message {
     EmitEvent {
         adapter_id: usize,
         event: Event,
+        /// The span in which the event was generated, usually that of
+        /// the tree update that caused it.
+        #[cfg(feature = "tracing")]
+        span: tracing::Span,
     },
+    /// A future returned by an [`AsyncActionHandler`], to be run
+    /// on the worker's executor.
+    RunAction {
+        future: BoxFuture<()>,
+    },
+    /// The connection to the accessibility bus was lost.
+    BusDisconnected,
+    /// It's time to try again to connect to the accessibility bus,
+    /// after the given number of failed attempts.
+    RetryBusConnection {
+        attempt: u32,
+    },
+}
+
+impl Message {
+    /// Returns the span in which this message should be processed
+    /// by the AT-SPI bus task.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn span(&self) -> tracing::Span {
+        match self {
+            Self::EmitEvent {
+                adapter_id, span, ..
+            } => tracing::debug_span!(parent: span, "accesskit_emit_event", adapter_id),
+            _ => tracing::Span::none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{
+        Action, ActionHandler, ActionRequest, AsyncActionHandler, AsyncActivationHandler,
+        BoxFuture, DeactivationHandler, NodeId, TreeUpdate,
+    };
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    use super::{Adapter, AsyncActionHandlerAdapter, Message};
+    use crate::{context::tests::test_messages, util::block_on, Backend};
+
+    struct Handler(Arc<AtomicBool>);
+
+    impl AsyncActionHandler for Handler {
+        fn do_action(&mut self, _request: ActionRequest) -> BoxFuture<()> {
+            let is_done = Arc::clone(&self.0);
+            Box::pin(async move { is_done.store(true, Ordering::SeqCst) })
+        }
+    }
+
+    struct UnexpectedActivation;
+
+    impl AsyncActivationHandler for UnexpectedActivation {
+        fn request_initial_tree(&mut self) -> BoxFuture<Option<TreeUpdate>> {
+            panic!("the adapter shouldn't activate without a backend");
+        }
+    }
+
+    struct NullDeactivationHandler;
+
+    impl DeactivationHandler for NullDeactivationHandler {
+        fn deactivate_accessibility(&mut self) {}
+    }
+
+    #[test]
+    fn async_action_runs_on_worker() {
+        let messages = test_messages();
+        let is_done = Arc::new(AtomicBool::new(false));
+        let mut handler = AsyncActionHandlerAdapter(Handler(Arc::clone(&is_done)));
+        handler.do_action(ActionRequest {
+            action: Action::Click,
+            target: NodeId(0),
+            data: None,
+        });
+        assert!(!is_done.load(Ordering::SeqCst));
+        let future = loop {
+            match messages.lock().unwrap().try_recv() {
+                Ok(Message::RunAction { future }) => break future,
+                Ok(_) => (),
+                Err(_) => panic!("the action wasn't sent to the worker"),
+            }
+        };
+        block_on(future);
+        assert!(is_done.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn async_handlers_with_backend() {
+        test_messages();
+        let adapter = Adapter::with_backend_and_async_handlers(
+            Backend::None,
+            UnexpectedActivation,
+            Handler(Arc::new(AtomicBool::new(false))),
+            NullDeactivationHandler,
+        );
+        assert_eq!(adapter.backend(), Backend::None);
+    }
 }
@@ -4,7 +4,7 @@
 // the LICENSE-MIT file), at your option.
 
 use crate::{
-    atspi::{interfaces::*, ObjectId},
+    atspi::{interfaces::*, ObjectId, OwnedObjectAddress},
     context::get_or_init_app_context,
     executor::{Executor, Task},
 };
@@ -17,24 +17,30 @@ use atspi::{
     proxy::{bus::BusProxy, socket::SocketProxy},
     Interface, InterfaceSet,
 };
+use futures_lite::StreamExt;
 use serde::Serialize;
 use std::{collections::HashMap, env::var, io};
 use zbus::{
     names::{BusName, InterfaceName, MemberName, OwnedUniqueName},
-    zvariant::{Str, Value},
-    Address, Connection, ConnectionBuilder, Result,
+    zvariant::{Str, Type, Value},
+    Address, Connection, ConnectionBuilder, MessageStream, Result,
 };
 
 pub(crate) struct Bus {
     conn: Connection,
     _task: Task<()>,
+    _disconnection_task: Task<()>,
     socket_proxy: SocketProxy<'static>,
 }
 
 impl Bus {
+    /// Connects to the accessibility bus. `on_disconnected` is called
+    /// if the connection is later lost, for example because the bus
+    /// was restarted, but not if the returned `Bus` is dropped first.
     pub(crate) async fn new(
         session_bus: &Connection,
         executor: &Executor<'_>,
+        on_disconnected: impl FnOnce() + Send + 'static,
     ) -> zbus::Result<Self> {
         let address = match var("AT_SPI_BUS_ADDRESS") {
             Ok(address) if !address.is_empty() => address,
@@ -54,10 +60,21 @@ impl Bus {
             },
             "accesskit_atspi_bus_task",
         );
+        let mut messages = MessageStream::from(&conn);
+        let _disconnection_task = executor.spawn(
+            async move {
+                // Once reading from the socket fails, the stream yields
+                // the error and then ends.
+                while let Some(Ok(_)) = messages.next().await {}
+                on_disconnected();
+            },
+            "accesskit_atspi_bus_disconnection_task",
+        );
         let socket_proxy = SocketProxy::new(&conn).await?;
         let mut bus = Bus {
             conn,
             _task,
+            _disconnection_task,
             socket_proxy,
         };
         bus.register_root_node().await?;
@@ -86,7 +103,15 @@ impl Bus {
                 .object_server()
                 .at(
                     path,
-                    RootAccessibleInterface::new(self.unique_name().to_owned(), node),
+                    RootAccessibleInterface::new(self.unique_name().to_owned(), node.clone()),
+                )
+                .await?;
+
+            self.conn
+                .object_server()
+                .at(
+                    CACHE_PATH,
+                    CacheInterface::new(self.unique_name().to_owned(), node),
                 )
                 .await?;
         }
@@ -101,7 +126,8 @@ impl Bus {
     ) -> zbus::Result<()> {
         let path = ObjectId::from(&node).path();
         let bus_name = self.unique_name().to_owned();
-        if new_interfaces.contains(Interface::Accessible) {
+        let is_new_object = new_interfaces.contains(Interface::Accessible);
+        if is_new_object {
             self.register_interface(
                 &path,
                 NodeAccessibleInterface::new(bus_name.clone(), node.clone()),
@@ -123,10 +149,53 @@ impl Bus {
             self.register_interface(&path, TextInterface::new(node.clone()))
                 .await?;
         }
+        if new_interfaces.contains(Interface::EditableText) {
+            self.register_interface(&path, EditableTextInterface::new(node.clone()))
+                .await?;
+        }
         if new_interfaces.contains(Interface::Value) {
             self.register_interface(&path, ValueInterface::new(node.clone()))
                 .await?;
         }
+        if new_interfaces.contains(Interface::Hypertext) {
+            self.register_interface(
+                &path,
+                HypertextInterface::new(bus_name.clone(), node.clone()),
+            )
+            .await?;
+        }
+        if new_interfaces.contains(Interface::Hyperlink) {
+            self.register_interface(
+                &path,
+                HyperlinkInterface::new(bus_name.clone(), node.clone()),
+            )
+            .await?;
+        }
+        if new_interfaces.contains(Interface::Selection) {
+            self.register_interface(
+                &path,
+                SelectionInterface::new(bus_name.clone(), node.clone()),
+            )
+            .await?;
+        }
+        if new_interfaces.contains(Interface::Table) {
+            self.register_interface(&path, TableInterface::new(bus_name.clone(), node.clone()))
+                .await?;
+        }
+        if new_interfaces.contains(Interface::TableCell) {
+            self.register_interface(
+                &path,
+                TableCellInterface::new(bus_name.clone(), node.clone()),
+            )
+            .await?;
+        }
+        if is_new_object {
+            // The node may already have been removed, in which case
+            // there's nothing to add to the cache.
+            if let Ok(item) = CacheItem::new(&bus_name, &node) {
+                self.emit_cache_signal("AddAccessible", &item).await?;
+            }
+        }
 
         Ok(())
     }
@@ -167,13 +236,60 @@ impl Bus {
         if old_interfaces.contains(Interface::Text) {
             self.unregister_interface::<TextInterface>(&path).await?;
         }
+        if old_interfaces.contains(Interface::EditableText) {
+            self.unregister_interface::<EditableTextInterface>(&path)
+                .await?;
+        }
         if old_interfaces.contains(Interface::Value) {
             self.unregister_interface::<ValueInterface>(&path).await?;
         }
+        if old_interfaces.contains(Interface::Hypertext) {
+            self.unregister_interface::<HypertextInterface>(&path)
+                .await?;
+        }
+        if old_interfaces.contains(Interface::Hyperlink) {
+            self.unregister_interface::<HyperlinkInterface>(&path)
+                .await?;
+        }
+        if old_interfaces.contains(Interface::Selection) {
+            self.unregister_interface::<SelectionInterface>(&path)
+                .await?;
+        }
+        if old_interfaces.contains(Interface::Table) {
+            self.unregister_interface::<TableInterface>(&path).await?;
+        }
+        if old_interfaces.contains(Interface::TableCell) {
+            self.unregister_interface::<TableCellInterface>(&path)
+                .await?;
+        }
+        if old_interfaces.contains(Interface::Accessible) {
+            let address = OwnedObjectAddress::new(self.unique_name(), path);
+            self.emit_cache_signal("RemoveAccessible", &address).await?;
+        }
 
         Ok(())
     }
 
+    async fn emit_cache_signal<T: Serialize + Type>(
+        &self,
+        signal_name: &str,
+        body: &T,
+    ) -> Result<()> {
+        map_or_ignoring_broken_pipe(
+            self.conn
+                .emit_signal(
+                    Option::<BusName>::None,
+                    CACHE_PATH,
+                    InterfaceName::from_static_str_unchecked("org.a11y.atspi.Cache"),
+                    MemberName::from_str_unchecked(signal_name),
+                    &cache_signal_body(body),
+                )
+                .await,
+            (),
+            |_| (),
+        )
+    }
+
     async fn unregister_interface<T>(&self, path: &str) -> Result<bool>
     where
         T: zbus::Interface,
@@ -206,6 +322,7 @@ impl Bus {
             ObjectEvent::CaretMoved(_) => "TextCaretMoved",
             ObjectEvent::ChildAdded(_, _) | ObjectEvent::ChildRemoved(_) => "ChildrenChanged",
             ObjectEvent::PropertyChanged(_) => "PropertyChange",
+            ObjectEvent::SelectionChanged => "SelectionChanged",
             ObjectEvent::StateChanged(_, _) => "StateChanged",
             ObjectEvent::TextInserted { .. } | ObjectEvent::TextRemoved { .. } => "TextChanged",
             ObjectEvent::TextSelectionChanged => "TextSelectionChanged",
@@ -369,13 +486,18 @@ impl Bus {
                 start_index,
                 length,
                 content,
+                is_programmatic,
             } => {
                 self.emit_event(
                     target,
                     interface,
                     signal,
                     EventBody {
-                        kind: "insert",
+                        kind: if is_programmatic {
+                            "insert:system"
+                        } else {
+                            "insert"
+                        },
                         detail1: start_index,
                         detail2: length,
                         any_data: content.into(),
@@ -388,13 +510,18 @@ impl Bus {
                 start_index,
                 length,
                 content,
+                is_programmatic,
             } => {
                 self.emit_event(
                     target,
                     interface,
                     signal,
                     EventBody {
-                        kind: "delete",
+                        kind: if is_programmatic {
+                            "delete:system"
+                        } else {
+                            "delete"
+                        },
                         detail1: start_index,
                         detail2: length,
                         any_data: content.into(),
@@ -403,7 +530,7 @@ impl Bus {
                 )
                 .await
             }
-            ObjectEvent::TextSelectionChanged => {
+            ObjectEvent::SelectionChanged | ObjectEvent::TextSelectionChanged => {
                 self.emit_event(
                     target,
                     interface,
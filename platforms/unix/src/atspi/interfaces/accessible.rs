@@ -39,6 +39,11 @@ impl NodeAccessibleInterface {
         self.node.description().map_err(self.map_error())
     }
 
+    #[zbus(property)]
+    fn help_text(&self) -> fdo::Result<String> {
+        self.node.help_text().map_err(self.map_error())
+    }
+
     #[zbus(property)]
     fn parent(&self) -> fdo::Result<OwnedObjectAddress> {
         self.node.parent().map_err(self.map_error()).map(|parent| {
@@ -147,6 +152,11 @@ impl RootAccessibleInterface {
         ""
     }
 
+    #[zbus(property)]
+    fn help_text(&self) -> &str {
+        ""
+    }
+
     #[zbus(property)]
     fn parent(&self) -> OwnedObjectAddress {
         OwnedObjectAddress::null()
@@ -0,0 +1,126 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit_atspi_common::{NodeIdOrRoot, PlatformNode, PlatformRoot, Result};
+use atspi::{InterfaceSet, Role, StateSet};
+use serde::Serialize;
+use zbus::{fdo, interface, names::OwnedUniqueName, zvariant::Type};
+
+use super::map_root_error;
+use crate::atspi::{ObjectId, OwnedObjectAddress};
+
+pub(crate) const CACHE_PATH: &str = "/org/a11y/atspi/cache";
+
+/// The bulk representation of an accessible object, as used by
+/// `org.a11y.atspi.Cache`. The fields are in the order required
+/// by the `(so)(so)(so)iiassusau` signature.
+#[derive(Serialize, Type)]
+pub(crate) struct CacheItem {
+    object: OwnedObjectAddress,
+    app: OwnedObjectAddress,
+    parent: OwnedObjectAddress,
+    index_in_parent: i32,
+    child_count: i32,
+    interfaces: InterfaceSet,
+    name: String,
+    role: Role,
+    description: String,
+    states: StateSet,
+}
+
+impl CacheItem {
+    pub(crate) fn new(bus_name: &OwnedUniqueName, node: &PlatformNode) -> Result<Self> {
+        let parent = match node.parent()? {
+            NodeIdOrRoot::Node(parent) => ObjectId::Node {
+                adapter: node.adapter_id(),
+                node: parent,
+            },
+            NodeIdOrRoot::Root => ObjectId::Root,
+        };
+        Ok(Self {
+            object: ObjectId::from(node).to_address(bus_name.inner()),
+            app: ObjectId::Root.to_address(bus_name.inner()),
+            parent: parent.to_address(bus_name.inner()),
+            index_in_parent: node.index_in_parent()?,
+            child_count: node.child_count()?,
+            interfaces: node.interfaces()?,
+            name: node.name()?,
+            role: node.role()?,
+            description: node.description()?,
+            states: node.state(),
+        })
+    }
+}
+
+/// Wraps the argument of an `AddAccessible` or `RemoveAccessible` signal.
+/// Each of these signals has a single struct argument, but a struct
+/// passed directly as a message body is flattened into its fields.
+pub(crate) fn cache_signal_body<T>(item: &T) -> (&T,) {
+    (item,)
+}
+
+pub(crate) struct CacheInterface {
+    bus_name: OwnedUniqueName,
+    root: PlatformRoot,
+}
+
+impl CacheInterface {
+    pub fn new(bus_name: OwnedUniqueName, root: PlatformRoot) -> Self {
+        Self { bus_name, root }
+    }
+}
+
+#[interface(name = "org.a11y.atspi.Cache")]
+impl CacheInterface {
+    fn get_items(&self) -> fdo::Result<Vec<CacheItem>> {
+        // Nodes that were removed while we were building the list
+        // are skipped rather than failing the whole call.
+        let items: Vec<Option<CacheItem>> = self
+            .root
+            .map_descendants(|node| CacheItem::new(&self.bus_name, &node).ok())
+            .map_err(map_root_error)?;
+        Ok(items.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zbus::{message::Message, names::UniqueName};
+
+    fn body_signature<T: Serialize + Type>(signal_name: &str, item: &T) -> String {
+        let message = Message::signal(CACHE_PATH, "org.a11y.atspi.Cache", signal_name)
+            .unwrap()
+            .build(&cache_signal_body(item))
+            .unwrap();
+        message.body().signature().unwrap().to_string()
+    }
+
+    #[test]
+    fn signal_body_signatures() {
+        let bus_name = UniqueName::from_static_str_unchecked(":1.0");
+        let item = CacheItem {
+            object: ObjectId::Node {
+                adapter: 0,
+                node: accesskit::NodeId(1),
+            }
+            .to_address(&bus_name),
+            app: ObjectId::Root.to_address(&bus_name),
+            parent: ObjectId::Root.to_address(&bus_name),
+            index_in_parent: 0,
+            child_count: 0,
+            interfaces: InterfaceSet::empty(),
+            name: String::new(),
+            role: Role::PushButton,
+            description: String::new(),
+            states: StateSet::empty(),
+        };
+        assert_eq!(
+            body_signature("AddAccessible", &item),
+            "((so)(so)(so)iiassusau)"
+        );
+        assert_eq!(body_signature("RemoveAccessible", &item.object), "(so)");
+    }
+}
@@ -4,7 +4,7 @@
 // the LICENSE-MIT file), at your option.
 
 use accesskit_atspi_common::{PlatformNode, Rect};
-use atspi::{CoordType, Layer};
+use atspi::{CoordType, Layer, ScrollType};
 use zbus::{fdo, interface, names::OwnedUniqueName};
 
 use crate::atspi::{ObjectId, OwnedObjectAddress};
@@ -64,6 +64,10 @@ impl ComponentInterface {
         self.node.grab_focus().map_err(self.map_error())
     }
 
+    fn scroll_to(&self, scroll_type: ScrollType) -> fdo::Result<bool> {
+        self.node.scroll_to(scroll_type).map_err(self.map_error())
+    }
+
     fn scroll_to_point(&self, coord_type: CoordType, x: i32, y: i32) -> fdo::Result<bool> {
         self.node
             .scroll_to_point(coord_type, x, y)
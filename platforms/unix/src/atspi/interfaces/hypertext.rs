@@ -0,0 +1,100 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit_atspi_common::PlatformNode;
+use zbus::{fdo, interface, names::OwnedUniqueName};
+
+use crate::atspi::{ObjectId, OwnedObjectAddress};
+
+pub(crate) struct HypertextInterface {
+    bus_name: OwnedUniqueName,
+    node: PlatformNode,
+}
+
+impl HypertextInterface {
+    pub fn new(bus_name: OwnedUniqueName, node: PlatformNode) -> Self {
+        Self { bus_name, node }
+    }
+
+    fn map_error(&self) -> impl '_ + FnOnce(accesskit_atspi_common::Error) -> fdo::Error {
+        |error| crate::util::map_error_from_node(&self.node, error)
+    }
+}
+
+#[interface(name = "org.a11y.atspi.Hypertext")]
+impl HypertextInterface {
+    fn get_n_links(&self) -> fdo::Result<i32> {
+        self.node.n_links().map_err(self.map_error())
+    }
+
+    fn get_link(&self, link_index: i32) -> fdo::Result<(OwnedObjectAddress,)> {
+        let link = match usize::try_from(link_index) {
+            Ok(index) => self.node.link(index).map_err(self.map_error())?,
+            Err(_) => None,
+        }
+        .map(|node| ObjectId::Node {
+            adapter: self.node.adapter_id(),
+            node,
+        });
+        Ok(super::optional_object_address(&self.bus_name, link))
+    }
+
+    fn get_link_index(&self, character_index: i32) -> fdo::Result<i32> {
+        self.node
+            .link_index(character_index)
+            .map_err(self.map_error())
+    }
+}
+
+pub(crate) struct HyperlinkInterface {
+    bus_name: OwnedUniqueName,
+    node: PlatformNode,
+}
+
+impl HyperlinkInterface {
+    pub fn new(bus_name: OwnedUniqueName, node: PlatformNode) -> Self {
+        Self { bus_name, node }
+    }
+
+    fn map_error(&self) -> impl '_ + FnOnce(accesskit_atspi_common::Error) -> fdo::Error {
+        |error| crate::util::map_error_from_node(&self.node, error)
+    }
+}
+
+#[interface(name = "org.a11y.atspi.Hyperlink")]
+impl HyperlinkInterface {
+    #[zbus(property)]
+    fn n_anchors(&self) -> i16 {
+        1
+    }
+
+    #[zbus(property)]
+    fn start_index(&self) -> fdo::Result<i32> {
+        self.node.link_start_index().map_err(self.map_error())
+    }
+
+    #[zbus(property)]
+    fn end_index(&self) -> fdo::Result<i32> {
+        self.node.link_end_index().map_err(self.map_error())
+    }
+
+    fn get_object(&self, index: i32) -> (OwnedObjectAddress,) {
+        // A link has a single anchor, which is the link itself.
+        let object = (index == 0).then(|| ObjectId::from(&self.node));
+        super::optional_object_address(&self.bus_name, object)
+    }
+
+    #[zbus(name = "GetURI")]
+    fn get_uri(&self, index: i32) -> fdo::Result<String> {
+        if index != 0 {
+            return Ok(String::new());
+        }
+        self.node.uri().map_err(self.map_error())
+    }
+
+    fn is_valid(&self) -> bool {
+        true
+    }
+}
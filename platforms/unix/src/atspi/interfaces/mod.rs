@@ -6,7 +6,13 @@
 mod accessible;
 mod action;
 mod application;
+mod cache;
 mod component;
+mod editable_text;
+mod hypertext;
+mod selection;
+mod table;
+mod table_cell;
 mod text;
 mod value;
 
@@ -30,6 +36,12 @@ fn optional_object_address(
 pub(crate) use accessible::*;
 pub(crate) use action::*;
 pub(crate) use application::*;
+pub(crate) use cache::*;
 pub(crate) use component::*;
+pub(crate) use editable_text::*;
+pub(crate) use hypertext::*;
+pub(crate) use selection::*;
+pub(crate) use table::*;
+pub(crate) use table_cell::*;
 pub(crate) use text::*;
 pub(crate) use value::*;
@@ -0,0 +1,87 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::NodeId;
+use accesskit_atspi_common::PlatformNode;
+use zbus::{fdo, interface, names::OwnedUniqueName};
+
+use crate::atspi::{ObjectId, OwnedObjectAddress};
+
+pub(crate) struct TableCellInterface {
+    bus_name: OwnedUniqueName,
+    node: PlatformNode,
+}
+
+impl TableCellInterface {
+    pub fn new(bus_name: OwnedUniqueName, node: PlatformNode) -> Self {
+        Self { bus_name, node }
+    }
+
+    fn map_error(&self) -> impl '_ + FnOnce(accesskit_atspi_common::Error) -> fdo::Error {
+        |error| crate::util::map_error_from_node(&self.node, error)
+    }
+
+    fn object_addresses(&self, nodes: Vec<NodeId>) -> Vec<OwnedObjectAddress> {
+        nodes
+            .into_iter()
+            .map(|node| {
+                ObjectId::Node {
+                    adapter: self.node.adapter_id(),
+                    node,
+                }
+                .to_address(self.bus_name.inner())
+            })
+            .collect()
+    }
+}
+
+#[interface(name = "org.a11y.atspi.TableCell")]
+impl TableCellInterface {
+    #[zbus(property)]
+    fn column_span(&self) -> fdo::Result<i32> {
+        self.node.cell_column_span().map_err(self.map_error())
+    }
+
+    #[zbus(property)]
+    fn position(&self) -> fdo::Result<(i32, i32)> {
+        self.node.cell_position().map_err(self.map_error())
+    }
+
+    #[zbus(property)]
+    fn row_span(&self) -> fdo::Result<i32> {
+        self.node.cell_row_span().map_err(self.map_error())
+    }
+
+    #[zbus(property)]
+    fn table(&self) -> fdo::Result<OwnedObjectAddress> {
+        let table = self.node.cell_table().map_err(self.map_error())?;
+        let object_id = table.map(|node| ObjectId::Node {
+            adapter: self.node.adapter_id(),
+            node,
+        });
+        Ok(super::optional_object_address(&self.bus_name, object_id).0)
+    }
+
+    fn get_row_column_span(&self) -> fdo::Result<(i32, i32, i32, i32)> {
+        let (row, column) = self.node.cell_position().map_err(self.map_error())?;
+        let row_span = self.node.cell_row_span().map_err(self.map_error())?;
+        let column_span = self.node.cell_column_span().map_err(self.map_error())?;
+        Ok((row, column, row_span, column_span))
+    }
+
+    fn get_row_header_cells(&self) -> fdo::Result<Vec<OwnedObjectAddress>> {
+        self.node
+            .cell_row_header_cells()
+            .map(|cells| self.object_addresses(cells))
+            .map_err(self.map_error())
+    }
+
+    fn get_column_header_cells(&self) -> fdo::Result<Vec<OwnedObjectAddress>> {
+        self.node
+            .cell_column_header_cells()
+            .map(|cells| self.object_addresses(cells))
+            .map_err(self.map_error())
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::AdapterCapabilities;
+use accesskit_atspi_common::CAPABILITIES;
+use std::{env::var_os, thread};
+use zbus::{fdo::DBusProxy, names::BusName, ConnectionBuilder};
+
+use crate::util::block_on;
+
+const ACCESSIBILITY_BUS_NAME: &str = "org.a11y.Bus";
+
+/// The mechanism an [`Adapter`](crate::Adapter) uses to expose
+/// the accessibility tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// Expose the tree through AT-SPI over D-Bus. This works the same way
+    /// in X11 and Wayland sessions.
+    AtSpi,
+    /// Don't expose the tree at all. The adapter never activates,
+    /// so the activation, action and deactivation handlers are never
+    /// called. This is appropriate when running headless.
+    None,
+}
+
+impl Backend {
+    /// Chooses a backend based on the environment of the current process.
+    ///
+    /// If the `ACCESSKIT_UNIX_BACKEND` environment variable is set to
+    /// `atspi` or `none`, the corresponding backend is used. Otherwise,
+    /// AT-SPI is used if there is an X11 or Wayland display, or if
+    /// an accessibility bus address was explicitly provided. If none of
+    /// these are present, the session bus is asked whether
+    /// the accessibility bus is running or can be started, which covers
+    /// sessions without a display server, such as kiosks and remote
+    /// sessions. No backend is used if that fails too.
+    pub fn detect() -> Self {
+        if let Some(value) = var_os("ACCESSKIT_UNIX_BACKEND") {
+            match value.to_str() {
+                Some("atspi") => return Self::AtSpi,
+                Some("none") => return Self::None,
+                _ => (),
+            }
+        }
+        let has_variable = |name| var_os(name).is_some_and(|value| !value.is_empty());
+        if has_variable("WAYLAND_DISPLAY")
+            || has_variable("DISPLAY")
+            || has_variable("AT_SPI_BUS_ADDRESS")
+            || has_accessibility_bus()
+        {
+            Self::AtSpi
+        } else {
+            Self::None
+        }
+    }
+
+    /// Returns which parts of the AccessKit schema this backend exposes,
+    /// or `None` if it doesn't expose the tree at all.
+    pub fn capabilities(self) -> Option<&'static AdapterCapabilities> {
+        match self {
+            Self::AtSpi => Some(&CAPABILITIES),
+            Self::None => None,
+        }
+    }
+}
+
+fn has_accessibility_bus() -> bool {
+    // The probe runs on its own thread, because this may be called
+    // from within an async runtime, where blocking on a future isn't allowed.
+    thread::spawn(|| {
+        block_on(async {
+            let Ok(builder) = ConnectionBuilder::session() else {
+                return false;
+            };
+            let Ok(session_bus) = builder.build().await else {
+                return false;
+            };
+            let Ok(dbus) = DBusProxy::new(&session_bus).await else {
+                return false;
+            };
+            let name = BusName::from_static_str(ACCESSIBILITY_BUS_NAME).unwrap();
+            if dbus.name_has_owner(name).await.unwrap_or(false) {
+                return true;
+            }
+            dbus.list_activatable_names().await.is_ok_and(|names| {
+                names
+                    .iter()
+                    .any(|name| name.as_str() == ACCESSIBILITY_BUS_NAME)
+            })
+        })
+    })
+    .join()
+    .unwrap_or(false)
+}
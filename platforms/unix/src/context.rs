@@ -3,7 +3,7 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-use accesskit::{ActivationHandler, DeactivationHandler};
+use accesskit::DeactivationHandler;
 use accesskit_atspi_common::{Adapter as AdapterImpl, AppContext, Event};
 #[cfg(not(feature = "tokio"))]
 use async_channel::{Receiver, Sender};
@@ -13,6 +13,7 @@ use futures_util::{pin_mut as pin, select, StreamExt};
 use std::{
     sync::{Arc, Mutex, OnceLock, RwLock},
     thread,
+    time::Duration,
 };
 #[cfg(feature = "tokio")]
 use tokio::{
@@ -21,13 +22,13 @@ use tokio::{
 };
 #[cfg(feature = "tokio")]
 use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
-use zbus::{Connection, ConnectionBuilder};
+use zbus::{fdo::DBusProxy, Connection, ConnectionBuilder};
 
 use crate::{
-    adapter::{AdapterState, Callback, Message},
+    adapter::{AdapterState, AnyActivationHandler, Callback, Message},
     atspi::{map_or_ignoring_broken_pipe, Bus},
     executor::Executor,
-    util::block_on,
+    util::{block_on, sleep},
 };
 
 static APP_CONTEXT: OnceLock<Arc<RwLock<AppContext>>> = OnceLock::new();
@@ -44,39 +45,90 @@ pub(crate) fn get_or_init_app_context<'a>() -> &'a Arc<RwLock<AppContext>> {
     APP_CONTEXT.get_or_init(|| AppContext::new(app_name()))
 }
 
+pub(crate) type MessageReceiver = Receiver<Message>;
+
+fn message_channel() -> (Sender<Message>, MessageReceiver) {
+    #[cfg(not(feature = "tokio"))]
+    return async_channel::unbounded();
+    #[cfg(feature = "tokio")]
+    return tokio::sync::mpsc::unbounded_channel();
+}
+
 pub(crate) fn get_or_init_messages() -> Sender<Message> {
     MESSAGES
         .get_or_init(|| {
-            #[cfg(not(feature = "tokio"))]
-            let (tx, rx) = async_channel::unbounded();
-            #[cfg(feature = "tokio")]
-            let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-
-            thread::spawn(|| {
-                let executor = Executor::new();
-                block_on(executor.run(async {
-                    if let Ok(session_bus) = ConnectionBuilder::session() {
-                        if let Ok(session_bus) = session_bus.internal_executor(false).build().await
-                        {
-                            run_event_loop(&executor, session_bus, rx).await.unwrap();
-                        }
-                    }
-                }))
-            });
-
+            let (tx, rx) = message_channel();
+            thread::spawn(|| block_on(run_worker(rx)));
             tx
         })
         .clone()
 }
 
+/// Initializes the message channel without spawning the worker thread,
+/// returning the receiving end so the caller can run the worker itself.
+/// Returns `None` if the channel was already initialized.
+pub(crate) fn init_messages_for_external_worker() -> Option<MessageReceiver> {
+    let mut result = None;
+    MESSAGES.get_or_init(|| {
+        let (tx, rx) = message_channel();
+        result = Some(rx);
+        tx
+    });
+    result
+}
+
+fn send_message(message: Message) {
+    if let Some(messages) = MESSAGES.get() {
+        #[cfg(not(feature = "tokio"))]
+        let _ = messages.try_send(message);
+        #[cfg(feature = "tokio")]
+        let _ = messages.send(message);
+    }
+}
+
+fn notify_bus_disconnected() {
+    send_message(Message::BusDisconnected);
+}
+
+/// The number of times we try to connect to the accessibility bus
+/// after losing it, before waiting for it to reappear on the session bus.
+const MAX_RECONNECT_ATTEMPTS: u32 = 8;
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(100);
+
+/// Returns how long to wait before trying again to connect to
+/// the accessibility bus, after the given number of failed attempts,
+/// or `None` if we should give up.
+fn reconnect_delay(attempt: u32) -> Option<Duration> {
+    (attempt < MAX_RECONNECT_ATTEMPTS).then(|| INITIAL_RECONNECT_DELAY * 2u32.pow(attempt))
+}
+
+pub(crate) async fn run_worker(rx: MessageReceiver) {
+    let executor = Executor::new();
+    executor
+        .run(async {
+            if let Ok(session_bus) = ConnectionBuilder::session() {
+                if let Ok(session_bus) = session_bus.internal_executor(false).build().await {
+                    if let Err(_error) = run_event_loop(&executor, session_bus, rx).await {
+                        // Adapters ignore the failure to send messages
+                        // once the receiver is dropped, so the application
+                        // keeps running without accessibility.
+                        #[cfg(feature = "tracing")]
+                        tracing::error!(error = %_error, "AccessKit worker stopped");
+                    }
+                }
+            }
+        })
+        .await
+}
+
 struct AdapterEntry {
     id: usize,
-    activation_handler: Box<dyn ActivationHandler>,
-    deactivation_handler: Box<dyn DeactivationHandler>,
+    activation_handler: AnyActivationHandler,
+    deactivation_handler: Box<dyn DeactivationHandler + Send>,
     state: Arc<Mutex<AdapterState>>,
 }
 
-fn activate_adapter(entry: &mut AdapterEntry) {
+fn activate_adapter(executor: &Executor<'_>, entry: &mut AdapterEntry) {
     let mut state = entry.state.lock().unwrap();
     if let AdapterState::Inactive {
         is_window_focused,
@@ -84,7 +136,27 @@ fn activate_adapter(entry: &mut AdapterEntry) {
         action_handler,
     } = &*state
     {
-        *state = match entry.activation_handler.request_initial_tree() {
+        let initial_state = match &mut entry.activation_handler {
+            AnyActivationHandler::Sync(handler) => handler.request_initial_tree(),
+            AnyActivationHandler::Async(handler) => {
+                let future = handler.request_initial_tree();
+                let id = entry.id;
+                let adapter_state = Arc::clone(&entry.state);
+                executor
+                    .spawn(
+                        async move {
+                            if let Some(initial_state) = future.await {
+                                let mut state = adapter_state.lock().unwrap();
+                                state.activate_pending(id, initial_state);
+                            }
+                        },
+                        "accesskit_request_initial_tree",
+                    )
+                    .detach();
+                None
+            }
+        };
+        *state = match initial_state {
             Some(initial_state) => {
                 let r#impl = AdapterImpl::with_wrapped_action_handler(
                     entry.id,
@@ -135,6 +207,68 @@ fn deactivate_adapter(entry: &mut AdapterEntry) {
     }
 }
 
+/// Throw away the adapter's current tree, if any, and request a new one
+/// from the application, without going through deactivation.
+fn refresh_adapter(executor: &Executor<'_>, entry: &mut AdapterEntry) {
+    let mut state = entry.state.lock().unwrap();
+    if let AdapterState::Active(r#impl) = &*state {
+        *state = AdapterState::Inactive {
+            is_window_focused: r#impl.is_window_focused(),
+            root_window_bounds: r#impl.root_window_bounds(),
+            action_handler: r#impl.wrapped_action_handler(),
+        };
+        drop(state);
+        activate_adapter(executor, entry);
+    }
+}
+
+fn update_adapters(
+    executor: &Executor<'_>,
+    atspi_bus: &Option<Bus>,
+    adapters: &mut [AdapterEntry],
+) {
+    for entry in adapters {
+        if atspi_bus.is_some() {
+            activate_adapter(executor, entry);
+        } else {
+            deactivate_adapter(entry);
+        }
+    }
+}
+
+/// Tries to connect to the accessibility bus again. If this fails while
+/// assistive technologies are enabled, another attempt is scheduled,
+/// with an exponential backoff.
+async fn reconnect(
+    executor: &Executor<'_>,
+    session_bus: &Connection,
+    status: &StatusProxy<'_>,
+    attempt: u32,
+) -> Option<Bus> {
+    let result = match status.is_enabled().await {
+        // The bus may not be back yet, in which case we try again
+        // when it reappears on the session bus.
+        Ok(false) => return None,
+        Ok(true) => Bus::new(session_bus, executor, notify_bus_disconnected).await,
+        Err(error) => Err(error),
+    };
+    if result.is_err() {
+        if let Some(delay) = reconnect_delay(attempt) {
+            let attempt = attempt + 1;
+            executor
+                .spawn(
+                    async move {
+                        sleep(delay).await;
+                        send_message(Message::RetryBusConnection { attempt });
+                    },
+                    "accesskit_reconnect_task",
+                )
+                .detach();
+        }
+    }
+    result.ok()
+}
+
 async fn run_event_loop(
     executor: &Executor<'_>,
     session_bus: Connection,
@@ -154,6 +288,13 @@ async fn run_event_loop(
     let changes = status.receive_is_enabled_changed().await.fuse();
     pin!(changes);
 
+    let dbus = DBusProxy::new(&session_bus).await?;
+    let bus_owner_changes = dbus
+        .receive_name_owner_changed_with_args(&[(0, "org.a11y.Bus")])
+        .await?
+        .fuse();
+    pin!(bus_owner_changes);
+
     #[cfg(not(feature = "tokio"))]
     let messages = rx.fuse();
     #[cfg(feature = "tokio")]
@@ -169,28 +310,39 @@ async fn run_event_loop(
                 atspi_bus = None;
                 if let Some(change) = change {
                     if change.get().await? {
-                        atspi_bus = map_or_ignoring_broken_pipe(Bus::new(&session_bus, executor).await, None, Some)?;
+                        atspi_bus = map_or_ignoring_broken_pipe(Bus::new(&session_bus, executor, notify_bus_disconnected).await, None, Some)?;
                     }
                 }
-                for entry in &mut adapters {
-                    if atspi_bus.is_some() {
-                        activate_adapter(entry);
-                    } else {
-                        deactivate_adapter(entry);
-                    }
+                update_adapters(executor, &atspi_bus, &mut adapters);
+            }
+            change = bus_owner_changes.next() => {
+                let has_new_owner = change.is_some_and(|change| {
+                    change.args().is_ok_and(|args| args.new_owner().is_some())
+                });
+                if atspi_bus.is_none() && has_new_owner {
+                    atspi_bus = reconnect(executor, &session_bus, &status, 0).await;
+                    update_adapters(executor, &atspi_bus, &mut adapters);
                 }
             }
             message = messages.next() => {
                 if let Some(message) = message {
-                    process_adapter_message(&atspi_bus, &mut adapters, message).await?;
+                    #[cfg(feature = "tracing")]
+                    let span = message.span();
+                    let future = process_message(executor, &session_bus, &status, &mut atspi_bus, &mut adapters, message);
+                    #[cfg(feature = "tracing")]
+                    let future = tracing::Instrument::instrument(future, span);
+                    future.await?;
                 }
             }
         }
     }
 }
 
-async fn process_adapter_message(
-    atspi_bus: &Option<Bus>,
+async fn process_message(
+    executor: &Executor<'_>,
+    session_bus: &Connection,
+    status: &StatusProxy<'_>,
+    atspi_bus: &mut Option<Bus>,
     adapters: &mut Vec<AdapterEntry>,
     message: Message,
 ) -> zbus::Result<()> {
@@ -209,7 +361,7 @@ async fn process_adapter_message(
             });
             if atspi_bus.is_some() {
                 let entry = adapters.last_mut().unwrap();
-                activate_adapter(entry);
+                activate_adapter(executor, entry);
             }
         }
         Message::RemoveAdapter { id } => {
@@ -217,6 +369,13 @@ async fn process_adapter_message(
                 adapters.remove(index);
             }
         }
+        Message::RefreshAdapter { id } => {
+            if atspi_bus.is_some() {
+                if let Ok(index) = adapters.binary_search_by(|entry| entry.id.cmp(&id)) {
+                    refresh_adapter(executor, &mut adapters[index]);
+                }
+            }
+        }
         Message::RegisterInterfaces { node, interfaces } => {
             if let Some(bus) = atspi_bus {
                 bus.register_interfaces(node, interfaces).await?
@@ -235,11 +394,30 @@ async fn process_adapter_message(
         Message::EmitEvent {
             adapter_id,
             event: Event::Object { target, event },
+            ..
         } => {
             if let Some(bus) = atspi_bus {
                 bus.emit_object_event(adapter_id, target, event).await?
             }
         }
+        Message::RunAction { future } => {
+            executor.spawn(future, "accesskit_action").detach();
+        }
+        Message::BusDisconnected => {
+            // Let the application know that assistive technologies
+            // can no longer see its tree, then try to expose it again,
+            // requesting a full tree from each adapter.
+            *atspi_bus = None;
+            update_adapters(executor, atspi_bus, adapters);
+            *atspi_bus = reconnect(executor, session_bus, status, 0).await;
+            update_adapters(executor, atspi_bus, adapters);
+        }
+        Message::RetryBusConnection { attempt } => {
+            if atspi_bus.is_none() {
+                *atspi_bus = reconnect(executor, session_bus, status, attempt).await;
+                update_adapters(executor, atspi_bus, adapters);
+            }
+        }
         Message::EmitEvent {
             adapter_id,
             event:
@@ -248,6 +426,7 @@ async fn process_adapter_message(
                     name,
                     event,
                 },
+            ..
         } => {
             if let Some(bus) = atspi_bus {
                 bus.emit_window_event(adapter_id, target, name, event)
@@ -258,3 +437,88 @@ async fn process_adapter_message(
 
     Ok(())
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use accesskit::{
+        ActionHandler, ActionRequest, AsyncActivationHandler, BoxFuture, Node, NodeId, Role, Tree,
+        TreeUpdate,
+    };
+    use accesskit_atspi_common::ActionHandlerWrapper;
+
+    use super::*;
+
+    /// Claims the message channel for the tests, so that no worker thread
+    /// is spawned and the tests can inspect what the adapters send.
+    pub(crate) fn test_messages() -> &'static Mutex<MessageReceiver> {
+        static RECEIVER: OnceLock<Mutex<MessageReceiver>> = OnceLock::new();
+        RECEIVER.get_or_init(|| Mutex::new(init_messages_for_external_worker().unwrap()))
+    }
+
+    struct Activation;
+
+    impl AsyncActivationHandler for Activation {
+        fn request_initial_tree(&mut self) -> BoxFuture<Option<TreeUpdate>> {
+            Box::pin(async {
+                Some(TreeUpdate {
+                    nodes: vec![(NodeId(0), Node::new(Role::Window))],
+                    tree: Some(Tree::new(NodeId(0))),
+                    focus: NodeId(0),
+                })
+            })
+        }
+    }
+
+    struct NullActionHandler;
+
+    impl ActionHandler for NullActionHandler {
+        fn do_action(&mut self, _request: ActionRequest) {}
+    }
+
+    struct NullDeactivationHandler;
+
+    impl DeactivationHandler for NullDeactivationHandler {
+        fn deactivate_accessibility(&mut self) {}
+    }
+
+    #[test]
+    fn async_activation() {
+        test_messages();
+        let executor = Executor::new();
+        let state = Arc::new(Mutex::new(AdapterState::Inactive {
+            is_window_focused: false,
+            root_window_bounds: Default::default(),
+            action_handler: Arc::new(ActionHandlerWrapper::new(NullActionHandler)),
+        }));
+        let mut entry = AdapterEntry {
+            id: 0,
+            activation_handler: AnyActivationHandler::Async(Box::new(Activation)),
+            deactivation_handler: Box::new(NullDeactivationHandler),
+            state: Arc::clone(&state),
+        };
+        block_on(executor.run(async {
+            activate_adapter(&executor, &mut entry);
+            // The adapter waits for the initial tree without blocking
+            // the worker.
+            assert!(matches!(
+                *state.lock().unwrap(),
+                AdapterState::Pending { .. }
+            ));
+            while !matches!(*state.lock().unwrap(), AdapterState::Active(_)) {
+                futures_lite::future::yield_now().await;
+            }
+        }));
+    }
+
+    #[test]
+    fn reconnect_delay_backs_off_exponentially() {
+        assert_eq!(reconnect_delay(0), Some(Duration::from_millis(100)));
+        assert_eq!(reconnect_delay(1), Some(Duration::from_millis(200)));
+        assert_eq!(reconnect_delay(2), Some(Duration::from_millis(400)));
+        assert_eq!(
+            reconnect_delay(MAX_RECONNECT_ATTEMPTS - 1),
+            Some(Duration::from_millis(12800))
+        );
+        assert_eq!(reconnect_delay(MAX_RECONNECT_ATTEMPTS), None);
+    }
+}
@@ -3,12 +3,116 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-/// ## Compatibility with async runtimes
-///
-/// While this crate's API is purely blocking, it internally spawns asynchronous tasks on an executor.
-///
-/// - If you use tokio, make sure to enable the `tokio` feature of this crate.
-/// - If you use another async runtime or if you don't use one at all, the default feature will suit your needs.
+//! ## Compatibility with async runtimes
+//!
+//! While this crate's API is purely blocking, it internally spawns asynchronous tasks on an executor.
+//!
+//! - If you use tokio, make sure to enable the `tokio` feature of this crate.
+//! - If you use another async runtime or if you don't use one at all, the default feature will suit your needs.
+//!
+//! These tasks run on a dedicated thread by default. To drive them from your own event loop or
+//! executor instead, claim the [`Worker`] before creating any adapter.
+//!
+//! Applications built on an async runtime can implement
+//! [`AsyncActivationHandler`](accesskit::AsyncActivationHandler) and
+//! [`AsyncActionHandler`](accesskit::AsyncActionHandler) instead of their blocking
+//! counterparts, and create the adapter with [`Adapter::with_async_handlers`]. The futures
+//! returned by these handlers run on the same executor as the adapter's own tasks.
+//!
+//! ## Losing the accessibility bus
+//!
+//! If the connection to the accessibility bus is lost, for example because the bus was
+//! restarted, every active adapter is deactivated, calling its
+//! [`DeactivationHandler`](accesskit::DeactivationHandler). The adapters then reconnect as soon
+//! as the bus is available again, requesting a full tree through their
+//! [`ActivationHandler`](accesskit::ActivationHandler) as they did when they were first
+//! activated.
+//!
+//! ## Existing windows
+//!
+//! Unlike the Windows and macOS adapters, this adapter never receives requests through the
+//! window itself: assistive technologies talk to it over the accessibility bus. There's thus no
+//! need for a subclassing adapter to retrofit a window created by another toolkit. Create an
+//! [`Adapter`] as usual, and keep it informed of the window's geometry and focus state with
+//! [`Adapter::set_root_window_bounds`] and [`Adapter::update_window_focus_state`], from whatever
+//! events the application already observes for that window.
+//!
+//! ## Example
+//!
+//! All of the handlers are called on the adapter's own thread, while the
+//! application updates the adapter from its UI thread. A handler must
+//! therefore never block waiting for the UI thread, since the UI thread may
+//! itself be waiting for the adapter, which would deadlock. Instead, the
+//! handlers below forward everything to the UI thread's event loop.
+//!
+//! ```no_run
+//! use accesskit::{
+//!     ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler, Node, NodeId, Role,
+//!     Tree, TreeUpdate,
+//! };
+//! use accesskit_unix::Adapter;
+//! use std::sync::mpsc::{channel, Sender};
+//!
+//! const WINDOW_ID: NodeId = NodeId(0);
+//!
+//! fn build_tree() -> TreeUpdate {
+//!     let mut window = Node::new(Role::Window);
+//!     window.set_label("Hello world");
+//!     TreeUpdate {
+//!         nodes: vec![(WINDOW_ID, window)],
+//!         tree: Some(Tree::new(WINDOW_ID)),
+//!         focus: WINDOW_ID,
+//!     }
+//! }
+//!
+//! enum UiEvent {
+//!     AccessibilityActivated,
+//!     Action(ActionRequest),
+//! }
+//!
+//! struct Handler(Sender<UiEvent>);
+//!
+//! impl ActivationHandler for Handler {
+//!     fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+//!         // The tree lives on the UI thread, so ask it to send the tree
+//!         // through `update_if_active` rather than waiting for it here.
+//!         let _ = self.0.send(UiEvent::AccessibilityActivated);
+//!         None
+//!     }
+//! }
+//!
+//! impl ActionHandler for Handler {
+//!     fn do_action(&mut self, request: ActionRequest) {
+//!         let _ = self.0.send(UiEvent::Action(request));
+//!     }
+//! }
+//!
+//! struct NoopDeactivationHandler;
+//!
+//! impl DeactivationHandler for NoopDeactivationHandler {
+//!     fn deactivate_accessibility(&mut self) {}
+//! }
+//!
+//! let (sender, receiver) = channel();
+//! let mut adapter = Adapter::new(
+//!     Handler(sender.clone()),
+//!     Handler(sender),
+//!     NoopDeactivationHandler,
+//! );
+//! adapter.update_window_focus_state(true);
+//!
+//! // The UI thread's event loop.
+//! for event in receiver {
+//!     match event {
+//!         UiEvent::AccessibilityActivated => adapter.update_if_active(build_tree),
+//!         UiEvent::Action(request) => {
+//!             // Perform the action, then send any resulting changes
+//!             // through `update_if_active`.
+//!             let _ = request;
+//!         }
+//!     }
+//! }
+//! ```
 
 #[cfg(all(not(feature = "async-io"), not(feature = "tokio")))]
 compile_error!("Either \"async-io\" (default) or \"tokio\" feature must be enabled.");
@@ -20,8 +124,12 @@ compile_error!(
 
 mod adapter;
 mod atspi;
+mod backend;
 mod context;
 mod executor;
 mod util;
+mod worker;
 
 pub use adapter::Adapter;
+pub use backend::Backend;
+pub use worker::Worker;
@@ -4,6 +4,7 @@
 // the LICENSE-MIT file), at your option.
 
 use accesskit_atspi_common::{Error as InternalError, PlatformNode};
+use std::time::Duration;
 use zbus::fdo::Error as FdoError;
 
 use crate::atspi::ObjectId;
@@ -23,6 +24,16 @@ pub(crate) fn block_on<F: std::future::Future>(future: F) -> F::Output {
     runtime.block_on(future)
 }
 
+#[cfg(not(feature = "tokio"))]
+pub(crate) async fn sleep(duration: Duration) {
+    async_io::Timer::after(duration).await;
+}
+
+#[cfg(feature = "tokio")]
+pub(crate) async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await
+}
+
 pub(crate) fn map_error(source: ObjectId, error: InternalError) -> FdoError {
     match error {
         InternalError::Defunct | InternalError::UnsupportedInterface => {
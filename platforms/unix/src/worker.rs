@@ -0,0 +1,45 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use crate::context::{init_messages_for_external_worker, run_worker, MessageReceiver};
+
+/// The task that talks to the accessibility bus on behalf of every
+/// [`Adapter`](crate::Adapter) in the process.
+///
+/// By default, the worker runs on a thread of its own, which is spawned
+/// when the first adapter is created. Applications that can't have this
+/// thread, such as single-threaded Wayland clients or programs with
+/// a cooperative scheduler, can instead claim the worker with
+/// [`Worker::take`] before creating any adapter, then drive the future
+/// returned by [`Worker::run`] from their own event loop or executor.
+/// Until that future is polled, adapters queue their updates
+/// and no assistive technology can see the application.
+pub struct Worker {
+    messages: MessageReceiver,
+}
+
+impl Worker {
+    /// Claims the worker, so that no thread is spawned for it.
+    ///
+    /// Returns `None` if the worker has already been claimed, or if an
+    /// adapter has already been created and the worker thread has been
+    /// spawned.
+    pub fn take() -> Option<Self> {
+        init_messages_for_external_worker().map(|messages| Self { messages })
+    }
+
+    /// Runs the worker. The returned future only completes if the session
+    /// bus is unavailable, or if communicating with it fails, in which case
+    /// the error is logged when the `tracing` feature is enabled. Otherwise,
+    /// it should be polled for as long as the application has adapters.
+    ///
+    /// With the `tokio` feature, this future must be polled within
+    /// a tokio runtime that has the time driver enabled, which the worker
+    /// uses to wait before reconnecting to the accessibility bus.
+    /// Otherwise, any executor will do.
+    pub async fn run(self) {
+        run_worker(self.messages).await
+    }
+}
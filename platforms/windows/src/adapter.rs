@@ -4,10 +4,12 @@
 // the LICENSE-MIT file), at your option.
 
 use accesskit::{
-    ActionHandler, ActivationHandler, Live, Node as NodeProvider, NodeId, Role, Tree as TreeData,
-    TreeUpdate,
+    ActionHandler, ActivationHandler, AnnouncementKind, GeometryUpdate, Live, Node as NodeProvider,
+    NodeId, Role, Tree as TreeData, TreeUpdate, Vec2,
+};
+use accesskit_consumer::{
+    FilterResult, LiveRegionChange, Node, Tree, TreeChangeHandler, TreeState,
 };
-use accesskit_consumer::{FilterResult, Node, Tree, TreeChangeHandler};
 use hashbrown::HashSet;
 use std::sync::{atomic::Ordering, Arc};
 use windows::Win32::{
@@ -16,10 +18,10 @@ use windows::Win32::{
 };
 
 use crate::{
-    context::{ActionHandlerNoMut, ActionHandlerWrapper, Context},
+    context::{ActionHandlerNoMut, ActionHandlerWrapper, Context, HostParent},
     filters::filter,
-    node::{NodeWrapper, PlatformNode},
-    util::QueuedEvent,
+    node::{runtime_id_from_node_id, NodeWrapper, PlatformNode},
+    util::{raise_notification_event, QueuedEvent, Variant},
     window_handle::WindowHandle,
 };
 
@@ -36,6 +38,7 @@ struct AdapterChangeHandler<'a> {
     context: &'a Arc<Context>,
     queue: Vec<QueuedEvent>,
     text_changed: HashSet<NodeId>,
+    closed_tooltip_ancestors: Vec<Vec<NodeId>>,
 }
 
 impl<'a> AdapterChangeHandler<'a> {
@@ -44,6 +47,7 @@ impl<'a> AdapterChangeHandler<'a> {
             context,
             queue: Vec::new(),
             text_changed: HashSet::new(),
+            closed_tooltip_ancestors: Vec::new(),
         }
     }
 }
@@ -80,6 +84,54 @@ impl AdapterChangeHandler<'_> {
             self.insert_text_change_if_needed_parent(node);
         }
     }
+
+    fn enqueue_tooltip_event(&mut self, node: &Node, is_opened: bool) {
+        if node.role() != Role::Tooltip {
+            return;
+        }
+        self.enqueue_tooltip_event_on(node.id(), is_opened);
+    }
+
+    fn enqueue_tooltip_event_on(&mut self, id: NodeId, is_opened: bool) {
+        let platform_node = PlatformNode::new(self.context, id);
+        let element: IRawElementProviderSimple = platform_node.into();
+        self.queue.push(QueuedEvent::Simple {
+            element,
+            event_id: if is_opened {
+                UIA_ToolTipOpenedEventId
+            } else {
+                UIA_ToolTipClosedEventId
+            },
+        });
+    }
+
+    /// A removed tooltip no longer has an element that UIA clients could
+    /// query, so remember the ancestors of the old node, and raise
+    /// the event on the nearest one that's still in the tree once
+    /// the update has been applied.
+    fn remember_closed_tooltip(&mut self, node: &Node) {
+        if node.role() != Role::Tooltip {
+            return;
+        }
+        let mut ancestors = Vec::new();
+        let mut current = node.filtered_parent(&filter);
+        while let Some(ancestor) = current {
+            ancestors.push(ancestor.id());
+            current = ancestor.filtered_parent(&filter);
+        }
+        self.closed_tooltip_ancestors.push(ancestors);
+    }
+
+    fn enqueue_closed_tooltip_events(&mut self, state: &TreeState) {
+        for ancestors in std::mem::take(&mut self.closed_tooltip_ancestors) {
+            let remaining = ancestors
+                .into_iter()
+                .find(|id| state.node_by_id(*id).is_some());
+            if let Some(id) = remaining {
+                self.enqueue_tooltip_event_on(id, false);
+            }
+        }
+    }
 }
 
 impl TreeChangeHandler for AdapterChangeHandler<'_> {
@@ -88,6 +140,15 @@ impl TreeChangeHandler for AdapterChangeHandler<'_> {
         if filter(node) != FilterResult::Include {
             return;
         }
+        self.enqueue_tooltip_event(node, true);
+        if node.role() == Role::Alert {
+            let platform_node = PlatformNode::new(self.context, node.id());
+            let element: IRawElementProviderSimple = platform_node.into();
+            self.queue.push(QueuedEvent::Simple {
+                element,
+                event_id: UIA_SystemAlertEventId,
+            });
+        }
         let wrapper = NodeWrapper(node);
         if wrapper.name().is_some() && node.live() != Live::Off {
             let platform_node = PlatformNode::new(self.context, node.id());
@@ -103,7 +164,16 @@ impl TreeChangeHandler for AdapterChangeHandler<'_> {
         if old_node.raw_value() != new_node.raw_value() {
             self.insert_text_change_if_needed(new_node);
         }
-        if filter(new_node) != FilterResult::Include {
+        let old_filter_result = filter(old_node);
+        let new_filter_result = filter(new_node);
+        if new_filter_result != old_filter_result {
+            if new_filter_result == FilterResult::Include {
+                self.enqueue_tooltip_event(new_node, true);
+            } else if old_filter_result == FilterResult::Include {
+                self.enqueue_tooltip_event(old_node, false);
+            }
+        }
+        if new_filter_result != FilterResult::Include {
             return;
         }
         let platform_node = PlatformNode::new(self.context, new_node.id());
@@ -112,19 +182,76 @@ impl TreeChangeHandler for AdapterChangeHandler<'_> {
         let new_wrapper = NodeWrapper(new_node);
         new_wrapper.enqueue_property_changes(&mut self.queue, &element, &old_wrapper);
         let new_name = new_wrapper.name();
-        if new_name.is_some()
-            && new_node.live() != Live::Off
-            && (new_node.live() != old_node.live()
-                || filter(old_node) != FilterResult::Include
-                || new_name != old_wrapper.name())
-        {
+        if new_name.is_none() || new_node.live() == Live::Off {
+            return;
+        }
+        if new_node.live() != old_node.live() || old_filter_result != FilterResult::Include {
             self.queue.push(QueuedEvent::Simple {
                 element,
                 event_id: UIA_LiveRegionChangedEventId,
             });
+        } else if new_name != old_wrapper.name() {
+            // LiveRegionChanged makes the screen reader read the whole name,
+            // so when text is only appended, announce just the new text.
+            let old_text = live_region_text(old_node);
+            let new_text = live_region_text(new_node);
+            match LiveRegionChange::new(&old_text, &new_text) {
+                Some(LiveRegionChange::Appended(text)) => {
+                    self.queue.push(QueuedEvent::Notification {
+                        element,
+                        kind: NotificationKind_ItemAdded,
+                        processing: if new_node.live() == Live::Assertive {
+                            NotificationProcessing_ImportantAll
+                        } else {
+                            NotificationProcessing_All
+                        },
+                        text: text.into(),
+                    });
+                }
+                Some(LiveRegionChange::Replaced(_)) => {
+                    self.queue.push(QueuedEvent::Simple {
+                        element,
+                        event_id: UIA_LiveRegionChangedEventId,
+                    });
+                }
+                None => (),
+            }
         }
     }
 
+    fn children_reordered(&mut self, _old_node: &Node, new_node: &Node) {
+        let node = if filter(new_node) == FilterResult::Include {
+            *new_node
+        } else if let Some(node) = new_node.filtered_parent(&filter) {
+            node
+        } else {
+            return;
+        };
+        let platform_node = PlatformNode::new(self.context, node.id());
+        let element: IRawElementProviderSimple = platform_node.into();
+        self.queue.push(QueuedEvent::StructureChanged {
+            element,
+            change_type: StructureChangeType_ChildrenReordered,
+            runtime_id: runtime_id_from_node_id(node.id()).to_vec(),
+        });
+    }
+
+    fn bounds_changed(&mut self, _old_node: &Node, new_node: &Node) {
+        if filter(new_node) != FilterResult::Include {
+            return;
+        }
+        let platform_node = PlatformNode::new(self.context, new_node.id());
+        let element: IRawElementProviderSimple = platform_node.into();
+        // Clients get the new rectangle from the element itself,
+        // so the old and new values are left empty.
+        self.queue.push(QueuedEvent::PropertyChanged {
+            element,
+            property_id: UIA_BoundingRectanglePropertyId,
+            old_value: Variant::empty().into(),
+            new_value: Variant::empty().into(),
+        });
+    }
+
     fn focus_moved(&mut self, _old_node: Option<&Node>, new_node: Option<&Node>) {
         if let Some(new_node) = new_node {
             self.queue.push(focus_event(self.context, new_node.id()));
@@ -133,25 +260,110 @@ impl TreeChangeHandler for AdapterChangeHandler<'_> {
 
     fn node_removed(&mut self, node: &Node) {
         self.insert_text_change_if_needed(node);
+        if filter(node) == FilterResult::Include {
+            self.remember_closed_tooltip(node);
+        }
     }
 
     // TODO: handle other events (#20)
 }
 
+fn live_region_text(node: &Node) -> String {
+    node.name().unwrap_or_default()
+}
+
 const PLACEHOLDER_ROOT_ID: NodeId = NodeId(0);
 
 enum State {
     Inactive {
-        hwnd: WindowHandle,
-        is_window_focused: bool,
+        hwnd: Option<WindowHandle>,
         action_handler: Arc<dyn ActionHandlerNoMut + Send + Sync>,
+        content_origin: Vec2,
+        host_parent: Option<HostParent>,
     },
     Placeholder(Arc<Context>),
     Active(Arc<Context>),
 }
 
+/// A Windows platform adapter for a single window.
+///
+/// # Games and exclusive fullscreen
+///
+/// The adapter never observes window messages by itself; it relies on
+/// its owner to forward `WM_GETOBJECT` and to report focus changes.
+/// Games that render through DXGI in exclusive fullscreen often don't
+/// receive the usual `WM_SETFOCUS` and `WM_KILLFOCUS` messages when
+/// the user switches away, and may keep running while minimized or
+/// occluded. Such applications should drive the adapter's state
+/// explicitly:
+///
+/// - Call [`Adapter::update_window_focus_state`] when the game gains or
+///   loses input focus, for example on `WM_ACTIVATEAPP`, or when the engine's
+///   own focus tracking changes.
+/// - Call [`Adapter::set_window_visible`] when the game's output stops
+///   or resumes being presented, for example when
+///   `IDXGISwapChain::Present` reports `DXGI_STATUS_OCCLUDED`, or when
+///   the game leaves or re-enters fullscreen.
+///
+/// Both methods may be called redundantly, so it's fine to call them
+/// every frame with the engine's current state. The window handle passed
+/// to [`Adapter::new`] must still be the window that owns the swap chain,
+/// since UI Automation locates the accessibility tree through it, and
+/// node bounds are relative to its client area, which covers the whole
+/// monitor in exclusive fullscreen.
+///
+/// # Hosting without a window
+///
+/// Content that isn't the client area of a classic window, such as
+/// a Windows App SDK `ContentIsland` or a UWP `CoreWindow`, is made
+/// accessible through the hosting framework's own UI Automation provider
+/// rather than `WM_GETOBJECT`. Use [`Adapter::new_hosted`] for such content.
+/// The framework gives the application the provider of the element that
+/// hosts the content, for example through
+/// `ContentIsland.AutomationProviderRequested`, and the tree is exposed
+/// beneath it as described for [`Adapter::set_host_parent`].
+///
+/// Such an adapter can't observe any window state, so the application must
+/// report it explicitly: call [`Adapter::update_window_focus_state`] when
+/// the hosted content gains or loses focus, and
+/// [`Adapter::set_window_visible`] when it's shown or hidden.
+///
+/// # Example
+///
+/// The window procedure forwards `WM_GETOBJECT` to the adapter. UIA may
+/// send a nested `WM_GETOBJECT` while the result is converted to
+/// an `LRESULT`, so any lock on the adapter must be released first.
+/// For a complete program, see the `hello_world` example in this crate's
+/// repository.
+///
+/// ```no_run
+/// use accesskit::ActivationHandler;
+/// use accesskit_windows::{Adapter, LPARAM, LRESULT, WPARAM};
+/// use std::sync::Mutex;
+///
+/// struct WindowState<H: ActivationHandler> {
+///     adapter: Mutex<Adapter>,
+///     activation_handler: Mutex<H>,
+/// }
+///
+/// /// Returns `None` if the message should be passed to `DefWindowProcW`.
+/// fn handle_wm_getobject<H: ActivationHandler>(
+///     state: &WindowState<H>,
+///     wparam: WPARAM,
+///     lparam: LPARAM,
+/// ) -> Option<LRESULT> {
+///     let result = {
+///         let mut adapter = state.adapter.lock().unwrap();
+///         let mut activation_handler = state.activation_handler.lock().unwrap();
+///         adapter.handle_wm_getobject(wparam, lparam, &mut *activation_handler)
+///     };
+///     result.map(Into::into)
+/// }
+/// ```
 pub struct Adapter {
     state: State,
+    is_window_focused: bool,
+    is_window_visible: bool,
 }
 
 impl Adapter {
@@ -179,20 +391,146 @@ impl Adapter {
         )
     }
 
+    /// Creates a new Windows platform adapter for content that isn't
+    /// the client area of a window, beneath the given native UI Automation
+    /// element. See the [`Adapter`] documentation for details.
+    ///
+    /// Node coordinates are relative to the top-left corner of `parent`'s
+    /// bounding rectangle, offset by [`Adapter::set_content_origin`].
+    ///
+    /// The action handler may be called on any thread.
+    ///
+    /// Fails if an agile reference to `parent` can't be created,
+    /// for example because COM isn't initialized on the calling thread.
+    pub fn new_hosted(
+        parent: &IRawElementProviderFragment,
+        is_focused: bool,
+        action_handler: impl 'static + ActionHandler + Send,
+    ) -> windows::core::Result<Self> {
+        Ok(Self::with_state(
+            None,
+            Some(HostParent::new(parent)?),
+            is_focused,
+            Arc::new(ActionHandlerWrapper::new(action_handler)),
+        ))
+    }
+
     // Currently required by the test infrastructure
     pub(crate) fn with_wrapped_action_handler(
         hwnd: HWND,
         is_window_focused: bool,
         action_handler: Arc<dyn ActionHandlerNoMut + Send + Sync>,
+    ) -> Self {
+        Self::with_state(Some(hwnd.into()), None, is_window_focused, action_handler)
+    }
+
+    fn with_state(
+        hwnd: Option<WindowHandle>,
+        host_parent: Option<HostParent>,
+        is_window_focused: bool,
+        action_handler: Arc<dyn ActionHandlerNoMut + Send + Sync>,
     ) -> Self {
         init_uia();
 
         let state = State::Inactive {
-            hwnd: hwnd.into(),
-            is_window_focused,
+            hwnd,
             action_handler,
+            content_origin: Vec2::ZERO,
+            host_parent,
         };
-        Self { state }
+        Self {
+            state,
+            is_window_focused,
+            is_window_visible: true,
+        }
+    }
+
+    fn is_host_focused(&self) -> bool {
+        self.is_window_focused && self.is_window_visible
+    }
+
+    /// Set the offset of the origin of the node coordinate space
+    /// from the top-left corner of the window's client area, in physical
+    /// pixels. This is useful when the toolkit's coordinates are relative
+    /// to something other than the client area, for example a swapchain
+    /// that extends under a custom title bar. The default is zero.
+    ///
+    /// This method may be safely called on any thread.
+    pub fn set_content_origin(&mut self, origin: Vec2) {
+        match &mut self.state {
+            State::Inactive { content_origin, .. } => {
+                *content_origin = origin;
+            }
+            State::Placeholder(context) | State::Active(context) => {
+                *context.content_origin.write().unwrap() = origin;
+            }
+        }
+    }
+
+    /// Embed the tree beneath an element of a native UI Automation provider
+    /// implementation, rather than exposing it as the content of the window.
+    /// This allows an application to make part of a window accessible
+    /// through AccessKit, for example a custom-drawn canvas, while the rest
+    /// of the window keeps its existing accessibility implementation.
+    ///
+    /// While embedded, the root node reports `parent` as its parent and
+    /// the fragment root of `parent` as its fragment root, and it no longer
+    /// reports the window as its host. The application must then:
+    ///
+    /// - Stop forwarding `WM_GETOBJECT` to [`Adapter::handle_wm_getobject`].
+    /// - Return the provider from [`Adapter::root_provider`] when UIA
+    ///   navigates from `parent` to its children.
+    /// - Forward `IRawElementProviderFragmentRoot::ElementProviderFromPoint`
+    ///   to the same provider for points within the embedded content, and
+    ///   `IRawElementProviderFragmentRoot::GetFocus` while the embedded
+    ///   content has focus. In both cases, if the call fails, the root
+    ///   provider itself is the result.
+    /// - Call [`Adapter::update_window_focus_state`] when the embedded
+    ///   content gains or loses focus, rather than the whole window.
+    ///
+    /// Node coordinates are still relative to the window's client area,
+    /// so [`Adapter::set_content_origin`] should usually be set to
+    /// the position of the embedded content. Pass `None` to expose the tree
+    /// as the content of the window again.
+    ///
+    /// Fails if an agile reference to `parent` can't be created,
+    /// for example because COM isn't initialized on the calling thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parent` is `None` and the adapter was created with
+    /// [`Adapter::new_hosted`], since there's no window to fall back to.
+    pub fn set_host_parent(
+        &mut self,
+        parent: Option<&IRawElementProviderFragment>,
+    ) -> windows::core::Result<()> {
+        assert!(
+            parent.is_some() || self.hwnd().is_some(),
+            "an adapter without a window must have a host parent"
+        );
+        let parent = parent.map(HostParent::new).transpose()?;
+        match &mut self.state {
+            State::Inactive { host_parent, .. } => {
+                *host_parent = parent;
+            }
+            State::Placeholder(context) | State::Active(context) => {
+                *context.host_parent.write().unwrap() = parent;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the UI Automation provider for the root of the tree,
+    /// initializing the tree if necessary using the provided
+    /// [`ActivationHandler`] implementation. This is only needed when
+    /// the tree is embedded beneath a native element; see
+    /// [`Adapter::set_host_parent`].
+    pub fn root_provider<H: ActivationHandler + ?Sized>(
+        &mut self,
+        activation_handler: &mut H,
+    ) -> IRawElementProviderFragmentRoot {
+        let platform_node = self.root_platform_node(activation_handler);
+        platform_node.into()
     }
 
     /// If and only if the tree has been initialized, call the provided function
@@ -211,30 +549,59 @@ impl Adapter {
         &mut self,
         update_factory: impl FnOnce() -> TreeUpdate,
     ) -> Option<QueuedEvents> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("accesskit_update_if_active").entered();
         match &self.state {
             State::Inactive { .. } => None,
             State::Placeholder(context) => {
                 let is_window_focused = context.read_tree().state().is_host_focused();
                 let tree = Tree::new(update_factory(), is_window_focused);
-                *context.tree.write().unwrap() = tree;
+                context.write_tree(|old_tree| *old_tree = tree);
                 context.is_placeholder.store(false, Ordering::SeqCst);
                 let result = context
                     .read_tree()
                     .state()
                     .focus_id()
-                    .map(|id| QueuedEvents(vec![focus_event(context, id)]));
+                    .map(|id| QueuedEvents::new(vec![focus_event(context, id)]));
                 self.state = State::Active(Arc::clone(context));
                 result
             }
             State::Active(context) => {
                 let mut handler = AdapterChangeHandler::new(context);
-                let mut tree = context.tree.write().unwrap();
-                tree.update_and_process_changes(update_factory(), &mut handler);
-                Some(QueuedEvents(handler.queue))
+                context.write_tree(|tree| {
+                    tree.update_and_process_changes(update_factory(), &mut handler);
+                    handler.enqueue_closed_tooltip_events(tree.state());
+                });
+                Some(QueuedEvents::new(handler.queue))
             }
         }
     }
 
+    /// If and only if the tree has been initialized, call the provided function
+    /// and apply the resulting [`GeometryUpdate`]. Since such an update only
+    /// applies to an existing tree, the function isn't called while the
+    /// adapter is waiting for the initial tree.
+    ///
+    /// If a [`QueuedEvents`] instance is returned, the caller must call
+    /// [`QueuedEvents::raise`] on it.
+    ///
+    /// This method may be safely called on any thread, but refer to
+    /// [`QueuedEvents::raise`] for restrictions on the context in which
+    /// it should be called.
+    pub fn update_geometry_if_active(
+        &mut self,
+        update_factory: impl FnOnce() -> GeometryUpdate,
+    ) -> Option<QueuedEvents> {
+        let State::Active(context) = &self.state else {
+            return None;
+        };
+        let mut handler = AdapterChangeHandler::new(context);
+        context.write_tree(|tree| {
+            tree.update_geometry_and_process_changes(update_factory(), &mut handler)
+        });
+        Some(QueuedEvents::new(handler.queue))
+    }
+
     /// Update the tree state based on whether the window is focused.
     ///
     /// If a [`QueuedEvents`] instance is returned, the caller must call
@@ -244,24 +611,74 @@ impl Adapter {
     /// [`QueuedEvents::raise`] for restrictions on the context in which
     /// it should be called.
     pub fn update_window_focus_state(&mut self, is_focused: bool) -> Option<QueuedEvents> {
-        match &mut self.state {
-            State::Inactive {
-                is_window_focused, ..
-            } => {
-                *is_window_focused = is_focused;
-                None
-            }
-            State::Placeholder(context) => {
-                let mut handler = AdapterChangeHandler::new(context);
-                let mut tree = context.tree.write().unwrap();
-                tree.update_host_focus_state_and_process_changes(is_focused, &mut handler);
-                Some(QueuedEvents(handler.queue))
-            }
+        self.is_window_focused = is_focused;
+        self.update_host_focus_state()
+    }
+
+    /// Update the tree state based on whether the window's content is
+    /// visible to the user. While the window isn't visible, the tree
+    /// reports that nothing has focus, regardless of the state passed to
+    /// [`Adapter::update_window_focus_state`]. Windows are assumed to be
+    /// visible initially. See the [`Adapter`] documentation for when
+    /// this is needed.
+    ///
+    /// If a [`QueuedEvents`] instance is returned, the caller must call
+    /// [`QueuedEvents::raise`] on it.
+    ///
+    /// This method may be safely called on any thread, but refer to
+    /// [`QueuedEvents::raise`] for restrictions on the context in which
+    /// it should be called.
+    pub fn set_window_visible(&mut self, is_visible: bool) -> Option<QueuedEvents> {
+        self.is_window_visible = is_visible;
+        self.update_host_focus_state()
+    }
+
+    /// Ask assistive technologies to speak the given text, without
+    /// requiring a live region in the tree. This is raised as a UIA
+    /// notification event on the root of the tree.
+    ///
+    /// If the tree isn't active, there is no assistive technology to notify,
+    /// so this returns `None`.
+    ///
+    /// If a [`QueuedEvents`] instance is returned, the caller must call
+    /// [`QueuedEvents::raise`] on it.
+    ///
+    /// This method may be safely called on any thread, but refer to
+    /// [`QueuedEvents::raise`] for restrictions on the context in which
+    /// it should be called.
+    pub fn announce(&mut self, text: &str, kind: AnnouncementKind) -> Option<QueuedEvents> {
+        match &self.state {
+            State::Inactive { .. } | State::Placeholder(_) => None,
             State::Active(context) => {
+                let root_id = context.read_tree().state().root_id();
+                let platform_node = PlatformNode::new(context, root_id);
+                let element: IRawElementProviderSimple = platform_node.into();
+                Some(QueuedEvents::new(vec![QueuedEvent::Notification {
+                    element,
+                    kind: NotificationKind_Other,
+                    processing: match kind {
+                        AnnouncementKind::Polite => NotificationProcessing_All,
+                        AnnouncementKind::Assertive => NotificationProcessing_ImportantAll,
+                    },
+                    text: text.into(),
+                }]))
+            }
+        }
+    }
+
+    fn update_host_focus_state(&self) -> Option<QueuedEvents> {
+        let is_host_focused = self.is_host_focused();
+        match &self.state {
+            State::Inactive { .. } => None,
+            State::Placeholder(context) | State::Active(context) => {
+                if context.read_tree().state().is_host_focused() == is_host_focused {
+                    return None;
+                }
                 let mut handler = AdapterChangeHandler::new(context);
-                let mut tree = context.tree.write().unwrap();
-                tree.update_host_focus_state_and_process_changes(is_focused, &mut handler);
-                Some(QueuedEvents(handler.queue))
+                context.write_tree(|tree| {
+                    tree.update_host_focus_state_and_process_changes(is_host_focused, &mut handler)
+                });
+                Some(QueuedEvents::new(handler.queue))
             }
         }
     }
@@ -277,6 +694,9 @@ impl Adapter {
     /// message, can be done outside of any lock that the caller might hold
     /// on the `Adapter` or window state, while still abstracting away
     /// the details of that call to UIA.
+    ///
+    /// An adapter created with [`Adapter::new_hosted`] has no window,
+    /// so it never handles this message.
     pub fn handle_wm_getobject<H: ActivationHandler + ?Sized>(
         &mut self,
         wparam: WPARAM,
@@ -291,50 +711,78 @@ impl Adapter {
         if objid < 0 && objid != UiaRootObjectId && objid != OBJID_CLIENT.0 {
             return None;
         }
+        let hwnd = self.hwnd()?;
+
+        let platform_node = self.root_platform_node(activation_handler);
+        let el: IRawElementProviderSimple = platform_node.into();
+        Some(WmGetObjectResult {
+            hwnd,
+            wparam,
+            lparam,
+            el,
+        })
+    }
 
-        let (hwnd, platform_node) = match &self.state {
+    fn hwnd(&self) -> Option<WindowHandle> {
+        match &self.state {
+            State::Inactive { hwnd, .. } => *hwnd,
+            State::Placeholder(context) | State::Active(context) => context.hwnd,
+        }
+    }
+
+    fn root_platform_node<H: ActivationHandler + ?Sized>(
+        &mut self,
+        activation_handler: &mut H,
+    ) -> PlatformNode {
+        let is_host_focused = self.is_host_focused();
+        match &self.state {
             State::Inactive {
                 hwnd,
-                is_window_focused,
                 action_handler,
+                content_origin,
+                host_parent,
             } => match activation_handler.request_initial_tree() {
                 Some(initial_state) => {
-                    let hwnd = *hwnd;
-                    let tree = Tree::new(initial_state, *is_window_focused);
-                    let context = Context::new(hwnd, tree, Arc::clone(action_handler), false);
+                    let tree = Tree::new(initial_state, is_host_focused);
+                    let context = Context::new(
+                        *hwnd,
+                        tree,
+                        Arc::clone(action_handler),
+                        false,
+                        *content_origin,
+                        host_parent.clone(),
+                    );
                     let node_id = context.read_tree().state().root_id();
                     let platform_node = PlatformNode::new(&context, node_id);
                     self.state = State::Active(context);
-                    (hwnd, platform_node)
+                    platform_node
                 }
                 None => {
-                    let hwnd = *hwnd;
                     let placeholder_update = TreeUpdate {
                         nodes: vec![(PLACEHOLDER_ROOT_ID, NodeProvider::new(Role::Window))],
                         tree: Some(TreeData::new(PLACEHOLDER_ROOT_ID)),
                         focus: PLACEHOLDER_ROOT_ID,
                     };
-                    let placeholder_tree = Tree::new(placeholder_update, *is_window_focused);
-                    let context =
-                        Context::new(hwnd, placeholder_tree, Arc::clone(action_handler), true);
+                    let placeholder_tree = Tree::new(placeholder_update, is_host_focused);
+                    let context = Context::new(
+                        *hwnd,
+                        placeholder_tree,
+                        Arc::clone(action_handler),
+                        true,
+                        *content_origin,
+                        host_parent.clone(),
+                    );
                     let platform_node = PlatformNode::unspecified_root(&context);
                     self.state = State::Placeholder(context);
-                    (hwnd, platform_node)
+                    platform_node
                 }
             },
-            State::Placeholder(context) => (context.hwnd, PlatformNode::unspecified_root(context)),
+            State::Placeholder(context) => PlatformNode::unspecified_root(context),
             State::Active(context) => {
                 let node_id = context.read_tree().state().root_id();
-                (context.hwnd, PlatformNode::new(context, node_id))
+                PlatformNode::new(context, node_id)
             }
-        };
-        let el: IRawElementProviderSimple = platform_node.into();
-        Some(WmGetObjectResult {
-            hwnd,
-            wparam,
-            lparam,
-            el,
-        })
+        }
     }
 }
 
@@ -372,9 +820,21 @@ impl From<WmGetObjectResult> for LRESULT {
 
 /// Events generated by a tree update.
 #[must_use = "events must be explicitly raised"]
-pub struct QueuedEvents(Vec<QueuedEvent>);
+pub struct QueuedEvents {
+    events: Vec<QueuedEvent>,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
 
 impl QueuedEvents {
+    fn new(events: Vec<QueuedEvent>) -> Self {
+        Self {
+            events,
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::current(),
+        }
+    }
+
     /// Raise all queued events synchronously.
     ///
     /// The window may receive `WM_GETOBJECT` messages during this call.
@@ -386,7 +846,14 @@ impl QueuedEvents {
     /// but based on the known behavior of UIA, MSAA, and some ATs,
     /// it's strongly recommended.
     pub fn raise(self) {
-        for event in self.0 {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!(
+            parent: &self.span,
+            "accesskit_raise_events",
+            event_count = self.events.len()
+        )
+        .entered();
+        for event in self.events {
             match event {
                 QueuedEvent::Simple { element, event_id } => {
                     unsafe { UiaRaiseAutomationEvent(&element, event_id) }.unwrap();
@@ -407,6 +874,32 @@ impl QueuedEvents {
                     }
                     .unwrap();
                 }
+                QueuedEvent::Notification {
+                    element,
+                    kind,
+                    processing,
+                    text,
+                } => {
+                    if !raise_notification_event(&element, kind, processing, &text) {
+                        unsafe { UiaRaiseAutomationEvent(&element, UIA_LiveRegionChangedEventId) }
+                            .unwrap();
+                    }
+                }
+                QueuedEvent::StructureChanged {
+                    element,
+                    change_type,
+                    mut runtime_id,
+                } => {
+                    unsafe {
+                        UiaRaiseStructureChangedEvent(
+                            &element,
+                            change_type,
+                            runtime_id.as_mut_ptr(),
+                            runtime_id.len() as _,
+                        )
+                    }
+                    .unwrap();
+                }
             }
         }
     }
@@ -0,0 +1,153 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Action, AdapterCapabilities, Role};
+
+/// The parts of the AccessKit schema that are exposed through UI Automation.
+///
+/// This must be kept in sync with the mappings in the `node` and `text`
+/// modules. A unit test checks that each listed property and action
+/// is handled.
+pub const CAPABILITIES: AdapterCapabilities = AdapterCapabilities {
+    api: "UI Automation",
+    unmapped_roles: &[Role::Unknown, Role::TextRun, Role::PdfActionableHighlight],
+    properties: &[
+        "children",
+        "bounds",
+        "transform",
+        "label",
+        "description",
+        "active_descendant",
+        "described_by",
+        "value",
+        "author_id",
+        "class_name",
+        "placeholder",
+        "role_description",
+        "help_text",
+        "earcon",
+        "pronunciation",
+        "numeric_value",
+        "min_numeric_value",
+        "max_numeric_value",
+        "numeric_value_step",
+        "numeric_value_jump",
+        "scroll_x",
+        "scroll_x_min",
+        "scroll_x_max",
+        "scroll_y",
+        "scroll_y_min",
+        "scroll_y_max",
+        "line_height",
+        "text_indent",
+        "margin_top",
+        "margin_bottom",
+        "margin_leading",
+        "margin_trailing",
+        "font_family",
+        "font_size",
+        "font_weight",
+        "bold",
+        "italic",
+        "character_lengths",
+        "word_lengths",
+        "text_selection",
+        "text_selections",
+        "details",
+        "row_count",
+        "column_count",
+        "row_index",
+        "column_index",
+        "row_span",
+        "column_span",
+        "toggled",
+        "selected",
+        "grabbed",
+        "drop_target",
+        "live",
+        "orientation",
+        "disabled",
+        "read_only",
+        "required",
+        "offscreen",
+    ],
+    actions: &[
+        Action::Click,
+        Action::Focus,
+        Action::ScrollIntoView,
+        Action::ScrollUp,
+        Action::ScrollDown,
+        Action::ScrollLeft,
+        Action::ScrollRight,
+        Action::SetScrollOffset,
+        Action::SetTextSelection,
+        Action::SetValue,
+        Action::Increment,
+        Action::Decrement,
+        Action::SetNumericValueRelative,
+    ],
+    events: &[
+        "AutomationFocusChanged",
+        "AutomationPropertyChanged",
+        "Drag_DragComplete",
+        "Drag_DragStart",
+        "LiveRegionChanged",
+        "Notification",
+        "SelectionItem_ElementSelected",
+        "StructureChanged",
+        "SystemAlert",
+        "Text_TextChanged",
+        "Text_TextSelectionChanged",
+        "ToolTipClosed",
+        "ToolTipOpened",
+    ],
+};
+
+#[cfg(test)]
+mod tests {
+    use super::CAPABILITIES;
+
+    #[test]
+    fn capabilities_match_mappings() {
+        accesskit_testing::check_capabilities(
+            &CAPABILITIES,
+            &[
+                include_str!("adapter.rs"),
+                include_str!("node.rs"),
+                include_str!("text.rs"),
+                include_str!("util.rs"),
+            ],
+            &[
+                ("bounds", &["bounding_box"]),
+                ("label", &["name"]),
+                ("active_descendant", &["focus_id"]),
+                ("described_by", &["description"]),
+                ("value", &["write_value"]),
+                ("placeholder", &["placeholder_unless_name"]),
+                ("scroll_x", &["horizontal_scroll_percent"]),
+                ("scroll_x_min", &["is_horizontally_scrollable"]),
+                ("scroll_x_max", &["is_horizontally_scrollable"]),
+                ("scroll_y", &["vertical_scroll_percent"]),
+                ("scroll_y_min", &["is_vertically_scrollable"]),
+                ("scroll_y_max", &["is_vertically_scrollable"]),
+                ("character_lengths", &["document_range"]),
+                ("word_lengths", &["is_word_start"]),
+                ("text_selection", &["raw_text_selection"]),
+                ("row_count", &["table_row_count"]),
+                ("column_count", &["table_column_count"]),
+                ("row_index", &["table_cell_position"]),
+                ("column_index", &["table_cell_position"]),
+                ("row_span", &["table_cell_position"]),
+                ("column_span", &["table_cell_position"]),
+                ("Action::Increment", &["set_numeric_value_action"]),
+                ("Action::Decrement", &["set_numeric_value_action"]),
+                (
+                    "Action::SetNumericValueRelative",
+                    &["set_numeric_value_action"],
+                ),
+            ],
+        );
+    }
+}
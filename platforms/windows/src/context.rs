@@ -3,14 +3,26 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-use accesskit::{ActionHandler, ActionRequest, Point};
-use accesskit_consumer::Tree;
-use std::sync::{atomic::AtomicBool, Arc, Mutex, RwLock, RwLockReadGuard};
+use accesskit::{ActionHandler, ActionRequest, ActionRequestError, Point, Rect, Vec2};
+use accesskit_consumer::{Tree, TreeState};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, RwLock, RwLockReadGuard, Weak,
+    },
+};
+use windows::{
+    core::{AgileReference, Result},
+    Win32::UI::Accessibility::IRawElementProviderFragment,
+};
 
 use crate::{util::*, window_handle::WindowHandle};
 
 pub(crate) trait ActionHandlerNoMut {
     fn do_action(&self, request: ActionRequest);
+    fn action_request_dropped(&self, request: &ActionRequest, error: ActionRequestError);
 }
 
 pub(crate) struct ActionHandlerWrapper<H: ActionHandler + Send>(Mutex<H>);
@@ -25,27 +37,72 @@ impl<H: ActionHandler + Send> ActionHandlerNoMut for ActionHandlerWrapper<H> {
     fn do_action(&self, request: ActionRequest) {
         self.0.lock().unwrap().do_action(request)
     }
+
+    fn action_request_dropped(&self, request: &ActionRequest, error: ActionRequestError) {
+        self.0
+            .lock()
+            .unwrap()
+            .action_request_dropped(request, error)
+    }
+}
+
+/// The native UI Automation element that the tree is embedded beneath,
+/// if it isn't the content of a whole window. The element may belong
+/// to an apartment-threaded provider, while UIA calls our providers
+/// on its own threads, so it's kept as an agile reference and resolved
+/// on the calling thread whenever it's used.
+#[derive(Clone)]
+pub(crate) struct HostParent(AgileReference<IRawElementProviderFragment>);
+
+impl HostParent {
+    pub(crate) fn new(parent: &IRawElementProviderFragment) -> Result<Self> {
+        AgileReference::new(parent).map(Self)
+    }
 }
 
 pub(crate) struct Context {
-    pub(crate) hwnd: WindowHandle,
+    /// The window whose client area contains the tree, or `None` if the
+    /// adapter was created with [`crate::Adapter::new_hosted`].
+    pub(crate) hwnd: Option<WindowHandle>,
     pub(crate) tree: RwLock<Tree>,
     pub(crate) action_handler: Arc<dyn ActionHandlerNoMut + Send + Sync>,
     pub(crate) is_placeholder: AtomicBool,
+    pub(crate) content_origin: RwLock<Vec2>,
+    pub(crate) host_parent: RwLock<Option<HostParent>>,
+    generation: AtomicU64,
+}
+
+struct TreeSnapshot {
+    context: Weak<Context>,
+    generation: u64,
+    state: Rc<TreeState>,
+}
+
+thread_local! {
+    // UIA clients typically fetch many properties of the same element
+    // in quick succession, e.g. when building a cache request.
+    // Keeping the most recently used tree state for each thread lets
+    // those calls skip the tree lock until the tree changes again.
+    static TREE_SNAPSHOT: RefCell<Option<TreeSnapshot>> = const { RefCell::new(None) };
 }
 
 impl Context {
     pub(crate) fn new(
-        hwnd: WindowHandle,
+        hwnd: Option<WindowHandle>,
         tree: Tree,
         action_handler: Arc<dyn ActionHandlerNoMut + Send + Sync>,
         is_placeholder: bool,
+        content_origin: Vec2,
+        host_parent: Option<HostParent>,
     ) -> Arc<Self> {
         Arc::new(Self {
             hwnd,
             tree: RwLock::new(tree),
             action_handler,
             is_placeholder: AtomicBool::new(is_placeholder),
+            content_origin: RwLock::new(content_origin),
+            host_parent: RwLock::new(host_parent),
+            generation: AtomicU64::new(0),
         })
     }
 
@@ -53,11 +110,94 @@ impl Context {
         self.tree.read().unwrap()
     }
 
-    pub(crate) fn client_top_left(&self) -> Point {
-        client_top_left(self.hwnd)
+    /// Modifies the tree, invalidating any tree snapshots.
+    pub(crate) fn write_tree<T>(&self, f: impl FnOnce(&mut Tree) -> T) -> T {
+        let mut tree = self.tree.write().unwrap();
+        let result = f(&mut tree);
+        self.generation.fetch_add(1, Ordering::Release);
+        result
+    }
+
+    /// Returns the current tree state, reusing this thread's snapshot
+    /// if the tree hasn't changed since the snapshot was taken.
+    pub(crate) fn tree_snapshot(self: &Arc<Self>) -> Rc<TreeState> {
+        let generation = self.generation.load(Ordering::Acquire);
+        TREE_SNAPSHOT.with(|snapshot| {
+            let mut snapshot = snapshot.borrow_mut();
+            if let Some(snapshot) = &*snapshot {
+                if snapshot.generation == generation
+                    && Weak::as_ptr(&snapshot.context) == Arc::as_ptr(self)
+                {
+                    return Rc::clone(&snapshot.state);
+                }
+            }
+            let tree = self.read_tree();
+            let state = Rc::new(tree.state().clone());
+            // Read the generation again while holding the lock,
+            // in case the tree was updated since we first read it.
+            let generation = self.generation.load(Ordering::Acquire);
+            *snapshot = Some(TreeSnapshot {
+                context: Arc::downgrade(self),
+                generation,
+                state: Rc::clone(&state),
+            });
+            state
+        })
+    }
+
+    /// Returns true if the tree is embedded beneath a native element.
+    pub(crate) fn has_host_parent(&self) -> bool {
+        self.host_parent.read().unwrap().is_some()
+    }
+
+    /// Returns the native element that the tree is embedded beneath,
+    /// if any, resolved for use on the calling thread.
+    pub(crate) fn host_parent(&self) -> Option<Result<IRawElementProviderFragment>> {
+        self.host_parent
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|parent| parent.0.resolve())
+    }
+
+    /// Returns the screen coordinates of the point that node coordinates
+    /// are relative to, that is, the top-left corner of the client area,
+    /// or of the host element if there's no window, offset by
+    /// the configured content origin.
+    pub(crate) fn content_top_left(&self) -> Point {
+        let top_left = match self.hwnd {
+            Some(hwnd) => client_top_left(hwnd),
+            None => self
+                .host_parent()
+                .and_then(|parent| parent.ok())
+                .and_then(|parent| unsafe { parent.BoundingRectangle() }.ok())
+                .map_or(Point::ZERO, |rect| Point::new(rect.left, rect.top)),
+        };
+        top_left + *self.content_origin.read().unwrap()
+    }
+
+    /// Converts a rectangle in the tree's coordinate space, such as one
+    /// returned by `Node::bounding_box` or `TextRange::bounding_boxes`
+    /// with all ancestor transforms already applied, to screen coordinates.
+    pub(crate) fn rect_to_screen(&self, rect: Rect) -> Rect {
+        rect + self.content_top_left().to_vec2()
+    }
+
+    /// Converts a point in screen coordinates to the tree's coordinate
+    /// space. This is the inverse of [`Context::rect_to_screen`].
+    pub(crate) fn point_from_screen(&self, x: f64, y: f64) -> Point {
+        Point::new(x, y) - self.content_top_left().to_vec2()
     }
 
     pub(crate) fn do_action(&self, request: ActionRequest) {
         self.action_handler.do_action(request);
     }
+
+    pub(crate) fn action_request_dropped(
+        &self,
+        request: &ActionRequest,
+        error: ActionRequestError,
+    ) {
+        self.action_handler.action_request_dropped(request, error);
+    }
 }
@@ -3,6 +3,7 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
+mod capabilities;
 mod context;
 mod filters;
 mod node;
@@ -16,6 +17,8 @@ pub use adapter::{Adapter, QueuedEvents};
 mod subclass;
 pub use subclass::SubclassingAdapter;
 
+pub use capabilities::CAPABILITIES;
+
 pub use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
 
 #[cfg(test)]
@@ -11,8 +11,8 @@
 #![allow(non_upper_case_globals)]
 
 use accesskit::{
-    Action, ActionData, ActionRequest, Live, NodeId, NodeIdContent, Orientation, Point, Role,
-    Toggled,
+    Action, ActionData, ActionRequest, ActionRequestError, Live, NodeId, NodeIdContent,
+    Orientation, Role, Toggled,
 };
 use accesskit_consumer::{FilterResult, Node, TreeState};
 use paste::paste;
@@ -31,7 +31,7 @@ use crate::{
 
 const RUNTIME_ID_SIZE: usize = 3;
 
-fn runtime_id_from_node_id(id: NodeId) -> [i32; RUNTIME_ID_SIZE] {
+pub(crate) fn runtime_id_from_node_id(id: NodeId) -> [i32; RUNTIME_ID_SIZE] {
     static_assertions::assert_eq_size!(NodeIdContent, u64);
     let id = id.0;
     [
@@ -260,21 +260,17 @@ impl NodeWrapper<'_> {
 
     pub(crate) fn name(&self) -> Option<WideString> {
         let mut result = WideString::default();
-        if self.0.label_comes_from_value() {
-            self.0.write_value(&mut result)
-        } else {
-            self.0.write_label(&mut result)
-        }
-        .unwrap()
-        .then_some(result)
+        self.0.write_name(&mut result).unwrap().then_some(result)
     }
 
     fn description(&self) -> Option<String> {
         self.0.description()
     }
 
-    fn placeholder(&self) -> Option<&str> {
-        self.0.placeholder()
+    fn help_text(&self) -> Option<&str> {
+        self.0
+            .help_text()
+            .or_else(|| self.0.placeholder_unless_name())
     }
 
     fn is_content_element(&self) -> bool {
@@ -285,6 +281,10 @@ impl NodeWrapper<'_> {
         !self.0.is_disabled()
     }
 
+    fn is_offscreen(&self) -> bool {
+        self.0.is_offscreen()
+    }
+
     fn is_focusable(&self) -> bool {
         self.0.is_focusable()
     }
@@ -310,6 +310,32 @@ impl NodeWrapper<'_> {
         self.0.class_name()
     }
 
+    fn aria_properties(&self) -> Option<String> {
+        let mut result = String::new();
+        for (name, value) in [
+            ("earcon", self.0.earcon()),
+            ("pronunciation", self.0.pronunciation()),
+        ] {
+            let Some(value) = value else {
+                continue;
+            };
+            if !result.is_empty() {
+                result.push(';');
+            }
+            result.push_str(name);
+            result.push('=');
+            // Values in this property are separated by semicolons, and names
+            // from values by equals signs, so those must be escaped.
+            for c in value.chars() {
+                if matches!(c, '\\' | ';' | '=') {
+                    result.push('\\');
+                }
+                result.push(c);
+            }
+        }
+        (!result.is_empty()).then_some(result)
+    }
+
     fn orientation(&self) -> OrientationType {
         match self.0.orientation() {
             Some(Orientation::Horizontal) => OrientationType_Horizontal,
@@ -378,6 +404,10 @@ impl NodeWrapper<'_> {
         self.0.is_required()
     }
 
+    fn is_password(&self) -> bool {
+        self.0.role() == Role::PasswordInput
+    }
+
     fn is_selection_item_pattern_supported(&self) -> bool {
         match self.0.role() {
             // TODO: tables (#29)
@@ -411,10 +441,98 @@ impl NodeWrapper<'_> {
         }
     }
 
+    fn is_grid_pattern_supported(&self) -> bool {
+        self.0.is_table()
+    }
+
+    fn row_count(&self) -> i32 {
+        index_to_i32(self.0.table_row_count())
+    }
+
+    fn column_count(&self) -> i32 {
+        index_to_i32(self.0.table_column_count())
+    }
+
+    fn row_or_column_major(&self) -> RowOrColumnMajor {
+        RowOrColumnMajor_RowMajor
+    }
+
+    fn is_grid_item_pattern_supported(&self) -> bool {
+        self.0.table_cell_position().is_some()
+    }
+
+    fn cell_row(&self) -> i32 {
+        index_to_i32(self.0.table_cell_position().map_or(0, |p| p.row_index))
+    }
+
+    fn cell_column(&self) -> i32 {
+        index_to_i32(self.0.table_cell_position().map_or(0, |p| p.column_index))
+    }
+
+    fn cell_row_span(&self) -> i32 {
+        index_to_i32(self.0.table_cell_position().map_or(1, |p| p.row_span))
+    }
+
+    fn cell_column_span(&self) -> i32 {
+        index_to_i32(self.0.table_cell_position().map_or(1, |p| p.column_span))
+    }
+
     fn is_text_pattern_supported(&self) -> bool {
         self.0.supports_text_ranges()
     }
 
+    fn is_scroll_pattern_supported(&self) -> bool {
+        self.is_horizontally_scrollable() || self.is_vertically_scrollable()
+    }
+
+    fn is_drag_pattern_supported(&self) -> bool {
+        self.0.supports_drag() || self.0.is_grabbed()
+    }
+
+    fn is_grabbed(&self) -> bool {
+        self.0.is_grabbed()
+    }
+
+    fn is_drop_target_pattern_supported(&self) -> bool {
+        self.0.supports_drop() || self.0.is_drop_target()
+    }
+
+    fn is_scroll_item_pattern_supported(&self) -> bool {
+        self.0.supports_scroll_into_view()
+    }
+
+    fn horizontal_scroll_percent(&self) -> f64 {
+        self.0
+            .horizontal_scroll_percent()
+            .filter(|_| self.is_horizontally_scrollable())
+            .unwrap_or(UIA_ScrollPatternNoScroll)
+    }
+
+    fn vertical_scroll_percent(&self) -> f64 {
+        self.0
+            .vertical_scroll_percent()
+            .filter(|_| self.is_vertically_scrollable())
+            .unwrap_or(UIA_ScrollPatternNoScroll)
+    }
+
+    fn horizontal_view_size(&self) -> f64 {
+        self.0.horizontal_view_size()
+    }
+
+    fn vertical_view_size(&self) -> f64 {
+        self.0.vertical_view_size()
+    }
+
+    // UIA clients expect to be able to scroll in any direction
+    // that the Scroll pattern reports as scrollable.
+    fn is_horizontally_scrollable(&self) -> bool {
+        self.0.is_horizontally_scrollable() && self.0.supports_horizontal_scrolling()
+    }
+
+    fn is_vertically_scrollable(&self) -> bool {
+        self.0.is_vertically_scrollable() && self.0.supports_vertical_scrolling()
+    }
+
     pub(crate) fn enqueue_property_changes(
         &self,
         queue: &mut Vec<QueuedEvent>,
@@ -443,13 +561,24 @@ impl NodeWrapper<'_> {
         }
         if self.is_text_pattern_supported()
             && old.is_text_pattern_supported()
-            && self.0.raw_text_selection() != old.0.raw_text_selection()
+            && (self.0.raw_text_selection() != old.0.raw_text_selection()
+                || self.0.raw_text_selections() != old.0.raw_text_selections())
         {
             queue.push(QueuedEvent::Simple {
                 element: element.clone(),
                 event_id: UIA_Text_TextSelectionChangedEventId,
             });
         }
+        if self.is_drag_pattern_supported() && self.is_grabbed() != old.is_grabbed() {
+            queue.push(QueuedEvent::Simple {
+                element: element.clone(),
+                event_id: if self.is_grabbed() {
+                    UIA_Drag_DragStartEventId
+                } else {
+                    UIA_Drag_DragCompleteEventId
+                },
+            });
+        }
     }
 
     fn enqueue_property_change(
@@ -480,7 +609,16 @@ impl NodeWrapper<'_> {
     IValueProvider,
     IRangeValueProvider,
     ISelectionItemProvider,
-    ITextProvider
+    IGridProvider,
+    IGridItemProvider,
+    ITableProvider,
+    ITableItemProvider,
+    ITextProvider,
+    ITextProvider2,
+    IScrollProvider,
+    IScrollItemProvider,
+    IDragProvider,
+    IDropTargetProvider
 )]
 pub(crate) struct PlatformNode {
     pub(crate) context: Weak<Context>,
@@ -511,8 +649,8 @@ impl PlatformNode {
         F: FnOnce(&TreeState, &Context) -> Result<T>,
     {
         let context = self.upgrade_context()?;
-        let tree = context.read_tree();
-        f(tree.state(), &context)
+        let state = context.tree_snapshot();
+        f(&state, &context)
     }
 
     fn with_tree_state<F, T>(&self, f: F) -> Result<T>
@@ -591,22 +729,24 @@ impl PlatformNode {
             return Ok(());
         }
         let tree = context.read_tree();
-        let node_id = if let Some(id) = self.node_id {
-            if !tree.state().has_node(id) {
-                return Err(element_not_available());
-            }
-            id
-        } else {
-            tree.state().root_id()
-        };
-        drop(tree);
+        let node_id = self.node_id.unwrap_or_else(|| tree.state().root_id());
         let (action, data) = f();
         let request = ActionRequest {
             target: node_id,
             action,
             data,
         };
-        context.do_action(request);
+        let result = tree.state().check_action_request(&request);
+        drop(tree);
+        match result {
+            Ok(()) => context.do_action(request),
+            Err(error) => {
+                context.action_request_dropped(&request, error);
+                if error == ActionRequestError::UnknownTarget {
+                    return Err(element_not_available());
+                }
+            }
+        }
         Ok(())
     }
 
@@ -621,6 +761,17 @@ impl PlatformNode {
         }
     }
 
+    fn element_array(&self, ids: Vec<NodeId>) -> Result<*mut SAFEARRAY> {
+        let elements = ids
+            .into_iter()
+            .map(|id| {
+                let element: IRawElementProviderSimple = self.relative(id).into();
+                element.cast::<IUnknown>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(safe_array_from_com_slice(&elements))
+    }
+
     fn is_root(&self, state: &TreeState) -> bool {
         self.node_id.is_some_and(|id| id == state.root_id())
     }
@@ -633,6 +784,9 @@ impl IRawElementProviderSimple_Impl for PlatformNode_Impl {
     }
 
     fn GetPatternProvider(&self, pattern_id: UIA_PATTERN_ID) -> Result<IUnknown> {
+        if pattern_id == UIA_TextPattern2Id {
+            return self.text_pattern2_provider();
+        }
         self.pattern_provider(pattern_id)
     }
 
@@ -644,10 +798,14 @@ impl IRawElementProviderSimple_Impl for PlatformNode_Impl {
                 if node.is_root() {
                     match property_id {
                         UIA_NamePropertyId => {
-                            result = window_title(context.hwnd).into();
+                            if let Some(hwnd) = context.hwnd {
+                                result = window_title(hwnd).into();
+                            }
                         }
                         UIA_NativeWindowHandlePropertyId => {
-                            result = (context.hwnd.0 .0 as i32).into();
+                            if let Some(hwnd) = context.hwnd {
+                                result = (hwnd.0 .0 as i32).into();
+                            }
                         }
                         _ => (),
                     }
@@ -664,10 +822,13 @@ impl IRawElementProviderSimple_Impl for PlatformNode_Impl {
 
     fn HostRawElementProvider(&self) -> Result<IRawElementProviderSimple> {
         self.with_tree_state_and_context(|state, context| {
-            if self.is_root(state) {
-                unsafe { UiaHostProviderFromHwnd(context.hwnd.0) }
-            } else {
-                Err(Error::empty())
+            // When the tree is embedded beneath a native element,
+            // that element's window is the host, not ours.
+            match context.hwnd {
+                Some(hwnd) if self.is_root(state) && !context.has_host_parent() => unsafe {
+                    UiaHostProviderFromHwnd(hwnd.0)
+                },
+                _ => Err(Error::empty()),
             }
         })
     }
@@ -676,7 +837,7 @@ impl IRawElementProviderSimple_Impl for PlatformNode_Impl {
 #[allow(non_snake_case)]
 impl IRawElementProviderFragment_Impl for PlatformNode_Impl {
     fn Navigate(&self, direction: NavigateDirection) -> Result<IRawElementProviderFragment> {
-        self.resolve(|node| {
+        self.resolve_with_context(|node, context| {
             let result = match direction {
                 NavigateDirection_Parent => node.filtered_parent(&filter_with_root_exception),
                 NavigateDirection_NextSibling => node.following_filtered_siblings(&filter).next(),
@@ -689,6 +850,9 @@ impl IRawElementProviderFragment_Impl for PlatformNode_Impl {
             };
             match result {
                 Some(result) => Ok(self.relative(result.id()).into()),
+                None if direction == NavigateDirection_Parent && node.is_root() => {
+                    context.host_parent().unwrap_or_else(|| Err(Error::empty()))
+                }
                 None => Err(Error::empty()),
             }
         })
@@ -712,10 +876,10 @@ impl IRawElementProviderFragment_Impl for PlatformNode_Impl {
     fn BoundingRectangle(&self) -> Result<UiaRect> {
         self.resolve_with_context(|node, context| {
             let rect = node.bounding_box().map_or(UiaRect::default(), |rect| {
-                let client_top_left = context.client_top_left();
+                let rect = context.rect_to_screen(rect);
                 UiaRect {
-                    left: rect.x0 + client_top_left.x,
-                    top: rect.y0 + client_top_left.y,
+                    left: rect.x0,
+                    top: rect.y0,
                     width: rect.width(),
                     height: rect.height(),
                 }
@@ -733,8 +897,10 @@ impl IRawElementProviderFragment_Impl for PlatformNode_Impl {
     }
 
     fn FragmentRoot(&self) -> Result<IRawElementProviderFragmentRoot> {
-        self.with_tree_state(|state| {
-            if self.is_root(state) {
+        self.with_tree_state_and_context(|state, context| {
+            if let Some(host_parent) = context.host_parent() {
+                unsafe { host_parent?.FragmentRoot() }
+            } else if self.is_root(state) {
                 // SAFETY: We know &self is inside a full COM implementation.
                 unsafe { self.cast() }
             } else {
@@ -749,9 +915,7 @@ impl IRawElementProviderFragment_Impl for PlatformNode_Impl {
 impl IRawElementProviderFragmentRoot_Impl for PlatformNode_Impl {
     fn ElementProviderFromPoint(&self, x: f64, y: f64) -> Result<IRawElementProviderFragment> {
         self.resolve_with_context(|node, context| {
-            let client_top_left = context.client_top_left();
-            let point = Point::new(x - client_top_left.x, y - client_top_left.y);
-            let point = node.transform().inverse() * point;
+            let point = node.transform().inverse() * context.point_from_screen(x, y);
             node.node_at_point(point, &filter).map_or_else(
                 || Err(Error::empty()),
                 |node| Ok(self.relative(node.id()).into()),
@@ -880,17 +1044,20 @@ properties! {
     (LocalizedControlType, localized_control_type),
     (Name, name),
     (FullDescription, description),
-    (HelpText, placeholder),
+    (HelpText, help_text),
     (IsContentElement, is_content_element),
     (IsControlElement, is_content_element),
     (IsEnabled, is_enabled),
+    (IsOffscreen, is_offscreen),
     (IsKeyboardFocusable, is_focusable),
     (HasKeyboardFocus, is_focused),
     (LiveSetting, live_setting),
     (AutomationId, automation_id),
     (ClassName, class_name),
+    (AriaProperties, aria_properties),
     (Orientation, orientation),
-    (IsRequiredForForm, is_required)
+    (IsRequiredForForm, is_required),
+    (IsPassword, is_password)
 }
 
 patterns! {
@@ -926,9 +1093,8 @@ patterns! {
         (LargeChange, numeric_value_jump, f64)
     ), (
         fn SetValue(&self, value: f64) -> Result<()> {
-            self.do_action(|| {
-                (Action::SetValue, Some(ActionData::NumericValue(value)))
-            })
+            let (action, data) = self.resolve(|node| Ok(node.set_numeric_value_action(value)))?;
+            self.do_action(|| (action, data))
         }
     )),
     (SelectionItem, is_selection_item_pattern_supported, (
@@ -955,15 +1121,74 @@ patterns! {
             Err(E_FAIL.into())
         }
     )),
+    (Grid, is_grid_pattern_supported, (
+        (RowCount, row_count, i32),
+        (ColumnCount, column_count, i32)
+    ), (
+        fn GetItem(&self, row: i32, column: i32) -> Result<IRawElementProviderSimple> {
+            let row = usize::try_from(row).map_err(|_| invalid_arg())?;
+            let column = usize::try_from(column).map_err(|_| invalid_arg())?;
+            let id = self.resolve(|node| {
+                node.table_cell_at(row, column)
+                    .map(|cell| cell.id())
+                    .ok_or_else(invalid_arg)
+            })?;
+            Ok(self.relative(id).into())
+        }
+    )),
+    (GridItem, is_grid_item_pattern_supported, (
+        (Row, cell_row, i32),
+        (Column, cell_column, i32),
+        (RowSpan, cell_row_span, i32),
+        (ColumnSpan, cell_column_span, i32)
+    ), (
+        fn ContainingGrid(&self) -> Result<IRawElementProviderSimple> {
+            let id = self.resolve(|node| {
+                node.containing_table()
+                    .map(|table| table.id())
+                    .ok_or_else(element_not_available)
+            })?;
+            Ok(self.relative(id).into())
+        }
+    )),
+    (Table, is_grid_pattern_supported, (
+        (RowOrColumnMajor, row_or_column_major, RowOrColumnMajor)
+    ), (
+        fn GetRowHeaders(&self) -> Result<*mut SAFEARRAY> {
+            let ids = self.resolve(|node| Ok(table_header_ids(&node, Role::RowHeader)))?;
+            self.element_array(ids)
+        },
+
+        fn GetColumnHeaders(&self) -> Result<*mut SAFEARRAY> {
+            let ids = self.resolve(|node| Ok(table_header_ids(&node, Role::ColumnHeader)))?;
+            self.element_array(ids)
+        }
+    )),
+    (TableItem, is_grid_item_pattern_supported, (), (
+        fn GetRowHeaderItems(&self) -> Result<*mut SAFEARRAY> {
+            let ids = self.resolve(|node| Ok(table_header_ids(&node, Role::RowHeader)))?;
+            self.element_array(ids)
+        },
+
+        fn GetColumnHeaderItems(&self) -> Result<*mut SAFEARRAY> {
+            let ids = self.resolve(|node| Ok(table_header_ids(&node, Role::ColumnHeader)))?;
+            self.element_array(ids)
+        }
+    )),
     (Text, is_text_pattern_supported, (), (
         fn GetSelection(&self) -> Result<*mut SAFEARRAY> {
             self.resolve_for_text_pattern(|node| {
-                if let Some(range) = node.text_selection() {
-                    let platform_range: ITextRangeProvider = PlatformTextRange::new(&self.context, range).into();
-                    let iunknown: IUnknown = platform_range.cast()?;
-                    Ok(safe_array_from_com_slice(&[iunknown]))
-                } else {
+                let ranges = node
+                    .text_selections()
+                    .map(|range| {
+                        let platform_range: ITextRangeProvider = PlatformTextRange::new(&self.context, range).into();
+                        platform_range.cast::<IUnknown>()
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                if ranges.is_empty() {
                     Ok(std::ptr::null_mut())
+                } else {
+                    Ok(safe_array_from_com_slice(&ranges))
                 }
             })
         },
@@ -982,9 +1207,7 @@ patterns! {
 
         fn RangeFromPoint(&self, point: &UiaPoint) -> Result<ITextRangeProvider> {
             self.resolve_with_context_for_text_pattern(|node, context| {
-                let client_top_left = context.client_top_left();
-                let point = Point::new(point.x - client_top_left.x, point.y - client_top_left.y);
-                let point = node.transform().inverse() * point;
+                let point = node.transform().inverse() * context.point_from_screen(point.x, point.y);
                 let pos = node.text_position_at_point(point);
                 let range = pos.to_degenerate_range();
                 Ok(PlatformTextRange::new(&self.context, range).into())
@@ -1000,16 +1223,160 @@ patterns! {
 
         fn SupportedTextSelection(&self) -> Result<SupportedTextSelection> {
             self.resolve_for_text_pattern(|node| {
-                if node.has_text_selection() {
+                if !node.raw_text_selections().is_empty() {
+                    Ok(SupportedTextSelection_Multiple)
+                } else if node.has_text_selection() {
                     Ok(SupportedTextSelection_Single)
                 } else {
                     Ok(SupportedTextSelection_None)
                 }
             })
         }
+    )),
+    (Scroll, is_scroll_pattern_supported, (
+        (HorizontalScrollPercent, horizontal_scroll_percent, f64),
+        (VerticalScrollPercent, vertical_scroll_percent, f64),
+        (HorizontalViewSize, horizontal_view_size, f64),
+        (VerticalViewSize, vertical_view_size, f64),
+        (HorizontallyScrollable, is_horizontally_scrollable, BOOL),
+        (VerticallyScrollable, is_vertically_scrollable, BOOL)
+    ), (
+        fn Scroll(&self, horizontal_amount: ScrollAmount, vertical_amount: ScrollAmount) -> Result<()> {
+            // AccessKit only has actions for scrolling by about one screen,
+            // so small and large amounts are treated the same.
+            let horizontal = match horizontal_amount {
+                ScrollAmount_LargeDecrement | ScrollAmount_SmallDecrement => Some(Action::ScrollLeft),
+                ScrollAmount_LargeIncrement | ScrollAmount_SmallIncrement => Some(Action::ScrollRight),
+                _ => None,
+            };
+            let vertical = match vertical_amount {
+                ScrollAmount_LargeDecrement | ScrollAmount_SmallDecrement => Some(Action::ScrollUp),
+                ScrollAmount_LargeIncrement | ScrollAmount_SmallIncrement => Some(Action::ScrollDown),
+                _ => None,
+            };
+            for action in [horizontal, vertical].into_iter().flatten() {
+                self.do_action(|| (action, None))?;
+            }
+            Ok(())
+        },
+
+        fn SetScrollPercent(&self, horizontal_percent: f64, vertical_percent: f64) -> Result<()> {
+            let percent = |value: f64| (value != UIA_ScrollPatternNoScroll).then_some(value);
+            let offset = self.resolve(|node| {
+                Ok(node.scroll_offset_for_percent(percent(horizontal_percent), percent(vertical_percent)))
+            })?;
+            self.do_action(|| (Action::SetScrollOffset, Some(ActionData::SetScrollOffset(offset))))
+        }
+    )),
+    (ScrollItem, is_scroll_item_pattern_supported, (), (
+        fn ScrollIntoView(&self) -> Result<()> {
+            self.do_action(|| (Action::ScrollIntoView, None))
+        }
+    )),
+    (Drag, is_drag_pattern_supported, (
+        (IsGrabbed, is_grabbed, BOOL)
+    ), (
+        fn DropEffect(&self) -> Result<BSTR> {
+            // AccessKit doesn't describe the effect of a drop.
+            Ok(BSTR::new())
+        },
+
+        fn DropEffects(&self) -> Result<*mut SAFEARRAY> {
+            Ok(std::ptr::null_mut())
+        },
+
+        fn GetGrabbedItems(&self) -> Result<*mut SAFEARRAY> {
+            // A null array means that only this element is being dragged.
+            Ok(std::ptr::null_mut())
+        }
+    )),
+    (DropTarget, is_drop_target_pattern_supported, (), (
+        fn DropTargetEffect(&self) -> Result<BSTR> {
+            Ok(BSTR::new())
+        },
+
+        fn DropTargetEffects(&self) -> Result<*mut SAFEARRAY> {
+            Ok(std::ptr::null_mut())
+        }
     ))
 }
 
+fn table_header_ids(node: &Node, role: Role) -> Vec<NodeId> {
+    node.table_headers(role)
+        .into_iter()
+        .map(|header| header.id())
+        .collect()
+}
+
+// `ITextProvider2` extends `ITextProvider` rather than being a separate
+// provider for its own pattern, so it's implemented outside the
+// `patterns!` macro.
+impl PlatformNode {
+    fn text_pattern2_provider(&self) -> Result<IUnknown> {
+        self.resolve(|node| {
+            if NodeWrapper(&node).is_text_pattern_supported() {
+                // SAFETY: We know we're running inside a full COM implementation.
+                let intermediate: ITextProvider2 = unsafe { self.cast() }?;
+                return intermediate.cast();
+            }
+            Err(Error::empty())
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+impl ITextProvider2_Impl for PlatformNode_Impl {
+    fn RangeFromAnnotation(
+        &self,
+        annotation_element: Option<&IRawElementProviderSimple>,
+    ) -> Result<ITextRangeProvider> {
+        let annotation = required_param(annotation_element)?
+            .cast_object_ref::<PlatformNode>()
+            .map_err(|_| invalid_arg())?;
+        if !annotation.context.ptr_eq(&self.context) {
+            return Err(invalid_arg());
+        }
+        let annotation_id = annotation.node_id.ok_or_else(invalid_arg)?;
+        self.resolve_for_text_pattern(|node| {
+            // The annotated text is whatever refers to the annotation
+            // through the `details` relation.
+            let range = if node.data().details().contains(&annotation_id) {
+                Some(node.document_range())
+            } else {
+                find_annotated_descendant(&node, annotation_id)
+                    .and_then(|annotated| node.text_range_of_descendant(&annotated))
+            };
+            let range = range.ok_or_else(invalid_arg)?;
+            Ok(PlatformTextRange::new(&self.context, range).into())
+        })
+    }
+
+    fn GetCaretRange(&self, is_active: *mut BOOL) -> Result<ITextRangeProvider> {
+        if is_active.is_null() {
+            return Err(invalid_arg());
+        }
+        self.resolve_for_text_pattern(|node| {
+            // If there's no selection, report the caret at the start
+            // of the text, as UIA requires a range to be returned.
+            let caret = node
+                .text_selection_focus()
+                .unwrap_or_else(|| node.document_range().start());
+            unsafe { *is_active = node.is_focused().into() };
+            Ok(PlatformTextRange::new(&self.context, caret.to_degenerate_range()).into())
+        })
+    }
+}
+
+fn find_annotated_descendant<'a>(node: &Node<'a>, annotation_id: NodeId) -> Option<Node<'a>> {
+    node.children().find_map(|child| {
+        if child.data().details().contains(&annotation_id) {
+            Some(child)
+        } else {
+            find_annotated_descendant(&child, annotation_id)
+        }
+    })
+}
+
 // Ensures that `PlatformNode` is actually safe to use in the free-threaded
 // manner that we advertise via `ProviderOptions`.
 #[test]
@@ -3,7 +3,9 @@
 // the LICENSE-APACHE file) or the MIT license (found in
 // the LICENSE-MIT file), at your option.
 
-use accesskit::{ActionHandler, ActivationHandler, TreeUpdate};
+use accesskit::{
+    ActionHandler, ActivationHandler, AnnouncementKind, GeometryUpdate, TreeUpdate, Vec2,
+};
 use std::{
     cell::{Cell, RefCell},
     ffi::c_void,
@@ -39,6 +41,8 @@ struct SubclassImpl {
     state: RefCell<SubclassState>,
     prev_wnd_proc: WNDPROC,
     window_destroyed: Cell<bool>,
+    is_window_focused: Cell<bool>,
+    is_popup_focused: Cell<bool>,
 }
 
 extern "system" fn wnd_proc(window: HWND, message: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
@@ -60,10 +64,12 @@ extern "system" fn wnd_proc(window: HWND, message: u32, wparam: WPARAM, lparam:
             }
         }
         WM_SETFOCUS | WM_EXITMENULOOP | WM_EXITSIZEMOVE => {
-            r#impl.update_window_focus_state(true);
+            r#impl.is_window_focused.set(true);
+            r#impl.update_window_focus_state();
         }
         WM_KILLFOCUS | WM_ENTERMENULOOP | WM_ENTERSIZEMOVE => {
-            r#impl.update_window_focus_state(false);
+            r#impl.is_window_focused.set(false);
+            r#impl.update_window_focus_state();
         }
         WM_NCDESTROY => {
             r#impl.window_destroyed.set(true);
@@ -89,6 +95,8 @@ impl SubclassImpl {
             state,
             prev_wnd_proc: None,
             window_destroyed: Cell::new(false),
+            is_window_focused: Cell::new(false),
+            is_popup_focused: Cell::new(false),
         })
     }
 
@@ -109,7 +117,8 @@ impl SubclassImpl {
         self.prev_wnd_proc = unsafe { transmute::<LongPtr, WNDPROC>(result) };
     }
 
-    fn update_window_focus_state(&self, is_focused: bool) {
+    fn update_window_focus_state(&self) {
+        let is_focused = self.is_window_focused.get() || self.is_popup_focused.get();
         let mut state = self.state.borrow_mut();
         if let Some(events) = state.adapter.update_window_focus_state(is_focused) {
             drop(state);
@@ -138,6 +147,62 @@ impl SubclassImpl {
 /// Uses [Win32 subclassing] to handle `WM_GETOBJECT` messages on a window
 /// that provides no other way of adding custom message handlers.
 ///
+/// # Example
+///
+/// The activation handler is called on the window's thread while it
+/// handles `WM_GETOBJECT`, so it must not call back into the adapter.
+/// The action handler may be called on any thread, so it should hand
+/// requests to the window's thread rather than touching UI state itself.
+///
+/// This only shows the parts that are specific to this adapter; see the
+/// [`accesskit_unix` documentation](https://docs.rs/accesskit_unix) for a
+/// complete example of building a tree and implementing the handlers.
+///
+/// ```no_run
+/// # use accesskit::{ActivationHandler, TreeUpdate};
+/// use accesskit::{ActionHandler, ActionRequest};
+/// use accesskit_windows::{SubclassingAdapter, HWND};
+/// use std::sync::mpsc::{channel, Receiver, Sender};
+/// #
+/// # fn build_tree() -> TreeUpdate {
+/// #     unimplemented!()
+/// # }
+/// #
+/// # struct Activation;
+/// #
+/// # impl ActivationHandler for Activation {
+/// #     fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+/// #         Some(build_tree())
+/// #     }
+/// # }
+///
+/// struct Actions(Sender<ActionRequest>);
+///
+/// impl ActionHandler for Actions {
+///     fn do_action(&mut self, request: ActionRequest) {
+///         // A real application would also post a message to the window,
+///         // so that its thread wakes up and handles the request.
+///         let _ = self.0.send(request);
+///     }
+/// }
+///
+/// /// Must be called on the window's thread, before the window is shown.
+/// fn attach(hwnd: HWND) -> (SubclassingAdapter, Receiver<ActionRequest>) {
+///     let (sender, receiver) = channel();
+///     let adapter = SubclassingAdapter::new(hwnd, Activation, Actions(sender));
+///     (adapter, receiver)
+/// }
+///
+/// /// Called on the window's thread whenever the UI changes.
+/// fn update(adapter: &mut SubclassingAdapter) {
+///     if let Some(events) = adapter.update_if_active(build_tree) {
+///         // The window may receive `WM_GETOBJECT` while events are raised,
+///         // so no lock that the window procedure needs may be held here.
+///         events.raise();
+///     }
+/// }
+/// ```
+///
 /// [Win32 subclassing]: https://docs.microsoft.com/en-us/windows/win32/controls/subclassing-overview
 pub struct SubclassingAdapter(Box<SubclassImpl>);
 
@@ -179,6 +244,56 @@ impl SubclassingAdapter {
         let mut state = self.0.state.borrow_mut();
         state.adapter.update_if_active(update_factory)
     }
+
+    /// If and only if the tree has been initialized, call the provided function
+    /// and apply the resulting [`GeometryUpdate`].
+    /// See [`Adapter::update_geometry_if_active`] for details.
+    ///
+    /// If a [`QueuedEvents`] instance is returned, the caller must call
+    /// [`QueuedEvents::raise`] on it.
+    pub fn update_geometry_if_active(
+        &mut self,
+        update_factory: impl FnOnce() -> GeometryUpdate,
+    ) -> Option<QueuedEvents> {
+        // SAFETY: See `update_if_active`.
+        let mut state = self.0.state.borrow_mut();
+        state.adapter.update_geometry_if_active(update_factory)
+    }
+
+    /// Set the offset of the origin of the node coordinate space
+    /// from the top-left corner of the window's client area.
+    /// See [`Adapter::set_content_origin`] for details.
+    pub fn set_content_origin(&mut self, origin: Vec2) {
+        // SAFETY: See `update_if_active`.
+        let mut state = self.0.state.borrow_mut();
+        state.adapter.set_content_origin(origin);
+    }
+
+    /// Ask assistive technologies to speak the given text.
+    /// See [`Adapter::announce`] for details.
+    ///
+    /// If a [`QueuedEvents`] instance is returned, the caller must call
+    /// [`QueuedEvents::raise`] on it.
+    pub fn announce(&mut self, text: &str, kind: AnnouncementKind) -> Option<QueuedEvents> {
+        // SAFETY: See `update_if_active`.
+        let mut state = self.0.state.borrow_mut();
+        state.adapter.announce(text, kind)
+    }
+
+    /// Tell the adapter whether a popup window that logically belongs to
+    /// this window, such as the list of a drop-down, has keyboard focus.
+    /// While it does, this window is still treated as focused, so that
+    /// assistive technologies keep following the focus in this window's
+    /// tree. The popup's content should be part of this tree, typically
+    /// in a node whose [`popup_for`] property refers to the element
+    /// that opened it, and the popup window shouldn't have an adapter
+    /// of its own.
+    ///
+    /// [`popup_for`]: accesskit::Node::popup_for
+    pub fn set_popup_focused(&mut self, is_focused: bool) {
+        self.0.is_popup_focused.set(is_focused);
+        self.0.update_window_focus_state();
+    }
 }
 
 impl Drop for SubclassingAdapter {
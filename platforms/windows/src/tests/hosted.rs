@@ -0,0 +1,139 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{
+    ActionHandler, ActionRequest, ActivationHandler, Node, NodeId, Rect, Role, Tree, TreeUpdate,
+    Vec2,
+};
+use windows::{
+    core::*,
+    Win32::{System::Com::*, UI::Accessibility::*},
+};
+
+use super::MUTEX;
+use crate::Adapter;
+
+const ROOT_ID: NodeId = NodeId(0);
+const BUTTON_ID: NodeId = NodeId(1);
+
+const HOST_RECT: UiaRect = UiaRect {
+    left: 100.0,
+    top: 50.0,
+    width: 300.0,
+    height: 200.0,
+};
+
+/// Stands in for the element of a hosting framework, such as
+/// a `ContentIsland`, beneath which the tree is exposed.
+#[implement(IRawElementProviderFragment, IRawElementProviderFragmentRoot)]
+struct Host;
+
+#[allow(non_snake_case)]
+impl IRawElementProviderFragment_Impl for Host_Impl {
+    fn Navigate(&self, _direction: NavigateDirection) -> Result<IRawElementProviderFragment> {
+        Err(Error::empty())
+    }
+
+    fn GetRuntimeId(&self) -> Result<*mut SAFEARRAY> {
+        Ok(std::ptr::null_mut())
+    }
+
+    fn BoundingRectangle(&self) -> Result<UiaRect> {
+        Ok(HOST_RECT)
+    }
+
+    fn GetEmbeddedFragmentRoots(&self) -> Result<*mut SAFEARRAY> {
+        Ok(std::ptr::null_mut())
+    }
+
+    fn SetFocus(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn FragmentRoot(&self) -> Result<IRawElementProviderFragmentRoot> {
+        // SAFETY: We know &self is inside a full COM implementation.
+        unsafe { self.cast() }
+    }
+}
+
+#[allow(non_snake_case)]
+impl IRawElementProviderFragmentRoot_Impl for Host_Impl {
+    fn ElementProviderFromPoint(&self, _x: f64, _y: f64) -> Result<IRawElementProviderFragment> {
+        Err(Error::empty())
+    }
+
+    fn GetFocus(&self) -> Result<IRawElementProviderFragment> {
+        Err(Error::empty())
+    }
+}
+
+struct NullActionHandler;
+
+impl ActionHandler for NullActionHandler {
+    fn do_action(&mut self, _request: ActionRequest) {}
+}
+
+struct HostedActivationHandler;
+
+impl ActivationHandler for HostedActivationHandler {
+    fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+        let mut root = Node::new(Role::Pane);
+        root.set_bounds(Rect::new(0.0, 0.0, 300.0, 200.0));
+        root.set_children(vec![BUTTON_ID]);
+        let mut button = Node::new(Role::Button);
+        button.set_label("Button");
+        button.set_bounds(Rect::new(10.0, 20.0, 110.0, 60.0));
+        Some(TreeUpdate {
+            nodes: vec![(ROOT_ID, root), (BUTTON_ID, button)],
+            tree: Some(Tree::new(ROOT_ID)),
+            focus: ROOT_ID,
+        })
+    }
+}
+
+fn assert_rect(rect: UiaRect, expected: (f64, f64, f64, f64)) {
+    assert_eq!((rect.left, rect.top, rect.width, rect.height), expected);
+}
+
+#[test]
+fn tree_beneath_host() -> Result<()> {
+    let _lock_guard = MUTEX.lock().unwrap();
+    // The host parent is kept as an agile reference, which requires COM.
+    unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.ok()?;
+    let _com_guard = scopeguard::guard((), |_| unsafe { CoUninitialize() });
+
+    let host: IRawElementProviderFragment = Host.into();
+    let mut adapter = Adapter::new_hosted(&host, true, NullActionHandler)?;
+    let root: IRawElementProviderFragment =
+        adapter.root_provider(&mut HostedActivationHandler).cast()?;
+
+    let parent = unsafe { root.Navigate(NavigateDirection_Parent) }?;
+    assert_eq!(parent, host);
+    let fragment_root = unsafe { root.FragmentRoot() }?;
+    assert_eq!(
+        fragment_root,
+        host.cast::<IRawElementProviderFragmentRoot>()?
+    );
+    // Without a window, UIA gets the host provider from the host element.
+    let simple: IRawElementProviderSimple = root.cast()?;
+    assert!(unsafe { simple.HostRawElementProvider() }.is_err());
+
+    // Node coordinates are relative to the host element's bounds.
+    assert_rect(
+        unsafe { root.BoundingRectangle() }?,
+        (100.0, 50.0, 300.0, 200.0),
+    );
+    let button = unsafe { root.Navigate(NavigateDirection_FirstChild) }?;
+    assert_rect(
+        unsafe { button.BoundingRectangle() }?,
+        (110.0, 70.0, 100.0, 40.0),
+    );
+    adapter.set_content_origin(Vec2::new(5.0, 10.0));
+    assert_rect(
+        unsafe { button.BoundingRectangle() }?,
+        (115.0, 80.0, 100.0, 40.0),
+    );
+    Ok(())
+}
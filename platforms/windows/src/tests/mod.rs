@@ -332,5 +332,6 @@ impl IUIAutomationFocusChangedEventHandler_Impl for FocusEventHandler_Impl {
     }
 }
 
+mod hosted;
 mod simple;
 mod subclassed;
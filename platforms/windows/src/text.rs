@@ -5,9 +5,10 @@
 
 #![allow(non_upper_case_globals)]
 
-use accesskit::{Action, ActionData, ActionRequest};
+use accesskit::{Action, ActionData, ActionRequest, ActionRequestError, NodeId};
 use accesskit_consumer::{
-    Node, TextPosition as Position, TextRange as Range, TreeState, WeakTextRange as WeakRange,
+    Node, TextAttributeValue as AttributeValue, TextPosition as Position, TextRange as Range,
+    TreeState, WeakTextRange as WeakRange,
 };
 use std::sync::{Arc, RwLock, Weak};
 use windows::{
@@ -17,6 +18,16 @@ use windows::{
 
 use crate::{context::Context, node::PlatformNode, util::*};
 
+// UI Automation expresses paragraph formatting in points, while AccessKit
+// uses pixels at 96 DPI.
+fn pixels_to_points(value: f64) -> f64 {
+    value * 0.75
+}
+
+fn points_variant(value: f64) -> VARIANT {
+    pixels_to_points(value).into()
+}
+
 fn upgrade_range<'a>(weak: &WeakRange, tree_state: &'a TreeState) -> Result<Range<'a>> {
     if let Some(range) = weak.upgrade(tree_state) {
         Ok(range)
@@ -273,6 +284,25 @@ impl PlatformRange {
         self.read_with_context(|range, _| f(range))
     }
 
+    fn formatting_attribute<F, G, T>(&self, f: F, to_variant: G) -> Result<VARIANT>
+    where
+        F: Fn(&Node) -> Option<T>,
+        G: FnOnce(T) -> VARIANT,
+        T: PartialEq,
+    {
+        self.read(|range| {
+            Ok(match range.attribute(f) {
+                AttributeValue::Single(Some(value)) => to_variant(value),
+                AttributeValue::Single(None) => {
+                    unsafe { UiaGetReservedNotSupportedValue() }.unwrap().into()
+                }
+                AttributeValue::Mixed => unsafe { UiaGetReservedMixedAttributeValue() }
+                    .unwrap()
+                    .into(),
+            })
+        })
+    }
+
     fn write<F, T>(&self, f: F) -> Result<T>
     where
         F: FnOnce(&mut Range) -> Result<T>,
@@ -286,16 +316,40 @@ impl PlatformRange {
         })
     }
 
-    fn do_action<F>(&self, f: F) -> Result<()>
+    fn do_action<F>(&self, action: Action, f: F) -> Result<()>
     where
-        for<'a> F: FnOnce(Range<'a>) -> ActionRequest,
+        for<'a> F: FnOnce(Range<'a>) -> (NodeId, Option<ActionData>),
     {
         let context = self.upgrade_context()?;
         let tree = context.read_tree();
-        let range = self.upgrade_for_read(tree.state())?;
-        let request = f(range);
+        let state = self.state.read().unwrap();
+        if state.upgrade_node(tree.state()).is_none() {
+            // Report the request even though its data can't be computed
+            // without the node.
+            let request = ActionRequest {
+                action,
+                target: state.node_id(),
+                data: None,
+            };
+            drop(state);
+            drop(tree);
+            context.action_request_dropped(&request, ActionRequestError::UnknownTarget);
+            return Err(element_not_available());
+        }
+        let range = upgrade_range(&state, tree.state())?;
+        let (target, data) = f(range);
+        drop(state);
+        let request = ActionRequest {
+            action,
+            target,
+            data,
+        };
+        let result = tree.state().check_action_request(&request);
         drop(tree);
-        context.do_action(request);
+        match result {
+            Ok(()) => context.do_action(request),
+            Err(error) => context.action_request_dropped(&request, error),
+        }
         Ok(())
     }
 
@@ -443,6 +497,49 @@ impl ITextRangeProvider_Impl for PlatformRange_Impl {
                 }
                 Ok(value.0.into())
             }),
+            UIA_FontNameAttributeId => self.formatting_attribute(
+                |node| node.font_family().map(String::from),
+                |value| Variant::from(value).into(),
+            ),
+            UIA_FontSizeAttributeId => {
+                self.formatting_attribute(|node| node.font_size(), points_variant)
+            }
+            UIA_FontWeightAttributeId => self.formatting_attribute(
+                |node| {
+                    node.font_weight()
+                        .or_else(|| node.is_bold().then_some(700.0))
+                },
+                |value| Variant::from(value as i32).into(),
+            ),
+            UIA_IsItalicAttributeId => self.formatting_attribute(
+                |node| Some(node.is_italic()),
+                |value| Variant::from(value).into(),
+            ),
+            UIA_IndentationFirstLineAttributeId => {
+                self.formatting_attribute(|node| node.text_indent(), points_variant)
+            }
+            UIA_MarginTopAttributeId => {
+                self.formatting_attribute(|node| node.margin_top(), points_variant)
+            }
+            UIA_MarginBottomAttributeId => {
+                self.formatting_attribute(|node| node.margin_bottom(), points_variant)
+            }
+            UIA_MarginLeadingAttributeId => {
+                self.formatting_attribute(|node| node.margin_leading(), points_variant)
+            }
+            UIA_MarginTrailingAttributeId => {
+                self.formatting_attribute(|node| node.margin_trailing(), points_variant)
+            }
+            UIA_LineSpacingAttributeId => self.formatting_attribute(
+                |node| node.line_height(),
+                |value| {
+                    let value = format!(
+                        "LineSpacingRule: Exactly; LineSpacing: {}pt;",
+                        pixels_to_points(value)
+                    );
+                    Variant::from(value).into()
+                },
+            ),
             // TODO: implement more attributes
             _ => {
                 let value = unsafe { UiaGetReservedNotSupportedValue() }.unwrap();
@@ -457,11 +554,11 @@ impl ITextRangeProvider_Impl for PlatformRange_Impl {
             if rects.is_empty() {
                 return Ok(std::ptr::null_mut());
             }
-            let client_top_left = context.client_top_left();
             let mut result = Vec::<f64>::with_capacity(rects.len() * 4);
             for rect in rects {
-                result.push(rect.x0 + client_top_left.x);
-                result.push(rect.y0 + client_top_left.y);
+                let rect = context.rect_to_screen(rect);
+                result.push(rect.x0);
+                result.push(rect.y0);
                 result.push(rect.width());
                 result.push(rect.height());
             }
@@ -554,10 +651,11 @@ impl ITextRangeProvider_Impl for PlatformRange_Impl {
     }
 
     fn Select(&self) -> Result<()> {
-        self.do_action(|range| ActionRequest {
-            action: Action::SetTextSelection,
-            target: range.node().id(),
-            data: Some(ActionData::SetTextSelection(range.to_text_selection())),
+        self.do_action(Action::SetTextSelection, |range| {
+            (
+                range.node().id(),
+                Some(ActionData::SetTextSelection(range.to_text_selection())),
+            )
         })
     }
 
@@ -572,17 +670,13 @@ impl ITextRangeProvider_Impl for PlatformRange_Impl {
     }
 
     fn ScrollIntoView(&self, align_to_top: BOOL) -> Result<()> {
-        self.do_action(|range| {
+        self.do_action(Action::ScrollIntoView, |range| {
             let position = if align_to_top.into() {
                 range.start()
             } else {
                 range.end()
             };
-            ActionRequest {
-                action: Action::ScrollIntoView,
-                target: position.inner_node().id(),
-                data: None,
-            }
+            (position.inner_node().id(), None)
         })
     }
 
@@ -6,15 +6,17 @@
 use accesskit::Point;
 use accesskit_consumer::TreeState;
 use std::{
+    ffi::c_void,
     fmt::{self, Write},
-    sync::{Arc, Weak},
+    mem::transmute,
+    sync::{Arc, OnceLock, Weak},
 };
 use windows::{
     core::*,
     Win32::{
         Foundation::*,
         Graphics::Gdi::*,
-        System::{Com::*, Ole::*, Variant::*},
+        System::{Com::*, LibraryLoader::*, Ole::*, Variant::*},
         UI::{Accessibility::*, WindowsAndMessaging::*},
     },
 };
@@ -134,6 +136,12 @@ impl From<OrientationType> for Variant {
     }
 }
 
+impl From<RowOrColumnMajor> for Variant {
+    fn from(value: RowOrColumnMajor) -> Self {
+        Self(value.0.into())
+    }
+}
+
 impl From<bool> for Variant {
     fn from(value: bool) -> Self {
         Self(value.into())
@@ -189,6 +197,61 @@ pub(crate) enum QueuedEvent {
         old_value: VARIANT,
         new_value: VARIANT,
     },
+    Notification {
+        element: IRawElementProviderSimple,
+        kind: NotificationKind,
+        processing: NotificationProcessing,
+        text: BSTR,
+    },
+    StructureChanged {
+        element: IRawElementProviderSimple,
+        change_type: StructureChangeType,
+        runtime_id: Vec<i32>,
+    },
+}
+
+type UiaRaiseNotificationEventFn = unsafe extern "system" fn(
+    *mut c_void,
+    NotificationKind,
+    NotificationProcessing,
+    *const u16,
+    *const u16,
+) -> HRESULT;
+
+/// Raises a UIA notification event, if supported by this version
+/// of Windows. Returns whether the event was raised.
+pub(crate) fn raise_notification_event(
+    element: &IRawElementProviderSimple,
+    kind: NotificationKind,
+    processing: NotificationProcessing,
+    text: &BSTR,
+) -> bool {
+    // UiaRaiseNotificationEvent was added in Windows 10 version 1709,
+    // so it must be looked up at runtime.
+    static FUNCTION: OnceLock<Option<UiaRaiseNotificationEventFn>> = OnceLock::new();
+    let function = FUNCTION.get_or_init(|| {
+        let module = unsafe { GetModuleHandleW(w!("uiautomationcore.dll")) }.ok()?;
+        let address = unsafe { GetProcAddress(module, s!("UiaRaiseNotificationEvent")) }?;
+        Some(unsafe { transmute::<_, UiaRaiseNotificationEventFn>(address) })
+    });
+    let Some(function) = function else {
+        return false;
+    };
+    let activity_id = BSTR::new();
+    unsafe {
+        function(
+            element.as_raw(),
+            kind,
+            processing,
+            text.as_ptr(),
+            activity_id.as_ptr(),
+        )
+    }
+    .is_ok()
+}
+
+pub(crate) fn index_to_i32(index: usize) -> i32 {
+    index.try_into().unwrap_or(i32::MAX)
 }
 
 pub(crate) fn not_implemented() -> Error {
@@ -259,6 +259,7 @@ impl ApplicationHandler<AccessKitEvent> for Application {
                     }
                 }
             }
+            AccessKitWindowEvent::ActionRequestDropped(..) => (),
             AccessKitWindowEvent::AccessibilityDeactivated => (),
         }
     }
@@ -235,6 +235,9 @@ impl ApplicationHandler<AccessKitEvent> for Application {
                     }
                 }
             }
+            AccessKitWindowEvent::ActionRequestDropped(request, error) => {
+                eprintln!("dropped action request {:?}: {}", request, error);
+            }
             AccessKitWindowEvent::AccessibilityDeactivated => (),
         }
     }
@@ -2,14 +2,75 @@
 // Licensed under the Apache License, Version 2.0 (found in
 // the LICENSE-APACHE file).
 
-/// ## Compatibility with async runtimes
-///
-/// The following only applies on Linux/Unix:
-///
-/// While this crate's API is purely blocking, it internally spawns asynchronous tasks on an executor.
-///
-/// - If you use tokio, make sure to enable the `tokio` feature of this crate.
-/// - If you use another async runtime or if you don't use one at all, the default feature will suit your needs.
+//! ## Compatibility with async runtimes
+//!
+//! The following only applies on Linux/Unix:
+//!
+//! While this crate's API is purely blocking, it internally spawns asynchronous tasks on an executor.
+//!
+//! - If you use tokio, make sure to enable the `tokio` feature of this crate.
+//! - If you use another async runtime or if you don't use one at all, the default feature will suit your needs.
+//!
+//! ## Example
+//!
+//! The adapter must be created before the window is first shown, and must
+//! see every window event before the application handles it. The adapter
+//! created here sends its requests to the event loop as [`Event`]s, so
+//! the application handles them on the event loop's thread. Adapters
+//! created with [`Adapter::with_direct_handlers`] call the handlers on
+//! a platform-dependent thread instead, so those handlers must not block
+//! waiting for the event loop. For complete programs, see the examples in
+//! this crate's repository. For how to build a tree, see the
+//! [`accesskit_unix` documentation](https://docs.rs/accesskit_unix).
+//!
+//! ```no_run
+//! # use accesskit::TreeUpdate;
+//! use accesskit_winit::{Adapter, Event as AccessKitEvent, WindowEvent as AccessKitWindowEvent};
+//! use winit::{
+//!     event::WindowEvent,
+//!     event_loop::{ActiveEventLoop, EventLoopProxy},
+//!     window::Window,
+//! };
+//! #
+//! # fn build_tree() -> TreeUpdate {
+//! #     unimplemented!()
+//! # }
+//!
+//! struct WindowState {
+//!     window: Window,
+//!     adapter: Adapter,
+//! }
+//!
+//! impl WindowState {
+//!     fn new(event_loop: &ActiveEventLoop, proxy: EventLoopProxy<AccessKitEvent>) -> Self {
+//!         let attributes = Window::default_attributes().with_visible(false);
+//!         let window = event_loop.create_window(attributes).unwrap();
+//!         let adapter = Adapter::with_event_loop_proxy(&window, proxy);
+//!         window.set_visible(true);
+//!         Self { window, adapter }
+//!     }
+//!
+//!     fn window_event(&mut self, event: &WindowEvent) {
+//!         self.adapter.process_event(&self.window, event);
+//!         // Then handle the event as usual.
+//!     }
+//!
+//!     fn accesskit_event(&mut self, event: AccessKitWindowEvent) {
+//!         match event {
+//!             AccessKitWindowEvent::InitialTreeRequested => {
+//!                 self.adapter.update_if_active(build_tree);
+//!             }
+//!             AccessKitWindowEvent::ActionRequested(request) => {
+//!                 // Perform the action, then send any resulting changes
+//!                 // through `update_if_active`.
+//!                 let _ = request;
+//!             }
+//!             AccessKitWindowEvent::ActionRequestDropped(..) => (),
+//!             AccessKitWindowEvent::AccessibilityDeactivated => (),
+//!         }
+//!     }
+//! }
+//! ```
 
 #[cfg(all(
     feature = "accesskit_unix",
@@ -49,7 +110,14 @@ compile_error!(
     "Both \"rwh_06\" (default) and \"rwh_05\" features cannot be enabled at the same time."
 );
 
-use accesskit::{ActionHandler, ActionRequest, ActivationHandler, DeactivationHandler, TreeUpdate};
+use accesskit::{
+    ActionHandler, ActionRequest, ActionRequestError, ActivationHandler, AdapterCapabilities,
+    AnnouncementKind, DeactivationHandler, GeometryUpdate, TreeUpdate, Vec2,
+};
+use std::{
+    marker::PhantomData,
+    sync::mpsc::{channel, Receiver, Sender},
+};
 use winit::{
     event::WindowEvent as WinitWindowEvent,
     event_loop::EventLoopProxy,
@@ -75,6 +143,10 @@ pub struct Event {
 pub enum WindowEvent {
     InitialTreeRequested,
     ActionRequested(ActionRequest),
+    /// The platform adapter dropped an action request instead of sending
+    /// [`WindowEvent::ActionRequested`]. This is only useful for logging.
+    /// See [`ActionHandler::action_request_dropped`].
+    ActionRequestDropped(ActionRequest, ActionRequestError),
     AccessibilityDeactivated,
 }
 
@@ -107,6 +179,14 @@ impl<T: From<Event> + Send + 'static> ActionHandler for WinitActionHandler<T> {
         };
         self.proxy.send_event(event.into()).ok();
     }
+
+    fn action_request_dropped(&mut self, request: &ActionRequest, error: ActionRequestError) {
+        let event = Event {
+            window_id: self.window_id,
+            window_event: WindowEvent::ActionRequestDropped(request.clone(), error),
+        };
+        self.proxy.send_event(event.into()).ok();
+    }
 }
 
 struct WinitDeactivationHandler<T: From<Event> + Send + 'static> {
@@ -124,8 +204,40 @@ impl<T: From<Event> + Send + 'static> DeactivationHandler for WinitDeactivationH
     }
 }
 
+/// An AccessKit adapter for a winit window.
+///
+/// Some of the underlying platform adapters may only be used on the thread
+/// that owns the window, so this type is neither `Send` nor `Sync` on any
+/// platform. To submit tree updates from other threads, use
+/// [`Adapter::update_sender`].
 pub struct Adapter {
     inner: platform_impl::Adapter,
+    update_sender: UpdateSender,
+    queued_updates: Receiver<TreeUpdate>,
+    _not_send_or_sync: PhantomData<*const ()>,
+}
+
+/// A handle for submitting tree updates to an [`Adapter`] from any thread.
+///
+/// Updates sent through this handle are queued, and are applied on the
+/// thread that owns the adapter, the next time [`Adapter::process_event`],
+/// [`Adapter::update_if_active`], or [`Adapter::apply_queued_updates`]
+/// is called. Sending an update doesn't wake up the event loop; if you need
+/// the update to be applied promptly, send a user event through your own
+/// [`EventLoopProxy`] and call [`Adapter::apply_queued_updates`] when
+/// handling it.
+#[derive(Clone)]
+pub struct UpdateSender(Sender<TreeUpdate>);
+
+impl UpdateSender {
+    /// Queues an update for the adapter. Like updates passed to
+    /// [`Adapter::update_if_active`], the update is discarded if the tree
+    /// hasn't been initialized when it is applied, so the first update
+    /// applied after the tree is requested must contain a full tree.
+    /// Returns `false` if the adapter has been dropped.
+    pub fn send(&self, update: TreeUpdate) -> bool {
+        self.0.send(update).is_ok()
+    }
 }
 
 impl Adapter {
@@ -189,7 +301,13 @@ impl Adapter {
             action_handler,
             deactivation_handler,
         );
-        Self { inner }
+        let (sender, queued_updates) = channel();
+        Self {
+            inner,
+            update_sender: UpdateSender(sender),
+            queued_updates,
+            _not_send_or_sync: PhantomData,
+        }
     }
 
     /// Creates a new AccessKit adapter for a winit window. This must be done
@@ -228,6 +346,7 @@ impl Adapter {
     /// This must be called whenever a new window event is received
     /// and before it is handled by the application.
     pub fn process_event(&mut self, window: &Window, event: &WinitWindowEvent) {
+        self.apply_queued_updates();
         self.inner.process_event(window, event);
     }
 
@@ -238,6 +357,75 @@ impl Adapter {
     /// the [`TreeUpdate`] returned by the provided function must contain
     /// a full tree.
     pub fn update_if_active(&mut self, updater: impl FnOnce() -> TreeUpdate) {
+        self.apply_queued_updates();
         self.inner.update_if_active(updater);
     }
+
+    /// If and only if the tree has been initialized, call the provided function
+    /// and apply the resulting [`GeometryUpdate`], which only changes
+    /// the bounds and transforms of existing nodes.
+    pub fn update_geometry_if_active(&mut self, updater: impl FnOnce() -> GeometryUpdate) {
+        self.apply_queued_updates();
+        self.inner.update_geometry_if_active(updater);
+    }
+
+    /// Returns a handle that can be used to submit tree updates from
+    /// any thread. See [`UpdateSender`].
+    pub fn update_sender(&self) -> UpdateSender {
+        self.update_sender.clone()
+    }
+
+    /// Apply any updates that were sent through an [`UpdateSender`],
+    /// in the order in which they were sent.
+    pub fn apply_queued_updates(&mut self) {
+        while let Ok(update) = self.queued_updates.try_recv() {
+            self.inner.update_if_active(|| update);
+        }
+    }
+
+    /// Set the offset of the origin of the node coordinate space from
+    /// the top-left corner of the window's content area, in physical pixels.
+    /// Use this when the coordinates reported in the tree are relative to
+    /// something other than the content area, for example a surface that
+    /// extends under a custom title bar. The default is zero.
+    ///
+    /// This is currently only supported on Windows and macOS.
+    pub fn set_content_origin(&mut self, origin: Vec2) {
+        self.inner.set_content_origin(origin);
+    }
+
+    /// Declare whether a popup window that logically belongs to this
+    /// adapter's window, such as the list of a drop-down, has input focus.
+    /// Call this from the popup window's [`WinitWindowEvent::Focused`]
+    /// handler. While a popup has focus, this adapter keeps reporting its
+    /// window as focused, so that assistive technologies keep following
+    /// the focus in this window's tree rather than losing context when
+    /// the popup takes focus from the operating system.
+    ///
+    /// For this to work, the popup's content must be part of this window's
+    /// tree, typically in a node whose [`popup_for`] property refers to
+    /// the element that opened the popup, and the popup window shouldn't
+    /// have an adapter of its own.
+    ///
+    /// [`popup_for`]: accesskit::Node::popup_for
+    pub fn set_popup_focused(&mut self, is_focused: bool) {
+        self.inner.set_popup_focused(is_focused);
+    }
+
+    /// Ask assistive technologies to speak the given text, without
+    /// requiring a live region in the tree. Prefer a live region when the
+    /// text is also shown on screen, so it stays discoverable after
+    /// it's been spoken. If the tree isn't active, no assistive technology
+    /// is listening, so the announcement is dropped.
+    pub fn announce(&mut self, text: &str, kind: AnnouncementKind) {
+        self.inner.announce(text, kind);
+    }
+
+    /// Returns which parts of the AccessKit schema the underlying platform
+    /// adapter exposes, or `None` if there's no platform adapter, either
+    /// because the platform isn't supported or because accessibility
+    /// is disabled, as on Unix without a display.
+    pub fn capabilities(&self) -> Option<&'static AdapterCapabilities> {
+        self.inner.capabilities()
+    }
 }
@@ -6,12 +6,17 @@ use crate::raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
 #[cfg(feature = "rwh_06")]
 use crate::raw_window_handle::{HasWindowHandle, RawWindowHandle};
 
-use accesskit::{ActionHandler, ActivationHandler, DeactivationHandler, TreeUpdate};
-use accesskit_macos::SubclassingAdapter;
+use accesskit::{
+    ActionHandler, ActivationHandler, AdapterCapabilities, AnnouncementKind, DeactivationHandler,
+    GeometryUpdate, TreeUpdate, Vec2,
+};
+use accesskit_macos::{SubclassingAdapter, CAPABILITIES};
 use winit::{event::WindowEvent, window::Window};
 
 pub struct Adapter {
     adapter: SubclassingAdapter,
+    is_window_focused: bool,
+    is_popup_focused: bool,
 }
 
 impl Adapter {
@@ -35,7 +40,11 @@ impl Adapter {
         };
 
         let adapter = unsafe { SubclassingAdapter::new(view, activation_handler, action_handler) };
-        Self { adapter }
+        Self {
+            adapter,
+            is_window_focused: false,
+            is_popup_focused: false,
+        }
     }
 
     pub fn update_if_active(&mut self, updater: impl FnOnce() -> TreeUpdate) {
@@ -44,11 +53,42 @@ impl Adapter {
         }
     }
 
+    pub fn update_geometry_if_active(&mut self, updater: impl FnOnce() -> GeometryUpdate) {
+        if let Some(events) = self.adapter.update_geometry_if_active(updater) {
+            events.raise();
+        }
+    }
+
+    pub fn set_content_origin(&mut self, origin: Vec2) {
+        self.adapter.set_content_origin(origin);
+    }
+
+    fn update_view_focus_state(&mut self) {
+        let is_focused = self.is_window_focused || self.is_popup_focused;
+        if let Some(events) = self.adapter.update_view_focus_state(is_focused) {
+            events.raise();
+        }
+    }
+
+    pub fn set_popup_focused(&mut self, is_focused: bool) {
+        self.is_popup_focused = is_focused;
+        self.update_view_focus_state();
+    }
+
+    pub fn announce(&mut self, text: &str, kind: AnnouncementKind) {
+        if let Some(events) = self.adapter.announce(text, kind) {
+            events.raise();
+        }
+    }
+
+    pub fn capabilities(&self) -> Option<&'static AdapterCapabilities> {
+        Some(&CAPABILITIES)
+    }
+
     pub fn process_event(&mut self, _window: &Window, event: &WindowEvent) {
         if let WindowEvent::Focused(is_focused) = event {
-            if let Some(events) = self.adapter.update_view_focus_state(*is_focused) {
-                events.raise();
-            }
+            self.is_window_focused = *is_focused;
+            self.update_view_focus_state();
         }
     }
 }
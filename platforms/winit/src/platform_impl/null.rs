@@ -2,7 +2,10 @@
 // Licensed under the Apache License, Version 2.0 (found in
 // the LICENSE-APACHE file).
 
-use accesskit::{ActionHandler, ActivationHandler, DeactivationHandler, TreeUpdate};
+use accesskit::{
+    ActionHandler, ActivationHandler, AdapterCapabilities, AnnouncementKind, DeactivationHandler,
+    GeometryUpdate, TreeUpdate, Vec2,
+};
 use winit::{event::WindowEvent, window::Window};
 
 pub struct Adapter;
@@ -19,5 +22,17 @@ impl Adapter {
 
     pub fn update_if_active(&mut self, _updater: impl FnOnce() -> TreeUpdate) {}
 
+    pub fn update_geometry_if_active(&mut self, _updater: impl FnOnce() -> GeometryUpdate) {}
+
+    pub fn set_content_origin(&mut self, _origin: Vec2) {}
+
+    pub fn set_popup_focused(&mut self, _is_focused: bool) {}
+
+    pub fn announce(&mut self, _text: &str, _kind: AnnouncementKind) {}
+
+    pub fn capabilities(&self) -> Option<&'static AdapterCapabilities> {
+        None
+    }
+
     pub fn process_event(&mut self, _window: &Window, _event: &WindowEvent) {}
 }
@@ -2,12 +2,17 @@
 // Licensed under the Apache License, Version 2.0 (found in
 // the LICENSE-APACHE file).
 
-use accesskit::{ActionHandler, ActivationHandler, DeactivationHandler, Rect, TreeUpdate};
+use accesskit::{
+    ActionHandler, ActivationHandler, AdapterCapabilities, AnnouncementKind, DeactivationHandler,
+    GeometryUpdate, Rect, TreeUpdate, Vec2,
+};
 use accesskit_unix::Adapter as UnixAdapter;
 use winit::{event::WindowEvent, window::Window};
 
 pub struct Adapter {
     adapter: UnixAdapter,
+    is_window_focused: bool,
+    is_popup_focused: bool,
 }
 
 impl Adapter {
@@ -18,7 +23,11 @@ impl Adapter {
         deactivation_handler: impl 'static + DeactivationHandler + Send,
     ) -> Self {
         let adapter = UnixAdapter::new(activation_handler, action_handler, deactivation_handler);
-        Self { adapter }
+        Self {
+            adapter,
+            is_window_focused: false,
+            is_popup_focused: false,
+        }
     }
 
     fn set_root_window_bounds(&mut self, outer: Rect, inner: Rect) {
@@ -29,10 +38,32 @@ impl Adapter {
         self.adapter.update_if_active(updater);
     }
 
-    fn update_window_focus_state(&mut self, is_focused: bool) {
+    pub fn update_geometry_if_active(&mut self, updater: impl FnOnce() -> GeometryUpdate) {
+        self.adapter.update_geometry_if_active(updater);
+    }
+
+    pub fn set_content_origin(&mut self, _origin: Vec2) {
+        // Not yet supported by the Unix adapter.
+    }
+
+    fn update_window_focus_state(&mut self) {
+        let is_focused = self.is_window_focused || self.is_popup_focused;
         self.adapter.update_window_focus_state(is_focused);
     }
 
+    pub fn set_popup_focused(&mut self, is_focused: bool) {
+        self.is_popup_focused = is_focused;
+        self.update_window_focus_state();
+    }
+
+    pub fn announce(&mut self, text: &str, kind: AnnouncementKind) {
+        self.adapter.announce(text, kind);
+    }
+
+    pub fn capabilities(&self) -> Option<&'static AdapterCapabilities> {
+        self.adapter.backend().capabilities()
+    }
+
     pub fn process_event(&mut self, window: &Window, event: &WindowEvent) {
         match event {
             WindowEvent::Moved(outer_position) => {
@@ -68,7 +99,8 @@ impl Adapter {
                 )
             }
             WindowEvent::Focused(is_focused) => {
-                self.update_window_focus_state(*is_focused);
+                self.is_window_focused = *is_focused;
+                self.update_window_focus_state();
             }
             _ => (),
         }
@@ -6,8 +6,11 @@ use crate::raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
 #[cfg(feature = "rwh_06")]
 use crate::raw_window_handle::{HasWindowHandle, RawWindowHandle};
 
-use accesskit::{ActionHandler, ActivationHandler, DeactivationHandler, TreeUpdate};
-use accesskit_windows::{SubclassingAdapter, HWND};
+use accesskit::{
+    ActionHandler, ActivationHandler, AdapterCapabilities, AnnouncementKind, DeactivationHandler,
+    GeometryUpdate, TreeUpdate, Vec2,
+};
+use accesskit_windows::{SubclassingAdapter, CAPABILITIES, HWND};
 use winit::{event::WindowEvent, window::Window};
 
 pub struct Adapter {
@@ -44,5 +47,29 @@ impl Adapter {
         }
     }
 
+    pub fn update_geometry_if_active(&mut self, updater: impl FnOnce() -> GeometryUpdate) {
+        if let Some(events) = self.adapter.update_geometry_if_active(updater) {
+            events.raise();
+        }
+    }
+
+    pub fn set_content_origin(&mut self, origin: Vec2) {
+        self.adapter.set_content_origin(origin);
+    }
+
+    pub fn set_popup_focused(&mut self, is_focused: bool) {
+        self.adapter.set_popup_focused(is_focused);
+    }
+
+    pub fn announce(&mut self, text: &str, kind: AnnouncementKind) {
+        if let Some(events) = self.adapter.announce(text, kind) {
+            events.raise();
+        }
+    }
+
+    pub fn capabilities(&self) -> Option<&'static AdapterCapabilities> {
+        Some(&CAPABILITIES)
+    }
+
     pub fn process_event(&mut self, _window: &Window, _event: &WindowEvent) {}
 }
@@ -0,0 +1,452 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{AnnouncementKind, ChangeReason, Live, NodeId, TreeUpdate};
+use accesskit_consumer::{
+    common_filter as filter, FilterResult, LiveRegionChange, Node, ProgressThrottle, Tree,
+    TreeChangeHandler,
+};
+use std::time::{Duration, Instant};
+
+use crate::{
+    utterance::{describe, name, state_and_value},
+    Speaker,
+};
+
+struct SpeechChangeHandler<'a, S: Speaker> {
+    speaker: &'a mut S,
+    progress: &'a mut ProgressThrottle,
+    now: Duration,
+    /// The text announced for each live region during this update.
+    /// The label of a live region and the text that labels it may be
+    /// on different nodes of the region, which change together.
+    announced: Vec<(NodeId, String)>,
+}
+
+/// Returns the outermost node of the live region that contains the node.
+fn live_region_root(node: &Node) -> NodeId {
+    let mut root = *node;
+    while let Some(parent) = root.parent() {
+        if parent.live() == Live::Off {
+            break;
+        }
+        root = parent;
+    }
+    root.id()
+}
+
+impl<S: Speaker> SpeechChangeHandler<'_, S> {
+    fn announce_live_region(&mut self, node: &Node, old_text: &str) {
+        let text = name(node).unwrap_or_default();
+        if let Some(change) = LiveRegionChange::new(old_text, &text) {
+            let entry = (live_region_root(node), change.text().to_string());
+            if self.announced.contains(&entry) {
+                return;
+            }
+            self.speaker
+                .speak(change.text(), node.live() == Live::Assertive);
+            self.announced.push(entry);
+        }
+    }
+
+    fn add_node(&mut self, node: &Node) {
+        if node.live() != Live::Off {
+            self.announce_live_region(node, "");
+        }
+    }
+
+    /// Announces the live regions of a subtree that was excluded
+    /// and is now included, since its descendants don't change.
+    fn add_subtree(&mut self, node: &Node) {
+        self.add_node(node);
+        for child in node.filtered_children(filter) {
+            self.add_subtree(&child);
+        }
+    }
+}
+
+impl<S: Speaker> TreeChangeHandler for SpeechChangeHandler<'_, S> {
+    fn node_added(&mut self, node: &Node) {
+        if filter(node) == FilterResult::Include {
+            self.add_node(node);
+        }
+    }
+
+    fn node_updated(&mut self, old_node: &Node, new_node: &Node) {
+        let filter_old = filter(old_node);
+        if filter(new_node) != FilterResult::Include {
+            return;
+        }
+        if filter_old != FilterResult::Include {
+            if filter_old == FilterResult::ExcludeSubtree {
+                self.add_subtree(new_node);
+            } else {
+                self.add_node(new_node);
+            }
+            return;
+        }
+        if new_node.is_focused() && new_node.change_reason() != Some(ChangeReason::Programmatic) {
+            let state = state_and_value(new_node);
+            if state != state_and_value(old_node) && !state.is_empty() {
+                self.speaker.speak(&state.join(", "), true);
+            }
+        }
+        if new_node.live() != Live::Off {
+            let old_text = if old_node.live() != Live::Off {
+                name(old_node).unwrap_or_default()
+            } else {
+                String::new()
+            };
+            self.announce_live_region(new_node, &old_text);
+        }
+        if let Some(text) = self.progress.node_updated(old_node, new_node, self.now) {
+            self.speaker.speak(&text, false);
+        }
+    }
+
+    fn focus_moved(&mut self, _old_node: Option<&Node>, new_node: Option<&Node>) {
+        if let Some(new_node) = new_node {
+            if let Some(earcon) = new_node.earcon() {
+                self.speaker.play_earcon(earcon);
+            }
+            self.speaker.speak(&describe(new_node), true);
+        }
+    }
+
+    fn node_removed(&mut self, node: &Node) {
+        self.progress.node_removed(node);
+    }
+}
+
+/// Speaks an accessibility tree through a [`Speaker`].
+///
+/// This announces the focused node when focus moves, changes to the state
+/// or value of the focused node that weren't made programmatically (see
+/// [`ChangeReason`]), changes to live regions, and periodic progress of
+/// long operations, as decided by [`ProgressThrottle`].
+///
+/// The adapter has no handlers and never calls back into the application.
+/// Everything happens synchronously on the thread that calls its methods.
+///
+/// # Example
+///
+/// ```
+/// use accesskit::{Node, NodeId, Role, Tree, TreeUpdate};
+/// use accesskit_speech::{Adapter, Speaker};
+///
+/// #[derive(Default)]
+/// struct Transcript(Vec<String>);
+///
+/// impl Speaker for Transcript {
+///     fn speak(&mut self, text: &str, _interrupt: bool) {
+///         self.0.push(text.into());
+///     }
+///
+///     fn stop(&mut self) {}
+/// }
+///
+/// let mut button = Node::new(Role::Button);
+/// button.set_label("OK");
+/// let mut window = Node::new(Role::Window);
+/// window.set_children(vec![NodeId(1)]);
+/// let update = TreeUpdate {
+///     nodes: vec![(NodeId(0), window), (NodeId(1), button)],
+///     tree: Some(Tree::new(NodeId(0))),
+///     focus: NodeId(1),
+/// };
+/// let adapter = Adapter::new(update, true, Transcript::default());
+/// assert_eq!(adapter.speaker().0, ["OK, button"]);
+/// ```
+pub struct Adapter<S: Speaker> {
+    tree: Tree,
+    speaker: S,
+    progress: ProgressThrottle,
+    start: Instant,
+}
+
+impl<S: Speaker> Adapter<S> {
+    /// Creates a new self-voicing adapter. If the window is focused,
+    /// the focused node is announced immediately.
+    pub fn new(initial_state: TreeUpdate, is_window_focused: bool, speaker: S) -> Self {
+        let tree = Tree::new(initial_state, is_window_focused);
+        let mut result = Self {
+            tree,
+            speaker,
+            progress: ProgressThrottle::default(),
+            start: Instant::now(),
+        };
+        if is_window_focused {
+            result.speak_focus();
+        }
+        result
+    }
+
+    /// Applies the update and announces the resulting changes.
+    pub fn update(&mut self, update: TreeUpdate) {
+        let mut handler = SpeechChangeHandler {
+            speaker: &mut self.speaker,
+            progress: &mut self.progress,
+            now: self.start.elapsed(),
+            announced: Vec::new(),
+        };
+        self.tree.update_and_process_changes(update, &mut handler);
+    }
+
+    /// Updates the tree state based on whether the window is focused,
+    /// announcing the focused node when the window gains focus.
+    pub fn update_window_focus_state(&mut self, is_focused: bool) {
+        let mut handler = SpeechChangeHandler {
+            speaker: &mut self.speaker,
+            progress: &mut self.progress,
+            now: self.start.elapsed(),
+            announced: Vec::new(),
+        };
+        self.tree
+            .update_host_focus_state_and_process_changes(is_focused, &mut handler);
+    }
+
+    /// Announces the focused node again, for example in response to
+    /// a key that the application reserves for that purpose.
+    pub fn speak_focus(&mut self) {
+        if let Some(node) = self.tree.state().focus() {
+            self.speaker.speak(&describe(&node), true);
+        }
+    }
+
+    /// Speaks the given text, which doesn't need to be in the tree.
+    /// An assertive announcement interrupts any speech in progress.
+    pub fn announce(&mut self, text: &str, kind: AnnouncementKind) {
+        self.speaker
+            .speak(text, kind == AnnouncementKind::Assertive);
+    }
+
+    /// Sets the minimum time between announcements of the same
+    /// progress indicator. See [`ProgressThrottle`].
+    pub fn set_progress_interval(&mut self, interval: Duration) {
+        self.progress.set_interval(interval);
+    }
+
+    pub fn speaker(&self) -> &S {
+        &self.speaker
+    }
+
+    pub fn speaker_mut(&mut self) -> &mut S {
+        &mut self.speaker
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{AnnouncementKind, Live, Node, NodeId, Role, Toggled, Tree, TreeUpdate};
+
+    use super::Adapter;
+    use crate::Speaker;
+
+    #[derive(Default)]
+    struct Recorder(Vec<(String, bool)>);
+
+    impl Speaker for Recorder {
+        fn speak(&mut self, text: &str, interrupt: bool) {
+            self.0.push((text.into(), interrupt));
+        }
+
+        fn stop(&mut self) {}
+    }
+
+    fn window(children: Vec<NodeId>) -> Node {
+        let mut node = Node::new(Role::Window);
+        node.set_children(children);
+        node
+    }
+
+    fn check_box(toggled: Toggled) -> Node {
+        let mut node = Node::new(Role::CheckBox);
+        node.set_label("Remember me");
+        node.set_toggled(toggled);
+        node
+    }
+
+    fn status(text: &str) -> Node {
+        let mut node = Node::new(Role::Status);
+        node.set_label(text);
+        node.set_live(Live::Polite);
+        node
+    }
+
+    #[test]
+    fn announcements() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), window(vec![NodeId(1), NodeId(2)])),
+                (NodeId(1), check_box(Toggled::False)),
+                (NodeId(2), status("Ready")),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(1),
+        };
+        let mut adapter = Adapter::new(update, true, Recorder::default());
+        adapter.update(TreeUpdate {
+            nodes: vec![(NodeId(1), check_box(Toggled::True))],
+            tree: None,
+            focus: NodeId(1),
+        });
+        adapter.update(TreeUpdate {
+            nodes: vec![(NodeId(2), status("Saved"))],
+            tree: None,
+            focus: NodeId(1),
+        });
+        adapter.update(TreeUpdate {
+            nodes: vec![(NodeId(2), status("Saved\nSynced"))],
+            tree: None,
+            focus: NodeId(1),
+        });
+        assert_eq!(
+            adapter.speaker().0,
+            [
+                ("Remember me, check box, not checked".into(), true),
+                ("checked".into(), true),
+                ("Saved".into(), false),
+                ("Synced".into(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn hidden_live_region() {
+        let hidden_status = |text: &str| {
+            let mut node = status(text);
+            node.set_hidden();
+            node
+        };
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), window(vec![NodeId(1)])),
+                (NodeId(1), hidden_status("Ready")),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut adapter = Adapter::new(update, false, Recorder::default());
+        adapter.update(TreeUpdate {
+            nodes: vec![(NodeId(1), hidden_status("Saved"))],
+            tree: None,
+            focus: NodeId(0),
+        });
+        adapter.update(TreeUpdate {
+            nodes: vec![
+                (NodeId(0), window(vec![NodeId(1), NodeId(2)])),
+                (NodeId(2), hidden_status("Offline")),
+            ],
+            tree: None,
+            focus: NodeId(0),
+        });
+        assert!(adapter.speaker().0.is_empty());
+        adapter.update(TreeUpdate {
+            nodes: vec![(NodeId(1), status("Saved"))],
+            tree: None,
+            focus: NodeId(0),
+        });
+        assert_eq!(adapter.speaker().0, [("Saved".into(), false)]);
+    }
+
+    #[test]
+    fn live_region_labelled_by_descendant() {
+        let update = TreeUpdate {
+            nodes: vec![(NodeId(0), window(vec![]))],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut adapter = Adapter::new(update, false, Recorder::default());
+        adapter.update(TreeUpdate {
+            nodes: vec![
+                (NodeId(0), window(vec![NodeId(1)])),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Status);
+                    node.set_labelled_by(vec![NodeId(2)]);
+                    node.set_children(vec![NodeId(2)]);
+                    node
+                }),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Label);
+                    node.set_value("Saved");
+                    node
+                }),
+            ],
+            tree: None,
+            focus: NodeId(0),
+        });
+        assert_eq!(adapter.speaker().0, [("Saved".into(), false)]);
+    }
+
+    #[test]
+    fn announce() {
+        let update = TreeUpdate {
+            nodes: vec![(NodeId(0), window(vec![]))],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(0),
+        };
+        let mut adapter = Adapter::new(update, false, Recorder::default());
+        adapter.announce("Upload complete", AnnouncementKind::Polite);
+        adapter.announce("Disk full", AnnouncementKind::Assertive);
+        assert_eq!(
+            adapter.speaker().0,
+            [
+                ("Upload complete".into(), false),
+                ("Disk full".into(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn notifications() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), window(vec![NodeId(1)])),
+                (NodeId(1), check_box(Toggled::False)),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(1),
+        };
+        let mut adapter = Adapter::new(update, true, Recorder::default());
+        adapter.update(TreeUpdate {
+            nodes: vec![
+                (NodeId(0), window(vec![NodeId(1), NodeId(2)])),
+                (NodeId(2), {
+                    let mut node = Node::new(Role::Alert);
+                    node.set_label("Connection lost");
+                    node
+                }),
+            ],
+            tree: None,
+            focus: NodeId(1),
+        });
+        assert_eq!(
+            adapter.speaker().0,
+            [
+                ("Remember me, check box, not checked".into(), true),
+                ("Connection lost".into(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn pronunciation() {
+        let update = TreeUpdate {
+            nodes: vec![
+                (NodeId(0), window(vec![NodeId(1)])),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::Button);
+                    node.set_label("Dr. Smith");
+                    node.set_pronunciation("Doctor Smith");
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(1),
+        };
+        let adapter = Adapter::new(update, true, Recorder::default());
+        assert_eq!(adapter.speaker().0, [("Doctor Smith, button".into(), true)]);
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use objc2::rc::Id;
+use objc2_app_kit::NSSpeechSynthesizer;
+use objc2_foundation::NSString;
+
+pub(crate) struct Speaker {
+    synthesizer: Id<NSSpeechSynthesizer>,
+}
+
+impl Speaker {
+    pub(crate) fn new() -> Option<Self> {
+        let synthesizer = unsafe { NSSpeechSynthesizer::new() };
+        Some(Self { synthesizer })
+    }
+
+    pub(crate) fn speak(&mut self, text: &str, _interrupt: bool) {
+        // NSSpeechSynthesizer has no queue; starting new speech always
+        // stops the speech in progress.
+        let text = NSString::from_str(text);
+        unsafe { self.synthesizer.startSpeakingString(&text) };
+    }
+
+    pub(crate) fn stop(&mut self) {
+        unsafe { self.synthesizer.stopSpeaking() };
+    }
+}
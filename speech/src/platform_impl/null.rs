@@ -0,0 +1,20 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+pub(crate) enum Speaker {}
+
+impl Speaker {
+    pub(crate) fn new() -> Option<Self> {
+        None
+    }
+
+    pub(crate) fn speak(&mut self, _text: &str, _interrupt: bool) {
+        match *self {}
+    }
+
+    pub(crate) fn stop(&mut self) {
+        match *self {}
+    }
+}
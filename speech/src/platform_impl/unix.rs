@@ -0,0 +1,65 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use std::{
+    process::{Command, Stdio},
+    sync::mpsc::{channel, Sender},
+    thread,
+};
+
+fn spd_say(args: &[&str]) -> bool {
+    Command::new("spd-say")
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+enum Request {
+    Speak(String),
+    Stop,
+}
+
+pub(crate) struct Speaker {
+    requests: Sender<Request>,
+}
+
+impl Speaker {
+    pub(crate) fn new() -> Option<Self> {
+        if !spd_say(&["--version"]) {
+            return None;
+        }
+        // Starting `spd-say` takes a while, so it's run on a worker thread
+        // rather than blocking the caller. The thread runs one process
+        // at a time, so that a cancellation can't overtake the speech that
+        // follows it. It ends when the speaker is dropped.
+        let (requests, receiver) = channel();
+        thread::Builder::new()
+            .name("accesskit_speech".into())
+            .spawn(move || {
+                for request in receiver {
+                    match request {
+                        Request::Speak(text) => spd_say(&["--", &text]),
+                        Request::Stop => spd_say(&["--cancel"]),
+                    };
+                }
+            })
+            .ok()?;
+        Some(Self { requests })
+    }
+
+    pub(crate) fn speak(&mut self, text: &str, interrupt: bool) {
+        if interrupt {
+            self.stop();
+        }
+        let _ = self.requests.send(Request::Speak(text.into()));
+    }
+
+    pub(crate) fn stop(&mut self) {
+        let _ = self.requests.send(Request::Stop);
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use windows::{
+    core::*,
+    Win32::{Media::Speech::*, System::Com::*},
+};
+
+/// Balances a successful call to `CoInitializeEx`.
+struct ComGuard;
+
+impl Drop for ComGuard {
+    fn drop(&mut self) {
+        unsafe { CoUninitialize() };
+    }
+}
+
+pub(crate) struct Speaker {
+    voice: ISpVoice,
+    // Fields are dropped in declaration order, so the voice is released
+    // before COM is uninitialized.
+    _com: Option<ComGuard>,
+}
+
+impl Speaker {
+    pub(crate) fn new() -> Option<Self> {
+        // This fails harmlessly if COM is already initialized on this
+        // thread with the other threading model. Otherwise, including
+        // when COM was already initialized as an STA, the call must be
+        // balanced by `CoUninitialize`.
+        let com = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) }
+            .is_ok()
+            .then_some(ComGuard);
+        let voice: ISpVoice = unsafe { CoCreateInstance(&SpVoice, None, CLSCTX_ALL) }.ok()?;
+        Some(Self { voice, _com: com })
+    }
+
+    pub(crate) fn speak(&mut self, text: &str, interrupt: bool) {
+        let mut flags = SPF_ASYNC.0 | SPF_IS_NOT_XML.0;
+        if interrupt {
+            flags |= SPF_PURGEBEFORESPEAK.0;
+        }
+        let text = HSTRING::from(text);
+        let _ = unsafe { self.voice.Speak(PCWSTR(text.as_ptr()), flags as u32, None) };
+    }
+
+    pub(crate) fn stop(&mut self) {
+        let flags = SPF_ASYNC.0 | SPF_PURGEBEFORESPEAK.0;
+        let _ = unsafe { self.voice.Speak(PCWSTR::null(), flags as u32, None) };
+    }
+}
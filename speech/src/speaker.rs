@@ -0,0 +1,46 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use crate::platform_impl;
+
+/// Produces speech output.
+pub trait Speaker {
+    /// Speaks the given text. If `interrupt` is true, any speech that is
+    /// in progress or queued is stopped first.
+    fn speak(&mut self, text: &str, interrupt: bool);
+
+    /// Stops any speech that is in progress or queued.
+    fn stop(&mut self);
+
+    /// Plays the sound identified by [`accesskit::Node::earcon`], just before
+    /// the node is announced. The default implementation does nothing, since
+    /// the meaning of earcon identifiers is defined by the application.
+    fn play_earcon(&mut self, _earcon: &str) {}
+}
+
+/// Speaks through the platform's text-to-speech API.
+pub struct SystemSpeaker(platform_impl::Speaker);
+
+impl SystemSpeaker {
+    /// Connects to the platform's text-to-speech API. Returns `None`
+    /// if speech isn't available on this platform or system.
+    ///
+    /// On Windows, this initializes COM on the calling thread
+    /// as a single-threaded apartment, if it isn't already initialized,
+    /// until the speaker is dropped.
+    pub fn new() -> Option<Self> {
+        platform_impl::Speaker::new().map(Self)
+    }
+}
+
+impl Speaker for SystemSpeaker {
+    fn speak(&mut self, text: &str, interrupt: bool) {
+        self.0.speak(text, interrupt);
+    }
+
+    fn stop(&mut self) {
+        self.0.stop();
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{Role, Toggled};
+use accesskit_consumer::Node;
+
+fn role_name<'a>(node: &'a Node<'a>) -> Option<&'a str> {
+    if let Some(role_description) = node.role_description() {
+        return Some(role_description);
+    }
+    if node.is_text_input() {
+        return Some("edit");
+    }
+    Some(match node.role() {
+        Role::Button | Role::DefaultButton => "button",
+        Role::CheckBox | Role::MenuItemCheckBox => "check box",
+        Role::ComboBox | Role::EditableComboBox => "combo box",
+        Role::Dialog | Role::AlertDialog => "dialog",
+        Role::Heading => "heading",
+        Role::Link => "link",
+        Role::ListBoxOption | Role::MenuListOption => "option",
+        Role::MenuItem => "menu item",
+        Role::RadioButton | Role::MenuItemRadio => "radio button",
+        Role::ScrollBar => "scroll bar",
+        Role::Slider => "slider",
+        Role::SpinButton => "spin button",
+        Role::Splitter => "splitter",
+        Role::Switch => "switch",
+        Role::Tab => "tab",
+        Role::TreeItem => "tree item",
+        _ => return None,
+    })
+}
+
+/// Returns the name of the node, as it would be exposed by
+/// a platform adapter.
+pub(crate) fn name(node: &Node) -> Option<String> {
+    node.name()
+}
+
+/// Returns the parts of the node's description that can change while
+/// it has focus: its state and its value.
+pub(crate) fn state_and_value(node: &Node) -> Vec<String> {
+    let mut parts = Vec::new();
+    if let Some(toggled) = node.toggled() {
+        parts.push(
+            match toggled {
+                Toggled::True => "checked",
+                Toggled::False => "not checked",
+                Toggled::Mixed => "partially checked",
+            }
+            .into(),
+        );
+    }
+    if let Some(expanded) = node.is_expanded() {
+        parts.push(if expanded { "expanded" } else { "collapsed" }.into());
+    }
+    if node.is_selected() == Some(true) {
+        parts.push("selected".into());
+    }
+    if node.is_disabled() {
+        parts.push("unavailable".into());
+    }
+    if !node.label_comes_from_value() {
+        if let Some(value) = node.value() {
+            parts.push(value);
+        } else if let Some(value) = node.numeric_value() {
+            parts.push(value.to_string());
+        }
+    }
+    parts
+}
+
+/// Returns the full description of a node, as spoken when it gets focus.
+pub(crate) fn describe(node: &Node) -> String {
+    let mut parts = Vec::new();
+    parts.extend(
+        node.pronunciation()
+            .map(String::from)
+            .or_else(|| name(node)),
+    );
+    parts.extend(role_name(node).map(String::from));
+    parts.extend(state_and_value(node));
+    parts.extend(node.description());
+    parts.join(", ")
+}
@@ -0,0 +1,457 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{
+    ActionHandler, ActionRequest, ActivationHandler, GeometryUpdate, Role, TreeUpdate,
+};
+use accesskit_consumer::{common_filter_with_root_exception, Node, Query, Tree, TreeState};
+
+use crate::event::{Event, EventRecorder};
+
+enum State {
+    Inactive,
+    Pending,
+    Active(Tree),
+}
+
+/// A headless adapter that keeps the tree in memory for inspection
+/// by tests.
+///
+/// Like a platform adapter, it starts out inactive. Calling
+/// [`Adapter::activate`] simulates an assistive technology connecting,
+/// which calls the [`ActivationHandler`]. Updates sent through
+/// [`Adapter::update_if_active`] are then applied, and the resulting
+/// changes are recorded as [`Event`]s.
+///
+/// The query methods panic if the adapter isn't active, since a test
+/// that inspects a tree that was never provided is almost certainly wrong.
+///
+/// # Example
+///
+/// ```
+/// use accesskit::{
+///     ActionHandler, ActionRequest, ActivationHandler, Node, NodeId, Role, Tree, TreeUpdate,
+/// };
+/// use accesskit_testing::Adapter;
+///
+/// struct Handler;
+///
+/// impl ActivationHandler for Handler {
+///     fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+///         let mut button = Node::new(Role::Button);
+///         button.set_label("OK");
+///         let mut window = Node::new(Role::Window);
+///         window.set_children(vec![NodeId(1)]);
+///         Some(TreeUpdate {
+///             nodes: vec![(NodeId(0), window), (NodeId(1), button)],
+///             tree: Some(Tree::new(NodeId(0))),
+///             focus: NodeId(1),
+///         })
+///     }
+/// }
+///
+/// impl ActionHandler for Handler {
+///     fn do_action(&mut self, _request: ActionRequest) {}
+/// }
+///
+/// let mut adapter = Adapter::new(Handler, Handler);
+/// adapter.update_window_focus_state(true);
+/// adapter.activate();
+/// adapter.assert_focus("button[label=OK]");
+/// assert_eq!(adapter.find_by_label("OK").unwrap().role(), Role::Button);
+/// ```
+pub struct Adapter {
+    activation_handler: Box<dyn ActivationHandler>,
+    action_handler: Box<dyn ActionHandler>,
+    state: State,
+    is_window_focused: bool,
+    events: Vec<Event>,
+}
+
+impl Adapter {
+    /// Creates an inactive adapter. The window is initially
+    /// considered unfocused.
+    pub fn new(
+        activation_handler: impl 'static + ActivationHandler,
+        action_handler: impl 'static + ActionHandler,
+    ) -> Self {
+        Self {
+            activation_handler: Box::new(activation_handler),
+            action_handler: Box::new(action_handler),
+            state: State::Inactive,
+            is_window_focused: false,
+            events: Vec::new(),
+        }
+    }
+
+    /// Simulates an assistive technology connecting, by requesting the
+    /// initial tree from the [`ActivationHandler`] if the adapter
+    /// isn't already active.
+    pub fn activate(&mut self) {
+        if !matches!(self.state, State::Inactive) {
+            return;
+        }
+        self.state = match self.activation_handler.request_initial_tree() {
+            Some(initial_state) => State::Active(Tree::new(initial_state, self.is_window_focused)),
+            None => State::Pending,
+        };
+    }
+
+    /// Simulates the last assistive technology disconnecting,
+    /// dropping the tree.
+    pub fn deactivate(&mut self) {
+        self.state = State::Inactive;
+    }
+
+    /// Returns true if the adapter has a tree, that is, if it was activated
+    /// and the application has provided the initial tree.
+    pub fn is_active(&self) -> bool {
+        matches!(self.state, State::Active(_))
+    }
+
+    /// If and only if the adapter has been activated, call the provided
+    /// function and apply the resulting update. Note: If the caller's
+    /// implementation of [`ActivationHandler::request_initial_tree`]
+    /// initially returned `None`, the [`TreeUpdate`] returned by the
+    /// provided function must contain a full tree.
+    pub fn update_if_active(&mut self, update_factory: impl FnOnce() -> TreeUpdate) {
+        match &mut self.state {
+            State::Inactive => (),
+            State::Pending => {
+                self.state = State::Active(Tree::new(update_factory(), self.is_window_focused));
+            }
+            State::Active(tree) => {
+                let mut recorder = EventRecorder(&mut self.events);
+                tree.update_and_process_changes(update_factory(), &mut recorder);
+            }
+        }
+    }
+
+    /// If and only if the adapter has a tree, call the provided function
+    /// and apply the resulting [`GeometryUpdate`].
+    pub fn update_geometry_if_active(&mut self, update_factory: impl FnOnce() -> GeometryUpdate) {
+        if let State::Active(tree) = &mut self.state {
+            let mut recorder = EventRecorder(&mut self.events);
+            tree.update_geometry_and_process_changes(update_factory(), &mut recorder);
+        }
+    }
+
+    /// Updates the tree state based on whether the window is focused.
+    pub fn update_window_focus_state(&mut self, is_focused: bool) {
+        self.is_window_focused = is_focused;
+        if let State::Active(tree) = &mut self.state {
+            let mut recorder = EventRecorder(&mut self.events);
+            tree.update_host_focus_state_and_process_changes(is_focused, &mut recorder);
+        }
+    }
+
+    /// Simulates an assistive technology requesting an action. As with
+    /// a platform adapter, requests that target unknown or inert nodes,
+    /// that the target doesn't support, or that lack required data,
+    /// are passed to
+    /// [`ActionHandler::action_request_dropped`] instead of
+    /// [`ActionHandler::do_action`].
+    pub fn do_action(&mut self, request: ActionRequest) {
+        match self.tree_state().check_action_request(&request) {
+            Ok(()) => self.action_handler.do_action(request),
+            Err(error) => self.action_handler.action_request_dropped(&request, error),
+        }
+    }
+
+    /// Returns the current tree state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the adapter isn't active.
+    #[track_caller]
+    pub fn tree_state(&self) -> &TreeState {
+        match &self.state {
+            State::Active(tree) => tree.state(),
+            _ => panic!("the adapter isn't active"),
+        }
+    }
+
+    pub fn root(&self) -> Node<'_> {
+        self.tree_state().root()
+    }
+
+    /// Returns the focused node, or `None` if the window isn't focused.
+    pub fn focus(&self) -> Option<Node<'_>> {
+        self.tree_state().focus()
+    }
+
+    /// Returns the first node, in depth-first order, that matches the given
+    /// query. See [`Query`] for the syntax.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query is invalid.
+    #[track_caller]
+    pub fn find(&self, query: &str) -> Option<Node<'_>> {
+        parse_query(query).find_first(self.tree_state())
+    }
+
+    /// Returns all nodes that match the given query, in depth-first order.
+    /// See [`Query`] for the syntax.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query is invalid.
+    #[track_caller]
+    pub fn find_all(&self, query: &str) -> Vec<Node<'_>> {
+        parse_query(query).find_all(self.tree_state())
+    }
+
+    /// Returns all nodes with the given role, in depth-first order.
+    pub fn find_by_role(&self, role: Role) -> Vec<Node<'_>> {
+        self.nodes()
+            .into_iter()
+            .filter(|node| node.role() == role)
+            .collect()
+    }
+
+    /// Returns the first node, in depth-first order, whose computed label
+    /// is exactly the given text.
+    pub fn find_by_label(&self, label: &str) -> Option<Node<'_>> {
+        self.nodes()
+            .into_iter()
+            .find(|node| node.label().as_deref() == Some(label))
+    }
+
+    /// Returns the first node, in depth-first order, with the given
+    /// author ID.
+    pub fn find_by_author_id(&self, author_id: &str) -> Option<Node<'_>> {
+        self.nodes()
+            .into_iter()
+            .find(|node| node.author_id() == Some(author_id))
+    }
+
+    /// Returns every node in the tree, in depth-first order, including
+    /// nodes that platform adapters would filter out.
+    pub fn nodes(&self) -> Vec<Node<'_>> {
+        parse_query("*").find_all(self.tree_state())
+    }
+
+    /// Returns the nodes that a platform adapter would expose, in
+    /// depth-first order, starting with the root. The children of filtered
+    /// nodes take their place.
+    pub fn filtered_nodes(&self) -> Vec<Node<'_>> {
+        let mut result = Vec::new();
+        let mut stack = vec![self.root()];
+        while let Some(node) = stack.pop() {
+            result.push(node);
+            let start = stack.len();
+            stack.extend(node.filtered_children(common_filter_with_root_exception));
+            stack[start..].reverse();
+        }
+        result
+    }
+
+    /// Returns a textual dump of the tree as a platform adapter would
+    /// expose it. See [`TreeState::dump`] for the format.
+    pub fn dump(&self) -> String {
+        self.tree_state()
+            .dump_filtered(&common_filter_with_root_exception)
+    }
+
+    /// Asserts that the focused node matches the given query.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a dump of the tree if nothing is focused, or if the
+    /// focused node doesn't match.
+    #[track_caller]
+    pub fn assert_focus(&self, query: &str) {
+        let query = parse_query(query);
+        match self.focus() {
+            Some(node) if query.matches(&node) => (),
+            Some(node) => panic!(
+                "the focused node {:?} doesn't match the query\n{}",
+                node.id(),
+                self.dump()
+            ),
+            None => panic!("nothing is focused\n{}", self.dump()),
+        }
+    }
+
+    /// Returns the events recorded since the adapter was created,
+    /// or since the last call to [`Adapter::take_events`].
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Returns and clears the recorded events.
+    pub fn take_events(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+#[track_caller]
+fn parse_query(query: &str) -> Query {
+    match Query::parse(query) {
+        Ok(query) => query,
+        Err(error) => panic!("invalid query {:?}: {}", query, error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use accesskit::{
+        Action, ActionHandler, ActionRequest, ActionRequestError, ActivationHandler, Node, NodeId,
+        Role, Tree, TreeUpdate,
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::Adapter;
+    use crate::Event;
+
+    fn window(children: Vec<NodeId>) -> Node {
+        let mut node = Node::new(Role::Window);
+        node.set_children(children);
+        node
+    }
+
+    fn button(label: &str) -> Node {
+        let mut node = Node::new(Role::Button);
+        node.set_label(label);
+        node.add_action(Action::Focus);
+        node.add_action(Action::Click);
+        node
+    }
+
+    fn initial_tree() -> TreeUpdate {
+        TreeUpdate {
+            nodes: vec![
+                (NodeId(0), window(vec![NodeId(1)])),
+                (NodeId(1), {
+                    let mut node = Node::new(Role::GenericContainer);
+                    node.set_children(vec![NodeId(2), NodeId(3)]);
+                    node
+                }),
+                (NodeId(2), button("OK")),
+                (NodeId(3), {
+                    let mut node = button("Cancel");
+                    node.set_author_id("cancel");
+                    node
+                }),
+            ],
+            tree: Some(Tree::new(NodeId(0))),
+            focus: NodeId(2),
+        }
+    }
+
+    struct LazyActivation;
+
+    impl ActivationHandler for LazyActivation {
+        fn request_initial_tree(&mut self) -> Option<TreeUpdate> {
+            None
+        }
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        requests: Vec<ActionRequest>,
+        dropped: Vec<ActionRequestError>,
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedRecorder(Rc<RefCell<Recorder>>);
+
+    impl ActionHandler for SharedRecorder {
+        fn do_action(&mut self, request: ActionRequest) {
+            self.0.borrow_mut().requests.push(request);
+        }
+
+        fn action_request_dropped(&mut self, _request: &ActionRequest, error: ActionRequestError) {
+            self.0.borrow_mut().dropped.push(error);
+        }
+    }
+
+    #[test]
+    fn queries() {
+        let mut adapter = Adapter::new(LazyActivation, SharedRecorder::default());
+        adapter.activate();
+        assert!(!adapter.is_active());
+        adapter.update_if_active(initial_tree);
+        assert!(adapter.is_active());
+
+        assert_eq!(adapter.find_by_role(Role::Button).len(), 2);
+        assert_eq!(adapter.find_by_label("OK").unwrap().id(), NodeId(2));
+        assert_eq!(adapter.find_by_author_id("cancel").unwrap().id(), NodeId(3));
+        assert_eq!(
+            adapter.find("button[label=Cancel]").unwrap().id(),
+            NodeId(3)
+        );
+        assert_eq!(adapter.nodes().len(), 4);
+        let filtered = adapter
+            .filtered_nodes()
+            .iter()
+            .map(|node| node.id())
+            .collect::<Vec<_>>();
+        assert_eq!(filtered, [NodeId(0), NodeId(2), NodeId(3)]);
+        assert!(adapter.focus().is_none());
+    }
+
+    #[test]
+    fn events_and_focus() {
+        let mut adapter = Adapter::new(LazyActivation, SharedRecorder::default());
+        adapter.activate();
+        adapter.update_if_active(initial_tree);
+        adapter.update_window_focus_state(true);
+        adapter.assert_focus("button[label=OK]");
+        adapter.update_if_active(|| TreeUpdate {
+            nodes: vec![],
+            tree: None,
+            focus: NodeId(3),
+        });
+        adapter.assert_focus("[author_id=cancel]");
+        assert_eq!(
+            adapter.take_events(),
+            [
+                Event::NodeUpdated(NodeId(2)),
+                Event::FocusMoved {
+                    old: None,
+                    new: Some(NodeId(2)),
+                },
+                Event::NodeUpdated(NodeId(2)),
+                Event::NodeUpdated(NodeId(3)),
+                Event::FocusMoved {
+                    old: Some(NodeId(2)),
+                    new: Some(NodeId(3)),
+                },
+            ]
+        );
+        assert!(adapter.events().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't match")]
+    fn assert_focus_mismatch() {
+        let mut adapter = Adapter::new(LazyActivation, SharedRecorder::default());
+        adapter.update_window_focus_state(true);
+        adapter.activate();
+        adapter.update_if_active(initial_tree);
+        adapter.assert_focus("button[label=Cancel]");
+    }
+
+    #[test]
+    fn actions() {
+        let recorder = SharedRecorder::default();
+        let mut adapter = Adapter::new(LazyActivation, recorder.clone());
+        adapter.activate();
+        adapter.update_if_active(initial_tree);
+        for target in [NodeId(2), NodeId(4)] {
+            adapter.do_action(ActionRequest {
+                action: Action::Click,
+                target,
+                data: None,
+            });
+        }
+        let recorder = recorder.0.borrow();
+        assert_eq!(recorder.requests.len(), 1);
+        assert_eq!(recorder.requests[0].target, NodeId(2));
+        assert_eq!(recorder.dropped, [ActionRequestError::UnknownTarget]);
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::{metadata, AdapterCapabilities};
+
+/// Checks a platform adapter's [`AdapterCapabilities`] against its source
+/// code, so that the report can't silently drift from the mappings.
+///
+/// `sources` is the code of the modules that map nodes to the platform API,
+/// usually included with `include_str!`. Each listed property must be
+/// a property of the schema, and must be read by the sources through
+/// its getter, e.g. `.label(` or `.is_required(`. Each listed action must be
+/// mentioned as `Action::Name`. Properties and actions that adapters only
+/// handle through consumer methods may instead be covered by one of
+/// the methods given for them in `readers`, e.g. `("bounds", &["bounding_box"])`
+/// or `("Action::Increment", &["set_numeric_value_action"])`.
+///
+/// # Panics
+///
+/// Panics with a list of the properties and actions that fail these checks.
+pub fn check_capabilities(
+    capabilities: &AdapterCapabilities,
+    sources: &[&str],
+    readers: &[(&str, &[&str])],
+) {
+    let is_read = |method: &str| {
+        let call = format!(".{method}(");
+        sources.iter().any(|source| source.contains(&call))
+    };
+    let is_read_indirectly = |name: &str| {
+        readers
+            .iter()
+            .filter(|(key, _)| *key == name)
+            .any(|(_, methods)| methods.iter().any(|method| is_read(method)))
+    };
+    let mut problems = Vec::new();
+    for name in capabilities.properties {
+        let is_property = metadata::properties()
+            .any(|info| info.getter.strip_prefix("is_").unwrap_or(info.getter) == *name);
+        if !is_property {
+            problems.push(format!("`{name}` isn't a property"));
+        } else if !(is_read(name) || is_read(&format!("is_{name}")) || is_read_indirectly(name)) {
+            problems.push(format!("`{name}` is listed but never read"));
+        }
+    }
+    for action in capabilities.actions {
+        let path = format!("Action::{action:?}");
+        if !(sources.iter().any(|source| source.contains(&path)) || is_read_indirectly(&path)) {
+            problems.push(format!("`{path}` is listed but never handled"));
+        }
+    }
+    assert!(
+        problems.is_empty(),
+        "The {} capabilities don't match the adapter:\n{}",
+        capabilities.api,
+        problems.join("\n")
+    );
+}
@@ -0,0 +1,55 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+use accesskit::NodeId;
+use accesskit_consumer::{Node, TreeChangeHandler};
+
+/// A change to the tree, as reported to a platform adapter.
+///
+/// Events are recorded for every node, including nodes that platform
+/// adapters would filter out, in the order in which the tree reports them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Event {
+    NodeAdded(NodeId),
+    NodeUpdated(NodeId),
+    BoundsChanged(NodeId),
+    ChildrenReordered(NodeId),
+    NodeRemoved(NodeId),
+    FocusMoved {
+        old: Option<NodeId>,
+        new: Option<NodeId>,
+    },
+}
+
+pub(crate) struct EventRecorder<'a>(pub(crate) &'a mut Vec<Event>);
+
+impl TreeChangeHandler for EventRecorder<'_> {
+    fn node_added(&mut self, node: &Node) {
+        self.0.push(Event::NodeAdded(node.id()));
+    }
+
+    fn node_updated(&mut self, _old_node: &Node, new_node: &Node) {
+        self.0.push(Event::NodeUpdated(new_node.id()));
+    }
+
+    fn bounds_changed(&mut self, _old_node: &Node, new_node: &Node) {
+        self.0.push(Event::BoundsChanged(new_node.id()));
+    }
+
+    fn children_reordered(&mut self, _old_node: &Node, new_node: &Node) {
+        self.0.push(Event::ChildrenReordered(new_node.id()));
+    }
+
+    fn focus_moved(&mut self, old_node: Option<&Node>, new_node: Option<&Node>) {
+        self.0.push(Event::FocusMoved {
+            old: old_node.map(Node::id),
+            new: new_node.map(Node::id),
+        });
+    }
+
+    fn node_removed(&mut self, node: &Node) {
+        self.0.push(Event::NodeRemoved(node.id()));
+    }
+}
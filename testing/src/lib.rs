@@ -0,0 +1,12 @@
+// Copyright 2026 The AccessKit Authors. All rights reserved.
+// Licensed under the Apache License, Version 2.0 (found in
+// the LICENSE-APACHE file) or the MIT license (found in
+// the LICENSE-MIT file), at your option.
+
+mod adapter;
+mod capabilities;
+mod event;
+
+pub use adapter::Adapter;
+pub use capabilities::check_capabilities;
+pub use event::Event;